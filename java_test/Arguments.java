@@ -0,0 +1,10 @@
+public class Arguments {
+    public static void main(String[] args) {
+        if (args == null) System.exit(2);
+        if (args.length != 3) System.exit(3);
+        if (!args[0].equals("one")) System.exit(4);
+        if (!args[1].equals("two words")) System.exit(5);
+        if (!args[2].equals("")) System.exit(6);
+        System.exit(0);
+    }
+}
@@ -0,0 +1,19 @@
+public class Boot {
+    public static void main(String[] args) {
+        Thread main = Thread.currentThread();
+        if (main == null || !main.isAlive()) System.exit(2);
+        if (!"main".equals(main.getName())) System.exit(3);
+        if (!"main".equals(main.getThreadGroup().getName())) System.exit(4);
+        if (!"system".equals(main.getThreadGroup().getParent().getName())) System.exit(5);
+        if (main.getPriority() != 5) System.exit(6);
+        if (!"1.8".equals(System.getProperty("java.specification.version"))) System.exit(7);
+        if (System.getProperty("java.class.path") == null) System.exit(8);
+        if (new Boot().getClass().getSuperclass() != Object.class) System.exit(9);
+        if (!"[I".equals(new int[0].getClass().getName())) System.exit(10);
+        int[] a = {1, 2, 3};
+        int[] b = a.clone();
+        b[0] = 9;
+        if (a[0] != 1 || b[0] != 9) System.exit(11);
+        System.exit(0);
+    }
+}
@@ -0,0 +1,15 @@
+public class Console {
+    public static void main(String[] args) {
+        System.out.println("Hello, world");
+        System.out.print("caf\u00e9 ");
+        System.out.println(42);
+        System.out.println('x');
+        System.out.println(true);
+        System.out.println(1.5f);
+        System.out.println(new char[] {'o', 'k'});
+        System.err.println("to stderr");
+        System.out.println();
+        String s = null;
+        System.out.println(s);
+    }
+}
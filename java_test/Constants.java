@@ -0,0 +1,49 @@
+/*
+ * Constants reads the static final fields of Limits while Limits is
+ * being initialized. It is compiled against stubs/Limits.java, whose
+ * fields are not constants, so that it reads them with getstatic
+ * instead of javac inlining their values: only their ConstantValue
+ * attributes set them. They are set before the superclass of Limits,
+ * LimitsBase, is initialized, so LimitsBase sees them too.
+ */
+public class Constants {
+    static int max;
+    static long big;
+    static float ratio;
+    static double quarter;
+    static String name;
+    static int counted = -1;
+    static String label = "";
+    static int superclassMax;
+    static String superclassName;
+
+    static void duringSuperclassInitialization() {
+        superclassMax = Limits.MAX;
+        superclassName = Limits.NAME;
+    }
+
+    static void duringInitialization() {
+        max = Limits.MAX;
+        big = Limits.BIG;
+        ratio = Limits.RATIO;
+        quarter = Limits.QUARTER;
+        name = Limits.NAME;
+        counted = Limits.counted;
+        label = Limits.label;
+    }
+
+    public static void main(String[] args) {
+        if (Limits.counted != 5) System.exit(2);
+        if (max != 42) System.exit(3);
+        if (big != 1L << 40) System.exit(4);
+        if (ratio != 1.5f) System.exit(5);
+        if (quarter != 0.25) System.exit(6);
+        if (!"limits".equals(name)) System.exit(7);
+        if (name != "limits") System.exit(8);
+        if (counted != 0) System.exit(9);
+        if (label != null) System.exit(10);
+        if (superclassMax != 42) System.exit(11);
+        if (superclassName != "limits") System.exit(12);
+        System.exit(0);
+    }
+}
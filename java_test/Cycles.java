@@ -0,0 +1,29 @@
+/*
+ * Cycles builds thousands of object cycles that it drops at once and
+ * that hold far more than the heap allows in total (run it with
+ * -Xmx8m): it only completes if the collector reclaims them.
+ */
+public class Cycles {
+    static class Node {
+        Node next;
+        Node previous;
+        int[] payload = new int[4096];
+    }
+
+    public static void main(String[] args) {
+        Node kept = null;
+        for (int i = 0; i < 300; i++) {
+            Node first = new Node();
+            Node second = new Node();
+            first.next = second;
+            second.previous = first;
+            second.next = first;
+            first.previous = second;
+            first.payload[0] = i;
+            if (i == 150) kept = first;
+        }
+        if (kept.payload[0] != 150) System.exit(2);
+        if (kept.next.next != kept || kept.previous.previous != kept) System.exit(3);
+        System.exit(0);
+    }
+}
@@ -0,0 +1,27 @@
+public class DeepRecursion {
+    static int depth;
+
+    static long sum(int n) {
+        return n == 0 ? 0 : n + sum(n - 1);
+    }
+
+    static void recurse() {
+        depth++;
+        recurse();
+    }
+
+    public static void main(String[] args) {
+        if (sum(10000) != 50005000L) System.exit(2);
+        try {
+            recurse();
+            System.exit(3);
+        } catch (StackOverflowError e) {
+            if (depth < 10000) System.exit(4);
+        }
+        /*
+         * The stack unwound: deep recursion works again.
+         */
+        if (sum(10000) != 50005000L) System.exit(5);
+        System.exit(0);
+    }
+}
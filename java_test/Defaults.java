@@ -0,0 +1,37 @@
+public class Defaults {
+    boolean z;
+    byte b;
+    char c;
+    short s;
+    int i;
+    long j;
+    float f;
+    double d;
+    Object object;
+    String string;
+    Runnable runnable;
+    int[] ints;
+    Object[][] objects;
+
+    public static void main(String[] args) {
+        Defaults defaults = new Defaults();
+        if (defaults.z) System.exit(2);
+        if (defaults.b != 0) System.exit(3);
+        if (defaults.c != '\u0000') System.exit(4);
+        if (defaults.s != 0) System.exit(5);
+        if (defaults.i != 0) System.exit(6);
+        if (defaults.j != 0L) System.exit(7);
+        if (defaults.f != 0.0f) System.exit(8);
+        if (defaults.d != 0.0) System.exit(9);
+        if (defaults.object != null) System.exit(10);
+        if (defaults.string != null) System.exit(11);
+        if (defaults.runnable != null) System.exit(12);
+        if (defaults.ints != null) System.exit(13);
+        if (defaults.objects != null) System.exit(14);
+        defaults.ints = new int[2];
+        defaults.j = -1L;
+        if (defaults.ints.length != 2 || defaults.ints[1] != 0) System.exit(15);
+        if (defaults.j != -1L || defaults.i != 0) System.exit(16);
+        System.exit(0);
+    }
+}
@@ -0,0 +1,51 @@
+/*
+ * How the program exits, by its first argument:
+ * exit: System.exit(42), so 42.
+ * throw: an uncaught exception, so 1.
+ * hook: returns from main, and its shutdown hook halts with 7.
+ * hooks: System.exit(3), and its shutdown hooks run in order and the
+ * second halts with 8.
+ */
+public class ExitStatus {
+    static boolean firstRan;
+
+    public static void main(String[] args) {
+        Runtime runtime = Runtime.getRuntime();
+        if (args[0].equals("exit")) {
+            System.exit(42);
+        } else if (args[0].equals("throw")) {
+            throw new RuntimeException("uncaught");
+        } else if (args[0].equals("hook")) {
+            runtime.addShutdownHook(new Thread() {
+                public void run() {
+                    Runtime.getRuntime().halt(7);
+                }
+            });
+        } else if (args[0].equals("hooks")) {
+            Thread first = new Thread() {
+                public void run() {
+                    firstRan = true;
+                }
+            };
+            runtime.addShutdownHook(first);
+            try {
+                runtime.addShutdownHook(first);
+                System.exit(4);
+            } catch (IllegalArgumentException e) {
+            }
+            Thread removed = new Thread() {
+                public void run() {
+                    Runtime.getRuntime().halt(5);
+                }
+            };
+            runtime.addShutdownHook(removed);
+            if (!runtime.removeShutdownHook(removed)) System.exit(6);
+            runtime.addShutdownHook(new Thread() {
+                public void run() {
+                    Runtime.getRuntime().halt(firstRan ? 8 : 9);
+                }
+            });
+            System.exit(3);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+import java.io.*;
+
+public class FileStreams {
+    static void write(String name) throws IOException {
+        FileOutputStream out = new FileOutputStream(name, false);
+        out.write('h');
+        out.write('i');
+        out.close();
+        out.close();
+    }
+    static void read(String name) throws IOException {
+        FileInputStream in = new FileInputStream(name);
+        System.out.println(in.available());
+        int c = in.read();
+        while (c != -1) {
+            System.out.print(c);
+            c = in.read();
+        }
+        System.out.println();
+        in.close();
+    }
+    static void random(String name) throws IOException {
+        RandomAccessFile file = new RandomAccessFile(name, "rw");
+        file.read();
+        file.write('o');
+        System.out.println(file.getFilePointer());
+        System.out.println(file.length());
+        file.close();
+    }
+    static void missing() {
+        try {
+            new FileInputStream("/nonexistent/file");
+        } catch (FileNotFoundException e) {
+            Throwable t = e;
+            System.out.println(t.getMessage());
+        }
+    }
+    static void closed(String name) throws IOException {
+        FileInputStream in = new FileInputStream(name);
+        in.close();
+        try {
+            in.read();
+        } catch (IOException e) {
+            Throwable t = e;
+            System.out.println(t.getMessage());
+        }
+    }
+    public static void main(String[] args) throws IOException {
+        write(args[0]);
+        read(args[0]);
+        random(args[0]);
+        read(args[0]);
+        missing();
+        closed(args[0]);
+        System.out.println(System.in.read());
+    }
+}
@@ -0,0 +1,31 @@
+/*
+ * A class whose static initializer throws is erroneous: the first use
+ * throws an ExceptionInInitializerError that wraps the exception, and
+ * every later use throws a NoClassDefFoundError (JVMS 5.5).
+ */
+public class InitializerErrors {
+    static int zero = 0;
+
+    static class Broken {
+        static int value = 1 / zero;
+    }
+
+    public static void main(String args[]) {
+        try {
+            int value = Broken.value;
+            System.exit(1);
+        } catch (ExceptionInInitializerError e) {
+            if (!(e.getCause() instanceof ArithmeticException)) System.exit(2);
+        }
+        try {
+            int value = Broken.value;
+            System.exit(3);
+        } catch (NoClassDefFoundError e) {
+        }
+        try {
+            new Broken();
+            System.exit(4);
+        } catch (NoClassDefFoundError e) {
+        }
+    }
+}
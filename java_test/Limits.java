@@ -0,0 +1,14 @@
+public class Limits extends LimitsBase {
+    public static final int MAX = 42;
+    public static final long BIG = 1L << 40;
+    public static final float RATIO = 1.5f;
+    public static final double QUARTER = 0.25;
+    public static final String NAME = "limits";
+
+    static {
+        Constants.duringInitialization();
+    }
+
+    public static int counted = 5;
+    public static String label = "set";
+}
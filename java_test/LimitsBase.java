@@ -0,0 +1,8 @@
+/*
+ * The superclass of Limits (see Constants.java).
+ */
+public class LimitsBase {
+    static {
+        Constants.duringSuperclassInitialization();
+    }
+}
@@ -0,0 +1,8 @@
+/*
+ * The class that Loaders defines again, with a class loader of its own.
+ */
+public class Loaded {
+    static {
+        Loaders.instance = new Loaded();
+    }
+}
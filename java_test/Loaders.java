@@ -0,0 +1,77 @@
+import java.io.*;
+
+/*
+ * A class loader that defines Loaded itself, from the bytes of
+ * java_test/Loaded.class, and leaves every other class to its parent.
+ * The Loaded that it defines is not the Loaded of the class path.
+ */
+public class Loaders {
+    static Object instance;
+
+    static class Defining extends ClassLoader {
+        Defining(ClassLoader parent) {
+            super(parent);
+        }
+
+        protected Class<?> loadClass(String name, boolean resolve) throws ClassNotFoundException {
+            if (!name.equals("Loaded")) return super.loadClass(name, resolve);
+            Class<?> c = findLoadedClass(name);
+            return c != null ? c : findClass(name);
+        }
+
+        protected Class<?> findClass(String name) throws ClassNotFoundException {
+            if (!name.equals("Loaded")) throw new ClassNotFoundException(name);
+            try {
+                FileInputStream in = new FileInputStream("java_test/Loaded.class");
+                byte[] bytes = new byte[in.available()];
+                int length = in.read(bytes);
+                in.close();
+                return defineClass(name, bytes, 0, length);
+            } catch (IOException e) {
+                throw new ClassNotFoundException(name);
+            }
+        }
+
+        Class<?> loaded(String name) {
+            return findLoadedClass(name);
+        }
+    }
+
+    static class Delegating extends ClassLoader {
+        Delegating(ClassLoader parent) {
+            super(parent);
+        }
+    }
+
+    public static void main(String[] args) throws Exception {
+        Defining defining = new Defining(null);
+        if (defining.loaded("Loaded") != null) System.exit(1);
+        Class<?> defined = Class.forName("Loaded", true, defining);
+        if (defined == Loaded.class) System.exit(2);
+        if (defined.getClassLoader() != defining) System.exit(3);
+        if (!defined.getName().equals("Loaded")) System.exit(4);
+        if (defining.loaded("Loaded") != defined) System.exit(5);
+        if (defining.loadClass("Loaded") != defined) System.exit(6);
+
+        /*
+         * Its static initializer ran and made an instance of it.
+         */
+        if (instance == null) System.exit(7);
+        if (instance.getClass() != defined) System.exit(8);
+        if (instance.getClass() == Loaded.class) System.exit(9);
+        if (instance instanceof Loaded) System.exit(10);
+
+        /*
+         * Loaders delegate to their parent first.
+         */
+        Delegating delegating = new Delegating(defining);
+        if (delegating.loadClass("Loaded") != defined) System.exit(11);
+        if (delegating.loadClass("java.lang.String") != String.class) System.exit(12);
+        if (defining.loadClass("Loaders") != Loaders.class) System.exit(13);
+        try {
+            delegating.loadClass("Missing");
+            System.exit(14);
+        } catch (ClassNotFoundException e) {
+        }
+    }
+}
@@ -0,0 +1,28 @@
+public class Natives {
+    static int copy() {
+        char[] a = new char[] {'a', 'b', 'c', 'd'};
+        char[] b = new char[4];
+        System.arraycopy(a, 1, b, 0, 3);
+        if (b[0] != 'b' || b[2] != 'd') return 2;
+        System.arraycopy(a, 0, a, 1, 3);
+        if (a[1] != 'a' || a[3] != 'c') return 3;
+        return 0;
+    }
+    static int bounds() {
+        char[] a = new char[4];
+        try {
+            System.arraycopy(a, 2, a, 0, 3);
+            return 6;
+        } catch (ArrayIndexOutOfBoundsException e) {
+            return 0;
+        }
+    }
+    public static void main(String[] args) {
+        int r = copy();
+        if (r != 0) System.exit(r);
+        if (Float.intBitsToFloat(Float.floatToRawIntBits(1.5f)) != 1.5f) System.exit(4);
+        Object o = new Object();
+        if (o.hashCode() != System.identityHashCode(o)) System.exit(5);
+        System.exit(bounds());
+    }
+}
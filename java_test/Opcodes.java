@@ -0,0 +1,89 @@
+public class Opcodes {
+    interface Shape { int sides(); }
+    static class Square implements Shape { public int sides() { return 4; } }
+    static class Triangle implements Shape { public int sides() { return 3; } }
+
+    static int arithmetic() {
+        long l = 1L << 40;
+        if ((l >>> 39) != 2L || (-l >> 40) != -1L) return 1;
+        double d = 7.5;
+        if ((int) (d * 2) != 15 || d % 2 != 1.5) return 2;
+        float f = -0.0f;
+        if (1 / f != Float.NEGATIVE_INFINITY) return 3;
+        if ((byte) 300 != 44 || (short) 70000 != 4464 || (char) -1 != 65535) return 4;
+        if ((int) Double.NaN != 0 || (long) 1e30 != Long.MAX_VALUE) return 5;
+        if (Integer.MIN_VALUE / -1 != Integer.MIN_VALUE || -7 % 3 != -1) return 6;
+        try {
+            int zero = 0;
+            int x = 1 / zero;
+            return 7;
+        } catch (ArithmeticException e) {
+        }
+        return 0;
+    }
+
+    static int switches(int i) {
+        switch (i) {
+            case 1: return 10;
+            case 2: return 20;
+            case 3: return 30;
+            default: break;
+        }
+        switch (i) {
+            case -1000: return -1;
+            case 1000000: return 1000;
+            default: return 0;
+        }
+    }
+
+    static int arrays() {
+        int[][][] cube = new int[2][3][4];
+        cube[1][2][3] = 5;
+        if (cube.length != 2 || cube[1].length != 3 || cube[1][2].length != 4) return 1;
+        if (cube[1][2][3] != 5 || cube[0][0][0] != 0) return 2;
+        long[] longs = new long[2];
+        longs[1] += 1L << 33;
+        if (longs[1] != 1L << 33) return 3;
+        Object[] objects = new Square[1];
+        try {
+            objects[0] = new Triangle();
+            return 4;
+        } catch (ArrayStoreException e) {
+        }
+        try {
+            new int[-1].clone();
+            return 5;
+        } catch (NegativeArraySizeException e) {
+        }
+        return 0;
+    }
+
+    static int types() {
+        Shape[] shapes = { new Square(), new Triangle() };
+        int sides = 0;
+        for (Shape shape : shapes) {
+            sides += shape.sides();
+        }
+        if (sides != 7) return 1;
+        Object o = shapes[0];
+        if (!(o instanceof Shape) || o instanceof Triangle) return 2;
+        try {
+            Triangle t = (Triangle) o;
+            return 3;
+        } catch (ClassCastException e) {
+        }
+        if (!(shapes instanceof Object[])) return 4;
+        return 0;
+    }
+
+    public static void main(String[] args) {
+        int r = arithmetic();
+        if (r != 0) System.exit(r);
+        if (switches(2) != 20 || switches(1000000) != 1000 || switches(7) != 0) System.exit(10);
+        r = arrays();
+        if (r != 0) System.exit(20 + r);
+        r = types();
+        if (r != 0) System.exit(30 + r);
+        System.exit(0);
+    }
+}
@@ -0,0 +1,31 @@
+/*
+ * OutOfMemory fills the heap (run it with -Xmx8m), catches the
+ * OutOfMemoryError and then, having let go of what it allocated,
+ * allocates again.
+ */
+public class OutOfMemory {
+    static class Chunk {
+        Chunk previous;
+        long[] payload = new long[8192];
+    }
+
+    public static void main(String[] args) {
+        Chunk chunks = null;
+        int count = 0;
+        try {
+            while (true) {
+                Chunk chunk = new Chunk();
+                chunk.previous = chunks;
+                chunks = chunk;
+                count++;
+            }
+        } catch (OutOfMemoryError e) {
+            chunks = null;
+        }
+        if (count == 0) System.exit(2);
+        Chunk chunk = new Chunk();
+        chunk.payload[0] = 1;
+        if (chunk.payload[0] != 1) System.exit(3);
+        System.exit(0);
+    }
+}
@@ -0,0 +1,35 @@
+public class Shadowing {
+    static class Base {
+        int value = 1;
+        String name = "base";
+        long wide = 10L;
+
+        int baseValue() {
+            return value;
+        }
+    }
+
+    static class Derived extends Base {
+        int value = 2;
+        String name = "derived";
+        int extra = 3;
+
+        int derivedValue() {
+            return value;
+        }
+    }
+
+    public static void main(String[] args) {
+        Derived derived = new Derived();
+        Base base = derived;
+        if (derived.value != 2) System.exit(2);
+        if (base.value != 1) System.exit(3);
+        if (derived.baseValue() != 1 || derived.derivedValue() != 2) System.exit(4);
+        if (!"derived".equals(derived.name) || !"base".equals(base.name)) System.exit(5);
+        derived.value = 20;
+        base.value = 10;
+        if (derived.value != 20 || base.value != 10) System.exit(6);
+        if (derived.wide != 10L || derived.extra != 3) System.exit(7);
+        System.exit(0);
+    }
+}
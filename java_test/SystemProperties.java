@@ -0,0 +1,14 @@
+/*
+ * Run with -Dfoo=bar and with JAVA_TEST_ENV=set in the environment.
+ */
+public class SystemProperties {
+    public static void main(String[] args) {
+        if (!"bar".equals(System.getProperty("foo"))) System.exit(2);
+        if (System.getProperty("missing.property") != null) System.exit(3);
+        if (!"fallback".equals(System.getProperty("missing.property", "fallback"))) System.exit(4);
+        if (!"set".equals(System.getenv("JAVA_TEST_ENV"))) System.exit(5);
+        if (!"set".equals(System.getenv().get("JAVA_TEST_ENV"))) System.exit(6);
+        if (System.getenv("JAVA_TEST_MISSING_ENV") != null) System.exit(7);
+        System.exit(0);
+    }
+}
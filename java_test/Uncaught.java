@@ -0,0 +1,13 @@
+public class Uncaught {
+    public static void thrower() {
+        throw new RuntimeException("Nobody catches this.");
+    }
+
+    public static void caller() {
+        thrower();
+    }
+
+    public static void main(String args[]) {
+        caller();
+    }
+}
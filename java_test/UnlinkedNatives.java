@@ -0,0 +1,21 @@
+public class UnlinkedNatives {
+    static native void unlinkedVoid();
+
+    static native int unlinkedInt(int value);
+
+    public static void main(String[] args) {
+        try {
+            unlinkedVoid();
+            System.exit(2);
+        } catch (UnsatisfiedLinkError e) {
+            if (!e.getMessage().equals("UnlinkedNatives.unlinkedVoid()V")) System.exit(3);
+        }
+        try {
+            unlinkedInt(1);
+            System.exit(4);
+        } catch (UnsatisfiedLinkError e) {
+            if (!e.getMessage().equals("UnlinkedNatives.unlinkedInt(I)I")) System.exit(5);
+        }
+        System.exit(0);
+    }
+}
@@ -0,0 +1,13 @@
+/*
+ * What Constants is compiled against in place of ../Limits.java (see
+ * Constants.java).
+ */
+public class Limits {
+    public static int MAX;
+    public static long BIG;
+    public static float RATIO;
+    public static double QUARTER;
+    public static String NAME;
+    public static int counted;
+    public static String label;
+}
@@ -0,0 +1,28 @@
+package java.io;
+
+/**
+ * A handle to an open file of the host: the VM's descriptor of it
+ * in fd (-1 when it is closed).
+ */
+public final class FileDescriptor {
+	private int fd;
+	private boolean append;
+
+	public static final FileDescriptor in = new FileDescriptor(0);
+	public static final FileDescriptor out = new FileDescriptor(1);
+	public static final FileDescriptor err = new FileDescriptor(2);
+
+	public FileDescriptor() {
+		fd = -1;
+	}
+
+	private FileDescriptor(int fd) {
+		this.fd = fd;
+	}
+
+	public boolean valid() {
+		return fd != -1;
+	}
+
+	public native void sync() throws SyncFailedException;
+}
@@ -0,0 +1,57 @@
+package java.io;
+
+public class FileInputStream extends InputStream {
+	private final FileDescriptor fd;
+
+	public FileInputStream(String name) throws FileNotFoundException {
+		if (name == null) {
+			throw new NullPointerException();
+		}
+		fd = new FileDescriptor();
+		open0(name);
+	}
+
+	public FileInputStream(FileDescriptor fdObj) {
+		fd = fdObj;
+	}
+
+	private native void open0(String name) throws FileNotFoundException;
+
+	public int read() throws IOException {
+		return read0();
+	}
+
+	private native int read0() throws IOException;
+
+	private native int readBytes(byte b[], int off, int len) throws IOException;
+
+	public int read(byte b[]) throws IOException {
+		return readBytes(b, 0, b.length);
+	}
+
+	public int read(byte b[], int off, int len) throws IOException {
+		return readBytes(b, off, len);
+	}
+
+	public long skip(long n) throws IOException {
+		return skip0(n);
+	}
+
+	private native long skip0(long n) throws IOException;
+
+	public int available() throws IOException {
+		return available0();
+	}
+
+	private native int available0() throws IOException;
+
+	public final FileDescriptor getFD() throws IOException {
+		return fd;
+	}
+
+	public void close() throws IOException {
+		close0();
+	}
+
+	private native void close0() throws IOException;
+}
@@ -0,0 +1,52 @@
+package java.io;
+
+public class FileOutputStream extends OutputStream {
+	private final FileDescriptor fd;
+	private final boolean append;
+
+	public FileOutputStream(String name) throws FileNotFoundException {
+		this(name, false);
+	}
+
+	public FileOutputStream(String name, boolean append) throws FileNotFoundException {
+		if (name == null) {
+			throw new NullPointerException();
+		}
+		this.fd = new FileDescriptor();
+		this.append = append;
+		open0(name, append);
+	}
+
+	public FileOutputStream(FileDescriptor fdObj) {
+		this.fd = fdObj;
+		this.append = false;
+	}
+
+	private native void open0(String name, boolean append) throws FileNotFoundException;
+
+	private native void write(int b, boolean append) throws IOException;
+
+	public void write(int b) throws IOException {
+		write(b, append);
+	}
+
+	private native void writeBytes(byte b[], int off, int len, boolean append) throws IOException;
+
+	public void write(byte b[]) throws IOException {
+		writeBytes(b, 0, b.length, append);
+	}
+
+	public void write(byte b[], int off, int len) throws IOException {
+		writeBytes(b, off, len, append);
+	}
+
+	public final FileDescriptor getFD() throws IOException {
+		return fd;
+	}
+
+	public void close() throws IOException {
+		close0();
+	}
+
+	private native void close0() throws IOException;
+}
@@ -0,0 +1,148 @@
+package java.io;
+
+/**
+ * Prints text, encoded as UTF-8, to an OutputStream. The VM creates
+ * System.out and System.err (with no OutputStream): it prints what
+ * they print to the host's standard streams.
+ */
+public class PrintStream extends OutputStream {
+	private OutputStream out;
+	private boolean trouble;
+
+	public PrintStream(OutputStream out) {
+		if (out == null) {
+			throw new NullPointerException("Null output stream");
+		}
+		this.out = out;
+	}
+
+	public PrintStream(String fileName) throws FileNotFoundException {
+		this(new FileOutputStream(fileName));
+	}
+
+	public boolean checkError() {
+		return trouble;
+	}
+
+	public void write(int b) {
+		try {
+			out.write(b);
+		} catch (IOException e) {
+			trouble = true;
+		}
+	}
+
+	public void write(byte b[], int off, int len) {
+		try {
+			out.write(b, off, len);
+		} catch (IOException e) {
+			trouble = true;
+		}
+	}
+
+	public void flush() {
+		try {
+			out.flush();
+		} catch (IOException e) {
+			trouble = true;
+		}
+	}
+
+	public void close() {
+		try {
+			out.close();
+		} catch (IOException e) {
+			trouble = true;
+		}
+	}
+
+	private void newLine() {
+		print(System.lineSeparator());
+	}
+
+	public void print(String s) {
+		byte[] bytes = String.valueOf(s).getBytes();
+		write(bytes, 0, bytes.length);
+	}
+
+	public void print(boolean b) {
+		print(String.valueOf(b));
+	}
+
+	public void print(char c) {
+		print(String.valueOf(c));
+	}
+
+	public void print(int i) {
+		print(String.valueOf(i));
+	}
+
+	public void print(long l) {
+		print(String.valueOf(l));
+	}
+
+	public void print(float f) {
+		print(String.valueOf(f));
+	}
+
+	public void print(double d) {
+		print(String.valueOf(d));
+	}
+
+	public void print(char s[]) {
+		print(new String(s));
+	}
+
+	public void print(Object obj) {
+		print(String.valueOf(obj));
+	}
+
+	public void println() {
+		newLine();
+	}
+
+	public void println(String x) {
+		print(x);
+		newLine();
+	}
+
+	public void println(boolean x) {
+		print(x);
+		newLine();
+	}
+
+	public void println(char x) {
+		print(x);
+		newLine();
+	}
+
+	public void println(int x) {
+		print(x);
+		newLine();
+	}
+
+	public void println(long x) {
+		print(x);
+		newLine();
+	}
+
+	public void println(float x) {
+		print(x);
+		newLine();
+	}
+
+	public void println(double x) {
+		print(x);
+		newLine();
+	}
+
+	public void println(char x[]) {
+		print(x);
+		newLine();
+	}
+
+	public void println(Object x) {
+		print(String.valueOf(x));
+		newLine();
+	}
+}
@@ -0,0 +1,80 @@
+package java.io;
+
+public class RandomAccessFile implements Closeable {
+	private static final int O_RDONLY = 1;
+	private static final int O_RDWR = 2;
+
+	private final FileDescriptor fd;
+
+	public RandomAccessFile(String name, String mode) throws FileNotFoundException {
+		int imode;
+		if (mode.equals("r")) {
+			imode = O_RDONLY;
+		} else if (mode.startsWith("rw")) {
+			imode = O_RDWR;
+		} else {
+			throw new IllegalArgumentException("Illegal mode \"" + mode + "\"");
+		}
+		fd = new FileDescriptor();
+		open0(name, imode);
+	}
+
+	private native void open0(String name, int mode) throws FileNotFoundException;
+
+	public int read() throws IOException {
+		return read0();
+	}
+
+	private native int read0() throws IOException;
+
+	private native int readBytes(byte b[], int off, int len) throws IOException;
+
+	public int read(byte b[], int off, int len) throws IOException {
+		return readBytes(b, off, len);
+	}
+
+	public int read(byte b[]) throws IOException {
+		return readBytes(b, 0, b.length);
+	}
+
+	public void write(int b) throws IOException {
+		write0(b);
+	}
+
+	private native void write0(int b) throws IOException;
+
+	private native void writeBytes(byte b[], int off, int len) throws IOException;
+
+	public void write(byte b[]) throws IOException {
+		writeBytes(b, 0, b.length);
+	}
+
+	public void write(byte b[], int off, int len) throws IOException {
+		writeBytes(b, off, len);
+	}
+
+	public native long getFilePointer() throws IOException;
+
+	public void seek(long pos) throws IOException {
+		if (pos < 0) {
+			throw new IOException("Negative seek offset");
+		}
+		seek0(pos);
+	}
+
+	private native void seek0(long pos) throws IOException;
+
+	public native long length() throws IOException;
+
+	public native void setLength(long newLength) throws IOException;
+
+	public final FileDescriptor getFD() throws IOException {
+		return fd;
+	}
+
+	public void close() throws IOException {
+		close0();
+	}
+
+	private native void close0() throws IOException;
+}
@@ -0,0 +1,11 @@
+package java.io;
+
+public class SyncFailedException extends IOException {
+	public SyncFailedException() {
+		super();
+	}
+
+	public SyncFailedException(String message) {
+		super(message);
+	}
+}
@@ -0,0 +1,15 @@
+package java.lang;
+
+public class ArrayIndexOutOfBoundsException extends IndexOutOfBoundsException {
+	public ArrayIndexOutOfBoundsException() {
+		super();
+	}
+
+	public ArrayIndexOutOfBoundsException(int index) {
+		super("Array index out of range: " + index);
+	}
+
+	public ArrayIndexOutOfBoundsException(String message) {
+		super(message);
+	}
+}
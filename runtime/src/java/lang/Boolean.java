@@ -0,0 +1,51 @@
+package java.lang;
+
+import java.io.Serializable;
+
+public final class Boolean implements Serializable, Comparable<Boolean> {
+	public static final Boolean TRUE = new Boolean(true);
+	public static final Boolean FALSE = new Boolean(false);
+	public static final Class<Boolean> TYPE = (Class<Boolean>) Class.getPrimitiveClass("boolean");
+
+	private final boolean value;
+
+	public Boolean(boolean value) {
+		this.value = value;
+	}
+
+	public static boolean parseBoolean(String s) {
+		return s != null && s.equalsIgnoreCase("true");
+	}
+
+	public static Boolean valueOf(boolean b) {
+		return b ? TRUE : FALSE;
+	}
+
+	public static Boolean valueOf(String s) {
+		return parseBoolean(s) ? TRUE : FALSE;
+	}
+
+	public static String toString(boolean b) {
+		return b ? "true" : "false";
+	}
+
+	public boolean booleanValue() {
+		return value;
+	}
+
+	public String toString() {
+		return toString(value);
+	}
+
+	public int hashCode() {
+		return value ? 1231 : 1237;
+	}
+
+	public boolean equals(Object other) {
+		return (other instanceof Boolean) && value == ((Boolean) other).value;
+	}
+
+	public int compareTo(Boolean other) {
+		return (value == other.value) ? 0 : (value ? 1 : -1);
+	}
+}
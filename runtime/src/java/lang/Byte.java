@@ -0,0 +1,79 @@
+package java.lang;
+
+public final class Byte extends Number implements Comparable<Byte> {
+	public static final byte MIN_VALUE = -128;
+	public static final byte MAX_VALUE = 127;
+	public static final int SIZE = 8;
+	public static final int BYTES = 1;
+	public static final Class<Byte> TYPE = (Class<Byte>) Class.getPrimitiveClass("byte");
+
+	private final byte value;
+
+	public Byte(byte value) {
+		this.value = value;
+	}
+
+	public static Byte valueOf(byte value) {
+		return new Byte(value);
+	}
+
+	public static byte parseByte(String s) throws NumberFormatException {
+		return parseByte(s, 10);
+	}
+
+	public static byte parseByte(String s, int radix) throws NumberFormatException {
+		int value = Integer.parseInt(s, radix);
+		if (value < MIN_VALUE || value > MAX_VALUE) {
+			throw new NumberFormatException("Value out of range. Value:\"" + s + "\" Radix:" + radix);
+		}
+		return (byte) value;
+	}
+
+	public static String toString(byte value) {
+		return Integer.toString(value);
+	}
+
+	public static int compare(byte x, byte y) {
+		return x - y;
+	}
+
+	public static int hashCode(byte value) {
+		return value;
+	}
+
+	public byte byteValue() {
+		return value;
+	}
+
+	public int intValue() {
+		return value;
+	}
+
+	public long longValue() {
+		return value;
+	}
+
+	public float floatValue() {
+		return value;
+	}
+
+	public double doubleValue() {
+		return value;
+	}
+
+	public String toString() {
+		return Integer.toString(value);
+	}
+
+	public int hashCode() {
+		return value;
+	}
+
+	public boolean equals(Object other) {
+		return (other instanceof Byte) && value == ((Byte) other).value;
+	}
+
+	public int compareTo(Byte other) {
+		return compare(value, other.value);
+	}
+}
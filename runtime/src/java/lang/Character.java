@@ -0,0 +1,119 @@
+package java.lang;
+
+import java.io.Serializable;
+
+/**
+ * A UTF-16 code unit. The classification methods know only about
+ * ASCII and Latin-1.
+ */
+public final class Character implements Serializable, Comparable<Character> {
+	public static final int MIN_RADIX = 2;
+	public static final int MAX_RADIX = 36;
+	public static final char MIN_VALUE = '\u0000';
+	public static final char MAX_VALUE = '\uffff';
+	public static final int SIZE = 16;
+	public static final int BYTES = 2;
+	public static final Class<Character> TYPE = (Class<Character>) Class.getPrimitiveClass("char");
+
+	private final char value;
+
+	public Character(char value) {
+		this.value = value;
+	}
+
+	public static Character valueOf(char c) {
+		return new Character(c);
+	}
+
+	public static boolean isDigit(char c) {
+		return c >= '0' && c <= '9';
+	}
+
+	public static boolean isLetter(char c) {
+		return isLowerCase(c) || isUpperCase(c);
+	}
+
+	public static boolean isLetterOrDigit(char c) {
+		return isLetter(c) || isDigit(c);
+	}
+
+	public static boolean isLowerCase(char c) {
+		return (c >= 'a' && c <= 'z') || (c >= '\u00df' && c <= '\u00ff' && c != '\u00f7');
+	}
+
+	public static boolean isUpperCase(char c) {
+		return (c >= 'A' && c <= 'Z') || (c >= '\u00c0' && c <= '\u00de' && c != '\u00d7');
+	}
+
+	public static boolean isWhitespace(char c) {
+		return c == ' ' || (c >= '\t' && c <= '\r') || (c >= '\u001c' && c <= '\u001f');
+	}
+
+	public static boolean isSpaceChar(char c) {
+		return c == ' ' || c == '\u00a0';
+	}
+
+	public static char toLowerCase(char c) {
+		return (isUpperCase(c)) ? (char) (c + 32) : c;
+	}
+
+	public static char toUpperCase(char c) {
+		return (isLowerCase(c) && c != '\u00df' && c != '\u00ff') ? (char) (c - 32) : c;
+	}
+
+	/**
+	 * The value of the digit `c` in base `radix`, or -1 if it is not one.
+	 */
+	public static int digit(char c, int radix) {
+		int value;
+		if (c >= '0' && c <= '9') {
+			value = c - '0';
+		} else if (c >= 'a' && c <= 'z') {
+			value = c - 'a' + 10;
+		} else if (c >= 'A' && c <= 'Z') {
+			value = c - 'A' + 10;
+		} else {
+			return -1;
+		}
+		return (value < radix && radix >= MIN_RADIX && radix <= MAX_RADIX) ? value : -1;
+	}
+
+	public static char forDigit(int digit, int radix) {
+		if (digit < 0 || digit >= radix || radix < MIN_RADIX || radix > MAX_RADIX) {
+			return '\0';
+		}
+		return (char) ((digit < 10) ? '0' + digit : 'a' - 10 + digit);
+	}
+
+	public static String toString(char c) {
+		return String.valueOf(c);
+	}
+
+	public static int compare(char x, char y) {
+		return x - y;
+	}
+
+	public static int hashCode(char value) {
+		return value;
+	}
+
+	public char charValue() {
+		return value;
+	}
+
+	public String toString() {
+		return String.valueOf(value);
+	}
+
+	public int hashCode() {
+		return value;
+	}
+
+	public boolean equals(Object other) {
+		return (other instanceof Character) && value == ((Character) other).value;
+	}
+
+	public int compareTo(Character other) {
+		return compare(value, other.value);
+	}
+}
@@ -0,0 +1,81 @@
+package java.lang;
+
+/**
+ * The run-time representation of a type. The VM creates the Class
+ * objects.
+ */
+public final class Class<T> {
+	private transient String name;
+
+	private Class() {
+	}
+
+	public static Class<?> forName(String className) throws ClassNotFoundException {
+		return forName0(className, true, null, null);
+	}
+
+	public static Class<?> forName(String name, boolean initialize, ClassLoader loader)
+			throws ClassNotFoundException {
+		return forName0(name, initialize, loader, null);
+	}
+
+	private static native Class<?> forName0(String name, boolean initialize, ClassLoader loader, Class<?> caller)
+			throws ClassNotFoundException;
+
+	static native Class<?> getPrimitiveClass(String name);
+
+	public String getName() {
+		String name = this.name;
+		if (name == null) {
+			this.name = name = getName0();
+		}
+		return name;
+	}
+
+	private native String getName0();
+
+	public String getSimpleName() {
+		if (isArray()) {
+			return getComponentType().getSimpleName() + "[]";
+		}
+		String name = getName();
+		return name.substring(Math.max(name.lastIndexOf('.'), name.lastIndexOf('$')) + 1);
+	}
+
+	public String toString() {
+		return (isInterface() ? "interface " : (isPrimitive() ? "" : "class ")) + getName();
+	}
+
+	public native boolean isInstance(Object obj);
+
+	public native boolean isAssignableFrom(Class<?> cls);
+
+	public native boolean isInterface();
+
+	public native boolean isArray();
+
+	public native boolean isPrimitive();
+
+	public native Class<? super T> getSuperclass();
+
+	public native Class<?> getComponentType();
+
+	public native int getModifiers();
+
+	public ClassLoader getClassLoader() {
+		return getClassLoader0();
+	}
+
+	native ClassLoader getClassLoader0();
+
+	public boolean desiredAssertionStatus() {
+		return false;
+	}
+
+	public T cast(Object obj) {
+		if (obj != null && !isInstance(obj)) {
+			throw new ClassCastException("Cannot cast " + obj.getClass().getName() + " to " + getName());
+		}
+		return (T) obj;
+	}
+}
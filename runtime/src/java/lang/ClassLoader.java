@@ -0,0 +1,79 @@
+package java.lang;
+
+import java.security.ProtectionDomain;
+
+/**
+ * Loads classes. A class loader first delegates to its parent (or, if
+ * it has none, to the bootstrap class loader) and only then finds the
+ * class itself.
+ */
+public abstract class ClassLoader {
+	private final ClassLoader parent;
+
+	protected ClassLoader() {
+		this(null);
+	}
+
+	protected ClassLoader(ClassLoader parent) {
+		this.parent = parent;
+	}
+
+	public final ClassLoader getParent() {
+		return parent;
+	}
+
+	public Class<?> loadClass(String name) throws ClassNotFoundException {
+		return loadClass(name, false);
+	}
+
+	protected Class<?> loadClass(String name, boolean resolve) throws ClassNotFoundException {
+		Class<?> c = findLoadedClass(name);
+		if (c == null) {
+			if (parent != null) {
+				try {
+					c = parent.loadClass(name, false);
+				} catch (ClassNotFoundException e) {
+				}
+			} else {
+				c = findBootstrapClass(name);
+			}
+			if (c == null) {
+				c = findClass(name);
+			}
+		}
+		if (resolve) {
+			resolveClass(c);
+		}
+		return c;
+	}
+
+	protected Class<?> findClass(String name) throws ClassNotFoundException {
+		throw new ClassNotFoundException(name);
+	}
+
+	protected final Class<?> defineClass(String name, byte[] b, int off, int len) throws ClassFormatError {
+		return defineClass(name, b, off, len, null);
+	}
+
+	protected final Class<?> defineClass(String name, byte[] b, int off, int len,
+			ProtectionDomain protectionDomain) throws ClassFormatError {
+		return defineClass1(name, b, off, len, protectionDomain, null);
+	}
+
+	protected final void resolveClass(Class<?> c) {
+		if (c == null) {
+			throw new NullPointerException();
+		}
+	}
+
+	protected final Class<?> findLoadedClass(String name) {
+		return findLoadedClass0(name);
+	}
+
+	private native Class<?> defineClass1(String name, byte[] b, int off, int len, ProtectionDomain pd,
+			String source);
+
+	private native Class<?> findBootstrapClass(String name);
+
+	private native Class<?> findLoadedClass0(String name);
+}
@@ -0,0 +1,128 @@
+package java.lang;
+
+public final class Float extends Number implements Comparable<Float> {
+	public static final float POSITIVE_INFINITY = 1.0f / 0.0f;
+	public static final float NEGATIVE_INFINITY = -1.0f / 0.0f;
+	public static final float NaN = 0.0f / 0.0f;
+	public static final float MAX_VALUE = 0x1.fffffeP+127f;
+	public static final float MIN_NORMAL = 0x1.0p-126f;
+	public static final float MIN_VALUE = 0x0.000002P-126f;
+	public static final int SIZE = 32;
+	public static final int BYTES = 4;
+	public static final Class<Float> TYPE = (Class<Float>) Class.getPrimitiveClass("float");
+
+	private final float value;
+
+	public Float(float value) {
+		this.value = value;
+	}
+
+	public static Float valueOf(float value) {
+		return new Float(value);
+	}
+
+	public static Float valueOf(String s) throws NumberFormatException {
+		return new Float(parseFloat(s));
+	}
+
+	public static float parseFloat(String s) throws NumberFormatException {
+		if (s == null) {
+			throw new NullPointerException();
+		}
+		return (float) Double.parseDouble(s);
+	}
+
+	/**
+	 * Java's string representation of `value`: the shortest decimal
+	 * that identifies it.
+	 */
+	public static native String toString(float value);
+
+	public static boolean isNaN(float value) {
+		return value != value;
+	}
+
+	public static boolean isInfinite(float value) {
+		return value == POSITIVE_INFINITY || value == NEGATIVE_INFINITY;
+	}
+
+	public static boolean isFinite(float value) {
+		return !isNaN(value) && !isInfinite(value);
+	}
+
+	public static native int floatToRawIntBits(float value);
+
+	public static int floatToIntBits(float value) {
+		return isNaN(value) ? 0x7fc00000 : floatToRawIntBits(value);
+	}
+
+	public static native float intBitsToFloat(int bits);
+
+	public static int compare(float x, float y) {
+		if (x < y) {
+			return -1;
+		}
+		if (x > y) {
+			return 1;
+		}
+		int xBits = floatToIntBits(x);
+		int yBits = floatToIntBits(y);
+		return (xBits == yBits) ? 0 : ((xBits < yBits) ? -1 : 1);
+	}
+
+	public static int hashCode(float value) {
+		return floatToIntBits(value);
+	}
+
+	public static float max(float a, float b) {
+		return Math.max(a, b);
+	}
+
+	public static float min(float a, float b) {
+		return Math.min(a, b);
+	}
+
+	public static float sum(float a, float b) {
+		return a + b;
+	}
+
+	public boolean isNaN() {
+		return isNaN(value);
+	}
+
+	public boolean isInfinite() {
+		return isInfinite(value);
+	}
+
+	public int intValue() {
+		return (int) value;
+	}
+
+	public long longValue() {
+		return (long) value;
+	}
+
+	public float floatValue() {
+		return (float) value;
+	}
+
+	public double doubleValue() {
+		return value;
+	}
+
+	public String toString() {
+		return toString(value);
+	}
+
+	public int hashCode() {
+		return hashCode(value);
+	}
+
+	public boolean equals(Object other) {
+		return (other instanceof Float) && floatToIntBits(((Float) other).value) == floatToIntBits(value);
+	}
+
+	public int compareTo(Float other) {
+		return compare(value, other.value);
+	}
+}
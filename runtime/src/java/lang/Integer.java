@@ -0,0 +1,137 @@
+package java.lang;
+
+public final class Integer extends Number implements Comparable<Integer> {
+	public static final int MIN_VALUE = 0x80000000;
+	public static final int MAX_VALUE = 0x7fffffff;
+	public static final int SIZE = 32;
+	public static final int BYTES = 4;
+	public static final Class<Integer> TYPE = (Class<Integer>) Class.getPrimitiveClass("int");
+
+	private static final Integer[] cache = new Integer[256];
+
+	private final int value;
+
+	public Integer(int value) {
+		this.value = value;
+	}
+
+	public Integer(String s) throws NumberFormatException {
+		this.value = parseInt(s, 10);
+	}
+
+	/**
+	 * The Integer for `i`: the same one every time for -128 to 127.
+	 */
+	public static Integer valueOf(int i) {
+		if (i >= -128 && i <= 127) {
+			Integer cached = cache[i + 128];
+			if (cached == null) {
+				cached = new Integer(i);
+				cache[i + 128] = cached;
+			}
+			return cached;
+		}
+		return new Integer(i);
+	}
+
+	public static Integer valueOf(String s) throws NumberFormatException {
+		return valueOf(parseInt(s, 10));
+	}
+
+	public static int parseInt(String s) throws NumberFormatException {
+		return parseInt(s, 10);
+	}
+
+	public static int parseInt(String s, int radix) throws NumberFormatException {
+		long value = Long.parseLong(s, radix);
+		if (value < MIN_VALUE || value > MAX_VALUE) {
+			throw new NumberFormatException("For input string: \"" + s + "\"");
+		}
+		return (int) value;
+	}
+
+	public static String toString(int i) {
+		return Long.toString(i, 10);
+	}
+
+	public static String toString(int i, int radix) {
+		return Long.toString(i, radix);
+	}
+
+	public static String toHexString(int i) {
+		return Long.toUnsignedString(i & 0xffffffffL, 4);
+	}
+
+	public static String toOctalString(int i) {
+		return Long.toUnsignedString(i & 0xffffffffL, 3);
+	}
+
+	public static String toBinaryString(int i) {
+		return Long.toUnsignedString(i & 0xffffffffL, 1);
+	}
+
+	public static int compare(int x, int y) {
+		return (x < y) ? -1 : ((x == y) ? 0 : 1);
+	}
+
+	public static int signum(int i) {
+		return (i >> 31) | (-i >>> 31);
+	}
+
+	public static int bitCount(int i) {
+		int count = 0;
+		while (i != 0) {
+			count += i & 1;
+			i >>>= 1;
+		}
+		return count;
+	}
+
+	public static int hashCode(int value) {
+		return value;
+	}
+
+	public static int sum(int a, int b) {
+		return a + b;
+	}
+
+	public static int max(int a, int b) {
+		return Math.max(a, b);
+	}
+
+	public static int min(int a, int b) {
+		return Math.min(a, b);
+	}
+
+	public int intValue() {
+		return value;
+	}
+
+	public long longValue() {
+		return value;
+	}
+
+	public float floatValue() {
+		return value;
+	}
+
+	public double doubleValue() {
+		return value;
+	}
+
+	public String toString() {
+		return toString(value);
+	}
+
+	public int hashCode() {
+		return value;
+	}
+
+	public boolean equals(Object other) {
+		return (other instanceof Integer) && value == ((Integer) other).value;
+	}
+
+	public int compareTo(Integer other) {
+		return compare(value, other.value);
+	}
+}
@@ -0,0 +1,170 @@
+package java.lang;
+
+public final class Long extends Number implements Comparable<Long> {
+	public static final long MIN_VALUE = 0x8000000000000000L;
+	public static final long MAX_VALUE = 0x7fffffffffffffffL;
+	public static final int SIZE = 64;
+	public static final int BYTES = 8;
+	public static final Class<Long> TYPE = (Class<Long>) Class.getPrimitiveClass("long");
+
+	private final long value;
+
+	public Long(long value) {
+		this.value = value;
+	}
+
+	public Long(String s) throws NumberFormatException {
+		this.value = parseLong(s, 10);
+	}
+
+	public static Long valueOf(long l) {
+		return new Long(l);
+	}
+
+	public static Long valueOf(String s) throws NumberFormatException {
+		return new Long(parseLong(s, 10));
+	}
+
+	public static long parseLong(String s) throws NumberFormatException {
+		return parseLong(s, 10);
+	}
+
+	public static long parseLong(String s, int radix) throws NumberFormatException {
+		if (s == null) {
+			throw new NumberFormatException("null");
+		}
+		if (radix < Character.MIN_RADIX || radix > Character.MAX_RADIX) {
+			throw new NumberFormatException("radix " + radix + " out of range");
+		}
+		int length = s.length();
+		int i = 0;
+		boolean negative = false;
+		if (length > 0 && (s.charAt(0) == '-' || s.charAt(0) == '+')) {
+			negative = s.charAt(0) == '-';
+			i++;
+		}
+		if (i == length) {
+			throw new NumberFormatException("For input string: \"" + s + "\"");
+		}
+		/*
+		 * Accumulate negatively, since MIN_VALUE has no positive
+		 * counterpart.
+		 */
+		long limit = negative ? MIN_VALUE : -MAX_VALUE;
+		long result = 0;
+		for (; i < length; i++) {
+			int digit = Character.digit(s.charAt(i), radix);
+			if (digit < 0 || result < limit / radix || result * radix < limit + digit) {
+				throw new NumberFormatException("For input string: \"" + s + "\"");
+			}
+			result = result * radix - digit;
+		}
+		return negative ? result : -result;
+	}
+
+	public static String toString(long l) {
+		return toString(l, 10);
+	}
+
+	public static String toString(long l, int radix) {
+		if (radix < Character.MIN_RADIX || radix > Character.MAX_RADIX) {
+			radix = 10;
+		}
+		char[] digits = new char[65];
+		int position = digits.length;
+		boolean negative = l < 0;
+		if (!negative) {
+			l = -l;
+		}
+		do {
+			digits[--position] = Character.forDigit((int) -(l % radix), radix);
+			l /= radix;
+		} while (l != 0);
+		if (negative) {
+			digits[--position] = '-';
+		}
+		return new String(digits, position, digits.length - position);
+	}
+
+	/**
+	 * `l`, as an unsigned number, in base 2 to the `shift`.
+	 */
+	static String toUnsignedString(long l, int shift) {
+		char[] digits = new char[64];
+		int position = digits.length;
+		int mask = (1 << shift) - 1;
+		do {
+			digits[--position] = Character.forDigit((int) (l & mask), 1 << shift);
+			l >>>= shift;
+		} while (l != 0);
+		return new String(digits, position, digits.length - position);
+	}
+
+	public static String toHexString(long l) {
+		return toUnsignedString(l, 4);
+	}
+
+	public static String toOctalString(long l) {
+		return toUnsignedString(l, 3);
+	}
+
+	public static String toBinaryString(long l) {
+		return toUnsignedString(l, 1);
+	}
+
+	public static int compare(long x, long y) {
+		return (x < y) ? -1 : ((x == y) ? 0 : 1);
+	}
+
+	public static int signum(long l) {
+		return (int) ((l >> 63) | (-l >>> 63));
+	}
+
+	public static int hashCode(long value) {
+		return (int) (value ^ (value >>> 32));
+	}
+
+	public static long sum(long a, long b) {
+		return a + b;
+	}
+
+	public static long max(long a, long b) {
+		return Math.max(a, b);
+	}
+
+	public static long min(long a, long b) {
+		return Math.min(a, b);
+	}
+
+	public int intValue() {
+		return (int) value;
+	}
+
+	public long longValue() {
+		return value;
+	}
+
+	public float floatValue() {
+		return value;
+	}
+
+	public double doubleValue() {
+		return value;
+	}
+
+	public String toString() {
+		return toString(value);
+	}
+
+	public int hashCode() {
+		return hashCode(value);
+	}
+
+	public boolean equals(Object other) {
+		return (other instanceof Long) && value == ((Long) other).value;
+	}
+
+	public int compareTo(Long other) {
+		return compare(value, other.value);
+	}
+}
@@ -0,0 +1,176 @@
+package java.lang;
+
+/**
+ * Basic numeric operations. The transcendental functions are native.
+ */
+public final class Math {
+	public static final double E = 2.7182818284590452354;
+	public static final double PI = 3.14159265358979323846;
+
+	private static long seed = System.nanoTime();
+
+	private Math() {
+	}
+
+	public static native double sin(double a);
+
+	public static native double cos(double a);
+
+	public static native double tan(double a);
+
+	public static native double asin(double a);
+
+	public static native double acos(double a);
+
+	public static native double atan(double a);
+
+	public static native double atan2(double y, double x);
+
+	public static native double exp(double a);
+
+	public static native double log(double a);
+
+	public static native double log10(double a);
+
+	public static native double sqrt(double a);
+
+	public static native double cbrt(double a);
+
+	public static native double pow(double a, double b);
+
+	public static native double hypot(double x, double y);
+
+	public static native double sinh(double x);
+
+	public static native double cosh(double x);
+
+	public static native double tanh(double x);
+
+	public static native double expm1(double x);
+
+	public static native double log1p(double x);
+
+	public static native double IEEEremainder(double dividend, double divisor);
+
+	public static native double floor(double a);
+
+	public static native double ceil(double a);
+
+	public static native double rint(double a);
+
+	public static long round(double a) {
+		return (long) floor(a + 0.5d);
+	}
+
+	public static int round(float a) {
+		return (int) floor(a + 0.5f);
+	}
+
+	/**
+	 * A pseudorandom number in [0, 1), from a linear congruential
+	 * generator.
+	 */
+	public static synchronized double random() {
+		seed = (seed * 0x5deece66dL + 0xbL) & ((1L << 48) - 1);
+		long high = seed >>> 22;
+		seed = (seed * 0x5deece66dL + 0xbL) & ((1L << 48) - 1);
+		long low = seed >>> 21;
+		return ((high << 27) + low) * 0x1.0p-53;
+	}
+
+	public static int abs(int a) {
+		return (a < 0) ? -a : a;
+	}
+
+	public static long abs(long a) {
+		return (a < 0) ? -a : a;
+	}
+
+	public static float abs(float a) {
+		return (a <= 0.0f) ? 0.0f - a : a;
+	}
+
+	public static double abs(double a) {
+		return (a <= 0.0d) ? 0.0d - a : a;
+	}
+
+	public static int max(int a, int b) {
+		return (a >= b) ? a : b;
+	}
+
+	public static long max(long a, long b) {
+		return (a >= b) ? a : b;
+	}
+
+	public static float max(float a, float b) {
+		if (a != a) {
+			return a;
+		}
+		if (a == 0.0f && b == 0.0f && Float.floatToRawIntBits(a) == Float.floatToRawIntBits(-0.0f)) {
+			return b;
+		}
+		return (a >= b) ? a : b;
+	}
+
+	public static double max(double a, double b) {
+		if (a != a) {
+			return a;
+		}
+		if (a == 0.0d && b == 0.0d && Double.doubleToRawLongBits(a) == Double.doubleToRawLongBits(-0.0d)) {
+			return b;
+		}
+		return (a >= b) ? a : b;
+	}
+
+	public static int min(int a, int b) {
+		return (a <= b) ? a : b;
+	}
+
+	public static long min(long a, long b) {
+		return (a <= b) ? a : b;
+	}
+
+	public static float min(float a, float b) {
+		if (a != a) {
+			return a;
+		}
+		if (a == 0.0f && b == 0.0f && Float.floatToRawIntBits(b) == Float.floatToRawIntBits(-0.0f)) {
+			return b;
+		}
+		return (a <= b) ? a : b;
+	}
+
+	public static double min(double a, double b) {
+		if (a != a) {
+			return a;
+		}
+		if (a == 0.0d && b == 0.0d && Double.doubleToRawLongBits(b) == Double.doubleToRawLongBits(-0.0d)) {
+			return b;
+		}
+		return (a <= b) ? a : b;
+	}
+
+	public static double signum(double d) {
+		return (d == 0.0 || d != d) ? d : ((d > 0) ? 1.0 : -1.0);
+	}
+
+	public static int floorDiv(int x, int y) {
+		int r = x / y;
+		if ((x % y != 0) && ((x ^ y) < 0)) {
+			r--;
+		}
+		return r;
+	}
+
+	public static int floorMod(int x, int y) {
+		return x - floorDiv(x, y) * y;
+	}
+
+	public static double toRadians(double degrees) {
+		return degrees / 180.0 * PI;
+	}
+
+	public static double toDegrees(double radians) {
+		return radians * 180.0 / PI;
+	}
+}
@@ -0,0 +1,11 @@
+package java.lang;
+
+public class NumberFormatException extends IllegalArgumentException {
+	public NumberFormatException() {
+		super();
+	}
+
+	public NumberFormatException(String message) {
+		super(message);
+	}
+}
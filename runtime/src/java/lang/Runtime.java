@@ -0,0 +1,38 @@
+package java.lang;
+
+public class Runtime {
+	private static final Runtime currentRuntime = new Runtime();
+
+	private Runtime() {
+	}
+
+	public static Runtime getRuntime() {
+		return currentRuntime;
+	}
+
+	public void exit(int status) {
+		Shutdown.exit(status);
+	}
+
+	public void addShutdownHook(Thread hook) {
+		Shutdown.add(hook);
+	}
+
+	public boolean removeShutdownHook(Thread hook) {
+		return Shutdown.remove(hook);
+	}
+
+	public void halt(int status) {
+		Shutdown.halt0(status);
+	}
+
+	public native int availableProcessors();
+
+	public native long freeMemory();
+
+	public native long totalMemory();
+
+	public native long maxMemory();
+
+	public native void gc();
+}
@@ -0,0 +1,71 @@
+package java.lang;
+
+import java.util.ArrayList;
+
+/**
+ * The shutdown sequence. Threads other than the main thread never
+ * run, so the shutdown hooks run one after another, on the thread that
+ * shuts down, in the order in which they were registered.
+ */
+class Shutdown {
+	private static ArrayList<Thread> hooks;
+	private static boolean shuttingDown;
+
+	static synchronized void add(Thread hook) {
+		if (shuttingDown) {
+			throw new IllegalStateException("Shutdown in progress");
+		}
+		if (hook == null) {
+			throw new NullPointerException();
+		}
+		if (hooks == null) {
+			hooks = new ArrayList<Thread>();
+		}
+		if (hooks.contains(hook)) {
+			throw new IllegalArgumentException("Hook previously registered");
+		}
+		hooks.add(hook);
+	}
+
+	static synchronized boolean remove(Thread hook) {
+		if (shuttingDown) {
+			throw new IllegalStateException("Shutdown in progress");
+		}
+		if (hook == null) {
+			throw new NullPointerException();
+		}
+		return hooks != null && hooks.remove(hook);
+	}
+
+	static void exit(int status) {
+		shutdown();
+		halt0(status);
+	}
+
+	static void shutdown() {
+		ArrayList<Thread> registered;
+		synchronized (Shutdown.class) {
+			if (shuttingDown) {
+				return;
+			}
+			shuttingDown = true;
+			registered = hooks;
+			hooks = null;
+		}
+		if (registered == null) {
+			return;
+		}
+		for (int i = 0; i < registered.size(); i++) {
+			try {
+				registered.get(i).run();
+			} catch (Throwable t) {
+				/*
+				 * Like an uncaught exception in a hook's thread, it
+				 * does not stop the other hooks.
+				 */
+			}
+		}
+	}
+
+	static native void halt0(int status);
+}
@@ -0,0 +1,412 @@
+package java.lang;
+
+import java.io.Serializable;
+
+/**
+ * An immutable sequence of UTF-16 code units, held in value.
+ */
+public final class String implements Serializable, Comparable<String>, CharSequence {
+	private final char value[];
+	private int hash;
+
+	public String() {
+		this.value = new char[0];
+	}
+
+	public String(String original) {
+		this.value = original.value;
+		this.hash = original.hash;
+	}
+
+	public String(char value[]) {
+		this(value, 0, value.length);
+	}
+
+	public String(char value[], int offset, int count) {
+		if (offset < 0 || count < 0 || offset > value.length - count) {
+			throw new StringIndexOutOfBoundsException(offset + count);
+		}
+		this.value = new char[count];
+		System.arraycopy(value, offset, this.value, 0, count);
+	}
+
+	/**
+	 * Decode `bytes` as UTF-8.
+	 */
+	public String(byte bytes[]) {
+		this(bytes, 0, bytes.length);
+	}
+
+	public String(byte bytes[], int offset, int length) {
+		char[] decoded = new char[length];
+		int count = 0;
+		int end = offset + length;
+		int i = offset;
+		while (i < end) {
+			int b = bytes[i++] & 0xff;
+			int codePoint;
+			int continuations;
+			if (b < 0x80) {
+				codePoint = b;
+				continuations = 0;
+			} else if (b >= 0xc0 && b < 0xe0) {
+				codePoint = b & 0x1f;
+				continuations = 1;
+			} else if (b >= 0xe0 && b < 0xf0) {
+				codePoint = b & 0x0f;
+				continuations = 2;
+			} else if (b >= 0xf0 && b < 0xf8) {
+				codePoint = b & 0x07;
+				continuations = 3;
+			} else {
+				decoded[count++] = '\ufffd';
+				continue;
+			}
+			while (continuations > 0 && i < end && (bytes[i] & 0xc0) == 0x80) {
+				codePoint = (codePoint << 6) | (bytes[i++] & 0x3f);
+				continuations--;
+			}
+			if (continuations > 0) {
+				decoded[count++] = '\ufffd';
+			} else if (codePoint >= 0x10000) {
+				codePoint -= 0x10000;
+				decoded[count++] = (char) (0xd800 + (codePoint >> 10));
+				decoded[count++] = (char) (0xdc00 + (codePoint & 0x3ff));
+			} else {
+				decoded[count++] = (char) codePoint;
+			}
+		}
+		this.value = new char[count];
+		System.arraycopy(decoded, 0, this.value, 0, count);
+	}
+
+	public String(StringBuilder builder) {
+		this(builder.toString());
+	}
+
+	public int length() {
+		return value.length;
+	}
+
+	public boolean isEmpty() {
+		return value.length == 0;
+	}
+
+	public char charAt(int index) {
+		if (index < 0 || index >= value.length) {
+			throw new StringIndexOutOfBoundsException(index);
+		}
+		return value[index];
+	}
+
+	public void getChars(int srcBegin, int srcEnd, char dst[], int dstBegin) {
+		if (srcBegin < 0 || srcEnd > value.length || srcBegin > srcEnd) {
+			throw new StringIndexOutOfBoundsException(srcBegin);
+		}
+		System.arraycopy(value, srcBegin, dst, dstBegin, srcEnd - srcBegin);
+	}
+
+	/**
+	 * Encode the string as UTF-8.
+	 */
+	public byte[] getBytes() {
+		byte[] encoded = new byte[value.length * 3];
+		int count = 0;
+		for (int i = 0; i < value.length; i++) {
+			int c = value[i];
+			if (c >= 0xd800 && c < 0xdc00 && i + 1 < value.length && value[i + 1] >= 0xdc00 && value[i + 1] < 0xe000) {
+				int codePoint = 0x10000 + ((c - 0xd800) << 10) + (value[++i] - 0xdc00);
+				encoded[count++] = (byte) (0xf0 | (codePoint >> 18));
+				encoded[count++] = (byte) (0x80 | ((codePoint >> 12) & 0x3f));
+				encoded[count++] = (byte) (0x80 | ((codePoint >> 6) & 0x3f));
+				encoded[count++] = (byte) (0x80 | (codePoint & 0x3f));
+			} else if (c < 0x80) {
+				encoded[count++] = (byte) c;
+			} else if (c < 0x800) {
+				encoded[count++] = (byte) (0xc0 | (c >> 6));
+				encoded[count++] = (byte) (0x80 | (c & 0x3f));
+			} else {
+				encoded[count++] = (byte) (0xe0 | (c >> 12));
+				encoded[count++] = (byte) (0x80 | ((c >> 6) & 0x3f));
+				encoded[count++] = (byte) (0x80 | (c & 0x3f));
+			}
+		}
+		byte[] result = new byte[count];
+		System.arraycopy(encoded, 0, result, 0, count);
+		return result;
+	}
+
+	public boolean equals(Object other) {
+		if (this == other) {
+			return true;
+		}
+		if (!(other instanceof String)) {
+			return false;
+		}
+		String string = (String) other;
+		if (string.value.length != value.length) {
+			return false;
+		}
+		for (int i = 0; i < value.length; i++) {
+			if (string.value[i] != value[i]) {
+				return false;
+			}
+		}
+		return true;
+	}
+
+	public boolean equalsIgnoreCase(String other) {
+		if (other == null || other.value.length != value.length) {
+			return false;
+		}
+		for (int i = 0; i < value.length; i++) {
+			if (Character.toLowerCase(value[i]) != Character.toLowerCase(other.value[i])) {
+				return false;
+			}
+		}
+		return true;
+	}
+
+	public boolean contentEquals(CharSequence sequence) {
+		return equals(sequence.toString());
+	}
+
+	public int compareTo(String other) {
+		int length = Math.min(value.length, other.value.length);
+		for (int i = 0; i < length; i++) {
+			if (value[i] != other.value[i]) {
+				return value[i] - other.value[i];
+			}
+		}
+		return value.length - other.value.length;
+	}
+
+	public int hashCode() {
+		int h = hash;
+		if (h == 0 && value.length > 0) {
+			for (int i = 0; i < value.length; i++) {
+				h = 31 * h + value[i];
+			}
+			hash = h;
+		}
+		return h;
+	}
+
+	public boolean startsWith(String prefix, int offset) {
+		if (offset < 0 || offset > value.length - prefix.value.length) {
+			return false;
+		}
+		for (int i = 0; i < prefix.value.length; i++) {
+			if (value[offset + i] != prefix.value[i]) {
+				return false;
+			}
+		}
+		return true;
+	}
+
+	public boolean startsWith(String prefix) {
+		return startsWith(prefix, 0);
+	}
+
+	public boolean endsWith(String suffix) {
+		return startsWith(suffix, value.length - suffix.value.length);
+	}
+
+	public int indexOf(int ch) {
+		return indexOf(ch, 0);
+	}
+
+	public int indexOf(int ch, int fromIndex) {
+		for (int i = Math.max(fromIndex, 0); i < value.length; i++) {
+			if (value[i] == ch) {
+				return i;
+			}
+		}
+		return -1;
+	}
+
+	public int lastIndexOf(int ch) {
+		for (int i = value.length - 1; i >= 0; i--) {
+			if (value[i] == ch) {
+				return i;
+			}
+		}
+		return -1;
+	}
+
+	public int indexOf(String string) {
+		return indexOf(string, 0);
+	}
+
+	public int indexOf(String string, int fromIndex) {
+		for (int i = Math.max(fromIndex, 0); i <= value.length - string.value.length; i++) {
+			if (startsWith(string, i)) {
+				return i;
+			}
+		}
+		return -1;
+	}
+
+	public int lastIndexOf(String string) {
+		for (int i = value.length - string.value.length; i >= 0; i--) {
+			if (startsWith(string, i)) {
+				return i;
+			}
+		}
+		return -1;
+	}
+
+	public boolean contains(CharSequence sequence) {
+		return indexOf(sequence.toString()) >= 0;
+	}
+
+	public String substring(int beginIndex) {
+		return substring(beginIndex, value.length);
+	}
+
+	public String substring(int beginIndex, int endIndex) {
+		if (beginIndex < 0) {
+			throw new StringIndexOutOfBoundsException(beginIndex);
+		}
+		if (endIndex > value.length) {
+			throw new StringIndexOutOfBoundsException(endIndex);
+		}
+		if (beginIndex > endIndex) {
+			throw new StringIndexOutOfBoundsException(endIndex - beginIndex);
+		}
+		return new String(value, beginIndex, endIndex - beginIndex);
+	}
+
+	public CharSequence subSequence(int beginIndex, int endIndex) {
+		return substring(beginIndex, endIndex);
+	}
+
+	public String concat(String string) {
+		if (string.value.length == 0) {
+			return this;
+		}
+		char[] result = new char[value.length + string.value.length];
+		System.arraycopy(value, 0, result, 0, value.length);
+		System.arraycopy(string.value, 0, result, value.length, string.value.length);
+		return new String(result);
+	}
+
+	public String replace(char oldChar, char newChar) {
+		char[] result = new char[value.length];
+		for (int i = 0; i < value.length; i++) {
+			result[i] = (value[i] == oldChar) ? newChar : value[i];
+		}
+		return new String(result);
+	}
+
+	public String replace(CharSequence target, CharSequence replacement) {
+		String from = target.toString();
+		String to = replacement.toString();
+		if (from.isEmpty()) {
+			throw new UnsupportedOperationException("replacing the empty string");
+		}
+		StringBuilder result = new StringBuilder();
+		int start = 0;
+		for (int index = indexOf(from); index >= 0; index = indexOf(from, start)) {
+			result.append(substring(start, index)).append(to);
+			start = index + from.value.length;
+		}
+		return result.append(substring(start)).toString();
+	}
+
+	public String trim() {
+		int begin = 0;
+		int end = value.length;
+		while (begin < end && value[begin] <= ' ') {
+			begin++;
+		}
+		while (end > begin && value[end - 1] <= ' ') {
+			end--;
+		}
+		return (begin > 0 || end < value.length) ? substring(begin, end) : this;
+	}
+
+	public String toLowerCase() {
+		char[] result = new char[value.length];
+		for (int i = 0; i < value.length; i++) {
+			result[i] = Character.toLowerCase(value[i]);
+		}
+		return new String(result);
+	}
+
+	public String toUpperCase() {
+		char[] result = new char[value.length];
+		for (int i = 0; i < value.length; i++) {
+			result[i] = Character.toUpperCase(value[i]);
+		}
+		return new String(result);
+	}
+
+	public String[] split(String separator) {
+		int count = 1;
+		for (int index = indexOf(separator); index >= 0 && separator.length() > 0; index = indexOf(separator, index + separator.length())) {
+			count++;
+		}
+		String[] parts = new String[count];
+		int start = 0;
+		for (int i = 0; i < count - 1; i++) {
+			int index = indexOf(separator, start);
+			parts[i] = substring(start, index);
+			start = index + separator.length();
+		}
+		parts[count - 1] = substring(start);
+		while (count > 1 && parts[count - 1].isEmpty()) {
+			count--;
+		}
+		if (count == parts.length) {
+			return parts;
+		}
+		String[] trimmed = new String[count];
+		System.arraycopy(parts, 0, trimmed, 0, count);
+		return trimmed;
+	}
+
+	public char[] toCharArray() {
+		char[] result = new char[value.length];
+		System.arraycopy(value, 0, result, 0, value.length);
+		return result;
+	}
+
+	public String toString() {
+		return this;
+	}
+
+	public native String intern();
+
+	public static String valueOf(Object object) {
+		return (object == null) ? "null" : object.toString();
+	}
+
+	public static String valueOf(char data[]) {
+		return new String(data);
+	}
+
+	public static String valueOf(boolean b) {
+		return b ? "true" : "false";
+	}
+
+	public static String valueOf(char c) {
+		return new String(new char[] { c });
+	}
+
+	public static String valueOf(int i) {
+		return Integer.toString(i);
+	}
+
+	public static String valueOf(long l) {
+		return Long.toString(l);
+	}
+
+	public static String valueOf(float f) {
+		return Float.toString(f);
+	}
+
+	public static String valueOf(double d) {
+		return Double.toString(d);
+	}
+}
@@ -0,0 +1,193 @@
+package java.lang;
+
+/**
+ * A mutable sequence of characters: the first count of value.
+ */
+public final class StringBuilder implements CharSequence {
+	private char[] value;
+	private int count;
+
+	public StringBuilder() {
+		this(16);
+	}
+
+	public StringBuilder(int capacity) {
+		value = new char[capacity];
+	}
+
+	public StringBuilder(String string) {
+		this(string.length() + 16);
+		append(string);
+	}
+
+	public StringBuilder(CharSequence sequence) {
+		this(sequence.toString());
+	}
+
+	private void ensureCapacity(int minimumCapacity) {
+		if (minimumCapacity > value.length) {
+			char[] grown = new char[Math.max(minimumCapacity, value.length * 2 + 2)];
+			System.arraycopy(value, 0, grown, 0, count);
+			value = grown;
+		}
+	}
+
+	public int length() {
+		return count;
+	}
+
+	public int capacity() {
+		return value.length;
+	}
+
+	public char charAt(int index) {
+		if (index < 0 || index >= count) {
+			throw new StringIndexOutOfBoundsException(index);
+		}
+		return value[index];
+	}
+
+	public void setCharAt(int index, char c) {
+		if (index < 0 || index >= count) {
+			throw new StringIndexOutOfBoundsException(index);
+		}
+		value[index] = c;
+	}
+
+	public void setLength(int length) {
+		if (length < 0) {
+			throw new StringIndexOutOfBoundsException(length);
+		}
+		ensureCapacity(length);
+		for (int i = count; i < length; i++) {
+			value[i] = '\0';
+		}
+		count = length;
+	}
+
+	public StringBuilder append(String string) {
+		if (string == null) {
+			string = "null";
+		}
+		int length = string.length();
+		ensureCapacity(count + length);
+		string.getChars(0, length, value, count);
+		count += length;
+		return this;
+	}
+
+	public StringBuilder append(Object object) {
+		return append(String.valueOf(object));
+	}
+
+	public StringBuilder append(CharSequence sequence) {
+		return append(String.valueOf(sequence));
+	}
+
+	public StringBuilder append(StringBuilder builder) {
+		return append(String.valueOf(builder));
+	}
+
+	public StringBuilder append(char[] characters) {
+		ensureCapacity(count + characters.length);
+		System.arraycopy(characters, 0, value, count, characters.length);
+		count += characters.length;
+		return this;
+	}
+
+	public StringBuilder append(char c) {
+		ensureCapacity(count + 1);
+		value[count++] = c;
+		return this;
+	}
+
+	public StringBuilder append(boolean b) {
+		return append(String.valueOf(b));
+	}
+
+	public StringBuilder append(int i) {
+		return append(Integer.toString(i));
+	}
+
+	public StringBuilder append(long l) {
+		return append(Long.toString(l));
+	}
+
+	public StringBuilder append(float f) {
+		return append(Float.toString(f));
+	}
+
+	public StringBuilder append(double d) {
+		return append(Double.toString(d));
+	}
+
+	public StringBuilder insert(int offset, String string) {
+		if (offset < 0 || offset > count) {
+			throw new StringIndexOutOfBoundsException(offset);
+		}
+		if (string == null) {
+			string = "null";
+		}
+		int length = string.length();
+		ensureCapacity(count + length);
+		System.arraycopy(value, offset, value, offset + length, count - offset);
+		string.getChars(0, length, value, offset);
+		count += length;
+		return this;
+	}
+
+	public StringBuilder insert(int offset, char c) {
+		return insert(offset, String.valueOf(c));
+	}
+
+	public StringBuilder delete(int start, int end) {
+		if (end > count) {
+			end = count;
+		}
+		if (start < 0 || start > end) {
+			throw new StringIndexOutOfBoundsException(start);
+		}
+		System.arraycopy(value, end, value, start, count - end);
+		count -= end - start;
+		return this;
+	}
+
+	public StringBuilder deleteCharAt(int index) {
+		if (index < 0 || index >= count) {
+			throw new StringIndexOutOfBoundsException(index);
+		}
+		return delete(index, index + 1);
+	}
+
+	public StringBuilder reverse() {
+		for (int i = 0, j = count - 1; i < j; i++, j--) {
+			char c = value[i];
+			value[i] = value[j];
+			value[j] = c;
+		}
+		return this;
+	}
+
+	public int indexOf(String string) {
+		return toString().indexOf(string);
+	}
+
+	public CharSequence subSequence(int start, int end) {
+		return substring(start, end);
+	}
+
+	public String substring(int start) {
+		return substring(start, count);
+	}
+
+	public String substring(int start, int end) {
+		if (start < 0 || end > count || start > end) {
+			throw new StringIndexOutOfBoundsException(start);
+		}
+		return new String(value, start, end - start);
+	}
+
+	public String toString() {
+		return new String(value, 0, count);
+	}
+}
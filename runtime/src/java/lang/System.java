@@ -0,0 +1,96 @@
+package java.lang;
+
+import java.io.InputStream;
+import java.io.PrintStream;
+import java.util.HashMap;
+import java.util.Map;
+import java.util.Properties;
+
+/**
+ * The standard streams, the system properties and the VM's services.
+ * The VM sets in, out and err while it boots.
+ */
+public final class System {
+	public final static InputStream in = null;
+	public final static PrintStream out = null;
+	public final static PrintStream err = null;
+
+	private static Properties props;
+
+	private System() {
+	}
+
+	public static native long currentTimeMillis();
+
+	public static native long nanoTime();
+
+	public static native void arraycopy(Object src, int srcPos, Object dest, int destPos, int length);
+
+	public static native int identityHashCode(Object x);
+
+	private static native Properties initProperties(Properties props);
+
+	public static Properties getProperties() {
+		return props;
+	}
+
+	public static String getProperty(String key) {
+		return props.getProperty(key);
+	}
+
+	public static String getProperty(String key, String def) {
+		return props.getProperty(key, def);
+	}
+
+	public static String setProperty(String key, String value) {
+		return (String) props.setProperty(key, value);
+	}
+
+	/**
+	 * The names and values of the variables of the environment,
+	 * alternately.
+	 */
+	private static native String[] environ();
+
+	public static String getenv(String name) {
+		if (name == null) {
+			throw new NullPointerException();
+		}
+		String[] environment = environ();
+		for (int i = 0; i < environment.length; i += 2) {
+			if (environment[i].equals(name)) {
+				return environment[i + 1];
+			}
+		}
+		return null;
+	}
+
+	public static Map<String, String> getenv() {
+		String[] environment = environ();
+		Map<String, String> variables = new HashMap<String, String>();
+		for (int i = 0; i < environment.length; i += 2) {
+			variables.put(environment[i], environment[i + 1]);
+		}
+		return variables;
+	}
+
+	public static String lineSeparator() {
+		return "\n";
+	}
+
+	public static void exit(int status) {
+		Runtime.getRuntime().exit(status);
+	}
+
+	public static void gc() {
+		Runtime.getRuntime().gc();
+	}
+
+	/**
+	 * Called by the VM while it boots.
+	 */
+	private static void initializeSystemClass() {
+		props = new Properties();
+		initProperties(props);
+	}
+}
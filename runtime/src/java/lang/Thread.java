@@ -0,0 +1,129 @@
+package java.lang;
+
+/**
+ * A thread of execution. Only the main thread, which the VM creates,
+ * runs: start does not run a new thread.
+ */
+public class Thread implements Runnable {
+	public static final int MIN_PRIORITY = 1;
+	public static final int NORM_PRIORITY = 5;
+	public static final int MAX_PRIORITY = 10;
+
+	private static int threadNumber;
+
+	private volatile String name;
+	private int priority;
+	private boolean daemon;
+	private Runnable target;
+	private ThreadGroup group;
+	private volatile int threadStatus;
+
+	public Thread() {
+		this(null, null, "Thread-" + nextThreadNumber());
+	}
+
+	public Thread(Runnable target) {
+		this(null, target, "Thread-" + nextThreadNumber());
+	}
+
+	public Thread(String name) {
+		this(null, null, name);
+	}
+
+	public Thread(ThreadGroup group, String name) {
+		this(group, null, name);
+	}
+
+	public Thread(Runnable target, String name) {
+		this(null, target, name);
+	}
+
+	public Thread(ThreadGroup group, Runnable target, String name) {
+		Thread parent = currentThread();
+		if (group == null) {
+			group = parent.getThreadGroup();
+		}
+		this.group = group;
+		this.target = target;
+		this.name = name;
+		this.priority = parent.getPriority();
+		this.daemon = parent.isDaemon();
+	}
+
+	private static synchronized int nextThreadNumber() {
+		return threadNumber++;
+	}
+
+	public static native Thread currentThread();
+
+	public static native void yield();
+
+	public static native void sleep(long millis) throws InterruptedException;
+
+	public static boolean holdsLock(Object obj) {
+		return true;
+	}
+
+	public synchronized void start() {
+		start0();
+	}
+
+	private native void start0();
+
+	public void run() {
+		if (target != null) {
+			target.run();
+		}
+	}
+
+	public final native boolean isAlive();
+
+	public boolean isInterrupted() {
+		return false;
+	}
+
+	public static boolean interrupted() {
+		return false;
+	}
+
+	public void interrupt() {
+	}
+
+	public final String getName() {
+		return name;
+	}
+
+	public final void setName(String name) {
+		this.name = name;
+	}
+
+	public final int getPriority() {
+		return priority;
+	}
+
+	public final void setPriority(int priority) {
+		if (priority < MIN_PRIORITY || priority > MAX_PRIORITY) {
+			throw new IllegalArgumentException();
+		}
+		this.priority = priority;
+	}
+
+	public final boolean isDaemon() {
+		return daemon;
+	}
+
+	public final void setDaemon(boolean daemon) {
+		this.daemon = daemon;
+	}
+
+	public final ThreadGroup getThreadGroup() {
+		return group;
+	}
+
+	public final void join() throws InterruptedException {
+	}
+
+	public String toString() {
+		return "Thread[" + getName() + "," + getPriority() + "," + (group == null ? "" : group.getName()) + "]";
+	}
+}
@@ -0,0 +1,35 @@
+package java.lang;
+
+public class ThreadGroup {
+	private final ThreadGroup parent;
+	private final String name;
+
+	/**
+	 * Create the system thread group. Only the VM calls this.
+	 */
+	private ThreadGroup() {
+		this.parent = null;
+		this.name = "system";
+	}
+
+	public ThreadGroup(String name) {
+		this(Thread.currentThread().getThreadGroup(), name);
+	}
+
+	public ThreadGroup(ThreadGroup parent, String name) {
+		this.parent = parent;
+		this.name = name;
+	}
+
+	public final String getName() {
+		return name;
+	}
+
+	public final ThreadGroup getParent() {
+		return parent;
+	}
+
+	public String toString() {
+		return getClass().getName() + "[name=" + getName() + "]";
+	}
+}
@@ -0,0 +1,100 @@
+package java.lang;
+
+import java.io.PrintStream;
+
+/**
+ * The superclass of everything that can be thrown. The VM records the
+ * stack trace (in backtrace) when a Throwable is created.
+ */
+public class Throwable {
+	private transient Object backtrace;
+	private String detailMessage;
+	private Throwable cause = this;
+
+	public Throwable() {
+		fillInStackTrace();
+	}
+
+	public Throwable(String message) {
+		fillInStackTrace();
+		detailMessage = message;
+	}
+
+	public Throwable(String message, Throwable cause) {
+		fillInStackTrace();
+		detailMessage = message;
+		this.cause = cause;
+	}
+
+	public Throwable(Throwable cause) {
+		fillInStackTrace();
+		detailMessage = (cause == null ? null : cause.toString());
+		this.cause = cause;
+	}
+
+	public String getMessage() {
+		return detailMessage;
+	}
+
+	public String getLocalizedMessage() {
+		return getMessage();
+	}
+
+	public synchronized Throwable getCause() {
+		return (cause == this ? null : cause);
+	}
+
+	public synchronized Throwable initCause(Throwable cause) {
+		if (this.cause != this) {
+			throw new IllegalStateException("Can't overwrite cause");
+		}
+		if (cause == this) {
+			throw new IllegalArgumentException("Self-causation not permitted");
+		}
+		this.cause = cause;
+		return this;
+	}
+
+	public String toString() {
+		String message = getLocalizedMessage();
+		String name = getClass().getName();
+		return (message != null) ? (name + ": " + message) : name;
+	}
+
+	public void printStackTrace() {
+		printStackTrace(System.err);
+	}
+
+	public void printStackTrace(PrintStream s) {
+		s.println(this);
+		StackTraceElement[] trace = getStackTrace();
+		for (int i = 0; i < trace.length; i++) {
+			s.println("\tat " + trace[i]);
+		}
+		Throwable cause = getCause();
+		if (cause != null) {
+			s.print("Caused by: ");
+			cause.printStackTrace(s);
+		}
+	}
+
+	public synchronized Throwable fillInStackTrace() {
+		fillInStackTrace(0);
+		return this;
+	}
+
+	private native Throwable fillInStackTrace(int dummy);
+
+	public StackTraceElement[] getStackTrace() {
+		int depth = getStackTraceDepth();
+		StackTraceElement[] trace = new StackTraceElement[depth];
+		for (int i = 0; i < depth; i++) {
+			trace[i] = getStackTraceElement(i);
+		}
+		return trace;
+	}
+
+	native int getStackTraceDepth();
+
+	native StackTraceElement getStackTraceElement(int index);
+}
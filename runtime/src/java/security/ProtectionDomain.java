@@ -0,0 +1,7 @@
+package java.security;
+
+/**
+ * The VM does not enforce protection domains.
+ */
+public class ProtectionDomain {
+}
@@ -0,0 +1,79 @@
+package java.util;
+
+public abstract class AbstractCollection<E> implements Collection<E> {
+	protected AbstractCollection() {
+	}
+
+	public abstract Iterator<E> iterator();
+
+	public abstract int size();
+
+	public boolean isEmpty() {
+		return size() == 0;
+	}
+
+	public boolean contains(Object o) {
+		Iterator<E> it = iterator();
+		while (it.hasNext()) {
+			if (Objects.equals(o, it.next())) {
+				return true;
+			}
+		}
+		return false;
+	}
+
+	public Object[] toArray() {
+		Object[] result = new Object[size()];
+		Iterator<E> it = iterator();
+		for (int i = 0; i < result.length && it.hasNext(); i++) {
+			result[i] = it.next();
+		}
+		return result;
+	}
+
+	public boolean add(E e) {
+		throw new UnsupportedOperationException();
+	}
+
+	public boolean remove(Object o) {
+		Iterator<E> it = iterator();
+		while (it.hasNext()) {
+			if (Objects.equals(o, it.next())) {
+				it.remove();
+				return true;
+			}
+		}
+		return false;
+	}
+
+	public boolean addAll(Collection<? extends E> c) {
+		boolean modified = false;
+		for (E e : c) {
+			if (add(e)) {
+				modified = true;
+			}
+		}
+		return modified;
+	}
+
+	public void clear() {
+		Iterator<E> it = iterator();
+		while (it.hasNext()) {
+			it.next();
+			it.remove();
+		}
+	}
+
+	public String toString() {
+		Iterator<E> it = iterator();
+		StringBuilder sb = new StringBuilder("[");
+		while (it.hasNext()) {
+			E e = it.next();
+			sb.append(e == this ? "(this Collection)" : e);
+			if (it.hasNext()) {
+				sb.append(", ");
+			}
+		}
+		return sb.append(']').toString();
+	}
+}
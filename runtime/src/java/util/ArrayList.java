@@ -0,0 +1,134 @@
+package java.util;
+
+public class ArrayList<E> extends AbstractCollection<E> implements List<E> {
+	private static final int DEFAULT_CAPACITY = 10;
+
+	private Object[] elementData;
+	private int size;
+
+	public ArrayList() {
+		this(DEFAULT_CAPACITY);
+	}
+
+	public ArrayList(int initialCapacity) {
+		if (initialCapacity < 0) {
+			throw new IllegalArgumentException("Illegal Capacity: " + initialCapacity);
+		}
+		elementData = new Object[initialCapacity];
+	}
+
+	public ArrayList(Collection<? extends E> c) {
+		elementData = c.toArray();
+		size = elementData.length;
+	}
+
+	private void ensureCapacity(int minCapacity) {
+		if (minCapacity > elementData.length) {
+			int capacity = elementData.length + (elementData.length >> 1) + 1;
+			elementData = Arrays.copyOf(elementData, capacity < minCapacity ? minCapacity : capacity);
+		}
+	}
+
+	private void checkIndex(int index, int bound) {
+		if (index < 0 || index >= bound) {
+			throw new IndexOutOfBoundsException("Index: " + index + ", Size: " + size);
+		}
+	}
+
+	public int size() {
+		return size;
+	}
+
+	public E get(int index) {
+		checkIndex(index, size);
+		return (E) elementData[index];
+	}
+
+	public E set(int index, E element) {
+		E old = get(index);
+		elementData[index] = element;
+		return old;
+	}
+
+	public boolean add(E e) {
+		ensureCapacity(size + 1);
+		elementData[size++] = e;
+		return true;
+	}
+
+	public void add(int index, E element) {
+		checkIndex(index, size + 1);
+		ensureCapacity(size + 1);
+		System.arraycopy(elementData, index, elementData, index + 1, size - index);
+		elementData[index] = element;
+		size++;
+	}
+
+	public E remove(int index) {
+		E old = get(index);
+		System.arraycopy(elementData, index + 1, elementData, index, size - index - 1);
+		elementData[--size] = null;
+		return old;
+	}
+
+	public boolean remove(Object o) {
+		int index = indexOf(o);
+		if (index < 0) {
+			return false;
+		}
+		remove(index);
+		return true;
+	}
+
+	public int indexOf(Object o) {
+		for (int i = 0; i < size; i++) {
+			if (Objects.equals(o, elementData[i])) {
+				return i;
+			}
+		}
+		return -1;
+	}
+
+	public boolean contains(Object o) {
+		return indexOf(o) >= 0;
+	}
+
+	public Object[] toArray() {
+		return Arrays.copyOf(elementData, size);
+	}
+
+	public void clear() {
+		for (int i = 0; i < size; i++) {
+			elementData[i] = null;
+		}
+		size = 0;
+	}
+
+	public Iterator<E> iterator() {
+		return new Iterator<E>() {
+			private int cursor;
+			private int last = -1;
+
+			public boolean hasNext() {
+				return cursor < size;
+			}
+
+			public E next() {
+				if (cursor >= size) {
+					throw new NoSuchElementException();
+				}
+				last = cursor;
+				return get(cursor++);
+			}
+
+			public void remove() {
+				if (last < 0) {
+					throw new IllegalStateException();
+				}
+				ArrayList.this.remove(last);
+				cursor = last;
+				last = -1;
+			}
+		};
+	}
+}
@@ -0,0 +1,76 @@
+package java.util;
+
+public class Arrays {
+	private Arrays() {
+	}
+
+	public static Object[] copyOf(Object[] original, int newLength) {
+		Object[] copy = new Object[newLength];
+		System.arraycopy(original, 0, copy, 0, Math.min(original.length, newLength));
+		return copy;
+	}
+
+	public static char[] copyOf(char[] original, int newLength) {
+		char[] copy = new char[newLength];
+		System.arraycopy(original, 0, copy, 0, Math.min(original.length, newLength));
+		return copy;
+	}
+
+	public static byte[] copyOf(byte[] original, int newLength) {
+		byte[] copy = new byte[newLength];
+		System.arraycopy(original, 0, copy, 0, Math.min(original.length, newLength));
+		return copy;
+	}
+
+	public static int[] copyOf(int[] original, int newLength) {
+		int[] copy = new int[newLength];
+		System.arraycopy(original, 0, copy, 0, Math.min(original.length, newLength));
+		return copy;
+	}
+
+	public static void fill(int[] a, int val) {
+		for (int i = 0; i < a.length; i++) {
+			a[i] = val;
+		}
+	}
+
+	public static void fill(char[] a, char val) {
+		for (int i = 0; i < a.length; i++) {
+			a[i] = val;
+		}
+	}
+
+	public static void fill(Object[] a, Object val) {
+		for (int i = 0; i < a.length; i++) {
+			a[i] = val;
+		}
+	}
+
+	public static String toString(int[] a) {
+		if (a == null) {
+			return "null";
+		}
+		StringBuilder sb = new StringBuilder("[");
+		for (int i = 0; i < a.length; i++) {
+			if (i > 0) {
+				sb.append(", ");
+			}
+			sb.append(a[i]);
+		}
+		return sb.append(']').toString();
+	}
+
+	public static String toString(Object[] a) {
+		if (a == null) {
+			return "null";
+		}
+		StringBuilder sb = new StringBuilder("[");
+		for (int i = 0; i < a.length; i++) {
+			if (i > 0) {
+				sb.append(", ");
+			}
+			sb.append(String.valueOf(a[i]));
+		}
+		return sb.append(']').toString();
+	}
+}
@@ -0,0 +1,19 @@
+package java.util;
+
+public interface Collection<E> extends Iterable<E> {
+	int size();
+
+	boolean isEmpty();
+
+	boolean contains(Object o);
+
+	Object[] toArray();
+
+	boolean add(E e);
+
+	boolean remove(Object o);
+
+	boolean addAll(Collection<? extends E> c);
+
+	void clear();
+}
@@ -0,0 +1,234 @@
+package java.util;
+
+/**
+ * A hash table of chained entries that doubles its buckets when it is
+ * three quarters full.
+ */
+public class HashMap<K, V> implements Map<K, V> {
+	private static final int DEFAULT_CAPACITY = 16;
+
+	static class Node<K, V> implements Map.Entry<K, V> {
+		final int hash;
+		final K key;
+		V value;
+		Node<K, V> next;
+
+		Node(int hash, K key, V value, Node<K, V> next) {
+			this.hash = hash;
+			this.key = key;
+			this.value = value;
+			this.next = next;
+		}
+
+		public K getKey() {
+			return key;
+		}
+
+		public V getValue() {
+			return value;
+		}
+
+		public V setValue(V value) {
+			V old = this.value;
+			this.value = value;
+			return old;
+		}
+
+		public String toString() {
+			return key + "=" + value;
+		}
+	}
+
+	private Node<K, V>[] table;
+	private int size;
+
+	public HashMap() {
+		this(DEFAULT_CAPACITY);
+	}
+
+	public HashMap(int initialCapacity) {
+		if (initialCapacity < 0) {
+			throw new IllegalArgumentException("Illegal initial capacity: " + initialCapacity);
+		}
+		int capacity = 1;
+		while (capacity < initialCapacity) {
+			capacity <<= 1;
+		}
+		table = (Node<K, V>[]) new Node[capacity];
+	}
+
+	static int hash(Object key) {
+		int h = key == null ? 0 : key.hashCode();
+		return h ^ (h >>> 16);
+	}
+
+	private Node<K, V> getNode(Object key) {
+		int hash = hash(key);
+		for (Node<K, V> e = table[hash & (table.length - 1)]; e != null; e = e.next) {
+			if (e.hash == hash && Objects.equals(key, e.key)) {
+				return e;
+			}
+		}
+		return null;
+	}
+
+	private void resize() {
+		Node<K, V>[] old = table;
+		table = (Node<K, V>[]) new Node[old.length * 2];
+		for (Node<K, V> e : old) {
+			while (e != null) {
+				Node<K, V> next = e.next;
+				int index = e.hash & (table.length - 1);
+				e.next = table[index];
+				table[index] = e;
+				e = next;
+			}
+		}
+	}
+
+	public int size() {
+		return size;
+	}
+
+	public boolean isEmpty() {
+		return size == 0;
+	}
+
+	public boolean containsKey(Object key) {
+		return getNode(key) != null;
+	}
+
+	public V get(Object key) {
+		Node<K, V> e = getNode(key);
+		return e == null ? null : e.value;
+	}
+
+	public V put(K key, V value) {
+		Node<K, V> e = getNode(key);
+		if (e != null) {
+			return e.setValue(value);
+		}
+		int hash = hash(key);
+		int index = hash & (table.length - 1);
+		table[index] = new Node<>(hash, key, value, table[index]);
+		if (++size > table.length - (table.length >> 2)) {
+			resize();
+		}
+		return null;
+	}
+
+	public V remove(Object key) {
+		int hash = hash(key);
+		int index = hash & (table.length - 1);
+		Node<K, V> previous = null;
+		for (Node<K, V> e = table[index]; e != null; previous = e, e = e.next) {
+			if (e.hash == hash && Objects.equals(key, e.key)) {
+				if (previous == null) {
+					table[index] = e.next;
+				} else {
+					previous.next = e.next;
+				}
+				size--;
+				return e.value;
+			}
+		}
+		return null;
+	}
+
+	public void clear() {
+		for (int i = 0; i < table.length; i++) {
+			table[i] = null;
+		}
+		size = 0;
+	}
+
+	private abstract class HashIterator<T> implements Iterator<T> {
+		private int index;
+		private Node<K, V> next;
+		private Node<K, V> current;
+
+		HashIterator() {
+			advance();
+		}
+
+		private void advance() {
+			while (next == null && index < table.length) {
+				next = table[index++];
+			}
+		}
+
+		public boolean hasNext() {
+			return next != null;
+		}
+
+		Node<K, V> nextNode() {
+			if (next == null) {
+				throw new NoSuchElementException();
+			}
+			current = next;
+			next = next.next;
+			advance();
+			return current;
+		}
+
+		public void remove() {
+			if (current == null) {
+				throw new IllegalStateException();
+			}
+			HashMap.this.remove(current.key);
+			current = null;
+		}
+	}
+
+	public Set<K> keySet() {
+		return new KeySet();
+	}
+
+	public Set<Map.Entry<K, V>> entrySet() {
+		return new EntrySet();
+	}
+
+	private class KeySet extends AbstractCollection<K> implements Set<K> {
+		public int size() {
+			return size;
+		}
+
+		public boolean contains(Object o) {
+			return containsKey(o);
+		}
+
+		public Iterator<K> iterator() {
+			return new HashIterator<K>() {
+				public K next() {
+					return nextNode().key;
+				}
+			};
+		}
+	}
+
+	private class EntrySet extends AbstractCollection<Map.Entry<K, V>> implements Set<Map.Entry<K, V>> {
+		public int size() {
+			return size;
+		}
+
+		public Iterator<Map.Entry<K, V>> iterator() {
+			return new HashIterator<Map.Entry<K, V>>() {
+				public Map.Entry<K, V> next() {
+					return nextNode();
+				}
+			};
+		}
+	}
+
+	public String toString() {
+		StringBuilder sb = new StringBuilder("{");
+		Iterator<Map.Entry<K, V>> it = entrySet().iterator();
+		while (it.hasNext()) {
+			sb.append(it.next());
+			if (it.hasNext()) {
+				sb.append(", ");
+			}
+		}
+		return sb.append('}').toString();
+	}
+}
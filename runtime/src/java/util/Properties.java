@@ -0,0 +1,28 @@
+package java.util;
+
+public class Properties extends HashMap<Object, Object> {
+	protected Properties defaults;
+
+	public Properties() {
+		this(null);
+	}
+
+	public Properties(Properties defaults) {
+		this.defaults = defaults;
+	}
+
+	public Object setProperty(String key, String value) {
+		return put(key, value);
+	}
+
+	public String getProperty(String key) {
+		Object value = get(key);
+		String s = value instanceof String ? (String) value : null;
+		return s == null && defaults != null ? defaults.getProperty(key) : s;
+	}
+
+	public String getProperty(String key, String defaultValue) {
+		String value = getProperty(key);
+		return value == null ? defaultValue : value;
+	}
+}
@@ -20,6 +20,8 @@
  * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use jvm::typevalues::JvmPrimitiveType;
+use jvm::typevalues::JvmType;
 use jvm::typevalues::JvmValue;
 use std::fmt;
 
@@ -31,10 +33,36 @@ enum_from_primitive! {
 		Double = 0x7,
 		Byte = 0x8,
 		Short = 0x9,
-		Integer = 0x10,
-		Long = 0x11,
+		Integer = 0xa,
+		Long = 0xb,
 	}
 }
+/// The form in which an array of `component`s holds `value`: an int
+/// stored in an array of booleans, bytes, chars or shorts is narrowed
+/// (a char is held in the 64-bit slot). Any other value is held as is.
+pub fn array_element(component: &JvmType, value: JvmValue) -> JvmValue {
+	let int = match &value {
+		JvmValue::Primitive(JvmPrimitiveType::Char, value, 0, _) => *value as i32,
+		JvmValue::Primitive(_, _, value, _) => *value as i32,
+		_ => return value,
+	};
+	match component {
+		JvmType::Primitive(JvmPrimitiveType::Boolean) => {
+			JvmValue::Primitive(JvmPrimitiveType::Boolean, 0, (int & 1) as u32, 0)
+		}
+		JvmType::Primitive(JvmPrimitiveType::Byte) => {
+			JvmValue::Primitive(JvmPrimitiveType::Byte, 0, int as i8 as i32 as u32, 0)
+		}
+		JvmType::Primitive(JvmPrimitiveType::Short) => {
+			JvmValue::Primitive(JvmPrimitiveType::Short, 0, int as i16 as i32 as u32, 0)
+		}
+		JvmType::Primitive(JvmPrimitiveType::Char) => {
+			JvmValue::Primitive(JvmPrimitiveType::Char, int as u16 as u64, 0, 0)
+		}
+		_ => value,
+	}
+}
+
 pub struct JvmArray {
 	dimension: usize,
 	values: Vec<Option<JvmValue>>,
@@ -66,9 +94,40 @@ impl JvmArray {
 		&self.values[index]
 	}
 
+	/// The `length` elements starting at `index` (None for
+	/// those that have never been set).
+	pub fn get_range(&self, index: usize, length: usize) -> Vec<Option<JvmValue>> {
+		self.values[index..index + length].to_vec()
+	}
+
+	/// Replace the elements starting at `index` with `values`.
+	pub fn set_range(&mut self, index: usize, values: Vec<Option<JvmValue>>) {
+		for (offset, value) in values.into_iter().enumerate() {
+			self.values[index + offset] = value;
+		}
+	}
+
 	pub fn dimension(&self) -> usize {
 		self.dimension
 	}
+
+	/// The values of all of the array's (non-empty) elements.
+	pub fn values(&self) -> impl Iterator<Item = &JvmValue> {
+		self.values.iter().flatten()
+	}
+
+	/// Remove (and return) the array's elements. The heap does
+	/// this to garbage, to break reference cycles.
+	pub fn clear(&mut self) -> Vec<Option<JvmValue>> {
+		self.dimension = 0;
+		std::mem::replace(&mut self.values, Vec::new())
+	}
+
+	/// The (approximate) number of bytes that the array occupies.
+	pub fn size(&self) -> usize {
+		std::mem::size_of::<JvmArray>()
+			+ self.values.len() * std::mem::size_of::<Option<JvmValue>>()
+	}
 }
 
 impl fmt::Display for JvmArray {
@@ -19,6 +19,7 @@
  * You should have received a copy of the GNU General Public License
  * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
  */
+use jvm::attribute::Attributes;
 use jvm::exceptions::ExceptionTable;
 use std::fmt;
 
@@ -30,6 +31,7 @@ pub struct CodeAttribute {
 	code_length: u32,
 	exceptions_table_count: u16,
 	exceptions: ExceptionTable,
+	attributes: Attributes,
 }
 
 impl From<Vec<u8>> for CodeAttribute {
@@ -52,7 +54,9 @@ impl From<Vec<u8>> for CodeAttribute {
 
 		let exceptions = ExceptionTable::from(&bytes[offset..].to_vec());
 
-		exceptions.byte_len();
+		offset += exceptions.byte_len();
+
+		let attributes = Attributes::from(&bytes[offset..].to_vec());
 
 		CodeAttribute {
 			bytes: bytes,
@@ -62,10 +66,22 @@ impl From<Vec<u8>> for CodeAttribute {
 			code_offset: code_offset,
 			exceptions_table_count: exceptions.exceptions_table_count(),
 			exceptions: exceptions,
+			attributes: attributes,
 		}
 	}
 }
 
+impl CodeAttribute {
+	pub fn exception_table(&self) -> &ExceptionTable {
+		&self.exceptions
+	}
+
+	/// The attributes of the code itself (e.g., its LineNumberTable).
+	pub fn attributes(&self) -> &Attributes {
+		&self.attributes
+	}
+}
+
 impl fmt::Display for CodeAttribute {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		let mut result: fmt::Result;
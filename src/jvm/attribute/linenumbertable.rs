@@ -0,0 +1,83 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use std::fmt;
+
+#[derive(Default, Clone)]
+pub struct LineNumber {
+	start_pc: u16,
+	line_number: u16,
+}
+
+/// The LineNumberTable attribute of a Code attribute (JVMS 4.7.12): which
+/// line of the source file each range of the method's code comes from.
+#[derive(Default, Clone)]
+pub struct LineNumberTable {
+	line_numbers: Vec<LineNumber>,
+}
+
+impl LineNumberTable {
+	/// The line of the source file that the instruction at `pc`
+	/// comes from: the line of the last entry that starts at or
+	/// before `pc`.
+	pub fn line_number(&self, pc: usize) -> Option<u16> {
+		self.line_numbers
+			.iter()
+			.filter(|line_number| (line_number.start_pc as usize) <= pc)
+			.max_by_key(|line_number| line_number.start_pc)
+			.map(|line_number| line_number.line_number)
+	}
+}
+
+impl<'l> From<&'l Vec<u8>> for LineNumberTable {
+	fn from(bytes: &'l Vec<u8>) -> Self {
+		let mut offset: usize = 0;
+		let line_number_table_length = (bytes[offset] as u16) << 8 | (bytes[offset + 1] as u16);
+		offset += 2;
+
+		let mut line_numbers = Vec::<LineNumber>::new();
+		for _ in 0..line_number_table_length {
+			let start_pc = (bytes[offset] as u16) << 8 | (bytes[offset + 1] as u16);
+			offset += 2;
+			let line_number = (bytes[offset] as u16) << 8 | (bytes[offset + 1] as u16);
+			offset += 2;
+			line_numbers.push(LineNumber {
+				start_pc,
+				line_number,
+			});
+		}
+		LineNumberTable { line_numbers }
+	}
+}
+
+impl fmt::Display for LineNumberTable {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut result = Ok(());
+		for line_number in &self.line_numbers {
+			result = write!(
+				f,
+				"start_pc: {}, line_number: {}\n",
+				line_number.start_pc, line_number.line_number
+			);
+		}
+		result
+	}
+}
@@ -23,6 +23,7 @@ use std::fmt;
 use std::iter::repeat;
 
 pub mod codeattributes;
+pub mod linenumbertable;
 
 #[derive(Default, Clone)]
 pub struct Attribute {
@@ -0,0 +1,220 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::classpath::ClassSource;
+use jvm::console::Charset;
+use jvm::debug::DebugLevel;
+use jvm::filesystem::FileSystemPolicy;
+use jvm::native::{NativeMethod, NativeRegistry};
+use jvm::trace::TraceHook;
+use jvm::Jvm;
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+/// A JvmBuilder configures a Jvm. The Jvm that it builds can run any
+/// number of programs (see Jvm::run) and boot any number of VMs to
+/// call into (see Jvm::start), each with its own heap and classes.
+///
+/// ```no_run
+/// # use jvm::jvm::JvmBuilder;
+/// let jvm = JvmBuilder::new()
+/// 	.classpath_entry("app.jar")
+/// 	.system_property("app.mode", "batch")
+/// 	.max_heap_size(64 * 1024 * 1024)
+/// 	.build();
+/// std::process::exit(jvm.run("com/acme/App", "main", &["input.txt"]));
+/// ```
+pub struct JvmBuilder {
+	jvm: Jvm,
+}
+
+impl Default for JvmBuilder {
+	fn default() -> JvmBuilder {
+		JvmBuilder::new()
+	}
+}
+
+impl JvmBuilder {
+	/// A builder of a Jvm with the default configuration: an empty class
+	/// path (so that programs use the VM's own class library), the
+	/// host's standard streams and no limits besides the default
+	/// maximum heap size and stack depth.
+	pub fn new() -> JvmBuilder {
+		JvmBuilder {
+			jvm: Jvm::new(DebugLevel::Error).unwrap(),
+		}
+	}
+
+	pub fn debug_level(mut self, debug_level: DebugLevel) -> JvmBuilder {
+		self.jvm.debug_level = debug_level;
+		self
+	}
+
+	/// Add `entry` to the end of the class path: a directory, a JAR
+	/// file, a jmod file, a jimage or dir/* (the JAR files in dir).
+	pub fn classpath_entry(mut self, entry: &str) -> JvmBuilder {
+		self.jvm.classpath.push(entry.to_string());
+		self
+	}
+
+	/// Add `entries` to the end of the class path. See classpath_entry.
+	pub fn classpath(mut self, entries: &[&str]) -> JvmBuilder {
+		self.jvm
+			.classpath
+			.extend(entries.iter().map(|entry| entry.to_string()));
+		self
+	}
+
+	/// Add `entry` to the end of the boot class path, which the VM
+	/// searches before the class path: where the class library is
+	/// (e.g., a JDK's lib/rt.jar). See classpath_entry.
+	pub fn boot_classpath_entry(mut self, entry: &str) -> JvmBuilder {
+		self.jvm.boot_classpath.push(entry.to_string());
+		self
+	}
+
+	/// Look for classes that are on neither class path in `source`
+	/// (after the sources added before it).
+	pub fn class_source<S>(mut self, source: S) -> JvmBuilder
+	where
+		S: ClassSource + 'static,
+	{
+		self.jvm.add_class_source(source);
+		self
+	}
+
+	/// Set the system property `key` to `value` (over the VM's own).
+	pub fn system_property(mut self, key: &str, value: &str) -> JvmBuilder {
+		self.jvm.system_properties.retain(|(other, _)| other != key);
+		self.jvm
+			.system_properties
+			.push((key.to_string(), value.to_string()));
+		self
+	}
+
+	/// Set the maximum size (in bytes) of the Java heap.
+	pub fn max_heap_size(mut self, max_heap_size: usize) -> JvmBuilder {
+		self.jvm.set_max_heap_size(max_heap_size);
+		self
+	}
+
+	/// Set the maximum number of frames on the Java stack.
+	pub fn max_stack_depth(mut self, max_stack_depth: usize) -> JvmBuilder {
+		self.jvm.set_max_stack_depth(max_stack_depth);
+		self
+	}
+
+	/// Halt a program (with INSTRUCTION_BUDGET_EXIT_STATUS) once it has
+	/// executed `instruction_budget` instructions.
+	pub fn instruction_budget(mut self, instruction_budget: u64) -> JvmBuilder {
+		self.jvm.instruction_budget = Some(instruction_budget);
+		self
+	}
+
+	/// Set the Java SE release whose versioned entries of multi-release
+	/// JAR files the VM uses.
+	pub fn release(mut self, release: u32) -> JvmBuilder {
+		self.jvm.set_release(release);
+		self
+	}
+
+	/// Set the encoding of text that the program prints to System.out
+	/// and System.err.
+	pub fn console_charset(mut self, console_charset: Charset) -> JvmBuilder {
+		self.jvm.set_console_charset(console_charset);
+		self
+	}
+
+	/// Read the program's standard input (System.in) from `stdin`.
+	pub fn stdin<R>(mut self, stdin: R) -> JvmBuilder
+	where
+		R: Read + Send + 'static,
+	{
+		self.jvm.standard_streams.set_input(stdin);
+		self
+	}
+
+	/// Write what the program prints to System.out to `stdout`.
+	pub fn stdout<W>(mut self, stdout: W) -> JvmBuilder
+	where
+		W: Write + Send + 'static,
+	{
+		self.jvm.standard_streams.set_output(stdout);
+		self
+	}
+
+	/// Write what the program prints to System.err, and the errors that
+	/// the VM reports, to `stderr`.
+	pub fn stderr<W>(mut self, stderr: W) -> JvmBuilder
+	where
+		W: Write + Send + 'static,
+	{
+		self.jvm.standard_streams.set_error(stderr);
+		self
+	}
+
+	/// Set the policy that decides which files the program may open.
+	pub fn file_system_policy<P>(mut self, policy: P) -> JvmBuilder
+	where
+		P: FileSystemPolicy + 'static,
+	{
+		self.jvm.set_file_system_policy(policy);
+		self
+	}
+
+	/// Use `natives` as the registry of native methods: usually
+	/// NativeRegistry::new() (the VM's own) with more registered on it.
+	pub fn natives(mut self, natives: NativeRegistry) -> JvmBuilder {
+		self.jvm.natives = natives;
+		self
+	}
+
+	/// Implement the native method `method_name` with type `descriptor`
+	/// of the class named `class_name` with `native`.
+	pub fn native<N>(
+		mut self,
+		class_name: &str,
+		method_name: &str,
+		descriptor: &str,
+		native: N,
+	) -> JvmBuilder
+	where
+		N: NativeMethod + 'static,
+	{
+		self.jvm
+			.register_native(class_name, method_name, descriptor, native);
+		self
+	}
+
+	/// Tell `hook` about the methods and instructions that programs
+	/// execute.
+	pub fn trace_hook<T>(mut self, hook: T) -> JvmBuilder
+	where
+		T: TraceHook + 'static,
+	{
+		self.jvm.trace_hooks.push(Arc::new(hook));
+		self
+	}
+
+	pub fn build(self) -> Jvm {
+		self.jvm
+	}
+}
@@ -21,11 +21,18 @@
  */
 use jvm::attribute::Attributes;
 use jvm::constant::Constant;
+use jvm::constant::Utf8Reserved;
 use jvm::constantpool::ConstantPool;
+use jvm::error::FatalError;
+use jvm::error::FatalErrorType;
 use jvm::field::Fields;
+use jvm::layout::FieldLayout;
 use jvm::method::Method;
 use jvm::method::Methods;
+use jvm::methodarea::ClassLoaderId;
 use jvm::methodarea::MethodArea;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt;
 use std::fs;
 use std::io::Read;
@@ -70,6 +77,15 @@ pub struct Class {
 	methods: Methods,
 	attributes_count: u16,
 	attributes: Attributes,
+	/// Where each instance field lives in the slots of
+	/// this class' objects. Computed when the class is linked.
+	layout: Rc<FieldLayout>,
+	/// Offsets of the fields that this class' getfield and
+	/// putfield instructions reference, keyed by the index of
+	/// their Fieldref in the constant pool.
+	field_offsets: RefCell<HashMap<u16, usize>>,
+	/// The class loader that defined this class.
+	defining_loader: ClassLoaderId,
 }
 
 impl Class {
@@ -77,6 +93,16 @@ impl Class {
 		&self.constant_pool
 	}
 
+	/// The class loader that defined this class. The names in its
+	/// constant pool resolve in that loader's namespace.
+	pub fn get_defining_loader(&self) -> ClassLoaderId {
+		self.defining_loader
+	}
+
+	pub fn set_defining_loader(&mut self, loader: ClassLoaderId) {
+		self.defining_loader = loader;
+	}
+
 	pub fn resolve_superclass(&self) -> Option<String> {
 		let mut superclass_name: Option<String> = None;
 		let cp = &self.constant_pool;
@@ -97,21 +123,65 @@ impl Class {
 		self.resolve_superclass()
 	}
 
+	/// The name of the class that the Class constant at
+	/// `class_index` in this class' constant pool refers to.
+	pub fn get_class_name_at(&self, class_index: u16) -> Option<String> {
+		let cp = &self.constant_pool;
+		if let Constant::Class(_, name_index) = cp.get_constant_ref(class_index as usize) {
+			if let Constant::Utf8(_, _, _, name) = cp.get_constant_ref(*name_index as usize) {
+				return Some(name.to_string());
+			}
+		}
+		None
+	}
+
+	/// The names of the direct superinterfaces of this class.
+	pub fn get_interface_names(&self) -> Vec<String> {
+		let cp = &self.constant_pool;
+		self.interfaces
+			.iter()
+			.filter_map(|interface| {
+				if let Constant::Class(_, name_index) = cp.get_constant_ref(*interface as usize) {
+					if let Constant::Utf8(_, _, _, name) = cp.get_constant_ref(*name_index as usize)
+					{
+						return Some(name.to_string());
+					}
+				}
+				None
+			})
+			.collect()
+	}
+
+	pub fn is_interface(&self) -> bool {
+		self.access_flags & (ClassAccessFlags::Interface as u16) != 0
+	}
+
 	/**
 	 * is_type_of
 	 *
-	 * Recursively, check whether `type` matches this class', or one of its
-	 * superclasses. Because the parameter is a mutable reference to the MethodArea,
-	 * it must be locked before calling. Assume that is the case. TODO: This locking
-	 * should be more precise.
+	 * Recursively, check whether `type` matches this class', one of its
+	 * superinterfaces or one of its superclasses. Because the parameter is a
+	 * mutable reference to the MethodArea, it must be locked before calling.
+	 * Assume that is the case. TODO: This locking should be more precise.
 	 */
 	pub fn is_type_of(&self, r#type: &String, methodarea: &mut MethodArea) -> bool {
 		if self.get_class_name().unwrap() == *r#type {
-			true
-		} else if let Some(parent_name) = self.superclass_name() {
-			methodarea.maybe_load_class(&parent_name);
-			if let Some(super_class) = methodarea.get_loaded_class(&parent_name) {
-				(*super_class).class.is_type_of(r#type, methodarea)
+			return true;
+		}
+		for interface_name in self.get_interface_names() {
+			if let Some(interface) =
+				methodarea.find_or_load_class(&interface_name, self.defining_loader)
+			{
+				if interface.is_type_of(r#type, methodarea) {
+					return true;
+				}
+			}
+		}
+		if let Some(parent_name) = self.superclass_name() {
+			if let Some(super_class) =
+				methodarea.find_or_load_class(&parent_name, self.defining_loader)
+			{
+				super_class.is_type_of(r#type, methodarea)
 			} else {
 				false
 			}
@@ -120,6 +190,25 @@ impl Class {
 		}
 	}
 
+	/// Whether this class is `target` or a subclass or subinterface of
+	/// it. Unlike is_type_of, a class of another class loader with the
+	/// same name is a different class.
+	pub fn is_subtype_of(&self, target: &Class, methodarea: &mut MethodArea) -> bool {
+		if self.defining_loader == target.defining_loader
+			&& self.get_class_name() == target.get_class_name()
+		{
+			return true;
+		}
+		let mut supertypes = self.get_interface_names();
+		supertypes.extend(self.superclass_name());
+		supertypes.iter().any(|supertype| {
+			match methodarea.find_or_load_class(supertype, self.defining_loader) {
+				Some(supertype) => supertype.is_subtype_of(target, methodarea),
+				None => false,
+			}
+		})
+	}
+
 	pub fn resolve_field_ref(&self, field_ref_index: usize) -> Option<(String, String, String)> {
 		let mut result: Option<(String, String, String)> = None;
 		let cp = &self.constant_pool;
@@ -183,12 +272,14 @@ impl Class {
 	/// # Arguments
 	///
 	/// `method_ref_index` - The index into this class' constant pool
-	/// that points to a method reference.
+	/// that points to a method reference (to a method of a class or
+	/// of an interface).
 	pub fn resolve_method_ref(&self, method_ref_index: usize) -> Option<(String, String, String)> {
 		let mut result: Option<(String, String, String)> = None;
 		let cp = &self.constant_pool;
 
-		if let Constant::Methodref(_, class_index, method_index) =
+		if let Constant::Methodref(_, class_index, method_index)
+		| Constant::InterfaceMethodref(_, class_index, method_index) =
 			cp.get_constant_ref(method_ref_index)
 		{
 			if let Constant::Class(_, class_name_index) = cp.get_constant_ref(*class_index as usize)
@@ -228,6 +319,29 @@ impl Class {
 			.get_by_name_and_type(&method_name, &method_type, &self.constant_pool)
 	}
 
+	/// The name of the source file that the class was compiled
+	/// from, if it has a SourceFile attribute (JVMS 4.7.10).
+	pub fn get_source_file(&self) -> Option<String> {
+		for i in 0..self.attributes.len() {
+			let attribute = self.attributes.get_ref(i);
+			if let Constant::Utf8(_, Utf8Reserved::SourceFile, _, _) = self
+				.constant_pool
+				.get_constant_ref(attribute.attribute_name_index as usize)
+			{
+				if attribute.info.len() < 2 {
+					return None;
+				}
+				let index = (attribute.info[0] as u16) << 8 | (attribute.info[1] as u16);
+				if let Constant::Utf8(_, _, _, source_file) =
+					self.constant_pool.get_constant_ref(index as usize)
+				{
+					return Some(source_file.clone());
+				}
+			}
+		}
+		None
+	}
+
 	pub fn get_methods_ref(&self) -> &Methods {
 		&self.methods
 	}
@@ -240,6 +354,63 @@ impl Class {
 		&self.fields
 	}
 
+	/// Prepare this class (JVMS 5.4.2): create its static fields and
+	/// set them to their default values. Static fields with a
+	/// ConstantValue attribute get that value later, when the class
+	/// is initialized.
+	pub fn prepare(&self) -> bool {
+		for field in self.fields.iter() {
+			if !field.prepare(&self.constant_pool) {
+				FatalError::new(FatalErrorType::InvalidFieldDescriptor(
+					field
+						.get_descriptor(&self.constant_pool)
+						.unwrap_or_default(),
+					field.get_name(&self.constant_pool).unwrap_or_default(),
+					self.get_class_name().unwrap_or_default(),
+				))
+				.call();
+				return false;
+			}
+		}
+		true
+	}
+
+	/// Compute the layout of this class' instances (the fields of
+	/// `superclass_layout` come first).
+	pub fn link(&mut self, superclass_layout: Option<&FieldLayout>) -> bool {
+		match FieldLayout::new(superclass_layout, self) {
+			Ok(layout) => {
+				self.layout = Rc::new(layout);
+				true
+			}
+			Err((name, descriptor)) => {
+				FatalError::new(FatalErrorType::InvalidFieldDescriptor(
+					descriptor,
+					name,
+					self.get_class_name().unwrap_or_default(),
+				))
+				.call();
+				false
+			}
+		}
+	}
+
+	pub fn get_layout(&self) -> Rc<FieldLayout> {
+		Rc::clone(&self.layout)
+	}
+
+	/// The offset of the field referenced by the Fieldref at
+	/// `field_ref_index`, if it was resolved before.
+	pub fn get_cached_field_offset(&self, field_ref_index: u16) -> Option<usize> {
+		self.field_offsets.borrow().get(&field_ref_index).cloned()
+	}
+
+	pub fn cache_field_offset(&self, field_ref_index: u16, offset: usize) {
+		self.field_offsets
+			.borrow_mut()
+			.insert(field_ref_index, offset);
+	}
+
 	pub fn get_class_name(&self) -> Option<String> {
 		match self
 			.constant_pool
@@ -353,16 +524,16 @@ impl Class {
 		match fs::File::open(class_with_path) {
 			Ok(mut fd) => {
 				if let Err(err) = fd.read_to_end(&mut bytes) {
-					print!(
-						"oops: could not read the class file '{}': {}\n",
+					eprintln!(
+						"oops: could not read the class file '{}': {}",
 						class_with_path, err
 					);
 					return None;
 				}
 			}
 			Err(err) => {
-				print!(
-					"oops: could not read the class file '{}': {}\n",
+				eprintln!(
+					"oops: could not read the class file '{}': {}",
 					class_with_path, err
 				);
 				return None;
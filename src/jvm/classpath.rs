@@ -20,155 +20,513 @@
  * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use jvm::class::Class;
 use jvm::debug::Debug;
 use jvm::debug::DebugLevel;
-use jvm::error::FatalError;
-use jvm::error::FatalErrorType;
+use jvm::jimage::{JImage, JImageResource, JIMAGE_MAGIC};
+use jvm::manifest::Manifest;
+use jvm::runtime;
 use rjar::Jar;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 #[derive(Clone)]
 pub enum ClassLocation {
 	ClassFile(String),
 	JarFile(String, String),
+	/// A class of the VM's own runtime library, and its class file.
+	Runtime(&'static [u8]),
+	/// A jmod file and the name of the class file in it.
+	JmodFile(String, String),
+	/// A jimage (e.g., the lib/modules of a JDK) and where the class
+	/// file is in it.
+	JImage(Arc<JImage>, JImageResource),
+	/// A class source of the embedder and the binary name of the class.
+	Source(Arc<dyn ClassSource>, String),
 }
 
+/// Where an embedder keeps class files that are not on the file system
+/// (e.g., classes that it generates or fetches): the class path
+/// consults each source after its entries.
+pub trait ClassSource: Send + Sync {
+	/// Whether the source has the class with the binary name
+	/// `class_name` (e.g., com/acme/Tool). This is called to find a
+	/// class before it is loaded, so it should be cheap.
+	fn has_class(&self, class_name: &str) -> bool;
+
+	/// The class file of the class with the binary name `class_name`.
+	fn class_bytes(&self, class_name: &str) -> Option<Vec<u8>>;
+}
+
+/// The class files under a directory (pkg/Name.class for pkg/Name).
+pub struct DirectorySource {
+	directory: PathBuf,
+}
+
+impl DirectorySource {
+	pub fn new<P: AsRef<Path>>(directory: P) -> DirectorySource {
+		DirectorySource {
+			directory: directory.as_ref().to_path_buf(),
+		}
+	}
+
+	fn class_file(&self, class_name: &str) -> PathBuf {
+		self.directory.join(format!("{}.class", class_name))
+	}
+}
+
+impl ClassSource for DirectorySource {
+	fn has_class(&self, class_name: &str) -> bool {
+		self.class_file(class_name).is_file()
+	}
+
+	fn class_bytes(&self, class_name: &str) -> Option<Vec<u8>> {
+		fs::read(self.class_file(class_name)).ok()
+	}
+}
+
+/// The class files in a JAR file (its base entries). The JAR file
+/// stays open for as long as the source does.
+pub struct JarSource {
+	jar: Mutex<Jar>,
+	files: HashSet<String>,
+}
+
+impl JarSource {
+	/// Read the names of the entries of the JAR file `jar_file`. None if
+	/// it cannot be opened.
+	pub fn open(jar_file: &str) -> Option<JarSource> {
+		let mut jar = Jar::open(&jar_file.to_string()).ok()?;
+		let files = jar
+			.file_names()
+			.into_iter()
+			.filter(|file| file.ends_with(".class"))
+			.collect();
+		Some(JarSource {
+			jar: Mutex::new(jar),
+			files,
+		})
+	}
+}
+
+impl ClassSource for JarSource {
+	fn has_class(&self, class_name: &str) -> bool {
+		self.files.contains(&format!("{}.class", class_name))
+	}
+
+	fn class_bytes(&self, class_name: &str) -> Option<Vec<u8>> {
+		if !self.has_class(class_name) {
+			return None;
+		}
+		self.jar
+			.lock()
+			.ok()?
+			.file_contents_by_name(&format!("{}.class", class_name))
+			.ok()
+	}
+}
+
+/// Class files in memory, by the binary names of their classes.
+#[derive(Default)]
+pub struct MapSource {
+	classes: HashMap<String, Vec<u8>>,
+}
+
+impl MapSource {
+	pub fn new() -> MapSource {
+		MapSource::default()
+	}
+
+	/// Add the class file `class_bytes` of the class with the binary
+	/// name `class_name` (with slashes or dots).
+	pub fn insert(&mut self, class_name: &str, class_bytes: Vec<u8>) {
+		self.classes
+			.insert(class_name.replace('.', "/"), class_bytes);
+	}
+}
+
+impl From<HashMap<String, Vec<u8>>> for MapSource {
+	fn from(classes: HashMap<String, Vec<u8>>) -> MapSource {
+		let mut source = MapSource::new();
+		for (class_name, class_bytes) in classes {
+			source.insert(&class_name, class_bytes);
+		}
+		source
+	}
+}
+
+impl ClassSource for MapSource {
+	fn has_class(&self, class_name: &str) -> bool {
+		self.classes.contains_key(class_name)
+	}
+
+	fn class_bytes(&self, class_name: &str) -> Option<Vec<u8>> {
+		self.classes.get(class_name).cloned()
+	}
+}
+
+/// An entry of the class path.
+enum ClassPathEntry {
+	/// A directory whose subdirectories are packages.
+	Directory(String),
+	/// A JAR file, the names of its entries and, if it is a
+	/// multi-release JAR file, the versions (newest first) whose
+	/// entries override its base entries.
+	JarFile(String, HashSet<String>, Vec<u32>),
+	/// A jmod file, the name of its module and the names of its
+	/// entries.
+	JmodFile(String, String, HashSet<String>),
+	/// A jimage (which knows its path), with its index.
+	JImage(Arc<JImage>),
+	/// A class source of the embedder.
+	Source(Arc<dyn ClassSource>),
+}
+
+/// The Java SE release of the VM, whose versioned entries (and those
+/// of earlier releases) of multi-release JAR files it uses.
+pub const DEFAULT_RELEASE: u32 = 8;
+
+/// Where the versioned entries of multi-release JAR files are.
+const VERSIONS_DIRECTORY: &str = "META-INF/versions/";
+
+/// The magic number of a jmod file: a JAR file after it.
+const JMOD_MAGIC: [u8; 4] = [0x4A, 0x4D, 0x01, 0x00];
+
+/// Where the class files of a jmod file are.
+const JMOD_CLASSES_DIRECTORY: &str = "classes/";
+
 pub struct ClassPath {
-	classes: HashMap<String, ClassLocation>,
+	entries: Vec<ClassPathEntry>,
+	/// Whether classes that the class path does not have come from the
+	/// VM's runtime.
+	runtime: bool,
 	debug_level: DebugLevel,
 }
 
-fn class_name_from_class_file(class_file: &String) -> Option<String> {
-	if let Some(class) = Class::load_from_file(class_file) {
-		class.get_class_name()
+/// The entry for the JAR file `jar_file`: only the names of the files
+/// in it (and its manifest) are read. When it is a multi-release JAR
+/// file, its entries for `release` and earlier override its base
+/// entries.
+fn jar_entry(jar_file: &str, release: u32, debug_level: &DebugLevel) -> Option<ClassPathEntry> {
+	match Jar::open(&jar_file.to_string()) {
+		Ok(mut jar) => {
+			Debug(
+				format!("Using JAR file {}", jar_file),
+				debug_level,
+				DebugLevel::Info,
+			);
+			let files: HashSet<String> = jar
+				.file_names()
+				.into_iter()
+				.filter(|file| file.ends_with(".class"))
+				.collect();
+			let mut versions = Vec::<u32>::new();
+			if Manifest::from_open_jar(&mut jar).map_or(false, |manifest| manifest.multi_release())
+			{
+				versions = files
+					.iter()
+					.filter_map(|file| file.strip_prefix(VERSIONS_DIRECTORY))
+					.filter_map(|file| file.split('/').next())
+					.filter_map(|version| version.parse::<u32>().ok())
+					.filter(|version| *version <= release)
+					.collect();
+				versions.sort_unstable_by(|a, b| b.cmp(a));
+				versions.dedup();
+			}
+			Some(ClassPathEntry::JarFile(
+				jar_file.to_string(),
+				files,
+				versions,
+			))
+		}
+		Err(_e) => {
+			Debug(
+				format!("Could not open JAR file {}.", jar_file),
+				debug_level,
+				DebugLevel::Warning,
+			);
+			None
+		}
+	}
+}
+
+/// The entry for the jmod file `jmod_file`, whose module is named
+/// after it (e.g., java.base.jmod has java.base).
+fn jmod_entry(jmod_file: &str, debug_level: &DebugLevel) -> Option<ClassPathEntry> {
+	let module = Path::new(jmod_file)
+		.file_stem()
+		.map(|module| module.to_string_lossy().into_owned())?;
+	match Jar::open(&jmod_file.to_string()) {
+		Ok(mut jmod) => {
+			Debug(
+				format!("Using jmod file {} (module {})", jmod_file, module),
+				debug_level,
+				DebugLevel::Info,
+			);
+			Some(ClassPathEntry::JmodFile(
+				jmod_file.to_string(),
+				module,
+				jmod.file_names()
+					.into_iter()
+					.filter(|file| file.starts_with(JMOD_CLASSES_DIRECTORY))
+					.collect(),
+			))
+		}
+		Err(_e) => {
+			Debug(
+				format!("Could not open jmod file {}.", jmod_file),
+				debug_level,
+				DebugLevel::Warning,
+			);
+			None
+		}
+	}
+}
+
+/// The entry for the jimage `image_file`.
+fn jimage_entry(image_file: &str, debug_level: &DebugLevel) -> Option<ClassPathEntry> {
+	match JImage::open(image_file) {
+		Some(image) => {
+			Debug(
+				format!("Using jimage {}", image_file),
+				debug_level,
+				DebugLevel::Info,
+			);
+			Some(ClassPathEntry::JImage(Arc::new(image)))
+		}
+		None => {
+			Debug(
+				format!("Could not read jimage {}.", image_file),
+				debug_level,
+				DebugLevel::Warning,
+			);
+			None
+		}
+	}
+}
+
+/// The entry for the file `path`: a jmod file, a jimage or (by
+/// default) a JAR file, by its magic number.
+fn file_entry(path: &str, release: u32, debug_level: &DebugLevel) -> Option<ClassPathEntry> {
+	let mut magic = [0u8; 4];
+	let has_magic = File::open(path)
+		.and_then(|mut file| file.read_exact(&mut magic))
+		.is_ok();
+	if has_magic && magic == JMOD_MAGIC {
+		jmod_entry(path, debug_level)
+	} else if has_magic
+		&& (u32::from_le_bytes(magic) == JIMAGE_MAGIC || u32::from_be_bytes(magic) == JIMAGE_MAGIC)
+	{
+		jimage_entry(path, debug_level)
+	} else {
+		jar_entry(path, release, debug_level)
+	}
+}
+
+/// The directory of the class path entry `path` when it is a wildcard
+/// (dir/* or *).
+fn wildcard_directory(path: &str) -> Option<&str> {
+	if path == "*" {
+		Some(".")
+	} else if path.ends_with("/*") || (cfg!(windows) && path.ends_with("\\*")) {
+		Some(&path[..path.len() - 1])
 	} else {
 		None
 	}
 }
 
+/// The JAR files (*.jar or *.JAR) in the directory `directory`, in the
+/// order of their names.
+fn jar_files_in_directory(directory: &str) -> Vec<String> {
+	let mut jar_files = fs::read_dir(Path::new(directory))
+		.map(|dir_list| {
+			dir_list
+				.filter_map(|dir| dir.ok())
+				.filter_map(|dir| dir.path().to_str().map(|path| path.to_string()))
+				.filter(|path| {
+					(path.ends_with(".jar") || path.ends_with(".JAR")) && Path::new(path).is_file()
+				})
+				.collect::<Vec<String>>()
+		})
+		.unwrap_or_default();
+	jar_files.sort();
+	jar_files
+}
+
+impl ClassPathEntry {
+	/// The module of this entry (a jmod file or a jimage) that has
+	/// the package `package` (e.g., java/lang).
+	fn module_for_package(&self, package: &str) -> Option<String> {
+		match self {
+			ClassPathEntry::JmodFile(_, module, files) => {
+				let prefix = format!("{}{}/", JMOD_CLASSES_DIRECTORY, package);
+				if files
+					.iter()
+					.any(|file| file.starts_with(&prefix) && !file[prefix.len()..].contains('/'))
+				{
+					Some(module.to_string())
+				} else {
+					None
+				}
+			}
+			ClassPathEntry::JImage(image) => image.module_for_package(package),
+			_ => None,
+		}
+	}
+
+	/// Where the resource `name` (e.g., java/lang/Object.class) of the
+	/// module `module` is in this entry (a jmod file or a jimage).
+	fn resource_location(&self, module: &str, name: &str) -> Option<ClassLocation> {
+		match self {
+			ClassPathEntry::JmodFile(jmod_file, jmod_module, files) if jmod_module == module => {
+				let file = format!("{}{}", JMOD_CLASSES_DIRECTORY, name);
+				if files.contains(&file) {
+					Some(ClassLocation::JmodFile(jmod_file.to_string(), file))
+				} else {
+					None
+				}
+			}
+			ClassPathEntry::JImage(image) => image
+				.find_resource(module, name)
+				.map(|resource| ClassLocation::JImage(image.clone(), resource)),
+			_ => None,
+		}
+	}
+
+	/// Where the class with the binary name `class` is in this entry:
+	/// `pkg/Name` is the file `pkg/Name.class` under the directory or
+	/// the entry `pkg/Name.class` of the JAR file. In a jmod file or a
+	/// jimage, it is the resource `pkg/Name.class` of the module that
+	/// has the package `pkg`.
+	fn class_location_for_class(&self, class: &str) -> Option<ClassLocation> {
+		let class_file = format!("{}.class", class);
+		match self {
+			ClassPathEntry::JmodFile(..) | ClassPathEntry::JImage(..) => {
+				let module = self.module_for_package(&class[..class.rfind('/')?])?;
+				self.resource_location(&module, &class_file)
+			}
+			ClassPathEntry::Directory(directory) => {
+				let path = Path::new(directory).join(&class_file);
+				if path.is_file() {
+					path.to_str()
+						.map(|path| ClassLocation::ClassFile(path.to_string()))
+				} else {
+					None
+				}
+			}
+			ClassPathEntry::JarFile(jar_file, files, versions) => versions
+				.iter()
+				.map(|version| format!("{}{}/{}", VERSIONS_DIRECTORY, version, class_file))
+				.chain(Some(class_file.clone()))
+				.find(|file| files.contains(file))
+				.map(|file| ClassLocation::JarFile(jar_file.to_string(), file)),
+			ClassPathEntry::Source(source) => {
+				if source.has_class(class) {
+					Some(ClassLocation::Source(Arc::clone(source), class.to_string()))
+				} else {
+					None
+				}
+			}
+		}
+	}
+}
+
 impl ClassPath {
+	/// Where the class with the binary name `class` is: in the first
+	/// entry of the class path that has it or, failing that, in the
+	/// VM's runtime. Nothing is parsed to find it.
 	pub fn class_location_for_class(&self, class: &str) -> Option<ClassLocation> {
-		if let Some(class) = self.classes.get(class) {
-			Some(class.clone())
-		} else {
-			None
+		/*
+		 * Binary names of classes never name a parent directory.
+		 */
+		if class.is_empty() || class.split('/').any(|part| part.is_empty() || part == "..") {
+			return None;
+		}
+		let location = self
+			.entries
+			.iter()
+			.filter_map(|entry| entry.class_location_for_class(class))
+			.next();
+		if location.is_some() || !self.runtime {
+			return location;
 		}
+		runtime::CLASSES
+			.iter()
+			.find(|(class_name, _)| *class_name == class)
+			.map(|(_, bytes)| ClassLocation::Runtime(*bytes))
 	}
-	pub fn new(classpath: &[&str], debug_level: DebugLevel) -> Self {
-		let mut classes = HashMap::<String, ClassLocation>::new();
+
+	/// The class path of the entries `classpath` followed by the class
+	/// sources `sources`.
+	pub fn new(
+		classpath: &[&str],
+		sources: &[Arc<dyn ClassSource>],
+		release: u32,
+		debug_level: DebugLevel,
+	) -> Self {
+		let mut entries = Vec::<ClassPathEntry>::new();
 		for path in classpath {
-			if let Ok(dir_list) = fs::read_dir(Path::new(&path)) {
-				for dir in dir_list {
-					if let Ok(path_entry) = dir {
-						if let Some(path_entry) = path_entry.path().to_str() {
-							let path_entry_file = path_entry.to_string();
-							if path_entry_file.ends_with("class") {
-								Debug(
-									format!("Loading class file {}", path_entry_file),
-									&debug_level,
-									DebugLevel::Info,
-								);
-								/*
-								 * TODO: This has to peak at the head of the file to get the
-								 * classname
-								 */
-								if let Some(class_name) =
-									class_name_from_class_file(&path_entry_file)
-								{
-									Debug(
-										format!(
-											"{} classpath entry contains object {}.",
-											path_entry_file, class_name
-										),
-										&debug_level,
-										DebugLevel::Info,
-									);
-									classes.insert(
-										class_name,
-										ClassLocation::ClassFile(path_entry_file),
-									);
-								} else {
-									Debug(
-										format!("Could not load a name for {}.", path_entry_file),
-										&debug_level,
-										DebugLevel::Info,
-									);
-								}
-							} else if path_entry_file.ends_with("jar") {
-								Debug(
-									format!("Loading JAR file {}", path_entry_file),
-									&debug_level,
-									DebugLevel::Info,
-								);
-
-								match Jar::open(&path_entry_file.clone()) {
-									Ok(mut jar) => {
-										for file in jar.file_names() {
-											/*
-											 * Skip class files.
-											 */
-											if !file.ends_with("class") {
-												continue;
-											}
-											if let Ok(file_bytes) = jar.file_contents_by_name(&file)
-											{
-												if let Some(class) =
-													Class::load_from_bytes(file_bytes)
-												{
-													if let Some(class_name) = class.get_class_name()
-													{
-														classes.insert(
-															class_name,
-															ClassLocation::JarFile(
-																path_entry_file.clone(),
-																file.clone(),
-															),
-														);
-													}
-												} else {
-													Debug(
-														format!(
-														"Could not read a class from bytes from {}:{}.",
-														path_entry_file, file
-													),
-														&debug_level,
-														DebugLevel::Warning,
-													);
-												}
-											} else {
-												Debug(
-													format!(
-														"Could not read a class from {}:{}.",
-														path_entry_file, file
-													),
-													&debug_level,
-													DebugLevel::Warning,
-												);
-											}
-										}
-									}
-									Err(_e) => {}
-								};
-
-								/*
-								 * TODO: This has to use *something* from the JAR file to figure
-								 * out which classes are in this JAR file.
-								for f in xxxx {
-									classes.insert(f, path_entry_file);
-								}
-								 */
-							}
-						}
-					}
+			/*
+			 * As in java, a wildcard entry (a directory followed by an
+			 * asterisk) is the JAR files in the directory, in the
+			 * order of their names, and an empty entry is the current
+			 * directory.
+			 */
+			if let Some(directory) = wildcard_directory(path) {
+				for jar_file in jar_files_in_directory(directory) {
+					entries.extend(jar_entry(&jar_file, release, &debug_level));
 				}
+				continue;
+			}
+			let path = if path.is_empty() { "." } else { *path };
+
+			/*
+			 * An entry is a directory of packages, a JAR file, a jmod
+			 * file or a jimage.
+			 */
+			if Path::new(path).is_dir() {
+				entries.push(ClassPathEntry::Directory(path.to_string()));
+			} else if Path::new(path).is_file() {
+				entries.extend(file_entry(path, release, &debug_level));
+			} else {
+				Debug(
+					format!("Class path entry {} does not exist.", path),
+					&debug_level,
+					DebugLevel::Warning,
+				);
 			}
 		}
-		ClassPath {
-			classes,
+		entries.extend(
+			sources
+				.iter()
+				.map(|source| ClassPathEntry::Source(Arc::clone(source))),
+		);
+		let mut classpath = ClassPath {
+			entries,
+			runtime: false,
 			debug_level,
+		};
+		/*
+		 * Without a class library on the class path, programs use the
+		 * VM's own.
+		 */
+		if classpath
+			.class_location_for_class("java/lang/Object")
+			.is_none()
+		{
+			Debug(
+				format!(
+					"The class path has no java/lang/Object; using the VM's runtime ({} classes).",
+					runtime::CLASSES.len()
+				),
+				&classpath.debug_level,
+				DebugLevel::Info,
+			);
+			classpath.runtime = true;
 		}
+		classpath
 	}
 }
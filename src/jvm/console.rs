@@ -0,0 +1,232 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use std::env;
+use std::fmt;
+use std::io;
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+
+/// The replacement for a character that a Charset cannot encode.
+const REPLACEMENT: u8 = b'?';
+
+/// A character encoding that the VM can write text in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Charset {
+	Utf8,
+	Iso88591,
+	UsAscii,
+}
+
+impl Charset {
+	/// The Charset named `name` (e.g., UTF-8 or latin1), if the VM
+	/// supports it. Names are case insensitive.
+	pub fn for_name(name: &str) -> Option<Charset> {
+		match name.to_ascii_lowercase().replace('_', "-").as_str() {
+			"utf-8" | "utf8" => Some(Charset::Utf8),
+			"iso-8859-1" | "iso8859-1" | "latin1" | "l1" => Some(Charset::Iso88591),
+			"us-ascii" | "ascii" | "ansi-x3.4-1968" | "646" => Some(Charset::UsAscii),
+			_ => None,
+		}
+	}
+
+	/// The Charset of the host's locale (from LC_ALL, LC_CTYPE or LANG,
+	/// like the C library does). The C (or POSIX) locale is US-ASCII.
+	/// Without a locale, or with one whose codeset the VM does not
+	/// support, it is UTF-8.
+	pub fn from_locale() -> Charset {
+		let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+			.iter()
+			.filter_map(|variable| env::var(variable).ok())
+			.find(|locale| !locale.is_empty());
+		match locale {
+			Some(locale) => {
+				if locale == "C" || locale == "POSIX" {
+					return Charset::UsAscii;
+				}
+				/*
+				 * language_territory.codeset@modifier
+				 */
+				let codeset = locale
+					.split('@')
+					.next()
+					.and_then(|locale| locale.split('.').nth(1));
+				codeset.and_then(Charset::for_name).unwrap_or(Charset::Utf8)
+			}
+			None => Charset::Utf8,
+		}
+	}
+
+	/// Encode `characters` (UTF-16 code units, like the characters of
+	/// a Java String). A character that cannot be encoded -- including
+	/// an unpaired surrogate -- becomes a '?'.
+	pub fn encode(&self, characters: &[u16]) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(characters.len());
+		for character in std::char::decode_utf16(characters.iter().cloned()) {
+			match (self, character) {
+				(Charset::Utf8, Ok(character)) => {
+					let mut buffer = [0u8; 4];
+					bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+				}
+				(Charset::Iso88591, Ok(character)) if (character as u32) < 0x100 => {
+					bytes.push(character as u8)
+				}
+				(Charset::UsAscii, Ok(character)) if character.is_ascii() => {
+					bytes.push(character as u8)
+				}
+				_ => bytes.push(REPLACEMENT),
+			}
+		}
+		bytes
+	}
+
+	/// Encode `value`. See encode.
+	pub fn encode_str(&self, value: &str) -> Vec<u8> {
+		self.encode(&value.encode_utf16().collect::<Vec<u16>>())
+	}
+}
+
+impl fmt::Display for Charset {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Charset::Utf8 => write!(f, "UTF-8"),
+			Charset::Iso88591 => write!(f, "ISO-8859-1"),
+			Charset::UsAscii => write!(f, "US-ASCII"),
+		}
+	}
+}
+
+/// One of the host's standard output streams.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StandardStream {
+	Out,
+	Err,
+}
+
+impl StandardStream {
+	/// The standard stream with file descriptor `fd`, if there is one.
+	pub fn from_fd(fd: i32) -> Option<StandardStream> {
+		match fd {
+			1 => Some(StandardStream::Out),
+			2 => Some(StandardStream::Err),
+			_ => None,
+		}
+	}
+
+	/// Write all of `bytes`. They are flushed right away, like they
+	/// are when written to a FileOutputStream: the VM may halt before
+	/// Rust would flush them.
+	pub fn write(&self, bytes: &[u8]) -> io::Result<()> {
+		match self {
+			StandardStream::Out => {
+				let mut out = io::stdout();
+				out.write_all(bytes)?;
+				out.flush()
+			}
+			StandardStream::Err => {
+				let mut err = io::stderr();
+				err.write_all(bytes)?;
+				err.flush()
+			}
+		}
+	}
+}
+
+impl fmt::Display for StandardStream {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			StandardStream::Out => write!(f, "stdout"),
+			StandardStream::Err => write!(f, "stderr"),
+		}
+	}
+}
+
+/// The program's standard streams: the host's, unless the embedder
+/// redirected them.
+#[derive(Clone, Default)]
+pub struct StandardStreams {
+	input: Option<Arc<Mutex<dyn Read + Send>>>,
+	output: Option<Arc<Mutex<dyn Write + Send>>>,
+	error: Option<Arc<Mutex<dyn Write + Send>>>,
+}
+
+fn redirect_error() -> io::Error {
+	io::Error::new(io::ErrorKind::Other, "Stream Closed")
+}
+
+impl StandardStreams {
+	/// Read the program's standard input from `input`.
+	pub fn set_input<R: Read + Send + 'static>(&mut self, input: R) {
+		self.input = Some(Arc::new(Mutex::new(input)));
+	}
+
+	/// Write what the program prints to System.out to `output`.
+	pub fn set_output<W: Write + Send + 'static>(&mut self, output: W) {
+		self.output = Some(Arc::new(Mutex::new(output)));
+	}
+
+	/// Write what the program prints to System.err (and what the VM
+	/// reports, e.g., uncaught exceptions) to `error`.
+	pub fn set_error<W: Write + Send + 'static>(&mut self, error: W) {
+		self.error = Some(Arc::new(Mutex::new(error)));
+	}
+
+	/// Read from the standard input into `buffer`.
+	///
+	/// # Return value:
+	/// The number of bytes read; 0 at the end of the input.
+	pub fn read(&self, buffer: &mut [u8]) -> io::Result<usize> {
+		match &self.input {
+			Some(input) => input.lock().map_err(|_| redirect_error())?.read(buffer),
+			None => io::stdin().read(buffer),
+		}
+	}
+
+	/// Skip (up to) `count` bytes of the standard input.
+	///
+	/// # Return value:
+	/// The number of bytes skipped.
+	pub fn skip(&self, count: u64) -> io::Result<u64> {
+		match &self.input {
+			Some(input) => {
+				let mut input = input.lock().map_err(|_| redirect_error())?;
+				io::copy(&mut (&mut *input).take(count), &mut io::sink())
+			}
+			None => io::copy(&mut io::stdin().take(count), &mut io::sink()),
+		}
+	}
+
+	/// Write all of `bytes` to `stream`. See StandardStream::write.
+	pub fn write(&self, stream: StandardStream, bytes: &[u8]) -> io::Result<()> {
+		let redirect = match stream {
+			StandardStream::Out => &self.output,
+			StandardStream::Err => &self.error,
+		};
+		match redirect {
+			Some(redirect) => {
+				let mut redirect = redirect.lock().map_err(|_| redirect_error())?;
+				redirect.write_all(bytes)?;
+				redirect.flush()
+			}
+			None => stream.write(bytes),
+		}
+	}
+}
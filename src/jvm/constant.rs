@@ -29,6 +29,8 @@ pub enum Utf8Reserved {
 	BoostrapMethods,
 	NestHost,
 	NestMembers,
+	LineNumberTable,
+	SourceFile,
 	NotReserved,
 }
 
@@ -39,6 +41,8 @@ impl fmt::Display for Utf8Reserved {
 			Utf8Reserved::Code => write!(f, "Code"),
 			Utf8Reserved::StackMapTable => write!(f, "StackMapTable"),
 			Utf8Reserved::ConstantValue => write!(f, "ConstantValue"),
+			Utf8Reserved::LineNumberTable => write!(f, "LineNumberTable"),
+			Utf8Reserved::SourceFile => write!(f, "SourceFile"),
 			_ => write!(f, "Unknown"),
 		}
 	}
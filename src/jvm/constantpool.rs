@@ -165,6 +165,7 @@ impl<'l> From<&'l Vec<u8>> for ConstantPool {
 						| (bytes[offset + 7] as u64) << 8
 						| (bytes[offset + 8] as u64) << 0;
 					offset += 9;
+					constants[i] = Constant::Long(tag, bytes);
 					/*
 					 * From https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html#jvms-4.4.5
 					 * "... then the next usable item in the pool is located at index n+2"
@@ -184,7 +185,6 @@ impl<'l> From<&'l Vec<u8>> for ConstantPool {
 					offset += 9;
 					// TODO: Parse a constant double from bytes into value
 					// https://docs.oracle.com/javase/specs/jvms/se13/html/jvms-4.html#jvms-4.4.5
-					constants[i] = Constant::Long(tag, bytes);
 					constants[i] = Constant::Double(tag, bytes);
 					/*
 					 * From https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html#jvms-4.4.5
@@ -228,6 +228,10 @@ impl<'l> From<&'l Vec<u8>> for ConstantPool {
 						reserved = Utf8Reserved::StackMapTable;
 					} else if value == "ConstantValue".to_string() {
 						reserved = Utf8Reserved::ConstantValue;
+					} else if value == "LineNumberTable".to_string() {
+						reserved = Utf8Reserved::LineNumberTable;
+					} else if value == "SourceFile".to_string() {
+						reserved = Utf8Reserved::SourceFile;
 					}
 
 					offset += 1 + 2 + (length as usize);
@@ -269,7 +273,7 @@ impl<'l> From<&'l Vec<u8>> for ConstantPool {
 					assert!(false, "TODO: Parse a package");
 				}
 				_ => {
-					print!("oops: unhandled constant pool tag.\n");
+					eprintln!("oops: unhandled constant pool tag.");
 				}
 			};
 		}
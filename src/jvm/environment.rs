@@ -1,15 +1,31 @@
 use jvm::classpath::ClassLocation;
 use jvm::classpath::ClassPath;
+use jvm::classpath::ClassSource;
 use jvm::debug::DebugLevel;
+use std::sync::Arc;
 
 pub struct Environment {
 	pub classpath: ClassPath,
+	/// The entries of the class path, as given.
+	pub classpath_entries: Vec<String>,
+	/// The entries of the boot class path, as given.
+	pub boot_classpath_entries: Vec<String>,
 	pub arguments: Vec<String>,
 }
 
 impl Environment {
-	pub fn new(cp: &[&str], args: &[&str], debug_level: DebugLevel) -> Self {
-		let classpath = ClassPath::new(cp, debug_level);
+	/// The environment of a program whose classes are on the boot class
+	/// path `boot_cp`, then on the class path `cp` and then in `sources`.
+	pub fn new(
+		boot_cp: &[&str],
+		cp: &[&str],
+		sources: &[Arc<dyn ClassSource>],
+		args: &[&str],
+		release: u32,
+		debug_level: DebugLevel,
+	) -> Self {
+		let entries: Vec<&str> = boot_cp.iter().chain(cp).cloned().collect();
+		let classpath = ClassPath::new(&entries, sources, release, debug_level);
 		let mut arguments = Vec::<String>::new();
 
 		for arg in args {
@@ -18,6 +34,8 @@ impl Environment {
 
 		Environment {
 			classpath,
+			classpath_entries: cp.iter().map(|entry| entry.to_string()).collect(),
+			boot_classpath_entries: boot_cp.iter().map(|entry| entry.to_string()).collect(),
 			arguments,
 		}
 	}
@@ -15,6 +15,7 @@ pub enum FatalErrorType {
 	MainMethodNotPublicStatic,
 	MainMethodNotVoid,
 	InvalidFieldType(char),
+	InvalidFieldDescriptor(String, String, String),
 	UninitializedField(String, String),
 	FieldNotFound(String, String),
 	InvalidMethodDescriptor,
@@ -26,6 +27,9 @@ pub enum FatalErrorType {
 	NotImplemented(String),
 	RequiredStackValueNotFound(String),
 	Exception(String),
+	OutOfMemory(usize),
+	StackOverflow(usize),
+	CompressedResource(String, String),
 	Todo(String),
 }
 
@@ -72,6 +76,11 @@ impl fmt::Display for FatalErrorType {
 				index, class, expected
 			),
 			FatalErrorType::InvalidFieldType(field) => write!(f, "Invalid field type: {}.", field),
+			FatalErrorType::InvalidFieldDescriptor(descriptor, field, class) => write!(
+				f,
+				"Field {}.{} has an invalid descriptor: {}.",
+				class, field, descriptor
+			),
 			FatalErrorType::UninitializedField(field, class) => {
 				write!(f, "Use of Uninitialized field {}.{}.", class, field)
 			}
@@ -91,6 +100,17 @@ impl fmt::Display for FatalErrorType {
 				write!(f, "{} needs a stack value that was not found.", requirement)
 			}
 			FatalErrorType::Exception(exception_type) => write!(f, "Exception: {}", exception_type),
+			FatalErrorType::OutOfMemory(size) => {
+				write!(f, "Could not allocate {} bytes: the heap is full.", size)
+			}
+			FatalErrorType::StackOverflow(depth) => {
+				write!(f, "Exceeded the maximum stack depth ({} frames).", depth)
+			}
+			FatalErrorType::CompressedResource(class, image) => write!(
+				f,
+				"Class {} is compressed in the jimage {}: compressed jimages are not supported.",
+				class, image
+			),
 			FatalErrorType::Todo(task) => write!(f, "TODO: {}", task),
 			_ => write!(f, "Unhandled FatalErrorType."),
 		}
@@ -106,33 +126,10 @@ impl FatalError {
 		FatalError { error: error }
 	}
 
+	/// Report the error and unwind: the VM cannot go on. The panic's
+	/// message is the error's (see Vm, which catches it).
 	pub fn call(&self) {
 		eprintln!("Fatal Error: {}", self.error);
-		assert!(false);
-	}
-}
-pub enum NonFatalErrorType {
-	NotImplemented(String),
-}
-
-impl fmt::Display for NonFatalErrorType {
-	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		match self {
-			NonFatalErrorType::NotImplemented(what) => write!(f, "{} not implemented.", what),
-		}
-	}
-}
-
-pub struct NonFatalError {
-	error: NonFatalErrorType,
-}
-
-impl NonFatalError {
-	pub fn new(error: NonFatalErrorType) -> Self {
-		NonFatalError { error: error }
-	}
-
-	pub fn call(&self) {
-		eprintln!("Non Fatal Error: {}", self.error);
+		panic!("{}", self.error);
 	}
 }
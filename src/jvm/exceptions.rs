@@ -34,6 +34,22 @@ impl Exception {
 	pub fn byte_len(&self) -> usize {
 		8 as usize
 	}
+
+	/// Whether this handler is active when the instruction at `pc`
+	/// throws. The range is exclusive of `end_pc` (JVMS 4.7.3).
+	pub fn covers(&self, pc: usize) -> bool {
+		(self.start_pc as usize) <= pc && pc < (self.end_pc as usize)
+	}
+
+	pub fn handler_pc(&self) -> usize {
+		self.handler_pc as usize
+	}
+
+	/// The index into the constant pool of the class of exceptions
+	/// that this handler catches. Zero if it catches all of them.
+	pub fn catch_type(&self) -> u16 {
+		self.catch_type
+	}
 }
 
 impl<'l> From<&'l Vec<u8>> for Exception {
@@ -77,6 +93,11 @@ impl ExceptionTable {
 	pub fn exceptions_table_count(&self) -> u16 {
 		self.exceptions.len() as u16
 	}
+
+	/// The handlers, in the order that they must be searched.
+	pub fn iter(&self) -> std::slice::Iter<Exception> {
+		self.exceptions.iter()
+	}
 }
 
 impl<'l> From<&'l Vec<u8>> for ExceptionTable {
@@ -21,7 +21,10 @@
  */
 use jvm::attribute::Attributes;
 use jvm::constant::Constant;
+use jvm::constant::Utf8Reserved;
 use jvm::constantpool::ConstantPool;
+use jvm::typevalues::create_default_value;
+use jvm::typevalues::JvmType;
 use jvm::typevalues::JvmValue;
 use std::fmt;
 use std::iter::repeat;
@@ -55,6 +58,70 @@ impl Field {
 	pub fn byte_len(&self) -> usize {
 		self.byte_len
 	}
+
+	pub fn is_static(&self) -> bool {
+		self.access_flags & (FieldAccessFlags::Static as u16) != 0
+	}
+
+	pub fn get_name(&self, cp: &ConstantPool) -> Option<String> {
+		if let Constant::Utf8(_, _, _, name) = cp.get_constant_ref(self.name_index as usize) {
+			Some(name.clone())
+		} else {
+			None
+		}
+	}
+
+	pub fn get_descriptor(&self, cp: &ConstantPool) -> Option<String> {
+		if let Constant::Utf8(_, _, _, descriptor) =
+			cp.get_constant_ref(self.descriptor_index as usize)
+		{
+			Some(descriptor.clone())
+		} else {
+			None
+		}
+	}
+
+	/// If this field has a ConstantValue attribute (JVMS 4.7.2), return
+	/// the index into the constant pool of its value.
+	///
+	/// # Arguments
+	///
+	/// `cp` - The constant pool of the class that declares this field.
+	pub fn get_constant_value_index(&self, cp: &ConstantPool) -> Option<u16> {
+		for i in 0..self.attributes.len() {
+			let attribute = self.attributes.get_ref(i);
+			if let Constant::Utf8(_, Utf8Reserved::ConstantValue, _, _) =
+				cp.get_constant_ref(attribute.attribute_name_index as usize)
+			{
+				if attribute.info.len() >= 2 {
+					return Some((attribute.info[0] as u16) << 8 | (attribute.info[1] as u16));
+				}
+			}
+		}
+		None
+	}
+
+	/// Prepare this field (JVMS 5.4.2): if it is static, set it to
+	/// the default value for its type.
+	///
+	/// # Arguments
+	///
+	/// `cp` - The constant pool of the class that declares this field.
+	pub fn prepare(&self, cp: &ConstantPool) -> bool {
+		if !self.is_static() {
+			return true;
+		}
+		if let Some(r#type) = self
+			.get_descriptor(cp)
+			.and_then(|descriptor| JvmType::from_field_descriptor(&descriptor))
+		{
+			if let Ok(mut value) = self.value.lock() {
+				*value = Some(create_default_value(&r#type, self.access_flags));
+				return true;
+			}
+		}
+		false
+	}
 }
 
 impl fmt::Display for Field {
@@ -121,6 +188,10 @@ impl Fields {
 		self.fields.len() as u16
 	}
 
+	pub fn iter(&self) -> std::slice::Iter<Field> {
+		self.fields.iter()
+	}
+
 	pub fn get_field_ref(&self, name: &str, r#type: &str, cp: &ConstantPool) -> Option<&Field> {
 		for field in &self.fields {
 			if let Constant::Utf8(_, _, _, current_name) =
@@ -133,7 +204,6 @@ impl Fields {
 					cp.get_constant_ref(field.descriptor_index as usize)
 				{
 					if r#type == current_descriptor {
-						println!("Found the field!");
 						return Some(field);
 					}
 				}
@@ -165,7 +235,6 @@ impl Fields {
 					cp.get_constant_ref(*descriptor_index as usize)
 				{
 					if r#type == current_descriptor {
-						println!("Found the field!");
 						return true;
 					}
 				}
@@ -0,0 +1,228 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::console::{StandardStream, StandardStreams};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The file descriptors of the host's standard streams.
+pub const STDIN_FD: i32 = 0;
+const FIRST_FILE_FD: i32 = 3;
+
+/// How the program opens a file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpenMode {
+	Read,
+	Write,
+	Append,
+	ReadWrite,
+}
+
+/// A FileSystemPolicy decides which files the program may open. The VM
+/// consults it whenever the program opens a file.
+///
+/// Any closure with the same signature as open is a FileSystemPolicy.
+pub trait FileSystemPolicy: Send + Sync {
+	/// The path of the file to open when the program opens `path` with
+	/// `mode` -- usually `path` itself, but it may redirect the program
+	/// elsewhere. An error (e.g., io::ErrorKind::PermissionDenied)
+	/// denies the program access; it gets a FileNotFoundException.
+	fn open(&self, path: &Path, mode: OpenMode) -> io::Result<PathBuf>;
+}
+
+impl<F> FileSystemPolicy for F
+where
+	F: Fn(&Path, OpenMode) -> io::Result<PathBuf> + Send + Sync,
+{
+	fn open(&self, path: &Path, mode: OpenMode) -> io::Result<PathBuf> {
+		self(path, mode)
+	}
+}
+
+/// The default FileSystemPolicy: the program may open any file that
+/// the VM can.
+pub struct AllowAll;
+
+impl FileSystemPolicy for AllowAll {
+	fn open(&self, path: &Path, _: OpenMode) -> io::Result<PathBuf> {
+		Ok(path.to_path_buf())
+	}
+}
+
+/// The message of `err`, without the OS error number that Rust adds to
+/// it (e.g., "No such file or directory", like Java's messages).
+pub fn error_message(err: &io::Error) -> String {
+	let message = err.to_string();
+	match message.rfind(" (os error ") {
+		Some(index) => message[..index].to_string(),
+		None => message,
+	}
+}
+
+/// The FileTable holds the files that the program has open, by their
+/// descriptors (the values of the fd fields of its FileDescriptors).
+/// Descriptors 0, 1 and 2 are the standard streams.
+pub struct FileTable {
+	policy: Arc<dyn FileSystemPolicy>,
+	standard_streams: StandardStreams,
+	files: HashMap<i32, File>,
+	next_fd: i32,
+}
+
+impl FileTable {
+	pub fn new(policy: Arc<dyn FileSystemPolicy>) -> Self {
+		FileTable {
+			policy,
+			standard_streams: StandardStreams::default(),
+			files: HashMap::new(),
+			next_fd: FIRST_FILE_FD,
+		}
+	}
+
+	pub fn set_policy(&mut self, policy: Arc<dyn FileSystemPolicy>) {
+		self.policy = policy;
+	}
+
+	pub fn set_standard_streams(&mut self, standard_streams: StandardStreams) {
+		self.standard_streams = standard_streams;
+	}
+
+	pub fn standard_streams(&self) -> &StandardStreams {
+		&self.standard_streams
+	}
+
+	/// Open the file at `path` (subject to the policy).
+	///
+	/// # Return value:
+	/// The descriptor of the open file.
+	pub fn open(&mut self, path: &str, mode: OpenMode) -> io::Result<i32> {
+		let path = self.policy.open(Path::new(path), mode)?;
+		if path.is_dir() {
+			return Err(io::Error::new(io::ErrorKind::Other, "Is a directory"));
+		}
+		let mut options = OpenOptions::new();
+		match mode {
+			OpenMode::Read => options.read(true),
+			OpenMode::Write => options.write(true).create(true).truncate(true),
+			OpenMode::Append => options.append(true).create(true),
+			OpenMode::ReadWrite => options.read(true).write(true).create(true),
+		};
+		let file = options.open(path)?;
+		let fd = self.next_fd;
+		self.next_fd += 1;
+		self.files.insert(fd, file);
+		Ok(fd)
+	}
+
+	fn file(&mut self, fd: i32) -> io::Result<&mut File> {
+		self.files
+			.get_mut(&fd)
+			.ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Stream Closed"))
+	}
+
+	/// Read into `buffer`.
+	///
+	/// # Return value:
+	/// The number of bytes read; 0 at the end of the file.
+	pub fn read(&mut self, fd: i32, buffer: &mut [u8]) -> io::Result<usize> {
+		if fd == STDIN_FD {
+			return self.standard_streams.read(buffer);
+		}
+		self.file(fd)?.read(buffer)
+	}
+
+	pub fn write(&mut self, fd: i32, bytes: &[u8]) -> io::Result<()> {
+		if let Some(stream) = StandardStream::from_fd(fd) {
+			return self.standard_streams.write(stream, bytes);
+		}
+		self.file(fd)?.write_all(bytes)
+	}
+
+	/// The number of bytes that can be read without blocking.
+	pub fn available(&mut self, fd: i32) -> io::Result<u64> {
+		if fd == STDIN_FD {
+			return Ok(0);
+		}
+		let file = self.file(fd)?;
+		let position = file.stream_position()?;
+		Ok(file.metadata()?.len().saturating_sub(position))
+	}
+
+	/// Skip (up to) `count` bytes.
+	///
+	/// # Return value:
+	/// The number of bytes skipped.
+	pub fn skip(&mut self, fd: i32, count: i64) -> io::Result<i64> {
+		if fd == STDIN_FD {
+			return self
+				.standard_streams
+				.skip(count.max(0) as u64)
+				.map(|skipped| skipped as i64);
+		}
+		let file = self.file(fd)?;
+		let position = file.stream_position()?;
+		Ok(file.seek(SeekFrom::Current(count))? as i64 - position as i64)
+	}
+
+	pub fn position(&mut self, fd: i32) -> io::Result<u64> {
+		self.file(fd)?.stream_position()
+	}
+
+	pub fn seek(&mut self, fd: i32, position: u64) -> io::Result<()> {
+		self.file(fd)?.seek(SeekFrom::Start(position)).map(|_| ())
+	}
+
+	pub fn length(&mut self, fd: i32) -> io::Result<u64> {
+		Ok(self.file(fd)?.metadata()?.len())
+	}
+
+	pub fn set_length(&mut self, fd: i32, length: u64) -> io::Result<()> {
+		let file = self.file(fd)?;
+		let position = file.stream_position()?;
+		file.set_len(length)?;
+		if position > length {
+			file.seek(SeekFrom::Start(length))?;
+		}
+		Ok(())
+	}
+
+	pub fn sync(&mut self, fd: i32) -> io::Result<()> {
+		if StandardStream::from_fd(fd).is_some() || fd == STDIN_FD {
+			return Ok(());
+		}
+		self.file(fd)?.sync_all()
+	}
+
+	/// Close the file. The host's standard streams stay open.
+	pub fn close(&mut self, fd: i32) -> io::Result<()> {
+		if fd < FIRST_FILE_FD {
+			return Ok(());
+		}
+		self.files
+			.remove(&fd)
+			.map(|_| ())
+			.ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Stream Closed"))
+	}
+}
@@ -20,6 +20,7 @@
  * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
  */
 use jvm::class::Class;
+use jvm::method::Method;
 use jvm::typevalues::JvmValue;
 use std::fmt;
 use std::rc::Rc;
@@ -29,6 +30,12 @@ pub struct Frame {
 	pub operand_stack: Vec<JvmValue>,
 	pub class: Option<Rc<Class>>,
 	pub locals: Vec<JvmValue>,
+	/// The method executing in this frame.
+	pub method: Option<Rc<Method>>,
+	/// The offset (into the method's code) of the instruction that
+	/// the frame is executing. In a frame that invoked a method, the
+	/// offset of the invoke instruction.
+	pub pc: usize,
 }
 
 impl Frame {
@@ -37,6 +44,8 @@ impl Frame {
 			operand_stack: Vec::<JvmValue>::new(),
 			class: None,
 			locals: Vec::<JvmValue>::new(),
+			method: None,
+			pc: 0,
 		}
 	}
 
@@ -47,6 +56,14 @@ impl Frame {
 			None
 		}
 	}
+
+	pub fn method(&self) -> Option<Rc<Method>> {
+		if let Some(method) = &self.method {
+			Some(Rc::clone(method))
+		} else {
+			None
+		}
+	}
 }
 
 impl fmt::Display for Frame {
@@ -64,9 +81,10 @@ impl fmt::Display for Frame {
 		result = write!(f, "==============\n");
 		if let Some(class) = &self.class {
 			if let Some(class_name) = (*class).get_class_name() {
-				result = write!(f, "Class: {}", class_name);
+				result = write!(f, "Class: {}\n", class_name);
 			}
 		}
+		result = write!(f, "pc: {}", self.pc);
 		result
 	}
 }
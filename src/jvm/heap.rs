@@ -0,0 +1,333 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::array::JvmArray;
+use jvm::debug::{Debug, DebugLevel};
+use jvm::object::JvmObject;
+use jvm::typevalues::JvmReferenceTargetType;
+use jvm::typevalues::JvmValue;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+/// The default maximum size (in bytes) of the Java heap.
+pub const DEFAULT_MAX_HEAP_SIZE: usize = 256 * 1024 * 1024;
+
+/// The heap is not collected until at least this many
+/// bytes are allocated.
+const INITIAL_COLLECTION_THRESHOLD: usize = 1024 * 1024;
+
+/// Parse a memory size the way the -Xmx option expects it: a number
+/// of bytes, optionally followed by k (or K), m (or M) or g (or G).
+pub fn parse_memory_size(size: &str) -> Option<usize> {
+	let (digits, multiplier) = match size.chars().last()? {
+		'k' | 'K' => (&size[..size.len() - 1], 1024),
+		'm' | 'M' => (&size[..size.len() - 1], 1024 * 1024),
+		'g' | 'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+		_ => (size, 1),
+	};
+	digits.parse::<usize>().ok()?.checked_mul(multiplier)
+}
+
+/// The handle that the heap keeps to each of the objects
+/// and arrays that it manages. The heap's own handles are
+/// weak: the heap never keeps anything alive by itself.
+enum HeapHandle {
+	Object(Weak<Mutex<JvmObject>>),
+	Array(Weak<Mutex<JvmArray>>),
+}
+
+/// A strong reference to a managed object or array. Only
+/// exists while the heap is being collected.
+enum HeapReference {
+	Object(Arc<Mutex<JvmObject>>),
+	Array(Arc<Mutex<JvmArray>>),
+}
+
+/// The identity of a managed object or array.
+fn address_of<T>(reference: &Arc<Mutex<T>>) -> usize {
+	&**reference as *const Mutex<T> as usize
+}
+
+/// The identity of the object or array that `value` references,
+/// if it references one.
+fn referenced_address(value: &JvmValue) -> Option<usize> {
+	match value {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+			Some(address_of(object))
+		}
+		JvmValue::Reference(_, JvmReferenceTargetType::Array(array), _) => Some(address_of(array)),
+		_ => None,
+	}
+}
+
+impl HeapHandle {
+	fn upgrade(&self) -> Option<HeapReference> {
+		match self {
+			HeapHandle::Object(object) => object.upgrade().map(HeapReference::Object),
+			HeapHandle::Array(array) => array.upgrade().map(HeapReference::Array),
+		}
+	}
+}
+
+impl HeapReference {
+	fn downgrade(&self) -> HeapHandle {
+		match self {
+			HeapReference::Object(object) => HeapHandle::Object(Arc::downgrade(object)),
+			HeapReference::Array(array) => HeapHandle::Array(Arc::downgrade(array)),
+		}
+	}
+
+	fn address(&self) -> usize {
+		match self {
+			HeapReference::Object(object) => address_of(object),
+			HeapReference::Array(array) => address_of(array),
+		}
+	}
+
+	fn strong_count(&self) -> usize {
+		match self {
+			HeapReference::Object(object) => Arc::strong_count(object),
+			HeapReference::Array(array) => Arc::strong_count(array),
+		}
+	}
+
+	/// The identities of everything that this object (or array)
+	/// references. None if it is locked and cannot be inspected.
+	fn references(&self) -> Option<Vec<usize>> {
+		match self {
+			HeapReference::Object(object) => object
+				.try_lock()
+				.ok()
+				.map(|object| object.values().filter_map(referenced_address).collect()),
+			HeapReference::Array(array) => array
+				.try_lock()
+				.ok()
+				.map(|array| array.values().filter_map(referenced_address).collect()),
+		}
+	}
+
+	/// Drop everything that this object (or array) references.
+	fn clear(&self) {
+		/*
+		 * The contents are dropped after the lock is released.
+		 */
+		match self {
+			HeapReference::Object(object) => {
+				let _contents = object.try_lock().ok().map(|mut object| object.clear());
+			}
+			HeapReference::Array(array) => {
+				let _contents = array.try_lock().ok().map(|mut array| array.clear());
+			}
+		}
+	}
+}
+
+/// The Heap manages every object and array that a Java program creates.
+///
+/// Objects and arrays are reference counted, so they are freed as soon as
+/// they become unreachable -- unless they are part of a cycle. The heap
+/// finds those with a tracing collector that works like the cycle
+/// collector in CPython: an object (or array) that is referenced from
+/// outside the heap (from a thread's frames, from a static field, from
+/// the intern table or from the VM itself) is a root; everything that
+/// the roots reference, directly or indirectly, is reachable. Whatever
+/// else the heap manages is garbage. The contents of the garbage are
+/// dropped, which breaks its cycles and frees it.
+pub struct Heap {
+	debug_level: DebugLevel,
+	handles: Vec<(HeapHandle, usize)>,
+	/// The bytes allocated to managed objects and arrays.
+	allocated: usize,
+	/// The heap is collected when allocated exceeds this threshold.
+	next_collection: usize,
+	max_size: usize,
+}
+
+impl Heap {
+	pub fn new(max_size: usize, debug_level: DebugLevel) -> Self {
+		Heap {
+			debug_level,
+			handles: Vec::new(),
+			allocated: 0,
+			next_collection: INITIAL_COLLECTION_THRESHOLD.min(max_size),
+			max_size,
+		}
+	}
+
+	/// The bytes allocated to managed objects and arrays.
+	pub fn allocated(&self) -> usize {
+		self.allocated
+	}
+
+	/// The most bytes that the heap may allocate.
+	pub fn max_size(&self) -> usize {
+		self.max_size
+	}
+
+	/// Move `object` into the heap.
+	///
+	/// # Return value:
+	/// A reference to `object`. None if there is no room for it
+	/// in the heap, even after it is collected.
+	pub fn allocate_object(&mut self, object: JvmObject) -> Option<Arc<Mutex<JvmObject>>> {
+		let size = object.size();
+		if !self.reserve(size) {
+			return None;
+		}
+		let object = Arc::new(Mutex::new(object));
+		self.handles
+			.push((HeapHandle::Object(Arc::downgrade(&object)), size));
+		Some(object)
+	}
+
+	/// Move `array` into the heap. See allocate_object.
+	pub fn allocate_array(&mut self, array: JvmArray) -> Option<Arc<Mutex<JvmArray>>> {
+		let size = array.size();
+		if !self.reserve(size) {
+			return None;
+		}
+		let array = Arc::new(Mutex::new(array));
+		self.handles
+			.push((HeapHandle::Array(Arc::downgrade(&array)), size));
+		Some(array)
+	}
+
+	/// Make room for `size` more bytes, collecting if necessary.
+	fn reserve(&mut self, size: usize) -> bool {
+		if self.allocated + size > self.next_collection {
+			self.collect();
+			self.next_collection = (2 * (self.allocated + size))
+				.max(INITIAL_COLLECTION_THRESHOLD)
+				.min(self.max_size);
+		}
+		if self.allocated + size > self.max_size {
+			return false;
+		}
+		self.allocated += size;
+		true
+	}
+
+	/// Collect the heap: free every object and array that is not
+	/// reachable from outside the heap.
+	pub fn collect(&mut self) {
+		let before = self.allocated;
+
+		/*
+		 * Hold a strong reference to everything that is still alive
+		 * while we work. Whatever is already gone was freed when its
+		 * reference count dropped to zero.
+		 */
+		let live: Vec<(HeapReference, usize)> = self
+			.handles
+			.drain(..)
+			.filter_map(|(handle, size)| handle.upgrade().map(|reference| (reference, size)))
+			.collect();
+		let indexes: HashMap<usize, usize> = live
+			.iter()
+			.enumerate()
+			.map(|(index, (reference, _))| (reference.address(), index))
+			.collect();
+
+		/*
+		 * Count the references to each object that come from outside
+		 * the heap: all of its references (less the one that we are
+		 * holding) less the ones from other managed objects.
+		 */
+		let references: Vec<Option<Vec<usize>>> = live
+			.iter()
+			.map(|(reference, _)| reference.references())
+			.collect();
+		let mut external: Vec<usize> = live
+			.iter()
+			.map(|(reference, _)| reference.strong_count() - 1)
+			.collect();
+		for referenced in references.iter().flatten() {
+			for address in referenced {
+				if let Some(index) = indexes.get(address) {
+					external[*index] = external[*index].saturating_sub(1);
+				}
+			}
+		}
+
+		/*
+		 * Mark everything reachable from the roots. Something that
+		 * could not be inspected (because it is locked) is in use
+		 * and, therefore, a root.
+		 */
+		let mut reachable = vec![false; live.len()];
+		let mut worklist: Vec<usize> = (0..live.len())
+			.filter(|index| external[*index] > 0 || references[*index].is_none())
+			.collect();
+		while let Some(index) = worklist.pop() {
+			if reachable[index] {
+				continue;
+			}
+			reachable[index] = true;
+			if let Some(referenced) = &references[index] {
+				for address in referenced {
+					if let Some(referenced_index) = indexes.get(address) {
+						if !reachable[*referenced_index] {
+							worklist.push(*referenced_index);
+						}
+					}
+				}
+			}
+		}
+
+		/*
+		 * Sweep.
+		 */
+		self.allocated = 0;
+		let mut freed = 0;
+		for ((reference, size), reachable) in live.iter().zip(reachable.iter()) {
+			if *reachable {
+				self.handles.push((reference.downgrade(), *size));
+				self.allocated += size;
+			} else {
+				reference.clear();
+				freed += 1;
+			}
+		}
+		std::mem::drop(live);
+
+		Debug(
+			format!(
+				"Collected the heap: freed {} objects and {} bytes; {} bytes remain allocated.",
+				freed,
+				before - self.allocated,
+				self.allocated
+			),
+			&self.debug_level,
+			DebugLevel::Info,
+		);
+	}
+}
+
+impl fmt::Display for Heap {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"Heap: {} of {} bytes allocated.",
+			self.allocated, self.max_size
+		)
+	}
+}
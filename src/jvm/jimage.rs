@@ -0,0 +1,295 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Mutex;
+
+/// The magic number of a jimage (e.g., the lib/modules of a JDK 9+),
+/// in the byte order of the platform that wrote it.
+pub const JIMAGE_MAGIC: u32 = 0xCAFE_DADA;
+
+/// The multiplier (and the default seed) of the hash of the names of
+/// resources.
+const HASH_MULTIPLIER: u32 = 0x0100_0193;
+
+/// The size of the header: magic, version, flags, resource count,
+/// table length, locations size and strings size.
+const HEADER_SIZE: usize = 7 * 4;
+
+/// The attributes of a location, in the order of their kinds.
+const ATTRIBUTE_END: u8 = 0;
+const ATTRIBUTE_MODULE: u8 = 1;
+const ATTRIBUTE_PARENT: u8 = 2;
+const ATTRIBUTE_BASE: u8 = 3;
+const ATTRIBUTE_EXTENSION: u8 = 4;
+const ATTRIBUTE_OFFSET: u8 = 5;
+const ATTRIBUTE_COMPRESSED: u8 = 6;
+const ATTRIBUTE_UNCOMPRESSED: u8 = 7;
+const ATTRIBUTE_COUNT: usize = 8;
+
+/// Where a resource is in a jimage: its offset from the start of the
+/// file, its size and whether it is compressed (which read_resource
+/// does not undo).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JImageResource {
+	pub offset: u64,
+	pub size: u64,
+	pub compressed: bool,
+}
+
+/// The index of a jimage: the container in which a JDK 9+ ships the
+/// class files (and other resources) of its modules. The resources
+/// themselves are read on demand (see read_resource), from the file
+/// that stays open for as long as the index does.
+pub struct JImage {
+	path: String,
+	file: Mutex<File>,
+	big_endian: bool,
+	/// For each slot of the hash table, where to look next (see
+	/// find_location).
+	redirect: Vec<i32>,
+	/// For each slot of the hash table, the offset of its location.
+	offsets: Vec<u32>,
+	locations: Vec<u8>,
+	strings: Vec<u8>,
+	/// Where the resources start.
+	index_size: u64,
+}
+
+/// The hash of the resource name `name` with seed `seed`.
+fn hash_code(name: &str, seed: u32) -> u32 {
+	name.bytes().fold(seed, |hash, byte| {
+		hash.wrapping_mul(HASH_MULTIPLIER) ^ byte as u32
+	}) & 0x7FFF_FFFF
+}
+
+impl JImage {
+	/// Read the index of the jimage `path`. None if it is not a jimage.
+	pub fn open(path: &str) -> Option<JImage> {
+		let mut file = File::open(path).ok()?;
+		let mut header = [0u8; HEADER_SIZE];
+		file.read_exact(&mut header).ok()?;
+
+		let big_endian = if u32::from_le_bytes([header[0], header[1], header[2], header[3]])
+			== JIMAGE_MAGIC
+		{
+			false
+		} else if u32::from_be_bytes([header[0], header[1], header[2], header[3]]) == JIMAGE_MAGIC {
+			true
+		} else {
+			return None;
+		};
+		let field = |index: usize| {
+			let bytes = [
+				header[index * 4],
+				header[index * 4 + 1],
+				header[index * 4 + 2],
+				header[index * 4 + 3],
+			];
+			if big_endian {
+				u32::from_be_bytes(bytes) as usize
+			} else {
+				u32::from_le_bytes(bytes) as usize
+			}
+		};
+		let table_length = field(4);
+		let locations_size = field(5);
+		let strings_size = field(6);
+
+		let mut index = vec![0u8; table_length * 8 + locations_size + strings_size];
+		file.read_exact(&mut index).ok()?;
+
+		let mut image = JImage {
+			path: path.to_string(),
+			file: Mutex::new(file),
+			big_endian,
+			redirect: Vec::with_capacity(table_length),
+			offsets: Vec::with_capacity(table_length),
+			locations: index[table_length * 8..table_length * 8 + locations_size].to_vec(),
+			strings: index[table_length * 8 + locations_size..].to_vec(),
+			index_size: (HEADER_SIZE + index.len()) as u64,
+		};
+		for slot in 0..table_length {
+			image.redirect.push(image.u32_at(&index, slot * 4) as i32);
+			image
+				.offsets
+				.push(image.u32_at(&index, (table_length + slot) * 4));
+		}
+		Some(image)
+	}
+
+	/// The path of the jimage.
+	pub fn path(&self) -> &str {
+		&self.path
+	}
+
+	/// The contents of the resource `resource`. None if it is
+	/// compressed.
+	pub fn read_resource(&self, resource: &JImageResource) -> Option<Vec<u8>> {
+		if resource.compressed {
+			return None;
+		}
+		let mut file = self.file.lock().ok()?;
+		file.seek(SeekFrom::Start(resource.offset)).ok()?;
+		let mut bytes = vec![0u8; resource.size as usize];
+		file.read_exact(&mut bytes).ok()?;
+		Some(bytes)
+	}
+
+	fn u32_at(&self, bytes: &[u8], offset: usize) -> u32 {
+		let bytes = [
+			bytes[offset],
+			bytes[offset + 1],
+			bytes[offset + 2],
+			bytes[offset + 3],
+		];
+		if self.big_endian {
+			u32::from_be_bytes(bytes)
+		} else {
+			u32::from_le_bytes(bytes)
+		}
+	}
+
+	/// The string at `offset` in the strings of the jimage.
+	fn string_at(&self, offset: u64) -> &str {
+		let start = (offset as usize).min(self.strings.len());
+		let end = self.strings[start..]
+			.iter()
+			.position(|byte| *byte == 0)
+			.map_or(self.strings.len(), |length| start + length);
+		std::str::from_utf8(&self.strings[start..end]).unwrap_or("")
+	}
+
+	/// The attributes of the location at `offset`: each is a byte
+	/// with its kind (in the high five bits) and length (less one, in
+	/// the low three bits) and then its value, big endian.
+	fn attributes_at(&self, offset: usize) -> [u64; ATTRIBUTE_COUNT] {
+		let mut attributes = [0u64; ATTRIBUTE_COUNT];
+		let mut position = offset;
+		while let Some(byte) = self.locations.get(position) {
+			let kind = byte >> 3;
+			if kind == ATTRIBUTE_END || kind as usize >= ATTRIBUTE_COUNT {
+				break;
+			}
+			let length = (byte & 0x7) as usize + 1;
+			let value = self
+				.locations
+				.iter()
+				.skip(position + 1)
+				.take(length)
+				.fold(0u64, |value, byte| value << 8 | *byte as u64);
+			attributes[kind as usize] = value;
+			position += length + 1;
+		}
+		attributes
+	}
+
+	/// The full name of the location with `attributes`:
+	/// /module/parent/base.extension.
+	fn name_of(&self, attributes: &[u64; ATTRIBUTE_COUNT]) -> String {
+		let mut name = String::new();
+		let module = self.string_at(attributes[ATTRIBUTE_MODULE as usize]);
+		if !module.is_empty() {
+			name.push('/');
+			name.push_str(module);
+			name.push('/');
+		}
+		let parent = self.string_at(attributes[ATTRIBUTE_PARENT as usize]);
+		if !parent.is_empty() {
+			name.push_str(parent);
+			name.push('/');
+		}
+		name.push_str(self.string_at(attributes[ATTRIBUTE_BASE as usize]));
+		let extension = self.string_at(attributes[ATTRIBUTE_EXTENSION as usize]);
+		if !extension.is_empty() {
+			name.push('.');
+			name.push_str(extension);
+		}
+		name
+	}
+
+	/// The attributes of the location of the resource named `name`.
+	/// The hash of the name picks a slot of the table; the slot
+	/// either is the location's (when its redirect is negative) or
+	/// gives the seed of a second hash that picks it.
+	fn find_location(&self, name: &str) -> Option<[u64; ATTRIBUTE_COUNT]> {
+		let table_length = self.redirect.len() as u32;
+		if table_length == 0 {
+			return None;
+		}
+		let slot = hash_code(name, HASH_MULTIPLIER) % table_length;
+		let redirect = self.redirect[slot as usize];
+		let slot = if redirect < 0 {
+			(-1 - redirect) as u32
+		} else if redirect > 0 {
+			hash_code(name, redirect as u32) % table_length
+		} else {
+			return None;
+		};
+		let attributes = self.attributes_at(*self.offsets.get(slot as usize)? as usize);
+		if self.name_of(&attributes) == name {
+			Some(attributes)
+		} else {
+			None
+		}
+	}
+
+	/// Where the resource `name` of the module `module` (e.g.,
+	/// java/lang/Object.class of java.base) is.
+	pub fn find_resource(&self, module: &str, name: &str) -> Option<JImageResource> {
+		let attributes = self.find_location(&format!("/{}/{}", module, name))?;
+		Some(self.resource_at(&attributes))
+	}
+
+	/// The resource of the location with `attributes`. Its size is
+	/// the compressed size when it is compressed.
+	fn resource_at(&self, attributes: &[u64; ATTRIBUTE_COUNT]) -> JImageResource {
+		let compressed = attributes[ATTRIBUTE_COMPRESSED as usize];
+		JImageResource {
+			offset: self.index_size + attributes[ATTRIBUTE_OFFSET as usize],
+			size: if compressed != 0 {
+				compressed
+			} else {
+				attributes[ATTRIBUTE_UNCOMPRESSED as usize]
+			},
+			compressed: compressed != 0,
+		}
+	}
+
+	/// The module that has the package `package` (e.g., java/lang).
+	/// The resource /packages/java.lang lists the modules that have
+	/// java.lang: pairs of a flag (whether the module's package is
+	/// empty) and the name of the module.
+	pub fn module_for_package(&self, package: &str) -> Option<String> {
+		let attributes = self.find_location(&format!("/packages/{}", package.replace('/', ".")))?;
+		let modules = self.read_resource(&self.resource_at(&attributes))?;
+		let modules: Vec<(u32, u32)> = modules
+			.chunks_exact(8)
+			.map(|pair| (self.u32_at(pair, 0), self.u32_at(pair, 4)))
+			.collect();
+		modules
+			.iter()
+			.find(|(empty, _)| *empty == 0)
+			.or_else(|| modules.first())
+			.map(|(_, module)| self.string_at(*module as u64).to_string())
+	}
+}
@@ -25,31 +25,43 @@
 //use arm_and_handler::handler;
 
 use enum_primitive::FromPrimitive;
+use jvm::array::array_element;
 use jvm::array::JvmArray;
 use jvm::array::JvmArrayType;
 use jvm::class::Class;
 use jvm::class::ClassAccessFlags;
 use jvm::class::ClassInitializationStatus;
+use jvm::classpath::ClassLocation;
 use jvm::comparison::ComparisonType;
+use jvm::console::{Charset, StandardStream};
 use jvm::constant::Constant;
 use jvm::debug::Debug;
 use jvm::debug::DebugLevel;
 use jvm::error::FatalError;
 use jvm::error::FatalErrorType;
-use jvm::error::NonFatalError;
-use jvm::error::NonFatalErrorType;
+use jvm::filesystem::{AllowAll, FileSystemPolicy, FileTable, STDIN_FD};
 use jvm::frame::Frame;
+use jvm::heap::Heap;
 use jvm::method::Method;
 use jvm::method::MethodAccessFlags;
 use jvm::methodarea::MethodArea;
-use jvm::object::{create_static_string_object, JvmObject};
+use jvm::methodarea::{ClassLoaderId, BOOTSTRAP_LOADER};
+use jvm::native::NativeRegistry;
+use jvm::nativememory::NativeMemory;
+use jvm::object::{create_static_string_object, get_string_value, JvmObject};
 use jvm::opcodes::OperandCode;
+use jvm::stacktrace::StackTraceElement;
+use jvm::trace::TraceHook;
+use jvm::typevalues::create_default_value;
 use jvm::typevalues::create_null_value;
+use jvm::typevalues::descriptor_of_type_name;
+use jvm::typevalues::type_name_of_descriptor;
 use jvm::typevalues::JvmPrimitiveType;
 use jvm::typevalues::JvmReferenceTargetType;
 use jvm::typevalues::JvmReferenceType;
 use jvm::typevalues::JvmType;
 use jvm::typevalues::JvmValue;
+use std::io;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -57,30 +69,147 @@ use std::sync::Mutex;
 pub struct JvmThread {
 	debug_level: DebugLevel,
 	methodarea: Arc<Mutex<MethodArea>>,
-	pc: usize,
+	heap: Arc<Mutex<Heap>>,
+	/// The thread's Java stack. The last frame is the one executing.
+	frames: Vec<Frame>,
 	initializing_class: Vec<String>,
+	/// The exception that is propagating through the thread's frames.
+	pending_exception: Option<JvmValue>,
+	/// The OutOfMemoryError to throw when the heap is full; it is
+	/// allocated before it is needed, since then it cannot be.
+	out_of_memory_error: Option<JvmValue>,
+	stack_overflow_error_class: Option<Rc<Class>>,
+	/// Set once the program halts (e.g., calls System.exit).
+	exit_status: Option<i32>,
+	/// Loaded before it is needed, so that a stack trace can be
+	/// filled in even when the stack is full.
+	stack_trace_element_class: Option<Rc<Class>>,
+	/// The implementations of native methods.
+	natives: NativeRegistry,
+	/// The PrintStreams that the VM created for System.out and
+	/// System.err and the host's streams that they print to.
+	standard_streams: Vec<(Arc<Mutex<JvmObject>>, StandardStream)>,
+	/// The encoding of text printed to the standard streams.
+	console_charset: Charset,
+	/// The files that the program has open.
+	files: FileTable,
+	/// The memory outside of the heap that the program allocated.
+	memory: NativeMemory,
+	/// The java.lang.Thread that represents this thread, once the
+	/// boot sequence creates it.
+	current_thread: Option<JvmValue>,
+	max_stack_depth: usize,
+	/// The system properties that the embedder set; they override the
+	/// VM's standard ones.
+	system_properties: Vec<(String, String)>,
+	/// Told about the methods and instructions that the thread executes.
+	trace_hooks: Vec<Arc<dyn TraceHook>>,
+	/// How many more instructions the thread may execute, if it is
+	/// limited.
+	instruction_budget: Option<u64>,
 }
 
+/// The default maximum number of frames on a thread's Java stack.
+/// Frames live in the JvmThread (not on the native stack), so this is
+/// about as deep as HotSpot's default stack lets ordinary recursion go.
+pub const DEFAULT_MAX_STACK_DEPTH: usize = 16 * 1024;
+
+/// About how many bytes of HotSpot's stack a frame of ordinary recursion
+/// takes: the -Xss stack size (in bytes) divided by this is the maximum
+/// number of frames, and HotSpot's default, 1m, gives the default above.
+pub const STACK_FRAME_SIZE: usize = 64;
+
+/// The most frames that a stack trace records, like HotSpot's
+/// MaxJavaStackTraceDepth: the rest (e.g., of a StackOverflowError's)
+/// are left out.
+const MAX_STACK_TRACE_DEPTH: usize = 1024;
+
+/// The exit status of a program that throws an exception that it
+/// does not catch.
+pub const UNCAUGHT_EXCEPTION_EXIT_STATUS: i32 = 1;
+
+/// The exit status of a program that exhausts its instruction budget,
+/// as timeout(1)'s.
+pub const INSTRUCTION_BUDGET_EXIT_STATUS: i32 = 124;
+
+/// The priority of the thread that runs main (Thread.NORM_PRIORITY).
+const THREAD_NORM_PRIORITY: i32 = 5;
+
+/// The status of a running thread, as Thread.threadStatus holds it
+/// (see sun.misc.VM.toThreadState).
+const THREAD_STATUS_RUNNABLE: i32 = 5;
+
 enum OpcodeResult {
 	Exception,
 	Incr(usize),
 	Decr(usize),
 	Value(JvmValue),
+	/// Invoke a method in a new frame.
+	Invoke(Rc<Method>, Frame),
 }
 
-pub fn move_parameters_to_locals(
-	method: &Method,
-	invoking_frame: &mut Frame,
-	invoked_frame: &mut Frame,
-) -> bool {
-	for i in 0..method.parameter_count {
-		if let Some(parameter) = invoking_frame.operand_stack.pop() {
-			invoked_frame.locals.insert(0, parameter);
-		} else {
-			return false;
+/// The length of the invoke instruction `opcode`; a frame resumes
+/// this far past its pc when the method that it invoked returns.
+fn invoke_instruction_length(opcode: u8) -> usize {
+	match OperandCode::from_u8(opcode) {
+		Some(OperandCode::Invokeinterface) | Some(OperandCode::Invokedynamic) => 5,
+		_ => 3,
+	}
+}
+
+/// The locals of a frame whose method gets `arguments`: a long or a
+/// double takes up two (JVMS 2.6.1).
+fn locals_for_arguments(arguments: Vec<JvmValue>) -> Vec<JvmValue> {
+	let mut locals = Vec::with_capacity(arguments.len());
+	for argument in arguments {
+		let category_2 = argument.is_category_2();
+		locals.push(argument);
+		if category_2 {
+			locals.push(JvmValue::Primitive(JvmPrimitiveType::Void, 0, 0, 0));
 		}
 	}
-	true
+	locals
+}
+
+/// How an invocation starts: a native method (or an intrinsic) runs to
+/// completion immediately; a method with bytecode gets a frame.
+enum Invocation {
+	Completed(JvmValue),
+	Threw,
+	Frame(Rc<Method>, Frame),
+}
+
+/// The branch (JVMS 6.5, e.g., goto) `offset` bytes from the instruction.
+fn branch(offset: i32) -> OpcodeResult {
+	if offset < 0 {
+		OpcodeResult::Decr(offset.unsigned_abs() as usize)
+	} else {
+		OpcodeResult::Incr(offset as usize)
+	}
+}
+
+/// The binary name of `class` and the name and descriptor of its
+/// `method`, as trace hooks get them.
+fn traced_method(class: &Class, method: &Method) -> (String, String, String) {
+	let cp = class.get_constant_pool_ref();
+	(
+		class.get_class_name().unwrap_or_default(),
+		method.get_name(cp).unwrap_or_default(),
+		method.get_descriptor(cp).unwrap_or_default(),
+	)
+}
+
+fn read_u16(bytes: &[u8], index: usize) -> u16 {
+	u16::from_be_bytes([bytes[index], bytes[index + 1]])
+}
+
+fn read_i32(bytes: &[u8], index: usize) -> i32 {
+	i32::from_be_bytes([
+		bytes[index],
+		bytes[index + 1],
+		bytes[index + 2],
+		bytes[index + 3],
+	])
 }
 
 impl JvmThread {
@@ -88,16 +217,726 @@ impl JvmThread {
 		self.debug_level.clone()
 	}
 
-	pub fn new(debug_level: DebugLevel, methodarea: Arc<Mutex<MethodArea>>) -> Self {
+	pub fn new(
+		debug_level: DebugLevel,
+		methodarea: Arc<Mutex<MethodArea>>,
+		heap: Arc<Mutex<Heap>>,
+		natives: NativeRegistry,
+		max_stack_depth: usize,
+	) -> Self {
 		JvmThread {
 			debug_level: debug_level,
 			methodarea: methodarea,
-			pc: 0,
+			heap: heap,
+			frames: Vec::<Frame>::new(),
 			initializing_class: Vec::<String>::new(),
+			pending_exception: None,
+			out_of_memory_error: None,
+			stack_overflow_error_class: None,
+			stack_trace_element_class: None,
+			exit_status: None,
+			natives,
+			standard_streams: Vec::new(),
+			console_charset: Charset::from_locale(),
+			files: FileTable::new(Arc::new(AllowAll)),
+			memory: NativeMemory::new(),
+			current_thread: None,
+			max_stack_depth,
+			system_properties: Vec::new(),
+			trace_hooks: Vec::new(),
+			instruction_budget: None,
+		}
+	}
+
+	/// Set the encoding of text printed to the standard streams.
+	pub fn set_console_charset(&mut self, console_charset: Charset) {
+		self.console_charset = console_charset;
+	}
+
+	pub fn console_charset(&self) -> Charset {
+		self.console_charset
+	}
+
+	/// Set the policy that decides which files the program may open.
+	pub fn set_file_system_policy(&mut self, policy: Arc<dyn FileSystemPolicy>) {
+		self.files.set_policy(policy);
+	}
+
+	pub fn files(&mut self) -> &mut FileTable {
+		&mut self.files
+	}
+
+	/// Write all of `bytes` to the standard stream `stream` (or to
+	/// where the embedder redirected it).
+	pub fn write_standard_stream(&self, stream: StandardStream, bytes: &[u8]) -> io::Result<()> {
+		self.files.standard_streams().write(stream, bytes)
+	}
+
+	/// Report `message` (and a line separator) on the standard error.
+	fn report_error(&self, message: &str) {
+		let _ =
+			self.write_standard_stream(StandardStream::Err, format!("{}\n", message).as_bytes());
+	}
+
+	/// Set the system property `key` to `value`, over the VM's own.
+	pub fn set_system_property(&mut self, key: &str, value: &str) {
+		self.system_properties.retain(|(other, _)| other != key);
+		self.system_properties
+			.push((key.to_string(), value.to_string()));
+	}
+
+	/// The system properties that the embedder set.
+	pub fn system_properties(&self) -> &[(String, String)] {
+		&self.system_properties
+	}
+
+	/// Tell `hook` about the methods and instructions that the thread
+	/// executes.
+	pub fn add_trace_hook(&mut self, hook: Arc<dyn TraceHook>) {
+		self.trace_hooks.push(hook);
+	}
+
+	/// Halt the program once it has executed `instruction_budget`
+	/// instructions.
+	pub fn set_instruction_budget(&mut self, instruction_budget: u64) {
+		self.instruction_budget = Some(instruction_budget);
+	}
+
+	pub fn memory(&mut self) -> &mut NativeMemory {
+		&mut self.memory
+	}
+
+	/// The java.lang.Thread that represents this thread (null before
+	/// the boot sequence creates it).
+	pub fn current_thread(&self) -> JvmValue {
+		self.current_thread
+			.clone()
+			.unwrap_or_else(create_null_value)
+	}
+
+	/// The host stream that `stream` (System.out or System.err, as the
+	/// VM created it) prints to. None for any other object.
+	pub fn standard_stream(&self, stream: &JvmValue) -> Option<StandardStream> {
+		if let JvmValue::Reference(_, JvmReferenceTargetType::Object(stream), _) = stream {
+			for (standard_stream, host_stream) in &self.standard_streams {
+				if Arc::ptr_eq(standard_stream, stream) {
+					return Some(*host_stream);
+				}
+			}
+		}
+		None
+	}
+
+	/// Initialize System and, unless its initialization already did,
+	/// set System.out and System.err to PrintStreams that print to
+	/// the host's stdout and stderr and System.in to a FileInputStream
+	/// that reads from its stdin. The VM creates them without running
+	/// their constructors: intrinsics implement the PrintStreams' print
+	/// methods (see native::io). In a runtime without a System (or a
+	/// PrintStream or FileInputStream), the streams are unavailable.
+	fn initialize_standard_streams(&mut self) {
+		let system_class_name = "java/lang/System".to_string();
+		if !self.can_load_class(&system_class_name) {
+			return;
+		}
+		let system_class = match self.load_and_initialize_class(&system_class_name) {
+			Some(system_class) => system_class,
+			None => return,
+		};
+
+		for (field_name, host_stream) in
+			&[("out", StandardStream::Out), ("err", StandardStream::Err)]
+		{
+			let field_type = "Ljava/io/PrintStream;";
+			if !self.is_static_null(&system_class, field_name, field_type)
+				|| !self.can_load_class(&"java/io/PrintStream".to_string())
+			{
+				continue;
+			}
+			if let Some(stream) = self.create_object_by_name("java/io/PrintStream") {
+				if let JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) = &stream {
+					self.standard_streams
+						.push((Arc::clone(object), *host_stream));
+				}
+				Debug(
+					format!(
+						"System.{} prints to {} in {}.",
+						field_name, host_stream, self.console_charset
+					),
+					&self.debug_level,
+					DebugLevel::Info,
+				);
+				self.set_static(&system_class, field_name, field_type, stream);
+			}
+		}
+
+		let field_type = "Ljava/io/InputStream;";
+		if self.is_static_null(&system_class, "in", field_type)
+			&& self.can_load_class(&"java/io/FileInputStream".to_string())
+			&& self.can_load_class(&"java/io/FileDescriptor".to_string())
+		{
+			let stream = self.create_object_by_name("java/io/FileInputStream");
+			let descriptor = self.create_object_by_name("java/io/FileDescriptor");
+			if let (
+				Some(stream),
+				Some(JvmValue::Reference(_, JvmReferenceTargetType::Object(descriptor_object), _)),
+			) = (stream, &descriptor)
+			{
+				if let Ok(mut descriptor_object) = descriptor_object.lock() {
+					(*descriptor_object).set_field_by_name(
+						"fd",
+						JvmValue::Primitive(JvmPrimitiveType::Integer, 0, STDIN_FD as u32, 0),
+					);
+				}
+				if let JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) = &stream {
+					if let (Ok(mut object), Some(descriptor)) = (object.lock(), descriptor.clone())
+					{
+						(*object).set_field_by_name("fd", descriptor);
+					}
+				}
+				self.set_static(&system_class, "in", field_type, stream);
+			}
+		}
+	}
+
+	pub fn can_load_class(&self, class_name: &String) -> bool {
+		if let Ok(methodarea) = self.methodarea.lock() {
+			(*methodarea).can_load_class(class_name)
+		} else {
+			false
+		}
+	}
+
+	/// Whether the static field `field_name` (of type `field_type`) of
+	/// `class` is null.
+	fn is_static_null(&self, class: &Rc<Class>, field_name: &str, field_type: &str) -> bool {
+		match class.get_fields_ref().get_field_ref(
+			field_name,
+			field_type,
+			class.get_constant_pool_ref(),
+		) {
+			Some(field) => match field.value.lock() {
+				Ok(value) => match &*value {
+					Some(JvmValue::Reference(_, JvmReferenceTargetType::Null, _)) | None => true,
+					_ => false,
+				},
+				_ => false,
+			},
+			None => false,
+		}
+	}
+
+	/// Set the static field `field_name` (of type `field_type`) of
+	/// `class` to `value`.
+	pub fn set_static(
+		&self,
+		class: &Rc<Class>,
+		field_name: &str,
+		field_type: &str,
+		value: JvmValue,
+	) {
+		if let Some(field) = class.get_fields_ref().get_field_ref(
+			field_name,
+			field_type,
+			class.get_constant_pool_ref(),
+		) {
+			if let Ok(mut field_value) = field.value.lock() {
+				*field_value = Some(value);
+			}
+		}
+	}
+
+	/// Create an instance of the class named `class_name`, loading and
+	/// initializing it. Its constructor does not run.
+	pub fn create_object_by_name(&mut self, class_name: &str) -> Option<JvmValue> {
+		let class = self.load_and_initialize_class(&class_name.to_string())?;
+		if self.pending_exception.is_some() {
+			return None;
+		}
+		self.create_object(class)
+	}
+
+	/// Throw `exception` (a reference to a Throwable). It propagates
+	/// from the current instruction.
+	pub fn throw(&mut self, exception: JvmValue) {
+		self.pending_exception = Some(exception);
+	}
+
+	/// Whether an exception is propagating (e.g., from the Java code
+	/// that a native method called).
+	pub fn exception_pending(&self) -> bool {
+		self.pending_exception.is_some()
+	}
+
+	/// Catch the pending exception, if there is one: it stops propagating.
+	pub fn catch(&mut self) -> Option<JvmValue> {
+		self.pending_exception.take()
+	}
+
+	/// Throw a new instance of the class named `class_name` (a
+	/// Throwable) whose message is `message`. Its constructor does
+	/// not run.
+	pub fn throw_new(&mut self, class_name: &str, message: Option<&str>) {
+		let exception = match self.load_and_initialize_class(&class_name.to_string()) {
+			Some(class) => self.create_object(class),
+			None => None,
+		};
+		if self.pending_exception.is_some() {
+			/*
+			 * Allocating (or initializing) it threw.
+			 */
+			return;
+		}
+		let exception = match exception {
+			Some(exception) => exception,
+			None => {
+				FatalError::new(FatalErrorType::Exception(class_name.to_string())).call();
+				return;
+			}
+		};
+		if let Some(message) = message {
+			let message = self.create_string(message);
+			if let (
+				Some(message),
+				JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _),
+			) = (message, &exception)
+			{
+				if let Ok(mut object) = object.lock() {
+					(*object).set_field_by_name("detailMessage", message);
+				}
+			}
+		}
+		self.fill_in_stack_trace(&exception);
+		self.throw(exception);
+	}
+
+	/// Load and initialize the class named `class_name`.
+	fn load_and_initialize_class(&mut self, class_name: &String) -> Option<Rc<Class>> {
+		let class = if let Ok(mut methodarea) = self.methodarea.lock() {
+			(*methodarea).maybe_load_class(class_name);
+			(*methodarea).get_class_rc(class_name)
+		} else {
+			FatalError::new(FatalErrorType::CouldNotLock(
+				"Method Area.".to_string(),
+				"load_and_initialize_class".to_string(),
+			))
+			.call();
+			None
+		};
+		if let Some(class) = &class {
+			self.maybe_initialize_class(class);
+		}
+		class
+	}
+
+	/// Create an instance of `class` (e.g., a Throwable for the VM to
+	/// throw). Its constructor does not run.
+	pub fn create_object(&mut self, class: Rc<Class>) -> Option<JvmValue> {
+		let class_name = class.get_class_name().unwrap_or_default();
+		let mut object = JvmObject::new(class, self.debug_level.clone());
+		object.instantiate();
+		let object = self.allocate_object(object)?;
+		Some(JvmValue::Reference(
+			JvmReferenceType::Class(class_name),
+			JvmReferenceTargetType::Object(object),
+			0,
+		))
+	}
+
+	/// Create an instance of the class named `class_name` and run its
+	/// constructor with type `descriptor` on `arguments`.
+	///
+	/// # Return value:
+	/// The object. None if creating it threw; the exception is pending.
+	pub fn new_object(
+		&mut self,
+		class_name: &str,
+		descriptor: &str,
+		arguments: Vec<JvmValue>,
+	) -> Option<JvmValue> {
+		let class = self.resolve_class(class_name)?;
+		let constructor =
+			class.get_method_rc_by_name_and_type(&"<init>".to_string(), &descriptor.to_string());
+		let constructor = match constructor {
+			Some(constructor) => constructor,
+			None => {
+				self.throw_new(
+					"java/lang/NoSuchMethodError",
+					Some(&format!(
+						"{}.<init>{}",
+						class_name.replace('/', "."),
+						descriptor
+					)),
+				);
+				return None;
+			}
+		};
+		let object = self.create_object_by_name(class_name)?;
+		let mut all_arguments = vec![object.clone()];
+		all_arguments.extend(arguments);
+		self.invoke(&class, &constructor, all_arguments)?;
+		Some(object)
+	}
+
+	/// Prepare the errors that the VM throws when it runs out of
+	/// resources: allocate the OutOfMemoryError (since it cannot be
+	/// allocated when it is needed) and load the StackOverflowError
+	/// and StackTraceElement classes (since they cannot be initialized
+	/// when they are needed). Without them, running out is a fatal
+	/// error (or, at least, one without a stack trace).
+	fn prepare_vm_errors(&mut self) {
+		self.stack_trace_element_class =
+			self.load_and_initialize_class(&"java/lang/StackTraceElement".to_string());
+		if let Some(out_of_memory_error_class) =
+			self.load_and_initialize_class(&"java/lang/OutOfMemoryError".to_string())
+		{
+			self.out_of_memory_error = self.create_object(out_of_memory_error_class);
+		} else {
+			Debug(
+				format!("Could not preallocate an OutOfMemoryError."),
+				&self.debug_level,
+				DebugLevel::Info,
+			);
+		}
+		self.stack_overflow_error_class =
+			self.load_and_initialize_class(&"java/lang/StackOverflowError".to_string());
+	}
+
+	/// Boot the class library, the way HotSpot does before it runs main:
+	///
+	/// 1. Prepare the errors that the VM throws (see prepare_vm_errors).
+	/// 2. If the runtime has threads, initialize String, System, Class,
+	///    ThreadGroup and Thread; create the system ThreadGroup and the
+	///    "main" ThreadGroup in it; and create the Thread that runs main.
+	///    It is the current thread before its constructor runs, since
+	///    the constructor inherits from the current thread.
+	/// 3. If the runtime's System has initializeSystemClass (like that of
+	///    OpenJDK 8), call it: it sets the system properties and the
+	///    standard streams (through natives).
+	/// 4. Set up the standard streams that the runtime did not (see
+	///    initialize_standard_streams).
+	///
+	/// A runtime without threads (or without initializeSystemClass) skips
+	/// their steps. If booting throws, the VM reports the exception and
+	/// halts with exit status 1, like HotSpot does.
+	///
+	/// The class library of JDK 9 and later does not boot: the VM does
+	/// not have its natives (the first that it calls is
+	/// jdk.internal.misc.CDS.isDumpingClassList0). The VM says so and
+	/// halts with exit status 1 instead.
+	///
+	/// # Return value:
+	/// True if the VM booted and main can run.
+	pub fn boot(&mut self) -> bool {
+		if self.has_modular_class_library() {
+			self.report_error("Error occurred during initialization of VM");
+			self.report_error(
+				"The class library of JDK 9 and later (in a jmod file or a jimage) cannot boot \
+				 this VM: use the rt.jar of JDK 8, or no class library to use the VM's own.",
+			);
+			self.halt(1);
+			return false;
+		}
+		self.prepare_vm_errors();
+
+		let thread_class_name = "java/lang/Thread".to_string();
+		let thread_group_class_name = "java/lang/ThreadGroup".to_string();
+		if self.can_load_class(&thread_class_name) && self.can_load_class(&thread_group_class_name)
+		{
+			for class_name in &[
+				"java/lang/String",
+				"java/lang/System",
+				"java/lang/Class",
+				"java/lang/ThreadGroup",
+				"java/lang/Thread",
+			] {
+				if !self.can_load_class(&class_name.to_string()) {
+					continue;
+				}
+				self.load_and_initialize_class(&class_name.to_string());
+				if self.pending_exception.is_some() {
+					return self.boot_failed();
+				}
+			}
+			if self.create_main_thread().is_none() || self.pending_exception.is_some() {
+				return self.boot_failed();
+			}
+		}
+
+		let system_class_name = "java/lang/System".to_string();
+		if self.can_load_class(&system_class_name) {
+			if let Some(system_class) = self.load_and_initialize_class(&system_class_name) {
+				if let Some(initialize_system_class) = system_class.get_method_rc_by_name_and_type(
+					&"initializeSystemClass".to_string(),
+					&"()V".to_string(),
+				) {
+					Debug(
+						format!("Initializing the system class."),
+						&self.debug_level,
+						DebugLevel::Info,
+					);
+					self.invoke(&system_class, &initialize_system_class, Vec::new());
+				}
+			}
+			if self.pending_exception.is_some() {
+				return self.boot_failed();
+			}
+		}
+
+		self.initialize_standard_streams();
+		self.pending_exception.is_none() || self.boot_failed()
+	}
+
+	/// Whether the class library is that of JDK 9 or later: whether its
+	/// java.lang.Object is in a jmod file or a jimage.
+	fn has_modular_class_library(&self) -> bool {
+		match self.methodarea.lock() {
+			Ok(methodarea) => match methodarea.get_class_location("java/lang/Object") {
+				Some(ClassLocation::JmodFile(..)) | Some(ClassLocation::JImage(..)) => true,
+				_ => false,
+			},
+			Err(_) => false,
+		}
+	}
+
+	/// Create the ThreadGroups and the Thread that runs main and make it
+	/// the current thread (see boot).
+	fn create_main_thread(&mut self) -> Option<()> {
+		let system_group = self.new_object("java/lang/ThreadGroup", "()V", Vec::new())?;
+		let main_group_name = self.create_string("main")?;
+		let main_group = self.new_object(
+			"java/lang/ThreadGroup",
+			"(Ljava/lang/ThreadGroup;Ljava/lang/String;)V",
+			vec![system_group, main_group_name],
+		)?;
+
+		let main_thread = self.create_object_by_name("java/lang/Thread")?;
+		if let JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) = &main_thread {
+			if let Ok(mut object) = object.lock() {
+				object.set_field_by_name("priority", JvmValue::int(THREAD_NORM_PRIORITY));
+				object.set_field_by_name("threadStatus", JvmValue::int(THREAD_STATUS_RUNNABLE));
+			}
+		}
+		self.current_thread = Some(main_thread.clone());
+
+		let thread_class = self.class_of_reference(&main_thread)?;
+		let constructor = thread_class.get_method_rc_by_name_and_type(
+			&"<init>".to_string(),
+			&"(Ljava/lang/ThreadGroup;Ljava/lang/String;)V".to_string(),
+		)?;
+		let main_thread_name = self.create_string("main")?;
+		self.invoke(
+			&thread_class,
+			&constructor,
+			vec![main_thread, main_group, main_thread_name],
+		)?;
+		Some(())
+	}
+
+	/// Booting failed: report the pending exception (if there is one)
+	/// and halt with exit status 1.
+	fn boot_failed(&mut self) -> bool {
+		self.report_error("Error occurred during initialization of VM");
+		if let Some(exception) = self.pending_exception.take() {
+			self.report_uncaught_exception(&exception);
+		}
+		self.halt(1);
+		false
+	}
+
+	/// The Java stack is full: throw a StackOverflowError.
+	fn throw_stack_overflow_error(&mut self) {
+		Debug(
+			format!(
+				"Exceeded the maximum stack depth ({}); throwing StackOverflowError.",
+				self.max_stack_depth
+			),
+			&self.debug_level,
+			DebugLevel::Info,
+		);
+		let stack_overflow_error = match self.stack_overflow_error_class.clone() {
+			Some(stack_overflow_error_class) => self.create_object(stack_overflow_error_class),
+			None => None,
+		};
+		if let Some(stack_overflow_error) = stack_overflow_error {
+			self.fill_in_stack_trace(&stack_overflow_error);
+			self.throw(stack_overflow_error);
+		} else if self.pending_exception.is_none() {
+			FatalError::new(FatalErrorType::StackOverflow(self.max_stack_depth)).call();
+		}
+	}
+
+	/// The heap is full: throw an OutOfMemoryError.
+	fn throw_out_of_memory_error(&mut self, size: usize) {
+		if let Some(out_of_memory_error) = self.out_of_memory_error.clone() {
+			Debug(
+				format!(
+					"Could not allocate {} bytes; throwing OutOfMemoryError.",
+					size
+				),
+				&self.debug_level,
+				DebugLevel::Info,
+			);
+			self.throw(out_of_memory_error);
+		} else {
+			FatalError::new(FatalErrorType::OutOfMemory(size)).call();
+		}
+	}
+
+	/// Find the handler for the pending exception in `method`, whose
+	/// instruction at `pc` threw it (JVMS 2.10).
+	///
+	/// # Return value:
+	/// The pc of the handler. None if `method` does not catch the exception.
+	fn find_exception_handler(
+		&mut self,
+		method: &Method,
+		class: &Rc<Class>,
+		pc: usize,
+	) -> Option<usize> {
+		let exception_class = match &self.pending_exception {
+			Some(JvmValue::Reference(_, JvmReferenceTargetType::Object(exception), _)) => {
+				exception.lock().ok()?.get_class()
+			}
+			_ => return None,
+		};
+
+		for handler in method.get_exception_table()?.iter() {
+			if !handler.covers(pc) {
+				continue;
+			}
+			if handler.catch_type() == 0 {
+				return Some(handler.handler_pc());
+			}
+			if let Some(catch_class_name) = class.get_class_name_at(handler.catch_type()) {
+				if let Ok(mut methodarea) = self.methodarea.lock() {
+					if exception_class.is_type_of(&catch_class_name, &mut *methodarea) {
+						return Some(handler.handler_pc());
+					}
+				}
+			}
+		}
+		None
+	}
+
+	/// Move `object` into the heap and return a reference to it.
+	pub fn allocate_object(&mut self, object: JvmObject) -> Option<Arc<Mutex<JvmObject>>> {
+		let size = object.size();
+		let allocated = if let Ok(mut heap) = self.heap.lock() {
+			(*heap).allocate_object(object)
+		} else {
+			FatalError::new(FatalErrorType::CouldNotLock(
+				"Heap.".to_string(),
+				"allocate_object".to_string(),
+			))
+			.call();
+			return None;
+		};
+		if allocated.is_none() {
+			self.throw_out_of_memory_error(size);
+		}
+		allocated
+	}
+
+	/// Move `array` into the heap and return a reference to it.
+	pub fn allocate_array(&mut self, array: JvmArray) -> Option<Arc<Mutex<JvmArray>>> {
+		let size = array.size();
+		let allocated = if let Ok(mut heap) = self.heap.lock() {
+			(*heap).allocate_array(array)
+		} else {
+			FatalError::new(FatalErrorType::CouldNotLock(
+				"Heap.".to_string(),
+				"allocate_array".to_string(),
+			))
+			.call();
+			return None;
+		};
+		if allocated.is_none() {
+			self.throw_out_of_memory_error(size);
+		}
+		allocated
+	}
+
+	/// The number of bytes of the heap that are in use and the most
+	/// that it may use.
+	pub fn heap_usage(&self) -> (usize, usize) {
+		match self.heap.lock() {
+			Ok(heap) => (heap.allocated(), heap.max_size()),
+			_ => (0, 0),
+		}
+	}
+
+	/// Collect the heap (see Heap::collect).
+	pub fn collect_garbage(&mut self) {
+		if let Ok(mut heap) = self.heap.lock() {
+			heap.collect();
+		}
+	}
+
+	/// Run the program: execute `method_name` (i.e., main) of the class
+	/// named `class_name` and then shut down.
+	///
+	/// # Return value:
+	/// The program's exit status.
+	pub fn run(&mut self, class_name: &String, method_name: &String) -> i32 {
+		self.run_main(class_name, method_name);
+		if let Some(exit_status) = self.exit_status {
+			return exit_status;
+		}
+
+		let exit_status = if let Some(exception) = self.pending_exception.take() {
+			self.report_uncaught_exception(&exception);
+			UNCAUGHT_EXCEPTION_EXIT_STATUS
+		} else {
+			0
+		};
+		self.run_shutdown_hooks();
+		self.exit_status.unwrap_or(exit_status)
+	}
+
+	/// The status with which the program exits, once it has halted
+	/// (e.g., called System.exit).
+	pub fn exit_status(&self) -> Option<i32> {
+		self.exit_status
+	}
+
+	/// Stop executing Java code; the program exits with `exit_status`.
+	pub fn halt(&mut self, exit_status: i32) {
+		Debug(
+			format!("Halting with exit status {}.", exit_status),
+			&self.debug_level,
+			DebugLevel::Info,
+		);
+		self.exit_status = Some(exit_status);
+	}
+
+	/// Run the shutdown hooks that the program registered, if the runtime
+	/// supports them: they run in java.lang.Shutdown.shutdown(), like
+	/// they do when HotSpot shuts down. An exception that they throw is
+	/// ignored.
+	pub fn run_shutdown_hooks(&mut self) {
+		let shutdown_class_name = "java/lang/Shutdown".to_string();
+		if !self.can_load_class(&shutdown_class_name) {
+			return;
+		}
+		if let Some(shutdown_class) = self.load_and_initialize_class(&shutdown_class_name) {
+			if let Some(shutdown_method) = shutdown_class
+				.get_method_rc_by_name_and_type(&"shutdown".to_string(), &"()V".to_string())
+			{
+				if self.pending_exception.is_none() {
+					let mut frame = Frame::new();
+					frame.class = Some(Rc::clone(&shutdown_class));
+					self.execute_method(shutdown_method, frame);
+				}
+			}
 		}
+		self.pending_exception = None;
 	}
 
-	pub fn run(&mut self, class_name: &String, method_name: &String) -> bool {
+	/// Execute `method_name` of the class named `class_name`. If it throws
+	/// an exception that it does not catch, the exception is pending when
+	/// this returns.
+	fn run_main(&mut self, class_name: &String, method_name: &String) {
 		/*
 		 * 3. Load the method.
 		 * 4. Create a frame.
@@ -121,6 +960,9 @@ impl JvmThread {
 			 * Per the spec, it is required that we initialize the main
 			 * class before calling the main method inside that class.
 			 */
+			if !self.boot() {
+				return;
+			}
 			self.maybe_initialize_class(&main_class);
 
 			if let Some(main_method) = main_class
@@ -137,11 +979,12 @@ impl JvmThread {
 				let mut frame = Frame::new();
 				frame.class = Some(Rc::clone(&main_class));
 				/*
-				 * Load up the frame's stack with the CLI arguments.
+				 * Load up the frame's locals with the CLI arguments.
 				 */
-				frame
-					.operand_stack
-					.push(JvmValue::Primitive(JvmPrimitiveType::Boolean, 0, 0, 0));
+				match self.create_arguments() {
+					Some(arguments) => frame.locals.push(arguments),
+					None => return,
+				}
 
 				Debug(
 					format!("Frame: {}", frame),
@@ -149,12 +992,13 @@ impl JvmThread {
 					DebugLevel::Info,
 				);
 
-				if let Some(v) = self.execute_method(&main_method, frame) {
-					if JvmValue::Primitive(JvmPrimitiveType::Void, 0, 0, 0) != v {
-						FatalError::new(FatalErrorType::VoidMethodReturnedValue).call();
+				if self.pending_exception.is_none() {
+					if let Some(v) = self.execute_method(main_method, frame) {
+						if JvmValue::Primitive(JvmPrimitiveType::Void, 0, 0, 0) != v {
+							FatalError::new(FatalErrorType::VoidMethodReturnedValue).call();
+						}
 					}
 				}
-				return true;
 			} else {
 				FatalError::new(FatalErrorType::MethodNotFound(
 					method_name.clone(),
@@ -165,16 +1009,867 @@ impl JvmThread {
 		} else {
 			FatalError::new(FatalErrorType::ClassNotFound(class_name.clone())).call()
 		}
-		false
 	}
 
-	fn execute_method(&mut self, method: &Method, mut frame: Frame) -> Option<JvmValue> {
+	/// Create the String[] of command-line arguments that main gets.
+	fn create_arguments(&mut self) -> Option<JvmValue> {
+		let arguments = if let Ok(methodarea) = self.methodarea.lock() {
+			(*methodarea).get_arguments()
+		} else {
+			FatalError::new(FatalErrorType::CouldNotLock(
+				"Method Area.".to_string(),
+				"create_arguments".to_string(),
+			))
+			.call();
+			return None;
+		};
+
+		let mut array = JvmArray::new(arguments.len());
+		for (index, argument) in arguments.iter().enumerate() {
+			array.set_at(index, self.create_string(argument)?);
+		}
+		Some(JvmValue::Reference(
+			JvmReferenceType::Array(
+				Rc::new(JvmType::Reference(JvmReferenceType::Class(
+					"java/lang/String".to_string(),
+				))),
+				arguments.len() as u32,
+			),
+			JvmReferenceTargetType::Array(self.allocate_array(array)?),
+			0,
+		))
+	}
+
+	/// Take the pending exception: the caller handles it.
+	pub fn take_exception(&mut self) -> Option<JvmValue> {
+		self.pending_exception.take()
+	}
+
+	/// The name of the class of `exception`, its message and its stack
+	/// trace. None if `exception` is not an object.
+	pub fn describe_exception(
+		&self,
+		exception: &JvmValue,
+	) -> Option<(String, Option<String>, Vec<StackTraceElement>)> {
+		let exception = match exception {
+			JvmValue::Reference(_, JvmReferenceTargetType::Object(exception), _) => exception,
+			_ => return None,
+		};
+		let (exception_class_name, message) = match exception.lock() {
+			Ok(exception) => (
+				exception.get_class().get_class_name().unwrap_or_default(),
+				exception
+					.get_field_by_name("detailMessage")
+					.and_then(|message| get_string_value(&message)),
+			),
+			_ => return None,
+		};
+		Some((
+			exception_class_name,
+			message,
+			self.get_stack_trace(exception),
+		))
+	}
+
+	/// Tell the user about `exception`, which nothing caught, the way
+	/// that HotSpot does: its class, its message and its stack trace.
+	fn report_uncaught_exception(&self, exception: &JvmValue) {
+		let (exception_class_name, message, stack_trace) = match self.describe_exception(exception)
+		{
+			Some(description) => description,
+			None => return,
+		};
+		match message {
+			Some(message) => self.report_error(&format!(
+				"Exception in thread \"main\" {}: {}",
+				exception_class_name.replace('/', "."),
+				message
+			)),
+			None => self.report_error(&format!(
+				"Exception in thread \"main\" {}",
+				exception_class_name.replace('/', ".")
+			)),
+		}
+		for element in stack_trace {
+			self.report_error(&format!("\tat {}", element));
+		}
+	}
+
+	/// The stack trace of the thread, newest frame first.
+	fn stack_trace(&self) -> Vec<StackTraceElement> {
+		self.frames
+			.iter()
+			.rev()
+			.filter_map(StackTraceElement::from_frame)
+			.collect()
+	}
+
+	/// Record the stack trace of the thread in `throwable`: an array of
+	/// StackTraceElements in its backtrace field. Like HotSpot, leave out
+	/// the frames that are creating the Throwable: those that are filling
+	/// in the stack trace and those that are running the constructors of
+	/// its class (and its superclasses).
+	pub fn fill_in_stack_trace(&mut self, throwable: &JvmValue) {
+		let throwable = match throwable {
+			JvmValue::Reference(_, JvmReferenceTargetType::Object(throwable), _) => throwable,
+			_ => return,
+		};
+		let stack_trace_element_class = match &self.stack_trace_element_class {
+			Some(stack_trace_element_class) => Rc::clone(stack_trace_element_class),
+			None => {
+				Debug(
+					format!("Cannot fill in a stack trace without StackTraceElement."),
+					&self.debug_level,
+					DebugLevel::Info,
+				);
+				return;
+			}
+		};
+		let throwable_class = match throwable.lock() {
+			Ok(throwable) => throwable.get_class(),
+			_ => return,
+		};
+
+		let mut stack_trace = self.stack_trace().into_iter().peekable();
+		while stack_trace
+			.peek()
+			.map_or(false, |element| element.method_name == "fillInStackTrace")
+		{
+			stack_trace.next();
+		}
+		while stack_trace.peek().map_or(false, |element| {
+			element.method_name == "<init>"
+				&& match self.methodarea.lock() {
+					Ok(mut methodarea) => throwable_class
+						.is_type_of(&element.class_name.replace('.', "/"), &mut *methodarea),
+					_ => false,
+				}
+		}) {
+			stack_trace.next();
+		}
+		let stack_trace: Vec<StackTraceElement> = stack_trace.take(MAX_STACK_TRACE_DEPTH).collect();
+
+		let stack_trace_element_class_name = stack_trace_element_class
+			.get_class_name()
+			.unwrap_or_default();
+		let mut elements = JvmArray::new(stack_trace.len());
+		for (index, element) in stack_trace.iter().enumerate() {
+			let mut object = JvmObject::new(
+				Rc::clone(&stack_trace_element_class),
+				self.debug_level.clone(),
+			);
+			object.instantiate();
+			let fields = vec![
+				("declaringClass", self.intern_string(&element.class_name)),
+				("methodName", self.intern_string(&element.method_name)),
+				(
+					"fileName",
+					match &element.file_name {
+						Some(file_name) => self.intern_string(file_name),
+						None => Some(create_null_value()),
+					},
+				),
+				(
+					"lineNumber",
+					Some(JvmValue::Primitive(
+						JvmPrimitiveType::Integer,
+						0,
+						element.line_number as u32,
+						0,
+					)),
+				),
+			];
+			for (field_name, value) in fields {
+				match value {
+					Some(value) => {
+						object.set_field_by_name(field_name, value);
+					}
+					None => return,
+				}
+			}
+			match self.allocate_object(object) {
+				Some(object) => elements.set_at(
+					index,
+					JvmValue::Reference(
+						JvmReferenceType::Class(stack_trace_element_class_name.clone()),
+						JvmReferenceTargetType::Object(object),
+						0,
+					),
+				),
+				None => return,
+			}
+		}
+		let elements = JvmValue::Reference(
+			JvmReferenceType::Array(
+				Rc::new(JvmType::Reference(JvmReferenceType::Class(
+					stack_trace_element_class_name,
+				))),
+				stack_trace.len() as u32,
+			),
+			match self.allocate_array(elements) {
+				Some(elements) => JvmReferenceTargetType::Array(elements),
+				None => return,
+			},
+			0,
+		);
+		if let Ok(mut throwable) = throwable.lock() {
+			throwable.set_field_by_name("backtrace", elements);
+		}
+	}
+
+	/// The StackTraceElements recorded in `throwable` by fill_in_stack_trace.
+	pub fn get_stack_trace_elements(throwable: &Arc<Mutex<JvmObject>>) -> Vec<JvmValue> {
+		let backtrace = match throwable.lock() {
+			Ok(throwable) => throwable.get_field_by_name("backtrace"),
+			_ => None,
+		};
+		match backtrace {
+			Some(JvmValue::Reference(_, JvmReferenceTargetType::Array(elements), _)) => {
+				match elements.lock() {
+					Ok(elements) => elements.values().cloned().collect(),
+					_ => Vec::new(),
+				}
+			}
+			_ => Vec::new(),
+		}
+	}
+
+	/// The stack trace recorded in `throwable` by fill_in_stack_trace.
+	fn get_stack_trace(&self, throwable: &Arc<Mutex<JvmObject>>) -> Vec<StackTraceElement> {
+		JvmThread::get_stack_trace_elements(throwable)
+			.iter()
+			.filter_map(|element| match element {
+				JvmValue::Reference(_, JvmReferenceTargetType::Object(element), _) => {
+					let element = element.lock().ok()?;
+					Some(StackTraceElement {
+						class_name: get_string_value(
+							&element.get_field_by_name("declaringClass")?,
+						)?,
+						method_name: get_string_value(&element.get_field_by_name("methodName")?)?,
+						file_name: get_string_value(&element.get_field_by_name("fileName")?),
+						line_number: match element.get_field_by_name("lineNumber")? {
+							JvmValue::Primitive(_, _, line_number, _) => line_number as i32,
+							_ => -1,
+						},
+					})
+				}
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// Execute the native method `method` of `class` with `arguments`
+	/// (for an instance method, the first is the object).
+	///
+	/// # Return value:
+	/// The method's result (Void for a void method). If it threw, the
+	/// exception is pending.
+	fn call_native(
+		&mut self,
+		class: &Rc<Class>,
+		method: &Rc<Method>,
+		arguments: &[JvmValue],
+	) -> Option<JvmValue> {
+		let cp = class.get_constant_pool_ref();
+		let class_name = class.get_class_name().unwrap_or_default();
+		let method_name = method.get_name(cp).unwrap_or_default();
+		let method_type = method.get_descriptor(cp).unwrap_or_default();
+
+		Debug(
+			format!(
+				"Invoke Native: {}.{}{}",
+				class_name, method_name, method_type
+			),
+			&self.debug_level,
+			DebugLevel::Info,
+		);
+		let native = match self.natives.get(&class_name, &method_name, &method_type) {
+			Some(native) => native,
+			None => {
+				/*
+				 * Like HotSpot when it cannot link a native method.
+				 */
+				let message = format!(
+					"{}.{}{}",
+					class_name.replace('/', "."),
+					method_name,
+					method_type
+				);
+				self.throw_new("java/lang/UnsatisfiedLinkError", Some(&message));
+				return None;
+			}
+		};
+		let methodarea = Arc::clone(&self.methodarea);
+		let result = native.invoke(self, &methodarea, arguments);
+		if result.is_none() && self.pending_exception.is_none() && self.exit_status.is_none() {
+			/*
+			 * A native that returns no value must have thrown.
+			 */
+			let message = format!(
+				"Native method {}.{}{} returned no value",
+				class_name.replace('/', "."),
+				method_name,
+				method_type
+			);
+			self.throw_new("java/lang/InternalError", Some(&message));
+		}
+		result
+	}
+
+	/// Start invoking `method` of `class` with `arguments` (for an
+	/// instance method, the first is the object): run it, if it is
+	/// native or an intrinsic replaces it, or give it a frame.
+	fn start_invocation(
+		&mut self,
+		class: Rc<Class>,
+		method: Rc<Method>,
+		arguments: Vec<JvmValue>,
+	) -> Option<Invocation> {
+		if method.access_flags & (MethodAccessFlags::Native as u16) != 0 {
+			return match self.call_native(&class, &method, &arguments) {
+				_ if self.pending_exception.is_some() => Some(Invocation::Threw),
+				Some(result) => Some(Invocation::Completed(result)),
+				None => None,
+			};
+		}
+
+		let cp = class.get_constant_pool_ref();
+		let intrinsic = self.natives.get_intrinsic(
+			&class.get_class_name().unwrap_or_default(),
+			&method.get_name(cp).unwrap_or_default(),
+			&method.get_descriptor(cp).unwrap_or_default(),
+		);
+		if let Some(intrinsic) = intrinsic {
+			let methodarea = Arc::clone(&self.methodarea);
+			match intrinsic.invoke(self, &methodarea, &arguments) {
+				_ if self.pending_exception.is_some() => return Some(Invocation::Threw),
+				Some(result) => return Some(Invocation::Completed(result)),
+				/*
+				 * The intrinsic declined.
+				 */
+				None => (),
+			}
+		}
+
+		if method.access_flags & (MethodAccessFlags::Abstract as u16) != 0 {
+			let message = format!(
+				"{}.{}{}",
+				class.get_class_name().unwrap_or_default().replace('/', "."),
+				method.get_name(cp).unwrap_or_default(),
+				method.get_descriptor(cp).unwrap_or_default()
+			);
+			self.throw_new("java/lang/AbstractMethodError", Some(&message));
+			return Some(Invocation::Threw);
+		}
+
+		let mut frame = Frame::new();
+		frame.class = Some(class);
+		frame.locals = locals_for_arguments(arguments);
+		Some(Invocation::Frame(method, frame))
+	}
+
+	/// Invoke `method` of `class` with `arguments` (popped from
+	/// `source_frame` by the invoke instruction `opcode`).
+	fn invoke_method(
+		&mut self,
+		class: Rc<Class>,
+		method: Rc<Method>,
+		arguments: Vec<JvmValue>,
+		opcode: u8,
+		source_frame: &mut Frame,
+	) -> Option<OpcodeResult> {
+		match self.start_invocation(class, method, arguments)? {
+			Invocation::Completed(result) => self.native_result(result, opcode, source_frame),
+			Invocation::Threw => Some(OpcodeResult::Exception),
+			Invocation::Frame(method, frame) => Some(OpcodeResult::Invoke(method, frame)),
+		}
+	}
+
+	/// Complete the invoke instruction `opcode` of a method that ran
+	/// natively and returned `result`.
+	fn native_result(
+		&mut self,
+		result: JvmValue,
+		opcode: u8,
+		source_frame: &mut Frame,
+	) -> Option<OpcodeResult> {
+		if self.pending_exception.is_some() {
+			return Some(OpcodeResult::Exception);
+		}
+		match result {
+			JvmValue::Primitive(JvmPrimitiveType::Void, _, _, _) => (),
+			result => source_frame.operand_stack.push(result),
+		}
+		Some(OpcodeResult::Incr(invoke_instruction_length(opcode)))
+	}
+
+	/// Invoke `method` of `class` with `arguments` (for an instance method,
+	/// the first is the object) and run it to completion. This is how the
+	/// VM (e.g., a native method) calls Java code. There is no method
+	/// selection: see call_virtual.
+	///
+	/// # Return value:
+	/// The method's result (Void for a void method). None if it threw; the
+	/// exception is pending.
+	pub fn invoke(
+		&mut self,
+		class: &Rc<Class>,
+		method: &Rc<Method>,
+		arguments: Vec<JvmValue>,
+	) -> Option<JvmValue> {
+		if self.pending_exception.is_some() || self.exit_status.is_some() {
+			return None;
+		}
+		match self.start_invocation(Rc::clone(class), Rc::clone(method), arguments)? {
+			Invocation::Completed(result) => Some(result),
+			Invocation::Threw => None,
+			Invocation::Frame(method, frame) => self.execute_method(method, frame),
+		}
+	}
+
+	/// Invoke the instance method `method_name` with type `descriptor`
+	/// on `receiver`, selecting the method the way that invokevirtual
+	/// does. See invoke.
+	pub fn call_virtual(
+		&mut self,
+		receiver: &JvmValue,
+		method_name: &str,
+		descriptor: &str,
+		arguments: Vec<JvmValue>,
+	) -> Option<JvmValue> {
+		if receiver.is_null() {
+			self.throw_new("java/lang/NullPointerException", None);
+			return None;
+		}
+		let receiver_class = self.class_of_reference(receiver)?;
+		let method_name = method_name.to_string();
+		let descriptor = descriptor.to_string();
+		let selected = match self.methodarea.lock() {
+			Ok(mut methodarea) => {
+				match (*methodarea).resolve_method(
+					&receiver_class,
+					&receiver_class,
+					&method_name,
+					&descriptor,
+				) {
+					Some((resolved_class, resolved_method))
+						if resolved_method.access_flags & (MethodAccessFlags::Private as u16)
+							!= 0 =>
+					{
+						Some((resolved_class, resolved_method))
+					}
+					Some((resolved_class, _)) => (*methodarea).select_method(
+						&receiver_class,
+						&resolved_class,
+						&method_name,
+						&descriptor,
+					),
+					None => None,
+				}
+			}
+			_ => None,
+		};
+		let (class, method) = match selected {
+			Some(selected) => selected,
+			None => {
+				self.throw_new(
+					"java/lang/NoSuchMethodError",
+					Some(&format!("{}{}", method_name, descriptor)),
+				);
+				return None;
+			}
+		};
+		let mut all_arguments = vec![receiver.clone()];
+		all_arguments.extend(arguments);
+		self.invoke(&class, &method, all_arguments)
+	}
+
+	/// Invoke the static method `method_name` with type `descriptor` of
+	/// the class named `class_name` the way that invokestatic does: the
+	/// method is resolved (in the class or its superclasses) and its
+	/// class is initialized first. See invoke.
+	pub fn call_static(
+		&mut self,
+		class_name: &str,
+		method_name: &str,
+		descriptor: &str,
+		arguments: Vec<JvmValue>,
+	) -> Option<JvmValue> {
+		if self.pending_exception.is_some() || self.exit_status.is_some() {
+			return None;
+		}
+		let class = self.resolve_class(class_name)?;
+		let resolved = match self.methodarea.lock() {
+			Ok(mut methodarea) => (*methodarea).resolve_method(
+				&class,
+				&class,
+				&method_name.to_string(),
+				&descriptor.to_string(),
+			),
+			_ => None,
+		};
+		let (resolved_class, resolved_method) = match resolved {
+			Some(resolved) => resolved,
+			None => {
+				self.throw_new(
+					"java/lang/NoSuchMethodError",
+					Some(&format!(
+						"{}.{}{}",
+						class_name.replace('/', "."),
+						method_name,
+						descriptor
+					)),
+				);
+				return None;
+			}
+		};
+		if resolved_method.access_flags & (MethodAccessFlags::Static as u16) == 0 {
+			self.throw_new(
+				"java/lang/IncompatibleClassChangeError",
+				Some(&format!(
+					"Expected static method {}.{}{}",
+					class_name.replace('/', "."),
+					method_name,
+					descriptor
+				)),
+			);
+			return None;
+		}
+		self.maybe_initialize_class(&resolved_class);
+		if self.pending_exception.is_some() {
+			return None;
+		}
+		self.invoke(&resolved_class, &resolved_method, arguments)
+	}
+
+	/// The offset (in the layout of the object that `reference`
+	/// references) of its field named `field_name` with type
+	/// `descriptor`, resolved the way that getfield and putfield do.
+	/// None if there is no such field; NoSuchFieldError (or, for null,
+	/// NullPointerException) is pending.
+	fn field_offset_by_name(
+		&mut self,
+		reference: &JvmValue,
+		field_name: &str,
+		descriptor: &str,
+	) -> Option<usize> {
+		if reference.is_null() {
+			self.throw_new("java/lang/NullPointerException", None);
+			return None;
+		}
+		let class = self.class_of_reference(reference)?;
+		let declaring_class = match self.methodarea.lock() {
+			Ok(mut methodarea) => (*methodarea).resolve_field(
+				&class,
+				&field_name.to_string(),
+				&descriptor.to_string(),
+			),
+			_ => None,
+		};
+		let offset = declaring_class.and_then(|declaring_class| {
+			declaring_class.get_layout().get_offset(
+				&declaring_class.get_class_name().unwrap_or_default(),
+				field_name,
+				descriptor,
+			)
+		});
+		if offset.is_none() {
+			self.throw_new("java/lang/NoSuchFieldError", Some(field_name));
+		}
+		offset
+	}
+
+	/// The value of the field named `field_name` with type `descriptor`
+	/// of the object that `reference` references. See
+	/// field_offset_by_name.
+	pub fn get_field(
+		&mut self,
+		reference: &JvmValue,
+		field_name: &str,
+		descriptor: &str,
+	) -> Option<JvmValue> {
+		let offset = self.field_offset_by_name(reference, field_name, descriptor)?;
+		match reference {
+			JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+				object.lock().ok()?.get_field(offset)
+			}
+			_ => None,
+		}
+	}
+
+	/// Set the field named `field_name` with type `descriptor` of the
+	/// object that `reference` references to `value`. See
+	/// field_offset_by_name.
+	pub fn set_field(
+		&mut self,
+		reference: &JvmValue,
+		field_name: &str,
+		descriptor: &str,
+		value: JvmValue,
+	) -> bool {
+		let offset = match self.field_offset_by_name(reference, field_name, descriptor) {
+			Some(offset) => offset,
+			None => return false,
+		};
+		match reference {
+			JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+				match object.lock() {
+					Ok(mut object) => object.set_field(offset, value),
+					_ => false,
+				}
+			}
+			_ => false,
+		}
+	}
+
+	/// The class of the object that `reference` references; for an
+	/// array, java/lang/Object (whose methods arrays have).
+	pub fn class_of_reference(&mut self, reference: &JvmValue) -> Option<Rc<Class>> {
+		match reference {
+			JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+				Some(object.lock().ok()?.get_class())
+			}
+			JvmValue::Reference(_, JvmReferenceTargetType::Array(_), _) => {
+				self.resolve_class("java/lang/Object")
+			}
+			_ => None,
+		}
+	}
+
+	/// Load the class named `class_name`. If there is no such class,
+	/// throw NoClassDefFoundError.
+	pub fn resolve_class(&mut self, class_name: &str) -> Option<Rc<Class>> {
+		let class_name = class_name.to_string();
+		let class = match self.methodarea.lock() {
+			Ok(mut methodarea) => {
+				if (*methodarea).can_load_class(&class_name) {
+					(*methodarea).maybe_load_class(&class_name);
+					(*methodarea).get_class_rc(&class_name)
+				} else {
+					None
+				}
+			}
+			_ => {
+				FatalError::new(FatalErrorType::CouldNotLock(
+					"Method Area.".to_string(),
+					"resolve_class".to_string(),
+				))
+				.call();
+				None
+			}
+		};
+		if class.is_none() {
+			self.throw_new("java/lang/NoClassDefFoundError", Some(&class_name));
+		}
+		class
+	}
+
+	/// Load the class named `class_name` in the namespace of `loader`.
+	/// The bootstrap class loader loads it from the class path; any other
+	/// loader is asked to with its loadClass (which delegates to its
+	/// parent as it sees fit), unless it has loaded the class already.
+	/// None if there is no such class; loadClass may have thrown.
+	pub fn load_class(&mut self, class_name: &str, loader: ClassLoaderId) -> Option<Rc<Class>> {
+		let loader_object = match self.methodarea.lock() {
+			Ok(mut methodarea) => {
+				if let Some(class) =
+					(*methodarea).find_or_load_class(&class_name.to_string(), loader)
+				{
+					return Some(class);
+				}
+				(*methodarea).class_loader_object(loader)?
+			}
+			_ => {
+				FatalError::new(FatalErrorType::CouldNotLock(
+					"Method Area.".to_string(),
+					"load_class".to_string(),
+				))
+				.call();
+				return None;
+			}
+		};
+		Debug(
+			format!("Asking class loader {:#x} to load {}.", loader, class_name),
+			&self.debug_level,
+			DebugLevel::Info,
+		);
+		let name = self.create_string(&class_name.replace('/', "."))?;
+		let mirror = self.call_virtual(
+			&loader_object,
+			"loadClass",
+			"(Ljava/lang/String;)Ljava/lang/Class;",
+			vec![name],
+		)?;
+		if mirror.is_null() {
+			return None;
+		}
+		let class = self.mirrored_class(&mirror)?;
+		if class.get_class_name().as_deref() != Some(class_name) {
+			return None;
+		}
+		let recorded = match self.methodarea.lock() {
+			Ok(mut methodarea) => (*methodarea).record_initiating_loader(&class, loader),
+			_ => Ok(()),
+		};
+		match recorded {
+			Ok(()) => Some(class),
+			Err(message) => {
+				self.throw_new("java/lang/LinkageError", Some(&message));
+				None
+			}
+		}
+	}
+
+	/// Define the class in the class file `class_bytes` with `loader` as
+	/// its defining loader. `loader` loads its superclass and its
+	/// superinterfaces first. If `class_name` is given, it must be the
+	/// name of the class.
+	///
+	/// # Return value:
+	/// The class. None if defining it threw (e.g., ClassFormatError or
+	/// LinkageError); the exception is pending.
+	pub fn define_class(
+		&mut self,
+		class_name: Option<&str>,
+		class_bytes: Vec<u8>,
+		loader: ClassLoaderId,
+	) -> Option<Rc<Class>> {
+		let class = match Class::load_from_bytes(class_bytes) {
+			Some(class) => class,
+			None => {
+				self.throw_new(
+					"java/lang/ClassFormatError",
+					Some("Incompatible magic value or truncated class file"),
+				);
+				return None;
+			}
+		};
+		let defined_name = class.get_class_name().unwrap_or_default();
+		if let Some(class_name) = class_name {
+			if class_name != defined_name {
+				self.throw_new(
+					"java/lang/NoClassDefFoundError",
+					Some(&format!("{} (wrong name: {})", class_name, defined_name)),
+				);
+				return None;
+			}
+		}
+		Debug(
+			format!("Defining {} with class loader {:#x}.", defined_name, loader),
+			&self.debug_level,
+			DebugLevel::Info,
+		);
+		let mut supertypes = class.get_interface_names();
+		supertypes.extend(class.resolve_superclass());
+		for supertype in supertypes {
+			self.resolve_class_in(loader, &supertype)?;
+		}
+		let defined = match self.methodarea.lock() {
+			Ok(mut methodarea) => (*methodarea).define_class_with_loader(class, loader),
+			_ => {
+				FatalError::new(FatalErrorType::CouldNotLock(
+					"Method Area.".to_string(),
+					"define_class".to_string(),
+				))
+				.call();
+				return None;
+			}
+		};
+		match defined {
+			Ok(class) => Some(class),
+			Err((exception, message)) => {
+				self.throw_new(exception, Some(&message));
+				None
+			}
+		}
+	}
+
+	/// Resolve the name `class_name` that `class` references (JVMS
+	/// 5.4.3.1): load it with `class`' defining loader. If there is no
+	/// such class, throw NoClassDefFoundError.
+	pub fn resolve_class_from(&mut self, class: &Rc<Class>, class_name: &str) -> Option<Rc<Class>> {
+		self.resolve_class_in(class.get_defining_loader(), class_name)
+	}
+
+	/// Load the class named `class_name` with `loader`. If there is no
+	/// such class, throw NoClassDefFoundError.
+	fn resolve_class_in(&mut self, loader: ClassLoaderId, class_name: &str) -> Option<Rc<Class>> {
+		if loader == BOOTSTRAP_LOADER {
+			return self.resolve_class(class_name);
+		}
+		let resolved = self.load_class(class_name, loader);
+		if resolved.is_none() {
+			/*
+			 * A ClassNotFoundException from loadClass means that there is
+			 * no such class; any other exception stands.
+			 */
+			let not_found = match &self.pending_exception {
+				Some(exception) => {
+					exception.type_name().as_deref() == Some("java/lang/ClassNotFoundException")
+				}
+				None => true,
+			};
+			if not_found {
+				self.pending_exception = None;
+				self.throw_new("java/lang/NoClassDefFoundError", Some(class_name));
+			}
+		}
+		resolved
+	}
+
+	/// The class whose Class object is `mirror`. None for the Class
+	/// object of a primitive type or of an array type (or if `mirror` is
+	/// not a Class object).
+	pub fn mirrored_class(&mut self, mirror: &JvmValue) -> Option<Rc<Class>> {
+		let (type_name, loader) = match self.methodarea.lock() {
+			Ok(methodarea) => (
+				(*methodarea).mirrored_type(mirror)?,
+				(*methodarea).mirrored_loader(mirror)?,
+			),
+			_ => return None,
+		};
+		if type_name.starts_with('[') || descriptor_of_type_name(&type_name).len() == 1 {
+			return None;
+		}
+		if loader == BOOTSTRAP_LOADER {
+			return self.resolve_class(&type_name);
+		}
+		self.load_class(&type_name, loader)
+	}
+
+	/// The thread's Java stack, from the oldest frame to the newest.
+	/// While an instruction executes, its frame on the stack has only
+	/// its class, its method and its pc.
+	pub fn frames(&self) -> &[Frame] {
+		&self.frames
+	}
+
+	/// Execute `method` in `frame`.
+	///
+	/// # Return value:
+	/// The value that the method returns (Void for void methods). None if
+	/// the method threw an exception that it did not catch; the exception
+	/// is still pending.
+	fn execute_method(&mut self, method: Rc<Method>, frame: Frame) -> Option<JvmValue> {
+		let base = self.frames.len();
+		if !self.push_frame(method, frame) {
+			return None;
+		}
+		self.interpret(base)
+	}
+
+	/// Push a frame in which to execute `method`. If the stack is full,
+	/// throw a StackOverflowError instead.
+	fn push_frame(&mut self, method: Rc<Method>, mut frame: Frame) -> bool {
+		if self.frames.len() >= self.max_stack_depth {
+			self.throw_stack_overflow_error();
+			return false;
+		}
+
 		/*
 		 * The locals are only going to have enough size for the parameters.
 		 * Resize as appropriate.
 		 */
-		let class = frame.class().unwrap();
-
 		Debug(
 			format!(
 				"Resizing local parameter array from {} to {}\n",
@@ -184,57 +1879,233 @@ impl JvmThread {
 			&self.debug_level,
 			DebugLevel::Info,
 		);
-
 		frame.locals.resize(
 			method.max_locals,
 			JvmValue::Primitive(JvmPrimitiveType::Void, 0, 0, 0),
 		);
+		if !self.trace_hooks.is_empty() {
+			if let Some(class) = frame.class() {
+				let (class_name, method_name, descriptor) = traced_method(&class, &method);
+				for hook in &self.trace_hooks {
+					hook.method_entry(
+						&class_name,
+						&method_name,
+						&descriptor,
+						self.frames.len() + 1,
+					);
+				}
+			}
+		}
+		frame.method = Some(method);
+		frame.pc = 0;
+		self.frames.push(frame);
+		true
+	}
 
-		if let Some(code) = method.get_code(class.get_constant_pool_ref()) {
-			let mut pc = 0;
-			while {
-				let mut pc_pos_delta = 0usize;
-				let mut pc_neg_delta = 0usize;
-				Debug(
-					format!("Doing next opcode\n"),
-					&self.debug_level,
-					DebugLevel::Info,
-				);
-				match self.execute_opcode(&code[pc..], &mut frame) {
-					OpcodeResult::Incr(incr) => pc_pos_delta = incr,
-					OpcodeResult::Decr(decr) => pc_neg_delta = decr,
-					OpcodeResult::Value(v) => return Some(v),
-					OpcodeResult::Exception => {
-						/*
-						 * TODO: Handle exceptions!
-						 */
-						FatalError::new(FatalErrorType::NotImplemented(format!("Exceptions.")))
-							.call();
+	/// Tell the trace hooks that `method` of `class`, whose frame is
+	/// `depth` deep, completed.
+	fn trace_method_exit(&self, class: &Class, method: &Method, depth: usize, threw: bool) {
+		if self.trace_hooks.is_empty() {
+			return;
+		}
+		let (class_name, method_name, descriptor) = traced_method(class, method);
+		for hook in &self.trace_hooks {
+			hook.method_exit(&class_name, &method_name, &descriptor, depth, threw);
+		}
+	}
+
+	/// Count an instruction against the budget; when the budget is
+	/// exhausted, the program halts.
+	///
+	/// # Return value:
+	/// Whether the instruction may execute.
+	fn charge_instruction(&mut self) -> bool {
+		match self.instruction_budget {
+			Some(0) => {
+				self.report_error("Error: the program exhausted its instruction budget");
+				self.halt(INSTRUCTION_BUDGET_EXIT_STATUS);
+				false
+			}
+			Some(remaining) => {
+				self.instruction_budget = Some(remaining - 1);
+				true
+			}
+			None => true,
+		}
+	}
+
+	/// Execute instructions until the stack is only `base` frames deep.
+	///
+	/// # Return value:
+	/// The value returned by the method of frame `base`. None if that method
+	/// threw an exception that it did not catch; the exception is still
+	/// pending.
+	fn interpret(&mut self, base: usize) -> Option<JvmValue> {
+		while self.frames.len() > base {
+			if self.exit_status.is_some() {
+				self.frames.truncate(base);
+				return None;
+			}
+			/*
+			 * The executing frame stays on the stack (so that stack traces
+			 * and natives see it) with only its class, method and pc; the
+			 * instruction gets its operand stack and locals.
+			 */
+			let index = self.frames.len() - 1;
+			let mut frame = std::mem::take(&mut self.frames[index]);
+			let method = frame.method().unwrap();
+			let class = frame.class().unwrap();
+			self.frames[index].class = Some(Rc::clone(&class));
+			self.frames[index].method = Some(Rc::clone(&method));
+			self.frames[index].pc = frame.pc;
+
+			if self.pending_exception.is_some() {
+				/*
+				 * If this frame's method has a handler for the exception, clear
+				 * the operand stack, push the exception and continue at the
+				 * handler. Otherwise, the method completes abruptly and the
+				 * exception propagates to its invoker.
+				 */
+				if let Some(handler_pc) = self.find_exception_handler(&method, &class, frame.pc) {
+					let exception = self.pending_exception.take().unwrap();
+					Debug(
+						format!("Caught an exception; continuing at {}.", handler_pc),
+						&self.debug_level,
+						DebugLevel::Info,
+					);
+					frame.operand_stack.clear();
+					frame.operand_stack.push(exception);
+					frame.pc = handler_pc;
+					self.frames[index] = frame;
+				} else {
+					self.trace_method_exit(&class, &method, index + 1, true);
+					self.frames.truncate(index);
+				}
+				continue;
+			}
+
+			let code = match method.get_code(class.get_constant_pool_ref()) {
+				Some(code) => code,
+				None => {
+					FatalError::new(FatalErrorType::MethodExecutionFailed(format!("{}", method)))
+						.call();
+					return None;
+				}
+			};
+			if !self.charge_instruction() {
+				continue;
+			}
+			if !self.trace_hooks.is_empty() {
+				let (class_name, method_name, descriptor) = traced_method(&class, &method);
+				for hook in &self.trace_hooks {
+					hook.instruction(
+						&class_name,
+						&method_name,
+						&descriptor,
+						frame.pc,
+						code[frame.pc],
+					);
+				}
+			}
+			Debug(
+				format!("Doing next opcode\n"),
+				&self.debug_level,
+				DebugLevel::Info,
+			);
+			let result = self.execute_opcode(&code[frame.pc..], &mut frame);
+
+			/*
+			 * An instruction throws by leaving an exception pending,
+			 * whatever it returns.
+			 */
+			let result = if self.pending_exception.is_some() {
+				OpcodeResult::Exception
+			} else {
+				result
+			};
+			match result {
+				OpcodeResult::Incr(0) | OpcodeResult::Decr(0) => {
+					/*
+					 * The instruction could not execute.
+					 */
+					self.frames.truncate(base);
+					return None;
+				}
+				OpcodeResult::Incr(incr) => {
+					/*
+					 * If the instruction threw, the exception is dispatched
+					 * from its pc.
+					 */
+					if self.pending_exception.is_none() {
+						frame.pc += incr;
 					}
-				};
-				Debug(
-					format!("pc_pos_delta: {}\n", pc_pos_delta),
-					&self.debug_level,
-					DebugLevel::Info,
-				);
-				Debug(
-					format!("pc_neg: {}\n", pc_neg_delta),
-					&self.debug_level,
-					DebugLevel::Info,
-				);
-				pc += pc_pos_delta;
-				pc -= pc_neg_delta;
-				Debug(format!("pc: {}\n", pc), &self.debug_level, DebugLevel::Info);
-				pc_pos_delta != 0 || pc_neg_delta != 0
-			} {}
+					Debug(
+						format!("pc: {}\n", frame.pc),
+						&self.debug_level,
+						DebugLevel::Info,
+					);
+					self.frames[index] = frame;
+				}
+				OpcodeResult::Decr(decr) => {
+					frame.pc -= decr;
+					Debug(
+						format!("pc: {}\n", frame.pc),
+						&self.debug_level,
+						DebugLevel::Info,
+					);
+					self.frames[index] = frame;
+				}
+				OpcodeResult::Exception => {
+					/*
+					 * The exception is dispatched from this frame (its pc is
+					 * still that of the throwing instruction) next time around.
+					 */
+					self.frames[index] = frame;
+				}
+				OpcodeResult::Invoke(invoked_method, invoked_frame) => {
+					/*
+					 * The invoker's pc stays at the invoke instruction until
+					 * the invoked method returns. If there is no room for the
+					 * invoked method's frame, the invoke instruction throws.
+					 */
+					self.frames[index] = frame;
+					self.push_frame(invoked_method, invoked_frame);
+				}
+				OpcodeResult::Value(v) => {
+					Debug(
+						format!("Returning from a method: {}", method),
+						&self.debug_level,
+						DebugLevel::Info,
+					);
+					self.trace_method_exit(&class, &method, index + 1, false);
+					self.frames.truncate(index);
+					if self.frames.len() == base {
+						return Some(v);
+					}
+					/*
+					 * Push the result of the invocation onto the invoker's
+					 * operand stack -- unless the method is void -- and move
+					 * the invoker past its invoke instruction.
+					 */
+					let invoker = self.frames.last_mut().unwrap();
+					match v {
+						JvmValue::Primitive(JvmPrimitiveType::Void, _, _, _) => (),
+						v => invoker.operand_stack.push(v),
+					}
+					let invoker_method = invoker.method().unwrap();
+					let invoker_class = invoker.class().unwrap();
+					if let Some(invoker_code) =
+						invoker_method.get_code(invoker_class.get_constant_pool_ref())
+					{
+						invoker.pc += invoke_instruction_length(invoker_code[invoker.pc]);
+					}
+				}
+			}
 		}
 		None
 	}
 
 	fn execute_opcode(&mut self, bytes: &[u8], frame: &mut Frame) -> OpcodeResult {
-		let class = frame.class().unwrap();
-		let constant_pool = class.get_constant_pool_ref();
-
 		let opcode = bytes[0];
 		Debug(
 			format!("code: 0x{:X}\n", opcode),
@@ -242,6 +2113,10 @@ impl JvmThread {
 			DebugLevel::Info,
 		);
 		match OperandCode::from_u8(opcode) {
+			Some(OperandCode::Nop) => {
+				Debug(format!("nop"), &self.debug_level, DebugLevel::Info);
+				OpcodeResult::Incr(1)
+			}
 			Some(OperandCode::Aconst_null) => {
 				Debug(format!("aconst_null"), &self.debug_level, DebugLevel::Info);
 				frame.operand_stack.push(create_null_value());
@@ -257,277 +2132,695 @@ impl JvmThread {
 				self.execute_iconst_x(0, frame);
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iconst_1) => {
-				Debug(format!("iconst_1"), &self.debug_level, DebugLevel::Info);
-				self.execute_iconst_x(1, frame);
+			Some(OperandCode::Iconst_1) => {
+				Debug(format!("iconst_1"), &self.debug_level, DebugLevel::Info);
+				self.execute_iconst_x(1, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Iconst_2) => {
+				Debug(format!("iconst_2"), &self.debug_level, DebugLevel::Info);
+				self.execute_iconst_x(2, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Iconst_3) => {
+				Debug(format!("iconst_3"), &self.debug_level, DebugLevel::Info);
+				self.execute_iconst_x(3, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Iconst_4) => {
+				Debug(format!("iconst_4"), &self.debug_level, DebugLevel::Info);
+				self.execute_iconst_x(4, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Iconst_5) => {
+				Debug(format!("iconst_5"), &self.debug_level, DebugLevel::Info);
+				self.execute_iconst_x(5, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Lconst_0) => {
+				Debug(format!("lconst_0"), &self.debug_level, DebugLevel::Info);
+				frame.operand_stack.push(JvmValue::long(0));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Lconst_1) => {
+				Debug(format!("lconst_1"), &self.debug_level, DebugLevel::Info);
+				frame.operand_stack.push(JvmValue::long(1));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Fconst_0) => {
+				Debug(format!("fconst_0"), &self.debug_level, DebugLevel::Info);
+				frame.operand_stack.push(JvmValue::float(0.0));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Fconst_1) => {
+				Debug(format!("fconst_1"), &self.debug_level, DebugLevel::Info);
+				frame.operand_stack.push(JvmValue::float(1.0));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Fconst_2) => {
+				Debug(format!("fconst_2"), &self.debug_level, DebugLevel::Info);
+				frame.operand_stack.push(JvmValue::float(2.0));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dconst_0) => {
+				Debug(format!("dconst_0"), &self.debug_level, DebugLevel::Info);
+				frame.operand_stack.push(JvmValue::double(0.0));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dconst_1) => {
+				Debug(format!("dconst_1"), &self.debug_level, DebugLevel::Info);
+				frame.operand_stack.push(JvmValue::double(1.0));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Bipush) => {
+				Debug(format!("bipush"), &self.debug_level, DebugLevel::Info);
+				frame
+					.operand_stack
+					.push(JvmValue::int(bytes[1] as i8 as i32));
+				OpcodeResult::Incr(2)
+			}
+			Some(OperandCode::Sipush) => {
+				Debug(format!("sipush"), &self.debug_level, DebugLevel::Info);
+				frame
+					.operand_stack
+					.push(JvmValue::int(read_u16(bytes, 1) as i16 as i32));
+				OpcodeResult::Incr(3)
+			}
+			Some(OperandCode::Ldc) => {
+				Debug(format!("ldc"), &self.debug_level, DebugLevel::Info);
+				self.execute_ldc(bytes[1] as usize, frame);
+				OpcodeResult::Incr(2)
+			}
+			Some(OperandCode::Ldc_w) => {
+				Debug(format!("ldc_w"), &self.debug_level, DebugLevel::Info);
+				self.execute_ldc(read_u16(bytes, 1) as usize, frame);
+				OpcodeResult::Incr(3)
+			}
+			Some(OperandCode::Ldc2_w) => {
+				Debug(format!("ldc2_w"), &self.debug_level, DebugLevel::Info);
+				self.execute_ldc(read_u16(bytes, 1) as usize, frame);
+				OpcodeResult::Incr(3)
+			}
+			Some(OperandCode::Iload)
+			| Some(OperandCode::Lload)
+			| Some(OperandCode::Fload)
+			| Some(OperandCode::Dload)
+			| Some(OperandCode::Aload) => {
+				Debug(format!("xload"), &self.debug_level, DebugLevel::Info);
+				self.execute_load(bytes[1] as usize, frame);
+				OpcodeResult::Incr(2)
+			}
+			Some(OperandCode::Iload_0)
+			| Some(OperandCode::Lload_0)
+			| Some(OperandCode::Fload_0)
+			| Some(OperandCode::Dload_0)
+			| Some(OperandCode::Aload_0) => {
+				Debug(format!("xload_0"), &self.debug_level, DebugLevel::Info);
+				self.execute_load(0, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Iload_1)
+			| Some(OperandCode::Lload_1)
+			| Some(OperandCode::Fload_1)
+			| Some(OperandCode::Dload_1)
+			| Some(OperandCode::Aload_1) => {
+				Debug(format!("xload_1"), &self.debug_level, DebugLevel::Info);
+				self.execute_load(1, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Iload_2)
+			| Some(OperandCode::Lload_2)
+			| Some(OperandCode::Fload_2)
+			| Some(OperandCode::Dload_2)
+			| Some(OperandCode::Aload_2) => {
+				Debug(format!("xload_2"), &self.debug_level, DebugLevel::Info);
+				self.execute_load(2, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Iload_3)
+			| Some(OperandCode::Lload_3)
+			| Some(OperandCode::Fload_3)
+			| Some(OperandCode::Dload_3)
+			| Some(OperandCode::Aload_3) => {
+				Debug(format!("xload_3"), &self.debug_level, DebugLevel::Info);
+				self.execute_load(3, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::IaLoad)
+			| Some(OperandCode::LaLoad)
+			| Some(OperandCode::FaLoad)
+			| Some(OperandCode::DaLoad)
+			| Some(OperandCode::AaLoad)
+			| Some(OperandCode::BaLoad)
+			| Some(OperandCode::CaLoad)
+			| Some(OperandCode::SaLoad) => {
+				Debug(format!("xaload"), &self.debug_level, DebugLevel::Info);
+				self.execute_xaload(frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Istore)
+			| Some(OperandCode::Lstore)
+			| Some(OperandCode::Fstore)
+			| Some(OperandCode::Dstore)
+			| Some(OperandCode::Astore) => {
+				Debug(format!("xstore"), &self.debug_level, DebugLevel::Info);
+				self.execute_store(bytes[1] as usize, frame);
+				OpcodeResult::Incr(2)
+			}
+			Some(OperandCode::Istore_0)
+			| Some(OperandCode::Lstore_0)
+			| Some(OperandCode::Fstore_0)
+			| Some(OperandCode::Dstore_0)
+			| Some(OperandCode::Astore_0) => {
+				Debug(format!("xstore_0"), &self.debug_level, DebugLevel::Info);
+				self.execute_store(0, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Istore_1)
+			| Some(OperandCode::Lstore_1)
+			| Some(OperandCode::Fstore_1)
+			| Some(OperandCode::Dstore_1)
+			| Some(OperandCode::Astore_1) => {
+				Debug(format!("xstore_1"), &self.debug_level, DebugLevel::Info);
+				self.execute_store(1, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Istore_2)
+			| Some(OperandCode::Lstore_2)
+			| Some(OperandCode::Fstore_2)
+			| Some(OperandCode::Dstore_2)
+			| Some(OperandCode::Astore_2) => {
+				Debug(format!("xstore_2"), &self.debug_level, DebugLevel::Info);
+				self.execute_store(2, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Istore_3)
+			| Some(OperandCode::Lstore_3)
+			| Some(OperandCode::Fstore_3)
+			| Some(OperandCode::Dstore_3)
+			| Some(OperandCode::Astore_3) => {
+				Debug(format!("xstore_3"), &self.debug_level, DebugLevel::Info);
+				self.execute_store(3, frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::IaStore)
+			| Some(OperandCode::LaStore)
+			| Some(OperandCode::FaStore)
+			| Some(OperandCode::DaStore)
+			| Some(OperandCode::AaStore)
+			| Some(OperandCode::BaStore)
+			| Some(OperandCode::CaStore)
+			| Some(OperandCode::SaStore) => {
+				Debug(format!("xastore"), &self.debug_level, DebugLevel::Info);
+				self.execute_xastore(frame);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Pop) => {
+				Debug(format!("pop"), &self.debug_level, DebugLevel::Info);
+				self.execute_stack_operation("pop", frame, 1, 0, false);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Pop2) => {
+				Debug(format!("pop2"), &self.debug_level, DebugLevel::Info);
+				self.execute_stack_operation("pop2", frame, 2, 0, false);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dup) => {
+				Debug(format!("dup"), &self.debug_level, DebugLevel::Info);
+				self.execute_stack_operation("dup", frame, 1, 0, true);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dup_x1) => {
+				Debug(format!("dup_x1"), &self.debug_level, DebugLevel::Info);
+				self.execute_stack_operation("dup_x1", frame, 1, 1, true);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dup_x2) => {
+				Debug(format!("dup_x2"), &self.debug_level, DebugLevel::Info);
+				self.execute_stack_operation("dup_x2", frame, 1, 2, true);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dup2) => {
+				Debug(format!("dup2"), &self.debug_level, DebugLevel::Info);
+				self.execute_stack_operation("dup2", frame, 2, 0, true);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dup2_x1) => {
+				Debug(format!("dup2_x1"), &self.debug_level, DebugLevel::Info);
+				self.execute_stack_operation("dup2_x1", frame, 2, 1, true);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dup2_x2) => {
+				Debug(format!("dup2_x2"), &self.debug_level, DebugLevel::Info);
+				self.execute_stack_operation("dup2_x2", frame, 2, 2, true);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Swap) => {
+				Debug(format!("swap"), &self.debug_level, DebugLevel::Info);
+				self.execute_stack_operation("swap", frame, 1, 1, false);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Iadd) => {
+				Debug(format!("iadd"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("iadd", frame, |a, b| Some(a.wrapping_add(b)));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Ladd) => {
+				Debug(format!("ladd"), &self.debug_level, DebugLevel::Info);
+				self.execute_lop("ladd", frame, |a, b| Some(a.wrapping_add(b)));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Fadd) => {
+				Debug(format!("fadd"), &self.debug_level, DebugLevel::Info);
+				self.execute_fop("fadd", frame, |a, b| a + b);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dadd) => {
+				Debug(format!("dadd"), &self.debug_level, DebugLevel::Info);
+				self.execute_dop("dadd", frame, |a, b| a + b);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Isub) => {
+				Debug(format!("isub"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("isub", frame, |a, b| Some(a.wrapping_sub(b)));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Lsub) => {
+				Debug(format!("lsub"), &self.debug_level, DebugLevel::Info);
+				self.execute_lop("lsub", frame, |a, b| Some(a.wrapping_sub(b)));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Fsub) => {
+				Debug(format!("fsub"), &self.debug_level, DebugLevel::Info);
+				self.execute_fop("fsub", frame, |a, b| a - b);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Dsub) => {
+				Debug(format!("dsub"), &self.debug_level, DebugLevel::Info);
+				self.execute_dop("dsub", frame, |a, b| a - b);
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Imul) => {
+				Debug(format!("imul"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("imul", frame, |a, b| Some(a.wrapping_mul(b)));
+				OpcodeResult::Incr(1)
+			}
+			Some(OperandCode::Lmul) => {
+				Debug(format!("lmul"), &self.debug_level, DebugLevel::Info);
+				self.execute_lop("lmul", frame, |a, b| Some(a.wrapping_mul(b)));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iconst_2) => {
-				Debug(format!("iconst_2"), &self.debug_level, DebugLevel::Info);
-				self.execute_iconst_x(2, frame);
+			Some(OperandCode::Fmul) => {
+				Debug(format!("fmul"), &self.debug_level, DebugLevel::Info);
+				self.execute_fop("fmul", frame, |a, b| a * b);
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iconst_3) => {
-				Debug(format!("iconst_3"), &self.debug_level, DebugLevel::Info);
-				self.execute_iconst_x(3, frame);
+			Some(OperandCode::Dmul) => {
+				Debug(format!("dmul"), &self.debug_level, DebugLevel::Info);
+				self.execute_dop("dmul", frame, |a, b| a * b);
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iconst_4) => {
-				Debug(format!("iconst_4"), &self.debug_level, DebugLevel::Info);
-				self.execute_iconst_x(4, frame);
+			Some(OperandCode::Idiv) => {
+				Debug(format!("idiv"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("idiv", frame, |a, b| {
+					if b == 0 {
+						None
+					} else {
+						Some(a.wrapping_div(b))
+					}
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iconst_5) => {
-				Debug(format!("iconst_5"), &self.debug_level, DebugLevel::Info);
-				self.execute_iconst_x(5, frame);
+			Some(OperandCode::Ldiv) => {
+				Debug(format!("ldiv"), &self.debug_level, DebugLevel::Info);
+				self.execute_lop("ldiv", frame, |a, b| {
+					if b == 0 {
+						None
+					} else {
+						Some(a.wrapping_div(b))
+					}
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fconst_0) => {
-				Debug(format!("fconst_0"), &self.debug_level, DebugLevel::Info);
-				let fzero = 0.0f32;
-				let uzero = u32::from_le_bytes(fzero.to_le_bytes());
-				frame
-					.operand_stack
-					.push(JvmValue::Primitive(JvmPrimitiveType::Float, 0, uzero, 0));
+			Some(OperandCode::Fdiv) => {
+				Debug(format!("fdiv"), &self.debug_level, DebugLevel::Info);
+				self.execute_fop("fdiv", frame, |a, b| a / b);
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fconst_1) => {
-				Debug(format!("fconst_1"), &self.debug_level, DebugLevel::Info);
-				let fone = 1.0f32;
-				let uone = u32::from_le_bytes(fone.to_le_bytes());
-				frame
-					.operand_stack
-					.push(JvmValue::Primitive(JvmPrimitiveType::Float, 0, uone, 0));
+			Some(OperandCode::Ddiv) => {
+				Debug(format!("ddiv"), &self.debug_level, DebugLevel::Info);
+				self.execute_dop("ddiv", frame, |a, b| a / b);
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Bipush) => {
-				Debug(format!("bipush"), &self.debug_level, DebugLevel::Info);
-				frame.operand_stack.push(JvmValue::Primitive(
-					JvmPrimitiveType::Integer,
-					0,
-					bytes[1] as u32,
-					0,
-				));
-				OpcodeResult::Incr(2)
-			}
-			Some(OperandCode::Ldc) => {
-				Debug(format!("ldc"), &self.debug_level, DebugLevel::Info);
-				self.execute_ldc(bytes, frame);
-				OpcodeResult::Incr(2)
+			Some(OperandCode::Irem) => {
+				Debug(format!("irem"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("irem", frame, |a, b| {
+					if b == 0 {
+						None
+					} else {
+						Some(a.wrapping_rem(b))
+					}
+				});
+				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iload_0) => {
-				Debug(format!("iload_0"), &self.debug_level, DebugLevel::Info);
-				self.execute_iload_x(0, frame);
+			Some(OperandCode::Lrem) => {
+				Debug(format!("lrem"), &self.debug_level, DebugLevel::Info);
+				self.execute_lop("lrem", frame, |a, b| {
+					if b == 0 {
+						None
+					} else {
+						Some(a.wrapping_rem(b))
+					}
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iload_1) => {
-				Debug(format!("iload_1"), &self.debug_level, DebugLevel::Info);
-				self.execute_iload_x(1, frame);
+			Some(OperandCode::Frem) => {
+				Debug(format!("frem"), &self.debug_level, DebugLevel::Info);
+				self.execute_fop("frem", frame, |a, b| a % b);
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iload_2) => {
-				Debug(format!("iload_2"), &self.debug_level, DebugLevel::Info);
-				self.execute_iload_x(2, frame);
+			Some(OperandCode::Drem) => {
+				Debug(format!("drem"), &self.debug_level, DebugLevel::Info);
+				self.execute_dop("drem", frame, |a, b| a % b);
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iload_3) => {
-				Debug(format!("iload_3"), &self.debug_level, DebugLevel::Info);
-				self.execute_iload_x(3, frame);
+			Some(OperandCode::Ineg) => {
+				Debug(format!("ineg"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("ineg", frame, |value| {
+					Some(JvmValue::int(value.as_int()?.wrapping_neg()))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fload_0) => {
-				Debug(format!("fload_0"), &self.debug_level, DebugLevel::Info);
-				self.execute_fload_x(0, frame);
+			Some(OperandCode::Lneg) => {
+				Debug(format!("lneg"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("lneg", frame, |value| {
+					Some(JvmValue::long(value.as_long()?.wrapping_neg()))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fload_1) => {
-				Debug(format!("fload_1"), &self.debug_level, DebugLevel::Info);
-				self.execute_fload_x(1, frame);
+			Some(OperandCode::Fneg) => {
+				Debug(format!("fneg"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("fneg", frame, |value| {
+					Some(JvmValue::float(-value.as_float()?))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fload_2) => {
-				Debug(format!("fload_2"), &self.debug_level, DebugLevel::Info);
-				self.execute_fload_x(2, frame);
+			Some(OperandCode::Dneg) => {
+				Debug(format!("dneg"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("dneg", frame, |value| {
+					Some(JvmValue::double(-value.as_double()?))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fload_3) => {
-				Debug(format!("fload_3"), &self.debug_level, DebugLevel::Info);
-				self.execute_fload_x(3, frame);
+			Some(OperandCode::Ishl) => {
+				Debug(format!("ishl"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("ishl", frame, |a, b| Some(a.wrapping_shl(b as u32)));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Aload_0) => {
-				Debug(format!("aload_0"), &self.debug_level, DebugLevel::Info);
-				self.execute_aload_x(0, frame);
+			Some(OperandCode::Ishr) => {
+				Debug(format!("ishr"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("ishr", frame, |a, b| Some(a.wrapping_shr(b as u32)));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Aload_1) => {
-				Debug(format!("aload_1"), &self.debug_level, DebugLevel::Info);
-				self.execute_aload_x(1, frame);
+			Some(OperandCode::Iushr) => {
+				Debug(format!("iushr"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("iushr", frame, |a, b| {
+					Some((a as u32).wrapping_shr(b as u32) as i32)
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Aload_2) => {
-				Debug(format!("aload_2"), &self.debug_level, DebugLevel::Info);
-				self.execute_aload_x(2, frame);
+			Some(OperandCode::Lshl) => {
+				Debug(format!("lshl"), &self.debug_level, DebugLevel::Info);
+				self.execute_lshift("lshl", frame, |a, b| a.wrapping_shl(b as u32));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Aload_3) => {
-				Debug(format!("aload_3"), &self.debug_level, DebugLevel::Info);
-				self.execute_aload_x(3, frame);
+			Some(OperandCode::Lshr) => {
+				Debug(format!("lshr"), &self.debug_level, DebugLevel::Info);
+				self.execute_lshift("lshr", frame, |a, b| a.wrapping_shr(b as u32));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::AaLoad) => {
-				Debug(format!("aaload"), &self.debug_level, DebugLevel::Info);
-				self.execute_aaload(frame);
+			Some(OperandCode::Lushr) => {
+				Debug(format!("lushr"), &self.debug_level, DebugLevel::Info);
+				self.execute_lshift("lushr", frame, |a, b| {
+					(a as u64).wrapping_shr(b as u32) as i64
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::CaLoad) => {
-				Debug(format!("caload"), &self.debug_level, DebugLevel::Info);
-				self.execute_caload(frame);
+			Some(OperandCode::Iand) => {
+				Debug(format!("iand"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("iand", frame, |a, b| Some(a & b));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fstore) => {
-				Debug(format!("fstore"), &self.debug_level, DebugLevel::Info);
-				let index = bytes[1];
-				self.execute_fstore(index as usize, frame);
-				OpcodeResult::Incr(2)
+			Some(OperandCode::Land) => {
+				Debug(format!("land"), &self.debug_level, DebugLevel::Info);
+				self.execute_lop("land", frame, |a, b| Some(a & b));
+				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Istore_0) => {
-				Debug(format!("istore_0"), &self.debug_level, DebugLevel::Info);
-				self.execute_istore_x(0, frame);
+			Some(OperandCode::Ior) => {
+				Debug(format!("ior"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("ior", frame, |a, b| Some(a | b));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Istore_1) => {
-				Debug(format!("istore_1"), &self.debug_level, DebugLevel::Info);
-				self.execute_istore_x(1, frame);
+			Some(OperandCode::Lor) => {
+				Debug(format!("lor"), &self.debug_level, DebugLevel::Info);
+				self.execute_lop("lor", frame, |a, b| Some(a | b));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Istore_2) => {
-				Debug(format!("istore_2"), &self.debug_level, DebugLevel::Info);
-				self.execute_istore_x(2, frame);
+			Some(OperandCode::Ixor) => {
+				Debug(format!("ixor"), &self.debug_level, DebugLevel::Info);
+				self.execute_iop("ixor", frame, |a, b| Some(a ^ b));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Istore_3) => {
-				Debug(format!("istore_3"), &self.debug_level, DebugLevel::Info);
-				self.execute_istore_x(3, frame);
+			Some(OperandCode::Lxor) => {
+				Debug(format!("lxor"), &self.debug_level, DebugLevel::Info);
+				self.execute_lop("lxor", frame, |a, b| Some(a ^ b));
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fstore_0) => {
-				Debug(format!("fstore_0"), &self.debug_level, DebugLevel::Info);
-				self.execute_fstore_x(0, frame);
+			Some(OperandCode::Iinc) => {
+				Debug(format!("iinc"), &self.debug_level, DebugLevel::Info);
+				self.execute_iinc(bytes[1] as usize, bytes[2] as i8 as i32, frame);
+				OpcodeResult::Incr(3)
+			}
+			Some(OperandCode::I2l) => {
+				Debug(format!("i2l"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("i2l", frame, |value| {
+					Some(JvmValue::long(value.as_int()? as i64))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fstore_1) => {
-				Debug(format!("fstore_1"), &self.debug_level, DebugLevel::Info);
-				self.execute_fstore_x(1, frame);
+			Some(OperandCode::I2f) => {
+				Debug(format!("i2f"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("i2f", frame, |value| {
+					Some(JvmValue::float(value.as_int()? as f32))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fstore_2) => {
-				Debug(format!("fstore_2"), &self.debug_level, DebugLevel::Info);
-				self.execute_fstore_x(2, frame);
+			Some(OperandCode::I2d) => {
+				Debug(format!("i2d"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("i2d", frame, |value| {
+					Some(JvmValue::double(value.as_int()? as f64))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fstore_3) => {
-				Debug(format!("fstore_3"), &self.debug_level, DebugLevel::Info);
-				self.execute_fstore_x(3, frame);
+			Some(OperandCode::L2i) => {
+				Debug(format!("l2i"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("l2i", frame, |value| {
+					Some(JvmValue::int(value.as_long()? as i32))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Astore_0) => {
-				Debug(format!("astore_0"), &self.debug_level, DebugLevel::Info);
-				self.execute_astore_x(0, frame);
+			Some(OperandCode::L2f) => {
+				Debug(format!("l2f"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("l2f", frame, |value| {
+					Some(JvmValue::float(value.as_long()? as f32))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Astore_1) => {
-				Debug(format!("astore_1"), &self.debug_level, DebugLevel::Info);
-				self.execute_astore_x(1, frame);
+			Some(OperandCode::L2d) => {
+				Debug(format!("l2d"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("l2d", frame, |value| {
+					Some(JvmValue::double(value.as_long()? as f64))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Astore_2) => {
-				Debug(format!("astore_2"), &self.debug_level, DebugLevel::Info);
-				self.execute_astore_x(2, frame);
+			/*
+			 * Rust's float-to-integer casts have the semantics of Java's:
+			 * NaN becomes zero and the rest saturate.
+			 */
+			Some(OperandCode::F2i) => {
+				Debug(format!("f2i"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("f2i", frame, |value| {
+					Some(JvmValue::int(value.as_float()? as i32))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Astore_3) => {
-				Debug(format!("astore_3"), &self.debug_level, DebugLevel::Info);
-				self.execute_astore_x(3, frame);
+			Some(OperandCode::F2l) => {
+				Debug(format!("f2l"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("f2l", frame, |value| {
+					Some(JvmValue::long(value.as_float()? as i64))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::AaStore) => {
-				Debug(format!("aastore"), &self.debug_level, DebugLevel::Info);
-				self.execute_aastore(frame);
+			Some(OperandCode::F2d) => {
+				Debug(format!("f2d"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("f2d", frame, |value| {
+					Some(JvmValue::double(value.as_float()? as f64))
+				});
 				OpcodeResult::Incr(1)
 			}
-
-			Some(OperandCode::CaStore) => {
-				Debug(format!("castore"), &self.debug_level, DebugLevel::Info);
-				self.execute_castore(frame);
+			Some(OperandCode::D2i) => {
+				Debug(format!("d2i"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("d2i", frame, |value| {
+					Some(JvmValue::int(value.as_double()? as i32))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Pop) => {
-				Debug(format!("pop"), &self.debug_level, DebugLevel::Info);
-				frame.operand_stack.pop();
+			Some(OperandCode::D2l) => {
+				Debug(format!("d2l"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("d2l", frame, |value| {
+					Some(JvmValue::long(value.as_double()? as i64))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Dup) => {
-				Debug(format!("dup"), &self.debug_level, DebugLevel::Info);
-				/*
-				 * TODO: The type on the stack must be a "category 1
-				 * computational type."
-				 */
-				if let Some(top) = frame.operand_stack.pop() {
-					frame.operand_stack.push(top.clone());
-					frame.operand_stack.push(top);
-				}
+			Some(OperandCode::D2f) => {
+				Debug(format!("d2f"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("d2f", frame, |value| {
+					Some(JvmValue::float(value.as_double()? as f32))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Iadd) => {
-				Debug(format!("iadd"), &self.debug_level, DebugLevel::Info);
-				self.execute_iadd(frame);
+			Some(OperandCode::I2b) => {
+				Debug(format!("i2b"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("i2b", frame, |value| {
+					Some(JvmValue::int(value.as_int()? as i8 as i32))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fadd) => {
-				Debug(format!("fadd"), &self.debug_level, DebugLevel::Info);
-				self.execute_fadd(frame);
+			Some(OperandCode::I2c) => {
+				Debug(format!("i2c"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("i2c", frame, |value| {
+					Some(JvmValue::int(value.as_int()? as u16 as i32))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fsub) => {
-				Debug(format!("fsub"), &self.debug_level, DebugLevel::Info);
-				self.execute_fsub(frame);
+			Some(OperandCode::I2s) => {
+				Debug(format!("i2s"), &self.debug_level, DebugLevel::Info);
+				self.execute_conversion("i2s", frame, |value| {
+					Some(JvmValue::int(value.as_int()? as i16 as i32))
+				});
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Imul) => {
-				Debug(format!("imul"), &self.debug_level, DebugLevel::Info);
-				self.execute_imul(frame);
+			Some(OperandCode::Lcmp) => {
+				Debug(format!("lcmp"), &self.debug_level, DebugLevel::Info);
+				self.execute_lcmp(frame);
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fmul) => {
-				Debug(format!("fmul"), &self.debug_level, DebugLevel::Info);
-				self.execute_fmul(frame);
+			Some(OperandCode::Fcmpl) | Some(OperandCode::Fcmpg) => {
+				Debug(format!("fcmp<op>"), &self.debug_level, DebugLevel::Info);
+				self.execute_fcmp(frame, opcode == OperandCode::Fcmpg as u8);
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fdiv) => {
-				Debug(format!("fdiv"), &self.debug_level, DebugLevel::Info);
-				self.execute_fdiv(frame);
+			Some(OperandCode::Dcmpl) | Some(OperandCode::Dcmpg) => {
+				Debug(format!("dcmp<op>"), &self.debug_level, DebugLevel::Info);
+				self.execute_dcmp(frame, opcode == OperandCode::Dcmpg as u8);
 				OpcodeResult::Incr(1)
 			}
+			Some(OperandCode::Ifeq) => {
+				Debug(format!("Ifeq"), &self.debug_level, DebugLevel::Info);
+				self.execute_if(ComparisonType::Equal, &[bytes[1], bytes[2]], frame)
+			}
+			Some(OperandCode::Ifne) => {
+				Debug(format!("Ifne"), &self.debug_level, DebugLevel::Info);
+				self.execute_if(ComparisonType::NotEqual, &[bytes[1], bytes[2]], frame)
+			}
+			Some(OperandCode::Iflt) => {
+				Debug(format!("Iflt"), &self.debug_level, DebugLevel::Info);
+				self.execute_if(ComparisonType::LessThan, &[bytes[1], bytes[2]], frame)
+			}
+			Some(OperandCode::Ifge) => {
+				Debug(format!("Ifge"), &self.debug_level, DebugLevel::Info);
+				self.execute_if(
+					ComparisonType::GreaterThanOrEqual,
+					&[bytes[1], bytes[2]],
+					frame,
+				)
+			}
+			Some(OperandCode::Ifgt) => {
+				Debug(format!("Ifgt"), &self.debug_level, DebugLevel::Info);
+				self.execute_if(ComparisonType::GreaterThan, &[bytes[1], bytes[2]], frame)
+			}
+			Some(OperandCode::Ifle) => {
+				Debug(format!("Ifle"), &self.debug_level, DebugLevel::Info);
+				self.execute_if(
+					ComparisonType::LessThanOrEqual,
+					&[bytes[1], bytes[2]],
+					frame,
+				)
+			}
 			cmpop @ Some(OperandCode::If_icmpeq)
 			| cmpop @ Some(OperandCode::If_icmpne)
 			| cmpop @ Some(OperandCode::If_icmple)
 			| cmpop @ Some(OperandCode::If_icmpge)
 			| cmpop @ Some(OperandCode::If_icmpgt)
 			| cmpop @ Some(OperandCode::If_icmplt) => self.execute_icmp(frame, bytes, cmpop.unwrap()),
+			Some(OperandCode::If_acmpeq) | Some(OperandCode::If_acmpne) => {
+				Debug(
+					format!("if_acmp<cond>"),
+					&self.debug_level,
+					DebugLevel::Info,
+				);
+				let value2 = frame.operand_stack.pop();
+				let value1 = frame.operand_stack.pop();
+				match (value1, value2) {
+					(Some(value1), Some(value2)) => {
+						let same = value1.is_same_reference(&value2);
+						if same == (opcode == OperandCode::If_acmpeq as u8) {
+							branch(read_u16(bytes, 1) as i16 as i32)
+						} else {
+							OpcodeResult::Incr(3)
+						}
+					}
+					_ => {
+						FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
+							"if_acmp<cond>"
+						)))
+						.call();
+						OpcodeResult::Incr(0)
+					}
+				}
+			}
 			Some(OperandCode::Goto) => {
 				Debug(format!("goto"), &self.debug_level, DebugLevel::Info);
-				let branch_target = i16::from_be_bytes([bytes[1], bytes[2]]);
-
-				if branch_target < 0 {
-					OpcodeResult::Decr(branch_target.abs() as usize)
-				} else {
-					OpcodeResult::Incr(branch_target.abs() as usize)
-				}
+				branch(read_u16(bytes, 1) as i16 as i32)
 			}
-			Some(OperandCode::Ireturn) => {
-				Debug(format!("ireturn"), &self.debug_level, DebugLevel::Info);
-				OpcodeResult::Value(frame.operand_stack.pop().unwrap())
+			Some(OperandCode::Goto_w) => {
+				Debug(format!("goto_w"), &self.debug_level, DebugLevel::Info);
+				branch(read_i32(bytes, 1))
+			}
+			Some(OperandCode::Tableswitch) => {
+				Debug(format!("tableswitch"), &self.debug_level, DebugLevel::Info);
+				self.execute_tableswitch(bytes, frame)
+			}
+			Some(OperandCode::Lookupswitch) => {
+				Debug(format!("lookupswitch"), &self.debug_level, DebugLevel::Info);
+				self.execute_lookupswitch(bytes, frame)
+			}
+			Some(OperandCode::Ireturn)
+			| Some(OperandCode::Lreturn)
+			| Some(OperandCode::Freturn)
+			| Some(OperandCode::Dreturn)
+			| Some(OperandCode::Areturn) => {
+				Debug(format!("xreturn"), &self.debug_level, DebugLevel::Info);
+				match frame.operand_stack.pop() {
+					Some(value) => OpcodeResult::Value(value),
+					None => {
+						FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
+							"xreturn"
+						)))
+						.call();
+						OpcodeResult::Incr(0)
+					}
+				}
 			}
 			Some(OperandCode::r#Return) => {
 				Debug(format!("return"), &self.debug_level, DebugLevel::Info);
@@ -559,14 +2852,8 @@ impl JvmThread {
 					&self.debug_level,
 					DebugLevel::Info,
 				);
-				/*
-				 * Start by assuming failure.
-				 */
-				let mut pc_incr: usize = 0;
-
-				let invokevirtual_result = self.execute_invokevirtual(bytes, frame);
-				pc_incr = self.handle_invoke_result(invokevirtual_result, frame, 3);
-				OpcodeResult::Incr(pc_incr)
+				self.execute_invokevirtual(bytes, frame)
+					.unwrap_or(OpcodeResult::Incr(0))
 			}
 			Some(OperandCode::Invokespecial) => {
 				Debug(
@@ -574,25 +2861,22 @@ impl JvmThread {
 					&self.debug_level,
 					DebugLevel::Info,
 				);
-				/*
-				 * Start by assuming failure.
-				 */
-				let mut pc_incr: usize = 0;
-
-				let invokespecial_result = self.execute_invokespecial(bytes, frame);
-				pc_incr = self.handle_invoke_result(invokespecial_result, frame, 3);
-				OpcodeResult::Incr(pc_incr)
+				self.execute_invokespecial(bytes, frame)
+					.unwrap_or(OpcodeResult::Incr(0))
 			}
 			Some(OperandCode::Invokestatic) => {
 				Debug(format!("invokestatic"), &self.debug_level, DebugLevel::Info);
-				/*
-				 * Start by assuming failure.
-				 */
-				let mut pc_incr: usize = 0;
-
-				let invokestatic_result = self.execute_invokestatic(bytes, frame);
-				pc_incr = self.handle_invoke_result(invokestatic_result, frame, 3);
-				OpcodeResult::Incr(pc_incr)
+				self.execute_invokestatic(bytes, frame)
+					.unwrap_or(OpcodeResult::Incr(0))
+			}
+			Some(OperandCode::Invokeinterface) => {
+				Debug(
+					format!("invokeinterface"),
+					&self.debug_level,
+					DebugLevel::Info,
+				);
+				self.execute_invokevirtual(bytes, frame)
+					.unwrap_or(OpcodeResult::Incr(0))
 			}
 			Some(OperandCode::New) => {
 				Debug(format!("New"), &self.debug_level, DebugLevel::Info);
@@ -608,172 +2892,189 @@ impl JvmThread {
 			}
 			Some(OperandCode::NewArray) => {
 				Debug(format!("NewArray"), &self.debug_level, DebugLevel::Info);
-				let newarray_len = frame.operand_stack.pop();
-				let newarray_type = bytes[1];
-
-				// First, make sure that there is a reasonable array size on the stack.
-				if let Some(newarray_len) = newarray_len {
-					match newarray_len {
-						JvmValue::Primitive(JvmPrimitiveType::Integer, _, len, _) => {
-							match JvmArrayType::from_u8(newarray_type) {
-								Some(JvmArrayType::Char) /* Character */ => {
-									frame.operand_stack.push(
-										JvmValue::Reference(
-											JvmReferenceType::Array(Rc::new(JvmType::Primitive(JvmPrimitiveType::Char)), len),
-											JvmReferenceTargetType::Array(Arc::new(Mutex::new(JvmArray::new(len as usize)))),
-											0));
-								},
-								Some(_) => {
-									FatalError::new(FatalErrorType::NotImplemented(
-										format!("Cannot handle new arrays with that type")))
-										.call();
-								}
-								_ => {
-									// We were asked to make an array for an invalid type
-									FatalError::new(FatalErrorType::WrongType(
-										format!("newarray"),
-										format!("JvmArrayType")
-									))
-									.call();
-								}
-							}
-						}
-						_ => {
-							// The value on the stack for the len of the array should be an integer
-							FatalError::new(FatalErrorType::WrongType(
-								format!("newarray"),
-								format!("integer"),
-							))
-							.call();
-						}
+				let component = match JvmArrayType::from_u8(bytes[1]) {
+					Some(JvmArrayType::Boolean) => JvmPrimitiveType::Boolean,
+					Some(JvmArrayType::Char) => JvmPrimitiveType::Char,
+					Some(JvmArrayType::Float) => JvmPrimitiveType::Float,
+					Some(JvmArrayType::Double) => JvmPrimitiveType::Double,
+					Some(JvmArrayType::Byte) => JvmPrimitiveType::Byte,
+					Some(JvmArrayType::Short) => JvmPrimitiveType::Short,
+					Some(JvmArrayType::Integer) => JvmPrimitiveType::Integer,
+					Some(JvmArrayType::Long) => JvmPrimitiveType::LongInteger,
+					None => {
+						// We were asked to make an array for an invalid type
+						FatalError::new(FatalErrorType::WrongType(
+							format!("newarray"),
+							format!("JvmArrayType"),
+						))
+						.call();
+						return OpcodeResult::Incr(0);
+					}
+				};
+				if let Some(count) = self.pop_array_count("newarray", frame) {
+					if let Some(array) =
+						self.create_default_array(JvmType::Primitive(component), count)
+					{
+						frame.operand_stack.push(array);
 					}
-				} else {
-					FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-						"newarray"
-					)))
-					.call();
 				}
 				OpcodeResult::Incr(2)
 			}
 			Some(OperandCode::ANewArray) => {
 				Debug(format!("ANewArray"), &self.debug_level, DebugLevel::Info);
-				let type_index = ((bytes[1] as u16) << 8) | (bytes[2] as u16);
-				if let Some(array_size) = frame.operand_stack.pop() {
-					if let JvmValue::Primitive(JvmPrimitiveType::Integer, _, count, _) = array_size
-					{
-						let res = self.execute_anewarray(type_index, count, frame);
-						Debug(
-							format!("frame after new: {}", frame),
-							&self.debug_level,
-							DebugLevel::Info,
-						);
-						res
-					} else {
-						// Wrong type for the count
+				let class = frame.class().unwrap();
+				let component_name = match class.get_class_name_at(read_u16(bytes, 1)) {
+					Some(component_name) => component_name,
+					None => {
 						FatalError::new(FatalErrorType::WrongType(
 							format!("anewarray"),
-							format!("integer primitive"),
+							format!("symbolic reference to class."),
 						))
 						.call();
-						OpcodeResult::Incr(3)
+						return OpcodeResult::Incr(0);
+					}
+				};
+				if let Some(count) = self.pop_array_count("anewarray", frame) {
+					if !component_name.starts_with('[')
+						&& self.resolve_class_from(&class, &component_name).is_none()
+					{
+						return OpcodeResult::Exception;
+					}
+					let component =
+						JvmType::from(descriptor_of_type_name(&component_name).as_bytes());
+					if let Some(array) = self.create_default_array(component, count) {
+						frame.operand_stack.push(array);
 					}
-				} else {
-					// Missing a count on the stack!
-					FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-						"anewarray"
-					)))
-					.call();
-					OpcodeResult::Incr(3)
 				}
+				OpcodeResult::Incr(3)
 			}
-			Some(OperandCode::ArrayLength) => {
-				Debug(format!("ArrayLength"), &self.debug_level, DebugLevel::Info);
+			Some(OperandCode::MultiANewArray) => {
 				Debug(
-					format!("Frame before arraylength: {}", frame),
+					format!("multianewarray"),
 					&self.debug_level,
 					DebugLevel::Info,
 				);
-
-				if let Some(array_ref) = frame.operand_stack.pop() {
-					if let JvmValue::Reference(
-						JvmReferenceType::Array(_, _),
-						JvmReferenceTargetType::Array(array),
-						_,
-					) = array_ref
-					{
-						// Try to lock the array.
-						if let Ok(array) = array.lock() {
-							let array_length = array.dimension();
-							frame.operand_stack.push(JvmValue::Primitive(
-								JvmPrimitiveType::Integer,
-								0,
-								array_length as u32,
-								0,
-							));
-						}
-						Debug(
-							format!("frame after new: {}", frame),
-							&self.debug_level,
-							DebugLevel::Info,
-						);
-					} else {
-						// Wrong type for the count
+				self.execute_multianewarray(bytes, frame);
+				OpcodeResult::Incr(4)
+			}
+			Some(OperandCode::ArrayLength) => {
+				Debug(format!("ArrayLength"), &self.debug_level, DebugLevel::Info);
+				match frame.operand_stack.pop() {
+					Some(JvmValue::Reference(_, JvmReferenceTargetType::Array(array), _)) => {
+						let array_length = match array.lock() {
+							Ok(array) => array.dimension(),
+							_ => 0,
+						};
+						frame.operand_stack.push(JvmValue::int(array_length as i32));
+					}
+					Some(JvmValue::Reference(_, JvmReferenceTargetType::Null, _)) => {
+						self.throw_new("java/lang/NullPointerException", None);
+					}
+					_ => {
 						FatalError::new(FatalErrorType::WrongType(
 							format!("arraylength"),
 							format!("reference to an array"),
 						))
 						.call();
 					}
-				} else {
-					// Missing a count on the stack!
-					FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-						"anewarray"
-					)))
-					.call();
 				}
-
 				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Fcmpgt) | Some(OperandCode::Fcmplt) => {
+			Some(OperandCode::AThrow) => {
+				Debug(format!("athrow"), &self.debug_level, DebugLevel::Info);
+				match frame.operand_stack.pop() {
+					Some(JvmValue::Reference(_, JvmReferenceTargetType::Null, _)) | None => {
+						self.throw_new("java/lang/NullPointerException", None);
+					}
+					Some(exception) => self.throw(exception),
+				}
+				OpcodeResult::Exception
+			}
+			Some(OperandCode::CheckCast) | Some(OperandCode::InstanceOf) => {
 				Debug(
-					format!("Fcmpgt or Fcmplt"),
+					format!("checkcast/instanceof"),
 					&self.debug_level,
 					DebugLevel::Info,
 				);
-				self.execute_fcmp(frame);
-				OpcodeResult::Incr(1)
-			}
-			Some(OperandCode::Ifeq) => {
-				Debug(format!("Ifeq"), &self.debug_level, DebugLevel::Info);
-				self.execute_if(ComparisonType::Equal, &[bytes[1], bytes[2]], frame)
-			}
-			Some(OperandCode::Ifne) => {
-				Debug(format!("Ifne"), &self.debug_level, DebugLevel::Info);
-				self.execute_if(ComparisonType::NotEqual, &[bytes[1], bytes[2]], frame)
-			}
-			Some(OperandCode::Iflt) => {
-				Debug(format!("Iflt"), &self.debug_level, DebugLevel::Info);
-				self.execute_if(ComparisonType::LessThan, &[bytes[1], bytes[2]], frame)
+				self.execute_type_check(bytes, frame, opcode == OperandCode::CheckCast as u8);
+				OpcodeResult::Incr(3)
 			}
-			Some(OperandCode::Ifge) => {
-				Debug(format!("Ifge"), &self.debug_level, DebugLevel::Info);
-				self.execute_if(
-					ComparisonType::GreaterThanOrEqual,
-					&[bytes[1], bytes[2]],
-					frame,
-				)
+			Some(OperandCode::MonitorEnter) | Some(OperandCode::MonitorExit) => {
+				Debug(format!("monitor"), &self.debug_level, DebugLevel::Info);
+				/*
+				 * There is only one thread, so there is never contention
+				 * for a monitor.
+				 */
+				match frame.operand_stack.pop() {
+					Some(JvmValue::Reference(_, JvmReferenceTargetType::Null, _)) => {
+						self.throw_new("java/lang/NullPointerException", None);
+					}
+					Some(_) => (),
+					None => {
+						FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
+							"monitorenter/monitorexit"
+						)))
+						.call();
+					}
+				}
+				OpcodeResult::Incr(1)
 			}
-			Some(OperandCode::Ifgt) => {
-				Debug(format!("Ifgt"), &self.debug_level, DebugLevel::Info);
-				self.execute_if(ComparisonType::GreaterThan, &[bytes[1], bytes[2]], frame)
+			Some(OperandCode::Wide) => {
+				Debug(format!("wide"), &self.debug_level, DebugLevel::Info);
+				let index = read_u16(bytes, 2) as usize;
+				match OperandCode::from_u8(bytes[1]) {
+					Some(OperandCode::Iinc) => {
+						self.execute_iinc(index, read_u16(bytes, 4) as i16 as i32, frame);
+						OpcodeResult::Incr(6)
+					}
+					Some(OperandCode::Iload)
+					| Some(OperandCode::Lload)
+					| Some(OperandCode::Fload)
+					| Some(OperandCode::Dload)
+					| Some(OperandCode::Aload) => {
+						self.execute_load(index, frame);
+						OpcodeResult::Incr(4)
+					}
+					Some(OperandCode::Istore)
+					| Some(OperandCode::Lstore)
+					| Some(OperandCode::Fstore)
+					| Some(OperandCode::Dstore)
+					| Some(OperandCode::Astore) => {
+						self.execute_store(index, frame);
+						OpcodeResult::Incr(4)
+					}
+					_ => {
+						FatalError::new(FatalErrorType::NotImplemented(format!(
+							"wide 0x{:x}",
+							bytes[1]
+						)))
+						.call();
+						OpcodeResult::Incr(0)
+					}
+				}
 			}
-			Some(OperandCode::Ifle) => {
-				Debug(format!("Ifle"), &self.debug_level, DebugLevel::Info);
-				self.execute_if(
-					ComparisonType::LessThanOrEqual,
-					&[bytes[1], bytes[2]],
-					frame,
-				)
+			Some(OperandCode::Ifnull) | Some(OperandCode::Ifnonnull) => {
+				Debug(
+					format!("ifnull/ifnonnull"),
+					&self.debug_level,
+					DebugLevel::Info,
+				);
+				match frame.operand_stack.pop() {
+					Some(value) => {
+						if value.is_null() == (opcode == OperandCode::Ifnull as u8) {
+							branch(read_u16(bytes, 1) as i16 as i32)
+						} else {
+							OpcodeResult::Incr(3)
+						}
+					}
+					None => {
+						FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
+							"ifnull/ifnonnull"
+						)))
+						.call();
+						OpcodeResult::Incr(0)
+					}
+				}
 			}
 			_ => {
 				FatalError::new(FatalErrorType::NotImplemented(format!("0x{:x}", opcode))).call();
@@ -782,50 +3083,6 @@ impl JvmThread {
 		}
 	}
 
-	fn handle_invoke_result(
-		&self,
-		result: Option<OpcodeResult>,
-		frame: &mut Frame,
-		step: usize,
-	) -> usize {
-		if let Some(OpcodeResult::Value(tv)) = result {
-			/*
-			 * Push the result of the invocation onto
-			 * the operand stack. Do not push anything
-			 * on to the stack if the return is Void.
-			 */
-			match tv.clone() {
-				/*
-				 * The JvmTypeValue::Primitive with tipe == JvmPrimitiveType::Void
-				 * is a sentinel value that indicates a return from a Void function.
-				 */
-				JvmValue::Primitive(t, _, _, _) => {
-					if t == JvmPrimitiveType::Void {
-						Debug(
-							format!("Not pushing a void onto the caller's stack."),
-							&self.debug_level,
-							DebugLevel::Info,
-						);
-					} else {
-						/*
-						 * Any JvmTypeValue::Primitive other than a JvmPrimitive::Void
-						 * gets pushed on to the stack.
-						 */
-						frame.operand_stack.push(tv);
-					}
-				}
-				/*
-				 * A non-JvmTypeValue::Primitive value always gets pushed
-				 * on to the stack.
-				 */
-				_ => {
-					frame.operand_stack.push(tv);
-				}
-			}
-			return step;
-		}
-		return 0;
-	}
 	fn execute_if(
 		&mut self,
 		comparison: ComparisonType,
@@ -838,212 +3095,185 @@ impl JvmThread {
 			&self.debug_level,
 			DebugLevel::Info,
 		);
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Integer, _, value, _)) =
-			frame.operand_stack.pop()
-		{
-			let ivalue = i32::from_le_bytes(value.to_le_bytes());
-			let mut take_branch = false;
-			match comparison {
-				ComparisonType::Equal => {
-					if ivalue == 0 {
-						take_branch = true;
-					}
-				}
-				ComparisonType::NotEqual => {
-					if ivalue != 0 {
-						take_branch = true;
-					}
-				}
-				ComparisonType::LessThan => {
-					if ivalue < 0 {
-						take_branch = true;
-					}
-				}
-				ComparisonType::LessThanOrEqual => {
-					if ivalue <= 0 {
-						take_branch = true;
-					}
-				}
-				ComparisonType::GreaterThan => {
-					if ivalue > 0 {
-						take_branch = true;
-					}
-				}
-				ComparisonType::GreaterThanOrEqual => {
-					if ivalue >= 0 {
-						take_branch = true;
-					}
-				}
+		if let Some(ivalue) = frame.operand_stack.pop().and_then(|value| value.as_int()) {
+			let take_branch = match comparison {
+				ComparisonType::Equal => ivalue == 0,
+				ComparisonType::NotEqual => ivalue != 0,
+				ComparisonType::LessThan => ivalue < 0,
+				ComparisonType::LessThanOrEqual => ivalue <= 0,
+				ComparisonType::GreaterThan => ivalue > 0,
+				ComparisonType::GreaterThanOrEqual => ivalue >= 0,
 			};
 			if take_branch {
-				if branch_offset < 0 {
-					OpcodeResult::Decr(branch_offset.abs() as usize)
-				} else {
-					OpcodeResult::Incr(branch_offset.abs() as usize)
-				}
+				branch(branch_offset as i32)
 			} else {
 				// Not taking the branch, so we just start at the next instruction after this one!
 				OpcodeResult::Incr(3)
 			}
 		} else {
-			FatalError::new(FatalErrorType::WrongType(format!("1"), format!("2"))).call();
-			OpcodeResult::Value(JvmValue::Primitive(JvmPrimitiveType::Void, 0, 0, 0))
+			FatalError::new(FatalErrorType::WrongType(
+				"if<cond>".to_string(),
+				"Integer".to_string(),
+			))
+			.call();
+			OpcodeResult::Incr(0)
 		}
 	}
 
-	fn execute_fsub(&mut self, frame: &mut Frame) {
-		Debug(
-			format!("fsub frame (pre): {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, op2, _)) =
-			frame.operand_stack.pop()
-		{
-			if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, op1, _)) =
-				frame.operand_stack.pop()
-			{
-				let fop1 = f32::from_le_bytes(op1.to_le_bytes());
-				let fop2 = f32::from_le_bytes(op2.to_le_bytes());
-				let res = fop1 - fop2;
-				let res_value = u32::from_le_bytes(res.to_le_bytes());
-
-				frame.operand_stack.push(JvmValue::Primitive(
-					JvmPrimitiveType::Float,
-					0,
-					res_value,
-					0,
-				));
+	/// Pop an operand off `frame`'s operand stack and convert it with
+	/// `convert`. A missing operand, or one of the wrong type, is fatal.
+	fn pop_operand<T>(
+		&self,
+		instruction: &str,
+		expected: &str,
+		frame: &mut Frame,
+		convert: fn(&JvmValue) -> Option<T>,
+	) -> Option<T> {
+		match frame.operand_stack.pop() {
+			Some(value) => match convert(&value) {
+				Some(value) => Some(value),
+				None => {
+					FatalError::new(FatalErrorType::WrongType(
+						instruction.to_string(),
+						expected.to_string(),
+					))
+					.call();
+					None
+				}
+			},
+			None => {
+				FatalError::new(FatalErrorType::RequiredStackValueNotFound(
+					instruction.to_string(),
+				))
+				.call();
+				None
 			}
 		}
-		Debug(
-			format!("fsub frame (post): {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
 	}
 
-	fn execute_fdiv(&mut self, frame: &mut Frame) {
-		Debug(
-			format!("fdiv frame (pre): {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, op2, _)) =
-			frame.operand_stack.pop()
-		{
-			if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, op1, _)) =
-				frame.operand_stack.pop()
-			{
-				let fop1 = f32::from_le_bytes(op1.to_le_bytes());
-				let fop2 = f32::from_le_bytes(op2.to_le_bytes());
-				let res = fop1 / fop2;
-				let res_value = u32::from_le_bytes(res.to_le_bytes());
+	/// Execute an instruction that pops two ints and pushes `operation`
+	/// of them. An operation that returns None divides by zero.
+	fn execute_iop(
+		&mut self,
+		instruction: &str,
+		frame: &mut Frame,
+		operation: fn(i32, i32) -> Option<i32>,
+	) {
+		let value2 = self.pop_operand(instruction, "Integer", frame, JvmValue::as_int);
+		let value1 = self.pop_operand(instruction, "Integer", frame, JvmValue::as_int);
+		if let (Some(value1), Some(value2)) = (value1, value2) {
+			match operation(value1, value2) {
+				Some(result) => frame.operand_stack.push(JvmValue::int(result)),
+				None => self.throw_new("java/lang/ArithmeticException", Some("/ by zero")),
+			}
+		}
+	}
 
-				frame.operand_stack.push(JvmValue::Primitive(
-					JvmPrimitiveType::Float,
-					0,
-					res_value,
-					0,
-				));
+	/// Execute an instruction that pops two longs and pushes `operation`
+	/// of them. See execute_iop.
+	fn execute_lop(
+		&mut self,
+		instruction: &str,
+		frame: &mut Frame,
+		operation: fn(i64, i64) -> Option<i64>,
+	) {
+		let value2 = self.pop_operand(instruction, "Long", frame, JvmValue::as_long);
+		let value1 = self.pop_operand(instruction, "Long", frame, JvmValue::as_long);
+		if let (Some(value1), Some(value2)) = (value1, value2) {
+			match operation(value1, value2) {
+				Some(result) => frame.operand_stack.push(JvmValue::long(result)),
+				None => self.throw_new("java/lang/ArithmeticException", Some("/ by zero")),
 			}
 		}
-		Debug(
-			format!("fdiv frame (post): {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
 	}
 
-	fn execute_fmul(&mut self, frame: &mut Frame) {
-		Debug(
-			format!("fmul frame (pre): {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, op2, _)) =
-			frame.operand_stack.pop()
-		{
-			if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, op1, _)) =
-				frame.operand_stack.pop()
-			{
-				let fop1 = f32::from_le_bytes(op1.to_le_bytes());
-				let fop2 = f32::from_le_bytes(op2.to_le_bytes());
-				let res = fop1 * fop2;
-				let res_value = u32::from_le_bytes(res.to_le_bytes());
+	/// Execute an instruction that pops a long and an int (the distance)
+	/// and pushes the long shifted by `operation`. Only the low six bits
+	/// of the distance count.
+	fn execute_lshift(
+		&mut self,
+		instruction: &str,
+		frame: &mut Frame,
+		operation: fn(i64, i32) -> i64,
+	) {
+		let value2 = self.pop_operand(instruction, "Integer", frame, JvmValue::as_int);
+		let value1 = self.pop_operand(instruction, "Long", frame, JvmValue::as_long);
+		if let (Some(value1), Some(value2)) = (value1, value2) {
+			frame
+				.operand_stack
+				.push(JvmValue::long(operation(value1, value2 & 0x3f)));
+		}
+	}
 
-				frame.operand_stack.push(JvmValue::Primitive(
-					JvmPrimitiveType::Float,
-					0,
-					res_value,
-					0,
-				));
-			}
+	/// Execute an instruction that pops two floats and pushes `operation`
+	/// of them.
+	fn execute_fop(
+		&mut self,
+		instruction: &str,
+		frame: &mut Frame,
+		operation: fn(f32, f32) -> f32,
+	) {
+		let value2 = self.pop_operand(instruction, "Float", frame, JvmValue::as_float);
+		let value1 = self.pop_operand(instruction, "Float", frame, JvmValue::as_float);
+		if let (Some(value1), Some(value2)) = (value1, value2) {
+			frame
+				.operand_stack
+				.push(JvmValue::float(operation(value1, value2)));
 		}
-		Debug(
-			format!("fmul frame (post): {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
 	}
 
-	fn execute_fadd(&mut self, frame: &mut Frame) {
-		Debug(
-			format!("fadd frame (pre): {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, op2, _)) =
-			frame.operand_stack.pop()
-		{
-			if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, op1, _)) =
-				frame.operand_stack.pop()
-			{
-				let fop1 = f32::from_le_bytes(op1.to_le_bytes());
-				let fop2 = f32::from_le_bytes(op2.to_le_bytes());
-				let res = fop1 + fop2;
-				let res_value = u32::from_le_bytes(res.to_le_bytes());
+	/// Execute an instruction that pops two doubles and pushes `operation`
+	/// of them.
+	fn execute_dop(
+		&mut self,
+		instruction: &str,
+		frame: &mut Frame,
+		operation: fn(f64, f64) -> f64,
+	) {
+		let value2 = self.pop_operand(instruction, "Double", frame, JvmValue::as_double);
+		let value1 = self.pop_operand(instruction, "Double", frame, JvmValue::as_double);
+		if let (Some(value1), Some(value2)) = (value1, value2) {
+			frame
+				.operand_stack
+				.push(JvmValue::double(operation(value1, value2)));
+		}
+	}
 
-				frame.operand_stack.push(JvmValue::Primitive(
-					JvmPrimitiveType::Float,
-					0,
-					res_value,
-					0,
-				));
-			}
+	/// Execute an instruction (e.g., i2l or ineg) that pops a value and
+	/// pushes what `conversion` makes of it.
+	fn execute_conversion(
+		&mut self,
+		instruction: &str,
+		frame: &mut Frame,
+		conversion: fn(&JvmValue) -> Option<JvmValue>,
+	) {
+		if let Some(result) = self.pop_operand(instruction, "numeric value", frame, conversion) {
+			frame.operand_stack.push(result);
 		}
-		Debug(
-			format!("fadd frame (post): {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
 	}
 
-	fn execute_fcmp(&mut self, frame: &mut Frame) {
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, val2, _)) =
-			frame.operand_stack.pop()
-		{
-			if let Some(JvmValue::Primitive(JvmPrimitiveType::Float, _, val1, _)) =
-				frame.operand_stack.pop()
-			{
-				let fval1 = f32::from_le_bytes(val1.to_le_bytes());
-				let fval2 = f32::from_le_bytes(val2.to_le_bytes());
-				let mut res = 0i32;
-				if fval1 > fval2 {
-					res = 1i32;
-				} else if fval1 == fval2 {
-					res = 0i32;
-				} else if fval1 < fval2 {
-					res = -1i32;
-				}
+	fn execute_lcmp(&mut self, frame: &mut Frame) {
+		let value2 = self.pop_operand("lcmp", "Long", frame, JvmValue::as_long);
+		let value1 = self.pop_operand("lcmp", "Long", frame, JvmValue::as_long);
+		if let (Some(value1), Some(value2)) = (value1, value2) {
+			frame
+				.operand_stack
+				.push(JvmValue::int(value1.cmp(&value2) as i32));
+		}
+	}
 
-				frame.operand_stack.push(JvmValue::Primitive(
-					JvmPrimitiveType::Integer,
-					0,
-					u32::from_le_bytes(res.to_le_bytes()),
-					0,
-				));
-			}
+	/// fcmpl and fcmpg: they differ only when a value is NaN; then,
+	/// fcmpg pushes 1 and fcmpl pushes -1.
+	fn execute_fcmp(&mut self, frame: &mut Frame, greater_on_nan: bool) {
+		let value2 = self.pop_operand("fcmp<op>", "Float", frame, JvmValue::as_float);
+		let value1 = self.pop_operand("fcmp<op>", "Float", frame, JvmValue::as_float);
+		if let (Some(value1), Some(value2)) = (value1, value2) {
+			let result = match value1.partial_cmp(&value2) {
+				Some(ordering) => ordering as i32,
+				None if greater_on_nan => 1,
+				None => -1,
+			};
+			frame.operand_stack.push(JvmValue::int(result));
 		}
 		Debug(
 			format!("fcmp frame (post): {}", frame),
@@ -1052,579 +3282,545 @@ impl JvmThread {
 		);
 	}
 
-	fn execute_iadd(&mut self, frame: &mut Frame) {
-		Debug(
-			format!("iadd frame: {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Integer, _, _op1, _)) =
-			frame.operand_stack.pop()
-		{
-			let op1 = _op1 as i32;
-			if let Some(JvmValue::Primitive(JvmPrimitiveType::Integer, _, _op2, _)) =
-				frame.operand_stack.pop()
-			{
-				let op2 = _op2 as i32;
-				frame.operand_stack.push(JvmValue::Primitive(
-					JvmPrimitiveType::Integer,
-					0,
-					(op1 + op2) as u32,
-					0,
-				));
+	/// dcmpl and dcmpg. See execute_fcmp.
+	fn execute_dcmp(&mut self, frame: &mut Frame, greater_on_nan: bool) {
+		let value2 = self.pop_operand("dcmp<op>", "Double", frame, JvmValue::as_double);
+		let value1 = self.pop_operand("dcmp<op>", "Double", frame, JvmValue::as_double);
+		if let (Some(value1), Some(value2)) = (value1, value2) {
+			let result = match value1.partial_cmp(&value2) {
+				Some(ordering) => ordering as i32,
+				None if greater_on_nan => 1,
+				None => -1,
+			};
+			frame.operand_stack.push(JvmValue::int(result));
+		}
+	}
+
+	fn execute_icmp(
+		&mut self,
+		frame: &mut Frame,
+		bytes: &[u8],
+		operation: OperandCode,
+	) -> OpcodeResult {
+		let value2 = self.pop_operand("if_icmp<cond>", "Integer", frame, JvmValue::as_int);
+		let value1 = self.pop_operand("if_icmp<cond>", "Integer", frame, JvmValue::as_int);
+		let (value1, value2) = match (value1, value2) {
+			(Some(value1), Some(value2)) => (value1, value2),
+			_ => return OpcodeResult::Incr(0),
+		};
+		let take_branch = match operation {
+			OperandCode::If_icmpeq => value1 == value2,
+			OperandCode::If_icmpne => value1 != value2,
+			OperandCode::If_icmplt => value1 < value2,
+			OperandCode::If_icmpge => value1 >= value2,
+			OperandCode::If_icmpgt => value1 > value2,
+			OperandCode::If_icmple => value1 <= value2,
+			_ => false,
+		};
+		if take_branch {
+			branch(read_u16(bytes, 1) as i16 as i32)
+		} else {
+			OpcodeResult::Incr(3)
+		}
+	}
+
+	/// The number of bytes of padding after a tableswitch or lookupswitch
+	/// at `pc`: its operands start at a multiple of four.
+	fn switch_padding(pc: usize) -> usize {
+		(4 - (pc + 1) % 4) % 4
+	}
+
+	fn execute_tableswitch(&mut self, bytes: &[u8], frame: &mut Frame) -> OpcodeResult {
+		let operands = 1 + JvmThread::switch_padding(frame.pc);
+		let default = read_i32(bytes, operands);
+		let low = read_i32(bytes, operands + 4);
+		let high = read_i32(bytes, operands + 8);
+		let index = match self.pop_operand("tableswitch", "Integer", frame, JvmValue::as_int) {
+			Some(index) => index,
+			None => return OpcodeResult::Incr(0),
+		};
+		if index < low || index > high {
+			branch(default)
+		} else {
+			branch(read_i32(
+				bytes,
+				operands + 12 + 4 * (index as i64 - low as i64) as usize,
+			))
+		}
+	}
+
+	fn execute_lookupswitch(&mut self, bytes: &[u8], frame: &mut Frame) -> OpcodeResult {
+		let operands = 1 + JvmThread::switch_padding(frame.pc);
+		let default = read_i32(bytes, operands);
+		let pairs = read_i32(bytes, operands + 4).max(0) as usize;
+		let key = match self.pop_operand("lookupswitch", "Integer", frame, JvmValue::as_int) {
+			Some(key) => key,
+			None => return OpcodeResult::Incr(0),
+		};
+		for pair in 0..pairs {
+			let offset = operands + 8 + 8 * pair;
+			if read_i32(bytes, offset) == key {
+				return branch(read_i32(bytes, offset + 4));
+			}
+		}
+		branch(default)
+	}
+
+	/// The stack instructions (pop, dup, swap and their variants):
+	/// take the values that make up the top `top_words` words of the
+	/// operand stack and those that make up the next `under_words` words
+	/// (a long or a double is two words; anything else, one). Then,
+	/// push (a copy of) the top values under the others, if `duplicate`,
+	/// and put them back in the opposite order (without `under_words`,
+	/// drop them instead).
+	fn execute_stack_operation(
+		&self,
+		instruction: &str,
+		frame: &mut Frame,
+		top_words: usize,
+		under_words: usize,
+		duplicate: bool,
+	) {
+		let take = |frame: &mut Frame, words: usize| -> Option<Vec<JvmValue>> {
+			let mut taken = Vec::new();
+			let mut count = 0;
+			while count < words {
+				let value = frame.operand_stack.pop()?;
+				count += if value.is_category_2() { 2 } else { 1 };
+				taken.insert(0, value);
+			}
+			if count == words {
+				Some(taken)
 			} else {
-				FatalError::new(FatalErrorType::WrongType(
-					"Iadd".to_string(),
-					"Integer".to_string(),
+				None
+			}
+		};
+		let top = take(frame, top_words);
+		let under = take(frame, under_words);
+		let (top, under) = match (top, under) {
+			(Some(top), Some(under)) => (top, under),
+			_ => {
+				FatalError::new(FatalErrorType::RequiredStackValueNotFound(
+					instruction.to_string(),
 				))
 				.call();
+				return;
 			}
-		} else {
-			FatalError::new(FatalErrorType::WrongType(
-				"Iadd".to_string(),
-				"Integer".to_string(),
+		};
+		if duplicate {
+			frame.operand_stack.extend(top.iter().cloned());
+			frame.operand_stack.extend(under);
+			frame.operand_stack.extend(top);
+		} else if under_words > 0 {
+			frame.operand_stack.extend(top);
+			frame.operand_stack.extend(under);
+		}
+	}
+
+	/// Push the local variable at `index`.
+	fn execute_load(&self, index: usize, frame: &mut Frame) {
+		match frame.locals.get(index) {
+			Some(value) => frame.operand_stack.push(value.clone()),
+			None => {
+				FatalError::new(FatalErrorType::NotEnough(
+					format!("load"),
+					index,
+					"locals".to_string(),
+				))
+				.call();
+			}
+		}
+	}
+
+	/// Pop a value into the local variable at `index`.
+	fn execute_store(&self, index: usize, frame: &mut Frame) {
+		if index >= frame.locals.len() {
+			FatalError::new(FatalErrorType::NotEnough(
+				format!("store"),
+				index,
+				format!("locals"),
 			))
 			.call();
+			return;
+		}
+		match frame.operand_stack.pop() {
+			Some(value) => frame.locals[index] = value,
+			None => {
+				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!("store")))
+					.call();
+			}
 		}
 	}
-	fn execute_imul(&mut self, frame: &mut Frame) {
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Integer, _, op1, _)) =
-			frame.operand_stack.pop()
-		{
-			if let Some(JvmValue::Primitive(JvmPrimitiveType::Integer, _, op2, _)) =
-				frame.operand_stack.pop()
-			{
-				frame.operand_stack.push(JvmValue::Primitive(
-					JvmPrimitiveType::Integer,
-					0,
-					op1 * op2,
-					0,
-				));
+
+	fn execute_iinc(&self, index: usize, increment: i32, frame: &mut Frame) {
+		match frame.locals.get(index).and_then(|value| value.as_int()) {
+			Some(value) => frame.locals[index] = JvmValue::int(value.wrapping_add(increment)),
+			None => {
+				FatalError::new(FatalErrorType::WrongType(
+					format!("iinc"),
+					format!("Integer local"),
+				))
+				.call();
 			}
 		}
 	}
 
-	fn execute_icmp(
+	/// Pop the index and the array reference of an array instruction.
+	/// A null reference throws NullPointerException and an index out of
+	/// the array's bounds throws ArrayIndexOutOfBoundsException.
+	///
+	/// # Return value:
+	/// The array's type, the array and the index.
+	fn pop_array_element(
 		&mut self,
+		instruction: &str,
 		frame: &mut Frame,
-		bytes: &[u8],
-		operation: OperandCode,
-	) -> OpcodeResult {
-		let mut pc_incr: usize = 3;
-		let success_incr: usize = (((bytes[1] as u16) << 8) | (bytes[2] as u16)) as usize;
-		let fail_incr: usize = 3 as usize;
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Integer, _, _value2, _)) =
-			frame.operand_stack.pop()
-		{
-			let value2 = _value2 as i32;
-			if let Some(JvmValue::Primitive(JvmPrimitiveType::Integer, _, _value1, _)) =
-				frame.operand_stack.pop()
-			{
-				let value1 = _value1 as i32;
-				pc_incr = match operation {
-					OperandCode::If_icmpeq => {
-						Debug(format!("If_icmpeq"), &self.debug_level, DebugLevel::Info);
-						if value1 == value2 {
-							success_incr
-						} else {
-							fail_incr
-						}
-					}
-					OperandCode::If_icmpne => {
-						Debug(format!("If_icmpne"), &self.debug_level, DebugLevel::Info);
-						if value1 != value2 {
-							success_incr
-						} else {
-							fail_incr
-						}
-					}
-					OperandCode::If_icmplt => {
-						Debug(format!("If_icmplt"), &self.debug_level, DebugLevel::Info);
-						if value1 < value2 {
-							success_incr
-						} else {
-							fail_incr
-						}
-					}
-					OperandCode::If_icmpge => {
-						Debug(format!("If_icmpge"), &self.debug_level, DebugLevel::Info);
-						if value1 >= value2 {
-							success_incr
-						} else {
-							fail_incr
-						}
-					}
-					OperandCode::If_icmpgt => {
-						Debug(format!("If_icmpgt"), &self.debug_level, DebugLevel::Info);
-						if value1 > value2 {
-							success_incr
-						} else {
-							fail_incr
-						}
-					}
-					OperandCode::If_icmple => {
-						Debug(format!("If_icmple"), &self.debug_level, DebugLevel::Info);
-						if value1 <= value2 {
-							success_incr
-						} else {
-							fail_incr
-						}
-					}
-					_ => fail_incr,
+	) -> Option<(Rc<JvmType>, Arc<Mutex<JvmArray>>, usize)> {
+		let index = self.pop_operand(instruction, "Integer", frame, JvmValue::as_int)?;
+		match frame.operand_stack.pop() {
+			Some(JvmValue::Reference(
+				JvmReferenceType::Array(component, _),
+				JvmReferenceTargetType::Array(array),
+				_,
+			)) => {
+				let in_bounds = match array.lock() {
+					Ok(array) => index >= 0 && array.inbounds(index as usize),
+					_ => false,
+				};
+				if !in_bounds {
+					self.throw_new(
+						"java/lang/ArrayIndexOutOfBoundsException",
+						Some(&index.to_string()),
+					);
+					return None;
 				}
-			} else {
+				Some((component, array, index as usize))
+			}
+			Some(JvmValue::Reference(_, JvmReferenceTargetType::Null, _)) => {
+				self.throw_new("java/lang/NullPointerException", None);
+				None
+			}
+			_ => {
 				FatalError::new(FatalErrorType::WrongType(
-					"If_icmpeq".to_string(),
-					"Integer".to_string(),
+					instruction.to_string(),
+					format!("Reference to an array"),
 				))
 				.call();
+				None
 			}
-		} else {
-			FatalError::new(FatalErrorType::WrongType(
-				"If_icmpeq".to_string(),
-				"Integer".to_string(),
-			))
-			.call();
 		}
-		OpcodeResult::Incr(pc_incr)
 	}
 
-	fn execute_caload(&mut self, frame: &mut Frame) {
-		let index = frame.operand_stack.pop();
-		let mut arrayref = frame.operand_stack.pop();
+	/// The xaload instructions: push an element of an array. Booleans,
+	/// bytes, chars and shorts are pushed as ints.
+	fn execute_xaload(&mut self, frame: &mut Frame) {
+		let (component, array, index) = match self.pop_array_element("xaload", frame) {
+			Some(element) => element,
+			None => return,
+		};
+		let element = match array.lock() {
+			Ok(mut array) => array.get_at(index).clone(),
+			_ => None,
+		};
+		let element = element.unwrap_or_else(|| create_default_value(&component, 0));
+		let element = match element.as_int() {
+			Some(value) if !element.is_category_2() => JvmValue::int(value),
+			_ => element,
+		};
+		frame.operand_stack.push(element);
+	}
 
-		if let Some(index) = index {
-			if let Some(arrayref) = &mut arrayref {
-				// The array reference must:
-				// 1. be a non-null reference
-				// 2. point to an array of characters.
-				// Check (1) first ...
-				match &arrayref {
-					JvmValue::Reference(
-						JvmReferenceType::Array(arrayreftype, _),
-						JvmReferenceTargetType::Array(array),
-						_,
-					) => {
-						if let JvmType::Primitive(JvmPrimitiveType::Char) = **arrayreftype {
-							// The index must be a Primitive Integer.
-							if let JvmValue::Primitive(JvmPrimitiveType::Integer, _, index, _) =
-								index
-							{
-								// We need a lock even though we are just reading.
-								if let Ok(mut exclusive_array) = array.lock() {
-									// Check that the access is inbounds.
-									if exclusive_array.inbounds(index as usize) {
-										if let Some(value) = exclusive_array.get_at(index as usize)
-										{
-											if let JvmValue::Primitive(
-												JvmPrimitiveType::Char,
-												char,
-												_,
-												char_access,
-											) = *value
-											{
-												let integer = JvmValue::Primitive(
-													JvmPrimitiveType::Integer,
-													char as u64,
-													0,
-													char_access,
-												);
-												frame.operand_stack.push(integer);
-											} else {
-												// The value that we retrieved from the array *should* be a character,
-												// but it is not.
-												FatalError::new(FatalErrorType::WrongType(
-													format!("caload loaded value from array"),
-													format!("Primitive Character"),
-												))
-												.call();
-											}
-										} else {
-											// The value that we retrieved from the array *should* be a character,
-											// but it was empty.
-											FatalError::new(FatalErrorType::WrongType(
-												format!("caload loaded value from array"),
-												format!("Primitive Character"),
-											))
-											.call();
-										}
-									} else {
-										// The load is from a position outside the size of the array.
-										FatalError::new(FatalErrorType::Exception(format!(
-											"IndexOutOfBounds"
-										)))
-										.call();
-									}
-								} else {
-									FatalError::new(FatalErrorType::CouldNotLock(
-										format!("Array."),
-										format!("execute_caload"),
-									))
-									.call();
-								}
-							} else {
-								// index is wrong type.
-								FatalError::new(FatalErrorType::WrongType(
-									format!("caload index"),
-									format!("Integer"),
-								))
-								.call();
-							}
-						} else {
-							// Yes, it is a reference but not to a character array
-							FatalError::new(FatalErrorType::WrongType(
-								format!("caload array reference not character reference"),
-								format!("Integer"),
-							))
-							.call();
-						}
-					}
-					JvmValue::Reference(
-						JvmReferenceType::Null,
-						JvmReferenceTargetType::Null,
-						_,
-					) => {
-						// arrayreference is Null!
-						FatalError::new(FatalErrorType::Exception(format!("NullPointerException")))
-							.call();
-					}
-					_ => {
-						// arrayreference is of the wrong type!
-						FatalError::new(FatalErrorType::WrongType(
-							format!("caload array reference"),
-							format!("Reference to an array"),
-						))
-						.call();
-					}
-				}
-			} else {
-				// Missing a reference to an array on the stack.
+	/// The xastore instructions: store a value in an array. An int is
+	/// narrowed to the array's component type (boolean, byte, char or
+	/// short). A reference must be assignable to the component type;
+	/// otherwise, ArrayStoreException.
+	fn execute_xastore(&mut self, frame: &mut Frame) {
+		let value = match frame.operand_stack.pop() {
+			Some(value) => value,
+			None => {
 				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-					"Reference to an array."
+					"xastore"
 				)))
 				.call();
+				return;
 			}
-		} else {
-			// Missing an index on the stack.
-			FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-				"Primitive"
-			)))
-			.call();
-		}
-	}
-	fn execute_aaload(&self, frame: &mut Frame) {
-		Debug(
-			format!("Frame before aaload: {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
-
-		// Pull the three things on the top of the stack.
-		let index = frame.operand_stack.pop();
-		let arrayref = frame.operand_stack.pop();
-
-		if let Some(JvmValue::Primitive(JvmPrimitiveType::Integer, _, index, _)) = index {
-			if let Some(arrayref) = arrayref {
-				// I now have everything that I need!
-
-				// Check to make sure that the arrayref is to something of the right type.
-				if let JvmValue::Reference(
-					JvmReferenceType::Array(_ /* check me*/, _),
-					JvmReferenceTargetType::Array(array),
-					_,
-				) = arrayref
+		};
+		let (component, array, index) = match self.pop_array_element("xastore", frame) {
+			Some(element) => element,
+			None => return,
+		};
+		let value = match &*component {
+			JvmType::Reference(_) if !value.is_null() => {
+				let component_name = type_name_of_descriptor(&component.descriptor());
+				let value_type_name = value.type_name().unwrap_or_default();
+				if component_name != "java/lang/Object"
+					&& !self.is_value_assignable(&value, &component_name, None)
 				{
-					if let Ok(mut array) = array.lock() {
-						if let Some(value_from_array) = array.get_at(index as usize) {
-							frame.operand_stack.push(value_from_array.clone());
-						}
-					} else {
-						// The load is from a position outside the size of the array.
-						FatalError::new(FatalErrorType::Exception(format!("IndexOutOfBounds")))
-							.call();
-					}
-				} else {
-					FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-						"Primitive Integer index into an array."
-					)))
-					.call();
+					self.throw_new(
+						"java/lang/ArrayStoreException",
+						Some(&value_type_name.replace('/', ".")),
+					);
+					return;
 				}
-			} else {
-				// Missing an index on the stack.
-				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-					"Index into an array."
-				)))
-				.call();
+				value
 			}
+			component => array_element(component, value),
+		};
+		if let Ok(mut array) = array.lock() {
+			array.set_at(index, value);
+		};
+	}
+
+	/// Pop the number of elements of a new array. A negative number
+	/// throws NegativeArraySizeException.
+	fn pop_array_count(&mut self, instruction: &str, frame: &mut Frame) -> Option<usize> {
+		let count = self.pop_operand(instruction, "Integer", frame, JvmValue::as_int)?;
+		if count < 0 {
+			self.throw_new(
+				"java/lang/NegativeArraySizeException",
+				Some(&count.to_string()),
+			);
+			return None;
 		}
+		Some(count as usize)
 	}
 
-	fn execute_aastore(&self, frame: &mut Frame) {
-		Debug(
-			format!("Frame before aastore: {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
+	/// Create an array of `component`s whose elements are `elements`.
+	pub fn create_array(
+		&mut self,
+		component: JvmType,
+		elements: Vec<JvmValue>,
+	) -> Option<JvmValue> {
+		let length = elements.len();
+		let mut array = JvmArray::new(length);
+		for (index, element) in elements.into_iter().enumerate() {
+			array.set_at(index, element);
+		}
+		Some(JvmValue::Reference(
+			JvmReferenceType::Array(Rc::new(component), length as u32),
+			JvmReferenceTargetType::Array(self.allocate_array(array)?),
+			0,
+		))
+	}
 
-		// Pull the three things on the top of the stack.
-		let value = frame.operand_stack.pop();
-		let index = frame.operand_stack.pop();
-		let arrayref = frame.operand_stack.pop();
+	/// Create an array of `count` `component`s, each with its default
+	/// value (JVMS 2.3 and 2.4).
+	pub fn create_default_array(&mut self, component: JvmType, count: usize) -> Option<JvmValue> {
+		let element = create_default_value(&component, 0);
+		self.create_array(component, vec![element; count])
+	}
 
-		if let Some(value) = value {
-			if let Some(JvmValue::Primitive(JvmPrimitiveType::Integer, _, index, _)) = index {
-				if let Some(arrayref) = arrayref {
-					// I now have everything that I need!
-
-					// Check to make sure that the arrayref is to something of the right type.
-					if let JvmValue::Reference(
-						JvmReferenceType::Array(_ /* check me*/, _),
-						JvmReferenceTargetType::Array(array),
-						_,
-					) = arrayref
-					{
-						if let Ok(mut array) = array.lock() {
-							array.set_at(index as usize, value);
-						} else {
-							FatalError::new(FatalErrorType::CouldNotLock(
-								format!("Array."),
-								format!("execute_aastore"),
-							))
-							.call();
-						}
-					}
-				} else {
-					FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-						"Primitive Integer index into an array."
-					)))
-					.call();
-				}
-			} else {
-				// Missing an index on the stack.
-				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-					"Index into an array."
-				)))
+	fn execute_multianewarray(&mut self, bytes: &[u8], frame: &mut Frame) {
+		let class = frame.class().unwrap();
+		let descriptor = match class.get_class_name_at(read_u16(bytes, 1)) {
+			Some(descriptor) => descriptor,
+			None => {
+				FatalError::new(FatalErrorType::WrongType(
+					format!("multianewarray"),
+					format!("symbolic reference to an array class."),
+				))
 				.call();
+				return;
 			}
-		} else {
-			// Missing a value on the stack.
-			FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-				"Primitive"
-			)))
-			.call();
+		};
+		let dimensions = bytes[3] as usize;
+		let mut counts = Vec::with_capacity(dimensions);
+		for _ in 0..dimensions {
+			match self.pop_operand("multianewarray", "Integer", frame, JvmValue::as_int) {
+				Some(count) => counts.insert(0, count),
+				None => return,
+			}
+		}
+		if let Some(count) = counts.iter().find(|count| **count < 0) {
+			self.throw_new(
+				"java/lang/NegativeArraySizeException",
+				Some(&count.to_string()),
+			);
+			return;
+		}
+		if let Some(array) = self.create_multidimensional_array(&descriptor, &counts) {
+			frame.operand_stack.push(array);
 		}
 	}
 
-	fn execute_castore(&self, frame: &mut Frame) {
-		/*
-		 * From the Java spec:
-		 * stack: arrayref, index, value →
-		 * The arrayref must be of type reference and must refer to an array whose components
-		 * are of type char. The index and the value must both be of type int. The arrayref,
-		 * index, and value are popped from the operand stack. The int value is truncated to a char
-		 * and stored as the component of the array indexed by index.
-		 * */
-
-		// Pull the three things on the top of the stack.
-		let value = frame.operand_stack.pop();
-		let index = frame.operand_stack.pop();
-		let mut arrayref = frame.operand_stack.pop();
+	/// Create an array whose descriptor is `descriptor` with `counts[0]`
+	/// elements, each of which is an array with `counts[1]` elements,
+	/// and so on.
+	fn create_multidimensional_array(
+		&mut self,
+		descriptor: &str,
+		counts: &[i32],
+	) -> Option<JvmValue> {
+		let component_descriptor = &descriptor[1..];
+		let component = JvmType::from(component_descriptor.as_bytes());
+		if counts.len() == 1 {
+			return self.create_default_array(component, counts[0] as usize);
+		}
+		let mut elements = Vec::with_capacity(counts[0] as usize);
+		for _ in 0..counts[0] {
+			elements.push(self.create_multidimensional_array(component_descriptor, &counts[1..])?);
+		}
+		self.create_array(component, elements)
+	}
 
-		if let Some(value) = value {
-			if let Some(index) = index {
-				match &mut arrayref {
-					// The array reference must:
-					// 1. be a non-null reference
-					// 2. point to an array of characters.
-					// Check (1) first ...
-					Some(JvmValue::Reference(
-						JvmReferenceType::Array(arrayreftype, _),
-						JvmReferenceTargetType::Array(array),
-						_,
-					)) => {
-						// Check (2) second ...
-						if let JvmType::Primitive(JvmPrimitiveType::Char) = **arrayreftype {
-							// The index must be a primitive Integer.
-							if let JvmValue::Primitive(JvmPrimitiveType::Integer, _, index, _) =
-								index
-							{
-								// Finally, the value must be a primitive Integer.
-								if let JvmValue::Primitive(
-									JvmPrimitiveType::Integer,
-									_,
-									value,
-									_len_access,
-								) = value
-								{
-									// All preconditions for this work are met! We can do the work now.
-
-									// Convert the integer from the stack and make it a jvm char value
-									let value_as_character = JvmValue::Primitive(
-										JvmPrimitiveType::Char,
-										value as u64,
-										0,
-										0,
-									);
-
-									// Since we are writing to the array, we need exclusive access to it.
-									if let Ok(mut exclusive_array) = array.lock() {
-										// Check to make sure that the array access is inbounds.
-										if exclusive_array.inbounds(index as usize) {
-											// Write to the array with the new value.
-											exclusive_array
-												.set_at(index as usize, value_as_character);
-										} else {
-											// array index out of bounds exception.
-											FatalError::new(FatalErrorType::Exception(format!(
-												"IndexOutOfBounds"
-											)))
-											.call();
-										}
-									} else {
-										// We could not get an exclusive lock on the array to which we are
-										// writing.
-										FatalError::new(FatalErrorType::CouldNotLock(
-											format!("Array."),
-											format!("execute_castore"),
-										))
-										.call();
-									}
-								} else {
-									// value should be a character but it is not.
-									FatalError::new(FatalErrorType::WrongType(
-										format!("castore value"),
-										format!("Character"),
-									))
-									.call();
-								}
-							} else {
-								// index should be a primitive Integer but it is not.
-								FatalError::new(FatalErrorType::WrongType(
-									format!("castore index"),
-									format!("Integer"),
-								))
-								.call();
-							}
-						} else {
-							// What should be an a reference to an array of characters
-							// is an array reference but it does not refer to array of characters.
-							FatalError::new(FatalErrorType::WrongType(
-								format!("castore arrayreference"),
-								format!("reference to an array of characters"),
-							))
-							.call();
-						}
-					}
-					Some(JvmValue::Reference(
-						JvmReferenceType::Null,
-						JvmReferenceTargetType::Null,
-						_,
-					)) => {
-						// What should be a reference to an array of characters is Null.
-						FatalError::new(FatalErrorType::Exception(format!("NullPointerException")))
-							.call();
+	/// Whether a value of the type named `source` may be assigned to a
+	/// variable of the type named `target` (the rules of checkcast and
+	/// instanceof). Types are named the way type_name_of_descriptor
+	/// names them.
+	pub fn is_assignable(&self, source: &str, target: &str) -> bool {
+		if source == target {
+			return true;
+		}
+		if let Some(source_component) = source.strip_prefix('[') {
+			return match target.strip_prefix('[') {
+				Some(target_component) => {
+					let source_component = type_name_of_descriptor(source_component);
+					let target_component = type_name_of_descriptor(target_component);
+					let primitive = |name: &str| descriptor_of_type_name(name).len() == 1;
+					if primitive(&source_component) || primitive(&target_component) {
+						source_component == target_component
+					} else {
+						self.is_assignable(&source_component, &target_component)
 					}
-					_ => {
-						// What should be an a reference to an array of characters is not even a reference.
-						FatalError::new(FatalErrorType::WrongType(
-							format!("castore arrayreference"),
-							format!("reference to an array"),
-						))
-						.call();
+				}
+				None => {
+					target == "java/lang/Object"
+						|| target == "java/lang/Cloneable"
+						|| target == "java/io/Serializable"
+				}
+			};
+		}
+		if target.starts_with('[') {
+			return false;
+		}
+		match self.methodarea.lock() {
+			Ok(mut methodarea) => {
+				let source = source.to_string();
+				if !(*methodarea).can_load_class(&source) {
+					return false;
+				}
+				(*methodarea).maybe_load_class(&source);
+				match (*methodarea).get_class_rc(&source) {
+					Some(source_class) => {
+						source_class.is_type_of(&target.to_string(), &mut *methodarea)
 					}
+					None => false,
 				}
-			} else {
-				// Missing an index on the stack.
-				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-					"Index into an array."
-				)))
-				.call();
 			}
-		} else {
-			// Missing a value on the stack.
-			FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-				"Primitive"
-			)))
-			.call();
+			_ => false,
 		}
 	}
 
-	fn execute_astore_x(&self, x: usize, frame: &mut Frame) {
-		Debug(
-			format!("Frame before astore_x: {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
-		if x < frame.locals.len() {
-			if let Some(top) = frame.operand_stack.pop() {
-				if let JvmValue::Reference(rt, reference, access) = top {
-					frame.locals[x] = JvmValue::Reference(rt, reference, access);
-				} else {
-					FatalError::new(FatalErrorType::WrongType(
-						format!("astore"),
-						format!("reference"),
-					))
-					.call();
-				}
-			} else {
-				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-					"astore"
-				)))
-				.call();
+	/// Whether the (non-null) reference `value` may be assigned to a
+	/// variable of the type named `target`. An object is checked against
+	/// its own class, whichever class loader defined it: against
+	/// `target_class`, if the type has been resolved to it, or else by
+	/// name.
+	pub fn is_value_assignable(
+		&self,
+		value: &JvmValue,
+		target: &str,
+		target_class: Option<&Rc<Class>>,
+	) -> bool {
+		let object_class = match value {
+			JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+				object.lock().ok().map(|object| object.get_class())
 			}
-		} else {
-			FatalError::new(FatalErrorType::NotEnough(
-				format!("astore"),
-				x,
-				format!("locals"),
-			))
-			.call();
+			_ => None,
+		};
+		match object_class {
+			Some(object_class) if !target.starts_with('[') => match self.methodarea.lock() {
+				Ok(mut methodarea) => match target_class {
+					Some(target_class) => {
+						object_class.is_subtype_of(target_class, &mut *methodarea)
+					}
+					None => object_class.is_type_of(&target.to_string(), &mut *methodarea),
+				},
+				_ => false,
+			},
+			_ => self.is_assignable(&value.type_name().unwrap_or_default(), target),
 		}
 	}
 
-	fn execute_aload_x(&self, x: usize, frame: &mut Frame) {
-		if x < frame.locals.len() {
-			if let JvmValue::Reference(_, _, _) = frame.locals[x] {
-				frame.operand_stack.push(frame.locals[x].clone());
-			} else {
+	/// checkcast (if `cast`) and instanceof.
+	fn execute_type_check(&mut self, bytes: &[u8], frame: &mut Frame, cast: bool) {
+		let class = frame.class().unwrap();
+		let target = match class.get_class_name_at(read_u16(bytes, 1)) {
+			Some(target) => target,
+			None => {
 				FatalError::new(FatalErrorType::WrongType(
-					format!("aload_{}", x),
-					"Reference".to_string(),
+					format!("checkcast/instanceof"),
+					format!("symbolic reference to a class."),
 				))
 				.call();
+				return;
+			}
+		};
+		let value = match frame.operand_stack.pop() {
+			Some(value) => value,
+			None => {
+				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
+					"checkcast/instanceof"
+				)))
+				.call();
+				return;
+			}
+		};
+		if value.is_null() {
+			if cast {
+				frame.operand_stack.push(value);
+			} else {
+				frame.operand_stack.push(JvmValue::int(0));
+			}
+			return;
+		}
+		let target_class = if target.starts_with('[') {
+			None
+		} else {
+			match self.resolve_class_from(&class, &target) {
+				Some(target_class) => Some(target_class),
+				None => return,
 			}
+		};
+		let source = value.type_name().unwrap_or_default();
+		let assignable = self.is_value_assignable(&value, &target, target_class.as_ref());
+		if !cast {
+			frame.operand_stack.push(JvmValue::int(assignable as i32));
+		} else if assignable {
+			frame.operand_stack.push(value);
 		} else {
-			FatalError::new(FatalErrorType::NotEnough(
-				format!("aload_{}", x),
-				x,
-				"locals".to_string(),
-			))
-			.call();
+			self.throw_new(
+				"java/lang/ClassCastException",
+				Some(&format!(
+					"{} cannot be cast to {}",
+					source.replace('/', "."),
+					target.replace('/', ".")
+				)),
+			);
 		}
 	}
 
-	fn execute_ldc(&mut self, bytes: &[u8], frame: &mut Frame) {
+	fn execute_ldc(&mut self, index: usize, frame: &mut Frame) {
 		let class = frame.class().unwrap();
 		let constant_pool = class.get_constant_pool_ref();
-		let instantiated_class_index = (bytes[1] as u16) as usize;
 
-		match constant_pool.get_constant_ref(instantiated_class_index) {
+		match constant_pool.get_constant_ref(index) {
 			Constant::String(_, string_index) => {
-				if let Some(string_object) = create_static_string_object(
-					format!("Testing"),
-					self,
-					Arc::clone(&self.methodarea),
-				) {
-					frame.operand_stack.push(JvmValue::Reference(
-						JvmReferenceType::Class(format!("java/lang/String")),
-						JvmReferenceTargetType::Object(Arc::new(Mutex::new(string_object))),
-						0,
-					));
+				let string_value = if let Constant::Utf8(_, _, _, value) =
+					constant_pool.get_constant_ref(*string_index as usize)
+				{
+					value.clone()
 				} else {
-					FatalError::new(FatalErrorType::Todo(format!(
-						"Handle this abnormal condition."
-					)))
+					FatalError::new(FatalErrorType::InvalidConstantReference(
+						class.get_class_name().unwrap(),
+						"Utf8".to_string(),
+						*string_index,
+					))
 					.call();
+					String::new()
+				};
+				if let Some(string) = self.intern_string(&string_value) {
+					frame.operand_stack.push(string);
 				}
 			}
 			Constant::Integer(_, value) => {
@@ -1635,9 +3831,39 @@ impl JvmThread {
 				let constant_float = JvmValue::Primitive(JvmPrimitiveType::Float, 0, *value, 0);
 				frame.operand_stack.push(constant_float);
 			}
+			Constant::Long(_, value) => {
+				frame.operand_stack.push(JvmValue::Primitive(
+					JvmPrimitiveType::LongInteger,
+					*value,
+					0,
+					0,
+				));
+			}
+			Constant::Double(_, value) => {
+				frame.operand_stack.push(JvmValue::Primitive(
+					JvmPrimitiveType::Double,
+					*value,
+					0,
+					0,
+				));
+			}
+			Constant::Class(_, _) => {
+				let type_name = class.get_class_name_at(index as u16).unwrap_or_default();
+				let mirror = if type_name.starts_with('[') {
+					self.class_mirror(&type_name)
+				} else {
+					match self.resolve_class_from(&class, &type_name) {
+						Some(resolved) => self.class_object(&resolved),
+						None => return,
+					}
+				};
+				if let Some(mirror) = mirror {
+					frame.operand_stack.push(mirror);
+				}
+			}
 			_ => {
 				FatalError::new(FatalErrorType::NotImplemented(format!(
-					"execute_ldc (class, method type or method handle.)"
+					"execute_ldc (method type or method handle.)"
 				)))
 				.call();
 			}
@@ -1649,121 +3875,93 @@ impl JvmThread {
 		);
 	}
 
-	fn execute_fload_x(&mut self, x: usize, frame: &mut Frame) {
-		frame.operand_stack.push(frame.locals[x].clone());
+	fn execute_iconst_x(&mut self, x: i32, frame: &mut Frame) {
+		frame.operand_stack.push(JvmValue::int(x));
 	}
 
-	fn execute_iload_x(&mut self, x: usize, frame: &mut Frame) {
-		frame.operand_stack.push(frame.locals[x].clone());
+	/// The Class object (java.lang.Class) of the type named `type_name`
+	/// (see typevalues::type_name_of_descriptor), created the first time
+	/// that it is needed. The VM creates it without running a constructor.
+	pub fn class_mirror(&mut self, type_name: &str) -> Option<JvmValue> {
+		self.loader_class_mirror(type_name, BOOTSTRAP_LOADER)
 	}
 
-	fn execute_fstore_x(&self, x: usize, frame: &mut Frame) {
-		Debug(
-			format!("Frame before fstore_x: {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
-		if x < frame.locals.len() {
-			if let Some(top) = frame.operand_stack.pop() {
-				if let JvmValue::Primitive(JvmPrimitiveType::Float, _, value, access) = top {
-					frame.locals[x] =
-						JvmValue::Primitive(JvmPrimitiveType::Float, 0, value, access);
-				} else {
-					FatalError::new(FatalErrorType::WrongType(
-						format!("fstore"),
-						format!("float primitive"),
-					))
-					.call();
-				}
-			} else {
-				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-					"fstore"
-				)))
-				.call();
+	/// The Class object of `class`, which may have been defined by any
+	/// class loader. See class_mirror.
+	pub fn class_object(&mut self, class: &Rc<Class>) -> Option<JvmValue> {
+		let class_name = class.get_class_name()?;
+		self.loader_class_mirror(&class_name, class.get_defining_loader())
+	}
+
+	/// The Class object of the type named `type_name` that `loader`
+	/// defined.
+	fn loader_class_mirror(&mut self, type_name: &str, loader: ClassLoaderId) -> Option<JvmValue> {
+		if let Ok(methodarea) = self.methodarea.lock() {
+			if let Some(mirror) = (*methodarea).get_mirror(type_name, loader) {
+				return Some(mirror);
 			}
-		} else {
-			FatalError::new(FatalErrorType::NotEnough(
-				format!("fstore"),
-				x,
-				format!("locals"),
-			))
-			.call();
 		}
-	}
-	fn execute_fstore(&self, index: usize, frame: &mut Frame) {
+		let mirror = self.create_object_by_name("java/lang/Class")?;
 		Debug(
-			format!("Frame before fstore_x: {}", frame),
+			format!("Created the Class object of {}.", type_name),
 			&self.debug_level,
 			DebugLevel::Info,
 		);
-		if index < frame.locals.len() {
-			if let Some(top) = frame.operand_stack.pop() {
-				if let JvmValue::Primitive(JvmPrimitiveType::Float, value64, value32, access) = top
-				{
-					frame.locals[index] =
-						JvmValue::Primitive(JvmPrimitiveType::Float, value64, value32, access);
-				} else {
-					FatalError::new(FatalErrorType::WrongType(
-						format!("fstore"),
-						format!("float primitive"),
-					))
-					.call();
-				}
-			} else {
-				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-					"fstore"
-				)))
-				.call();
-			}
-		} else {
-			FatalError::new(FatalErrorType::NotEnough(
-				format!("fstore"),
-				index,
-				format!("locals"),
-			))
-			.call();
+		match self.methodarea.lock() {
+			Ok(mut methodarea) => Some((*methodarea).add_mirror(type_name, loader, mirror)),
+			_ => None,
 		}
 	}
-	fn execute_istore_x(&self, x: usize, frame: &mut Frame) {
-		Debug(
-			format!("Frame before istore_x: {}", frame),
-			&self.debug_level,
-			DebugLevel::Info,
-		);
-		if x < frame.locals.len() {
-			if let Some(top) = frame.operand_stack.pop() {
-				if let JvmValue::Primitive(pt, value64, value32, access) = top {
-					frame.locals[x] = JvmValue::Primitive(pt, value64, value32, access);
-				} else {
-					FatalError::new(FatalErrorType::WrongType(
-						format!("istore"),
-						format!("integer primitive"),
-					))
-					.call();
-				}
-			} else {
-				FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-					"istore"
-				)))
-				.call();
-			}
-		} else {
-			FatalError::new(FatalErrorType::NotEnough(
-				format!("fstore"),
-				x,
-				format!("locals"),
-			))
-			.call();
+
+	/// The name of the type whose Class object is `mirror`. None if
+	/// `mirror` is not a Class object.
+	pub fn mirrored_type(&self, mirror: &JvmValue) -> Option<String> {
+		self.methodarea.lock().ok()?.mirrored_type(mirror)
+	}
+
+	/// The loader that defined the type whose Class object is `mirror`
+	/// (the bootstrap class loader if `mirror` is not a Class object).
+	pub fn mirrored_loader(&self, mirror: &JvmValue) -> ClassLoaderId {
+		match self.methodarea.lock() {
+			Ok(methodarea) => (*methodarea)
+				.mirrored_loader(mirror)
+				.unwrap_or(BOOTSTRAP_LOADER),
+			_ => BOOTSTRAP_LOADER,
 		}
 	}
 
-	fn execute_iconst_x(&mut self, x: i32, frame: &mut Frame) {
-		frame.operand_stack.push(JvmValue::Primitive(
-			JvmPrimitiveType::Integer,
-			0,
-			x as u32,
-			0,
-		));
+	/// The identity of the class loader whose ClassLoader object is
+	/// `class_loader` (the bootstrap class loader for null).
+	pub fn register_class_loader(&self, class_loader: &JvmValue) -> ClassLoaderId {
+		match self.methodarea.lock() {
+			Ok(mut methodarea) => (*methodarea).register_class_loader(class_loader),
+			_ => BOOTSTRAP_LOADER,
+		}
+	}
+
+	/// The ClassLoader object of `loader` (null for the bootstrap class
+	/// loader).
+	pub fn class_loader_object(&self, loader: ClassLoaderId) -> JvmValue {
+		match self.methodarea.lock() {
+			Ok(methodarea) => (*methodarea).class_loader_object(loader),
+			_ => None,
+		}
+		.unwrap_or_else(create_null_value)
+	}
+
+	/// Whether `class` is initialized (JVMS 5.5).
+	pub fn is_initialized(&self, class: &Rc<Class>) -> bool {
+		let loaded_class = match self.methodarea.lock() {
+			Ok(methodarea) => (*methodarea).loaded_class_of(class),
+			_ => None,
+		};
+		match loaded_class {
+			Some(loaded_class) => match loaded_class.lc.lock() {
+				Ok(lc) => matches!(*lc, ClassInitializationStatus::Initialized),
+				_ => false,
+			},
+			None => false,
+		}
 	}
 
 	pub fn maybe_initialize_class(&mut self, class: &Rc<Class>) {
@@ -1786,7 +3984,7 @@ impl JvmThread {
 		 */
 		let loaded_class = match {
 			match self.methodarea.lock() {
-				Ok(mut methodarea) => (*methodarea).get_loaded_class(&class_name),
+				Ok(methodarea) => (*methodarea).loaded_class_of(class),
 				_ => {
 					FatalError::new(FatalErrorType::CouldNotLock(
 						"Method Area.".to_string(),
@@ -1827,27 +4025,24 @@ impl JvmThread {
 
 		match *lc {
 			ClassInitializationStatus::BeingInitialized => {
-				if let Some(class_being_initialized_by_current_thread) =
-					self.initializing_class.last()
-				{
-					if *class_being_initialized_by_current_thread == class_name.to_string() {
-						/*
-						 * We are the ones doing the current initialization, so
-						 * we just return.
-						 */
-						Debug(
-							format!("Recursive initialization; returning"),
-							&self.debug_level,
-							DebugLevel::Info,
-						);
+				if self.initializing_class.contains(&class_name) {
+					/*
+					 * We are the ones doing the current initialization (perhaps
+					 * further up the stack, while initializing a subclass), so
+					 * we just return.
+					 */
+					Debug(
+						format!("Recursive initialization; returning"),
+						&self.debug_level,
+						DebugLevel::Info,
+					);
 
-						Debug(
-							format!("Unlocked LC of: {}", class_name),
-							&self.debug_level,
-							DebugLevel::Info,
-						);
-						return;
-					}
+					Debug(
+						format!("Unlocked LC of: {}", class_name),
+						&self.debug_level,
+						DebugLevel::Info,
+					);
+					return;
 				} else {
 					/*
 					 * This thread is not initializing a class. Therefore, this class
@@ -1863,7 +4058,8 @@ impl JvmThread {
 					);
 					while {
 						match *lc {
-							ClassInitializationStatus::Initialized => false,
+							ClassInitializationStatus::Initialized
+							| ClassInitializationStatus::Error => false,
 							_ => true,
 						}
 					} {
@@ -1874,11 +4070,16 @@ impl JvmThread {
 						&self.debug_level,
 						DebugLevel::Info,
 					);
+					let erroneous = matches!(*lc, ClassInitializationStatus::Error);
+					std::mem::drop(lc);
 					Debug(
 						format!("Unlocked LC of: {}", class_name),
 						&self.debug_level,
 						DebugLevel::Info,
 					);
+					if erroneous {
+						self.throw_erroneous_class(&class_name);
+					}
 					return;
 				}
 			}
@@ -1903,8 +4104,17 @@ impl JvmThread {
 					DebugLevel::Info,
 				);
 			}
-			_ => {
-				FatalError::new(FatalErrorType::ClassInstantiationFailed(class_name)).call();
+			ClassInitializationStatus::Error => {
+				/*
+				 * Its initialization threw before (JVMS 5.5, step 5).
+				 */
+				std::mem::drop(lc);
+				Debug(
+					format!("Unlocked LC of: {}", class_name),
+					&self.debug_level,
+					DebugLevel::Info,
+				);
+				self.throw_erroneous_class(&class_name);
 				return;
 			}
 		};
@@ -1923,16 +4133,51 @@ impl JvmThread {
 			DebugLevel::Info,
 		);
 
+		/*
+		 * Final static fields with a ConstantValue attribute are
+		 * initialized first (JVMS 5.5, step 6), so that even the
+		 * superclass' initialization sees them.
+		 */
+		self.initialize_constant_fields(class);
+
+		/*
+		 * A class' superclass is initialized before the class
+		 * itself (JVMS 5.5, step 7). If that throws, so does this
+		 * class' initialization, with the same exception.
+		 */
+		if let Some(superclass_name) = class.resolve_superclass() {
+			let superclass = if let Ok(mut methodarea) = self.methodarea.lock() {
+				(*methodarea).find_or_load_class(&superclass_name, class.get_defining_loader())
+			} else {
+				FatalError::new(FatalErrorType::CouldNotLock(
+					"Method Area.".to_string(),
+					"maybe_initialize_class".to_string(),
+				))
+				.call();
+				None
+			};
+			if let Some(superclass) = superclass {
+				self.maybe_initialize_class(&superclass);
+			} else {
+				FatalError::new(FatalErrorType::ClassNotLoaded(superclass_name)).call();
+			}
+		}
+
 		let clinit: String = "<clinit>".into();
 
 		/*
 		 * We must invoke the clinit method, if one exists.
 		 */
-		if let Some(clinit_method) = class.get_methods_ref().get_by_name_and_type(
-			&clinit,
-			&"()V".to_string(),
-			class.get_constant_pool_ref(),
-		) {
+		let clinit_method = if self.pending_exception.is_none() {
+			class.get_methods_ref().get_by_name_and_type(
+				&clinit,
+				&"()V".to_string(),
+				class.get_constant_pool_ref(),
+			)
+		} else {
+			None
+		};
+		if let Some(clinit_method) = clinit_method {
 			Debug(
 				format!("clinit Method: {}", clinit_method),
 				&self.debug_level,
@@ -1948,11 +4193,28 @@ impl JvmThread {
 				DebugLevel::Info,
 			);
 
-			if let Some(v) = self.execute_method(&clinit_method, clinit_frame) {
+			if let Some(v) = self.execute_method(clinit_method, clinit_frame) {
 				if JvmValue::Primitive(JvmPrimitiveType::Void, 0, 0, 0) != v {
 					FatalError::new(FatalErrorType::ClassInitMethodReturnedValue).call();
 				}
 			}
+
+			/*
+			 * An exception that <clinit> threw that is not an Error is
+			 * wrapped in an ExceptionInInitializerError (JVMS 5.5, step
+			 * 11).
+			 */
+			if let Some(exception) = self.pending_exception.take() {
+				if self.is_value_assignable(&exception, "java/lang/Error", None) {
+					self.throw(exception);
+				} else if let Some(error) = self.new_object(
+					"java/lang/ExceptionInInitializerError",
+					"(Ljava/lang/Throwable;)V",
+					vec![exception],
+				) {
+					self.throw(error);
+				}
+			}
 		}
 
 		/*
@@ -1966,7 +4228,15 @@ impl JvmThread {
 					&self.debug_level,
 					DebugLevel::Info,
 				);
-				*lc = ClassInitializationStatus::Initialized;
+				/*
+				 * If its initialization threw, the class is erroneous
+				 * (JVMS 5.5, steps 7 and 12).
+				 */
+				*lc = if self.pending_exception.is_some() {
+					ClassInitializationStatus::Error
+				} else {
+					ClassInitializationStatus::Initialized
+				};
 				self.initializing_class.pop();
 				(*loaded_class).lc_waitq.notify_all();
 				/*
@@ -1989,6 +4259,174 @@ impl JvmThread {
 		};
 	}
 
+	/// Throw the NoClassDefFoundError for a use of the class named
+	/// `class_name`, whose initialization threw (JVMS 5.5, step 5).
+	fn throw_erroneous_class(&mut self, class_name: &str) {
+		self.throw_new(
+			"java/lang/NoClassDefFoundError",
+			Some(&format!(
+				"Could not initialize class {}",
+				class_name.replace('/', ".")
+			)),
+		);
+	}
+
+	/// Set each final static field of `class` that has a ConstantValue
+	/// attribute (JVMS 4.7.2) to that value, in the order that the fields
+	/// appear in the class file.
+	fn initialize_constant_fields(&mut self, class: &Rc<Class>) {
+		let constant_pool = class.get_constant_pool_ref();
+		for field in class.get_fields_ref().iter() {
+			if !field.is_static() {
+				continue;
+			}
+			let constant_value_index = match field.get_constant_value_index(constant_pool) {
+				Some(constant_value_index) => constant_value_index,
+				None => continue,
+			};
+			let descriptor = field.get_descriptor(constant_pool).unwrap_or_default();
+
+			let value = match (
+				descriptor.as_str(),
+				constant_pool.get_constant_ref(constant_value_index as usize),
+			) {
+				("I", Constant::Integer(_, value)) => Some(JvmValue::Primitive(
+					JvmPrimitiveType::Integer,
+					0,
+					*value,
+					field.access_flags,
+				)),
+				("S", Constant::Integer(_, value)) => Some(JvmValue::Primitive(
+					JvmPrimitiveType::Short,
+					0,
+					*value,
+					field.access_flags,
+				)),
+				("C", Constant::Integer(_, value)) => Some(JvmValue::Primitive(
+					JvmPrimitiveType::Char,
+					0,
+					*value,
+					field.access_flags,
+				)),
+				("B", Constant::Integer(_, value)) => Some(JvmValue::Primitive(
+					JvmPrimitiveType::Byte,
+					0,
+					*value,
+					field.access_flags,
+				)),
+				("Z", Constant::Integer(_, value)) => Some(JvmValue::Primitive(
+					JvmPrimitiveType::Boolean,
+					0,
+					*value,
+					field.access_flags,
+				)),
+				("J", Constant::Long(_, value)) => Some(JvmValue::Primitive(
+					JvmPrimitiveType::LongInteger,
+					*value,
+					0,
+					field.access_flags,
+				)),
+				("F", Constant::Float(_, value)) => Some(JvmValue::Primitive(
+					JvmPrimitiveType::Float,
+					0,
+					*value,
+					field.access_flags,
+				)),
+				("D", Constant::Double(_, value)) => Some(JvmValue::Primitive(
+					JvmPrimitiveType::Double,
+					*value,
+					0,
+					field.access_flags,
+				)),
+				("Ljava/lang/String;", Constant::String(_, string_index)) => {
+					if let Constant::Utf8(_, _, _, string_value) =
+						constant_pool.get_constant_ref(*string_index as usize)
+					{
+						self.intern_string(string_value)
+					} else {
+						None
+					}
+				}
+				_ => None,
+			};
+
+			if let Some(value) = value {
+				if let Ok(mut field_value) = field.value.lock() {
+					*field_value = Some(value);
+				} else {
+					FatalError::new(FatalErrorType::CouldNotLock(
+						field.get_name(constant_pool).unwrap_or_default(),
+						"initialize_constant_fields".to_string(),
+					))
+					.call();
+				}
+			} else {
+				FatalError::new(FatalErrorType::InvalidConstantReference(
+					class.get_class_name().unwrap(),
+					format!("constant value for {}", descriptor),
+					constant_value_index,
+				))
+				.call();
+			}
+		}
+	}
+
+	/// Get a reference to the interned String object whose contents are
+	/// `value`, creating (and interning) it if it does not already exist.
+	pub fn intern_string(&mut self, value: &str) -> Option<JvmValue> {
+		if let Ok(methodarea) = self.methodarea.lock() {
+			if let Some(string) = (*methodarea).get_interned_string(value) {
+				return Some(string);
+			}
+		} else {
+			FatalError::new(FatalErrorType::CouldNotLock(
+				"Method Area.".to_string(),
+				"intern_string".to_string(),
+			))
+			.call();
+		}
+
+		let string = self.create_string(value)?;
+		if let Ok(mut methodarea) = self.methodarea.lock() {
+			return Some((*methodarea).intern_string(value, string));
+		}
+		None
+	}
+
+	/// Create a new String object whose contents are `value`.
+	pub fn create_string(&mut self, value: &str) -> Option<JvmValue> {
+		let string_class_name = "java/lang/String".to_string();
+		let mut string_class: Option<Rc<Class>> = None;
+		if let Ok(mut methodarea) = self.methodarea.lock() {
+			(*methodarea).maybe_load_class(&string_class_name);
+			string_class = (*methodarea).get_class_rc(&string_class_name);
+		} else {
+			FatalError::new(FatalErrorType::CouldNotLock(
+				"Method Area.".to_string(),
+				"create_string".to_string(),
+			))
+			.call();
+		}
+
+		if let Some(string_class) = string_class {
+			self.maybe_initialize_class(&string_class);
+		} else {
+			FatalError::new(FatalErrorType::ClassNotFound(string_class_name)).call();
+			return None;
+		}
+
+		if let Some(string_object) =
+			create_static_string_object(value.to_string(), self, Arc::clone(&self.methodarea))
+		{
+			return Some(JvmValue::Reference(
+				JvmReferenceType::Class(string_class_name),
+				JvmReferenceTargetType::Object(self.allocate_object(string_object)?),
+				0,
+			));
+		}
+		None
+	}
+
 	fn execute_new(&mut self, bytes: &[u8], source_frame: &mut Frame) -> Option<JvmValue> {
 		let class = source_frame.class().unwrap();
 		let constant_pool = class.get_constant_pool_ref();
@@ -2005,47 +4443,31 @@ impl JvmThread {
 						);
 
 						let mut result: Option<JvmValue> = None;
-						let mut instantiated_class: Option<Rc<Class>> = None;
-						if let Ok(mut methodarea) = self.methodarea.lock() {
-							(*methodarea).maybe_load_class(&instantiated_class_name);
-							instantiated_class =
-								(*methodarea).get_class_rc(instantiated_class_name);
-						} else {
-							FatalError::new(FatalErrorType::CouldNotLock(
-								"Method Area.".to_string(),
-								"execute_new".to_string(),
-							))
-							.call();
-						}
+						let instantiated_class =
+							self.resolve_class_from(&class, instantiated_class_name);
 						if let Some(instantiated_class) = instantiated_class {
 							self.maybe_initialize_class(&instantiated_class);
+							if self.pending_exception.is_some() {
+								return None;
+							}
 
 							let mut object =
 								JvmObject::new(instantiated_class, self.debug_level.clone());
 
-							object.instantiate(self, Arc::clone(&self.methodarea));
+							object.instantiate();
 							Debug(
 								format!("Made a new {}.", instantiated_class_name),
 								&self.debug_level,
 								DebugLevel::Info,
 							);
 
-							Debug(
-								format!("hierarchy: {}", object.hierarchy()),
-								&self.debug_level,
-								DebugLevel::Info,
-							);
-
-							result = Some(JvmValue::Reference(
-								JvmReferenceType::Class(instantiated_class_name.to_string()),
-								JvmReferenceTargetType::Object(Arc::new(Mutex::new(object))),
-								0,
-							));
-						} else {
-							FatalError::new(FatalErrorType::ClassNotLoaded(
-								instantiated_class_name.to_string(),
-							))
-							.call();
+							if let Some(object) = self.allocate_object(object) {
+								result = Some(JvmValue::Reference(
+									JvmReferenceType::Class(instantiated_class_name.to_string()),
+									JvmReferenceTargetType::Object(object),
+									0,
+								));
+							}
 						}
 						result
 					}
@@ -2090,20 +4512,13 @@ impl JvmThread {
 			);
 			let mut resolved_field_class: Option<Rc<Class>> = None;
 
+			let field_class = match self.resolve_class_from(&class, &field_class_name) {
+				Some(field_class) => field_class,
+				None => return,
+			};
 			if let Ok(mut methodarea) = self.methodarea.lock() {
-				(*methodarea).maybe_load_class(&field_class_name);
-				if let Some(field_class) = (*methodarea).get_class_rc(&field_class_name) {
-					if let Some(resolved_field_class_name) =
-						(*methodarea).resolve_field(&field_class, &field_name, &field_type)
-					{
-						// TODO: I think that this should be resolved_field_class_name
-						// As it stands now, this is a repetition of the maybe_load_class
-						// on line 2023.
-						(*methodarea).maybe_load_class(&field_class_name);
-						resolved_field_class =
-							(*methodarea).get_class_rc(&resolved_field_class_name);
-					}
-				}
+				resolved_field_class =
+					(*methodarea).resolve_field(&field_class, &field_name, &field_type);
 			} else {
 				FatalError::new(FatalErrorType::CouldNotLock(
 					"Method Area.".to_string(),
@@ -2114,6 +4529,9 @@ impl JvmThread {
 
 			if let Some(resolved_field_class) = resolved_field_class {
 				self.maybe_initialize_class(&resolved_field_class);
+				if self.pending_exception.is_some() {
+					return;
+				}
 				let resolved_field_class_constant_pool =
 					resolved_field_class.get_constant_pool_ref();
 				if let Some(_field_ref) = resolved_field_class.get_fields_ref().get_field_ref(
@@ -2123,7 +4541,9 @@ impl JvmThread {
 				) {
 					if let Ok(_field_ref_value) = _field_ref.value.lock() {
 						if let Some(field_ref_value) = (*_field_ref_value).clone() {
-							source_frame.operand_stack.push(field_ref_value);
+							source_frame
+								.operand_stack
+								.push(field_ref_value.to_computational_type());
 						} else {
 							FatalError::new(FatalErrorType::UninitializedField(
 								field_name,
@@ -2172,17 +4592,13 @@ impl JvmThread {
 			);
 			let mut resolved_field_class: Option<Rc<Class>> = None;
 
+			let field_class = match self.resolve_class_from(&class, &field_class_name) {
+				Some(field_class) => field_class,
+				None => return,
+			};
 			if let Ok(mut methodarea) = self.methodarea.lock() {
-				(*methodarea).maybe_load_class(&field_class_name);
-				if let Some(field_class) = (*methodarea).get_class_rc(&field_class_name) {
-					if let Some(resolved_field_class_name) =
-						(*methodarea).resolve_field(&field_class, &field_name, &field_type)
-					{
-						(*methodarea).maybe_load_class(&resolved_field_class_name);
-						resolved_field_class =
-							(*methodarea).get_class_rc(&resolved_field_class_name);
-					}
-				}
+				resolved_field_class =
+					(*methodarea).resolve_field(&field_class, &field_name, &field_type);
 			} else {
 				FatalError::new(FatalErrorType::CouldNotLock(
 					"Method Area.".to_string(),
@@ -2193,6 +4609,9 @@ impl JvmThread {
 
 			if let Some(resolved_field_class) = resolved_field_class {
 				self.maybe_initialize_class(&resolved_field_class);
+				if self.pending_exception.is_some() {
+					return;
+				}
 				let resolved_field_class_constant_pool =
 					resolved_field_class.get_constant_pool_ref();
 				if let Some(field_ref) = resolved_field_class.get_fields_ref().get_field_ref(
@@ -2228,129 +4647,112 @@ impl JvmThread {
 		}
 	}
 
-	fn execute_getfield(&mut self, index: u16, frame: &mut Frame) {
-		/*
-		 * 1.
-		 */
-		let class = frame.class().unwrap();
-		let constant_pool = class.get_constant_pool_ref();
-		let field_index = index as usize;
+	/// Resolve the Fieldref at `field_ref_index` in `class`' constant pool
+	/// to the offset of the field in the slots of an object. The offset is
+	/// cached in `class`, so only the first getfield/putfield through a
+	/// particular Fieldref pays for resolution.
+	fn resolve_field_offset(&mut self, class: &Rc<Class>, field_ref_index: u16) -> Option<usize> {
+		if let Some(offset) = class.get_cached_field_offset(field_ref_index) {
+			return Some(offset);
+		}
 
-		let objectref = frame.operand_stack.pop();
+		let (field_class_name, field_name, field_type) =
+			match class.resolve_field_ref(field_ref_index as usize) {
+				Some(field_ref) => field_ref,
+				None => {
+					FatalError::new(FatalErrorType::InvalidConstantReference(
+						class.get_class_name().unwrap(),
+						"FieldRef".to_string(),
+						field_ref_index,
+					))
+					.call();
+					return None;
+				}
+			};
 
-		if let Some(objectref) = objectref {
-			if let Constant::Fieldref(_, class_ref, name_and_type_ref) =
-				constant_pool.get_constant_ref(field_index)
-			{
-				// Let's get the name and the type of the field!
+		Debug(
+			format!(
+				"resolve field: {}.{} ({})",
+				field_class_name, field_name, field_type
+			),
+			&self.debug_level,
+			DebugLevel::Info,
+		);
 
-				// Let's get the class name
-				let mut field_class_name = None;
-				let mut field_name = None;
-				let mut field_type = None;
+		let mut offset: Option<usize> = None;
+		let field_class = self.resolve_class_from(&class, &field_class_name)?;
+		if let Ok(mut methodarea) = self.methodarea.lock() {
+			if let Some(declaring_class) =
+				(*methodarea).resolve_field(&field_class, &field_name, &field_type)
+			{
+				offset = declaring_class.get_layout().get_offset(
+					&declaring_class.get_class_name().unwrap_or_default(),
+					&field_name,
+					&field_type,
+				);
+			}
+		} else {
+			FatalError::new(FatalErrorType::CouldNotLock(
+				"Method Area.".to_string(),
+				"resolve_field_offset".to_string(),
+			))
+			.call();
+			return None;
+		}
 
-				if let Constant::Class(_, class_name_index) =
-					constant_pool.get_constant_ref(*class_ref as usize)
-				{
-					if let Constant::Utf8(_, _, _, class_name) =
-						constant_pool.get_constant_ref(*class_name_index as usize)
-					{
-						// class_name is the name of the class where the field exists.
-						field_class_name = Some(class_name);
-					}
-				}
+		if let Some(offset) = offset {
+			class.cache_field_offset(field_ref_index, offset);
+		} else {
+			FatalError::new(FatalErrorType::FieldNotFound(field_name, field_class_name)).call();
+		}
+		offset
+	}
 
-				if let Constant::NameAndType(_, field_name_index, field_type_index) =
-					constant_pool.get_constant_ref(*name_and_type_ref as usize)
-				{
-					if let Constant::Utf8(_, _, _, field_name_constant) =
-						constant_pool.get_constant_ref(*field_name_index as usize)
-					{
-						field_name = Some(field_name_constant);
-					}
-					if let Constant::Utf8(_, _, _, field_type_constant) =
-						constant_pool.get_constant_ref(*field_type_index as usize)
-					{
-						field_type = Some(field_type_constant);
-					}
-				}
+	fn execute_getfield(&mut self, index: u16, frame: &mut Frame) {
+		let class = frame.class().unwrap();
 
-				Debug(
-					format!("field_class_name: {}", field_class_name.unwrap()),
-					&self.debug_level,
-					DebugLevel::Info,
-				);
-				Debug(
-					format!("field_name: {}", field_name.unwrap()),
-					&self.debug_level,
-					DebugLevel::Info,
-				);
-				Debug(
-					format!("field_type: {}", field_type.unwrap()),
-					&self.debug_level,
-					DebugLevel::Info,
-				);
+		let offset = match self.resolve_field_offset(&class, index) {
+			Some(offset) => offset,
+			None => return,
+		};
 
-				// Value on the top of the stack must be a reference.
-				if let JvmValue::Reference(
-					JvmReferenceType::Class(objectref_class_name),
-					JvmReferenceTargetType::Object(objectref_object),
-					_,
-				) = objectref
-				{
-					// Lock the object on the top of the stack so that it can be mutated.
-					if let Ok(mut objectref_object) = objectref_object.lock() {
-						// We lock the methodarea because is_type_of may have to load superclasses to check whether
-						// the type of the field matches the type of the object on the top of the stack.
-						// TODO: This could be done more precisely. I.e., do not lock the methodarea until
-						// we have to walk up the object's class hierarchy.
-						if let Ok(mut methodarea) = self.methodarea.lock() {
-							// Check whether the class of the object on the top of the stack (or its superclasses)
-							// matches the class of the field that is being gotten.
-							if objectref_object
-								.get_class()
-								.is_type_of(field_class_name.unwrap(), &mut *methodarea)
-							{
-								if let Some(field_value) =
-									objectref_object.get_field(field_name.unwrap())
-								{
-									frame.operand_stack.push((*field_value).clone())
-								} else {
-									FatalError::new(FatalErrorType::FieldNotFound(
-										field_name.unwrap().clone(),
-										field_class_name.unwrap().clone(),
-									))
-									.call();
-								}
-							} else {
-								FatalError::new(FatalErrorType::WrongType(
-									format!("execute_getfield"),
-									field_class_name.unwrap().clone(),
-								))
-								.call();
-							}
-						} else {
-							FatalError::new(FatalErrorType::CouldNotLock(
-								"Method Area.".to_string(),
-								"get_field".to_string(),
-							))
-							.call();
-						}
+		// Value on the top of the stack must be a reference.
+		match frame.operand_stack.pop() {
+			Some(JvmValue::Reference(
+				JvmReferenceType::Class(objectref_class_name),
+				JvmReferenceTargetType::Object(objectref_object),
+				_,
+			)) => {
+				if let Ok(objectref_object) = objectref_object.lock() {
+					if let Some(field_value) = objectref_object.get_field(offset) {
+						frame
+							.operand_stack
+							.push(field_value.to_computational_type())
 					} else {
-						FatalError::new(FatalErrorType::CouldNotLock(
+						FatalError::new(FatalErrorType::FieldNotFound(
+							format!("at offset {}", offset),
 							objectref_class_name,
-							"get_field".to_string(),
 						))
 						.call();
 					}
 				} else {
-					FatalError::new(FatalErrorType::WrongType(
-						format!("execute_getfield"),
-						format!("Reference"),
+					FatalError::new(FatalErrorType::CouldNotLock(
+						objectref_class_name,
+						"get_field".to_string(),
 					))
 					.call();
 				}
 			}
+			Some(JvmValue::Reference(_, JvmReferenceTargetType::Null, _)) => {
+				self.throw_new("java/lang/NullPointerException", None);
+			}
+			_ => {
+				FatalError::new(FatalErrorType::WrongType(
+					format!("execute_getfield"),
+					format!("Reference"),
+				))
+				.call();
+			}
 		}
 	}
 
@@ -2358,263 +4760,231 @@ impl JvmThread {
 		// GENERAL: See comments in execute_getfield -- the skeleton of the functions' operations
 		// are the same.
 		let class = frame.class().unwrap();
-		let constant_pool = class.get_constant_pool_ref();
-		let field_index = index as usize;
-
-		let value = frame.operand_stack.pop();
-		let objectref = frame.operand_stack.pop();
-
-		if let Some(value) = value {
-			if let Some(objectref) = objectref {
-				if let Constant::Fieldref(_, class_ref, name_and_type_ref) =
-					constant_pool.get_constant_ref(field_index)
-				{
-					let mut field_class_name = None;
-					let mut field_name = None;
-					let mut field_type = None;
-
-					if let Constant::Class(_, class_name_index) =
-						constant_pool.get_constant_ref(*class_ref as usize)
-					{
-						if let Constant::Utf8(_, _, _, class_name) =
-							constant_pool.get_constant_ref(*class_name_index as usize)
-						{
-							field_class_name = Some(class_name);
-						}
-					}
-
-					if let Constant::NameAndType(_, field_name_index, field_type_index) =
-						constant_pool.get_constant_ref(*name_and_type_ref as usize)
-					{
-						if let Constant::Utf8(_, _, _, field_name_constant) =
-							constant_pool.get_constant_ref(*field_name_index as usize)
-						{
-							field_name = Some(field_name_constant);
-						}
-						if let Constant::Utf8(_, _, _, field_type_constant) =
-							constant_pool.get_constant_ref(*field_type_index as usize)
-						{
-							field_type = Some(field_type_constant);
-						}
-					}
-
-					Debug(
-						format!("field_class_name: {}", field_class_name.unwrap()),
-						&self.debug_level,
-						DebugLevel::Info,
-					);
-					Debug(
-						format!("field_name: {}", field_name.unwrap()),
-						&self.debug_level,
-						DebugLevel::Info,
-					);
+
+		let offset = match self.resolve_field_offset(&class, index) {
+			Some(offset) => offset,
+			None => return,
+		};
+
+		let value = frame.operand_stack.pop();
+		let objectref = frame.operand_stack.pop();
+
+		if let Some(value) = value {
+			match objectref {
+				Some(JvmValue::Reference(
+					JvmReferenceType::Class(objectref_class_name),
+					JvmReferenceTargetType::Object(objectref_object),
+					_,
+				)) => {
 					Debug(
-						format!("field_type: {}", field_type.unwrap()),
+						format!("objectref_class_name: {}", objectref_class_name),
 						&self.debug_level,
 						DebugLevel::Info,
 					);
-
-					if let JvmValue::Reference(
-						JvmReferenceType::Class(objectref_class_name),
-						JvmReferenceTargetType::Object(objectref_object),
-						_,
-					) = objectref
-					{
-						Debug(
-							format!("objectref_class_name: {}", objectref_class_name),
-							&self.debug_level,
-							DebugLevel::Info,
-						);
-						if let Ok(mut objectref_object) = objectref_object.lock() {
-							if let Ok(mut methodarea) = self.methodarea.lock() {
-								if objectref_object
-									.get_class()
-									.is_type_of(field_class_name.unwrap(), &mut *methodarea)
-								{
-									objectref_object.set_field(field_name.unwrap(), Rc::new(value))
-								} else {
-									FatalError::new(FatalErrorType::WrongType(
-										format!("execute_putfield"),
-										field_class_name.unwrap().clone(),
-									))
-									.call();
-								}
-							} else {
-								FatalError::new(FatalErrorType::CouldNotLock(
-									"Method Area.".to_string(),
-									"put_field".to_string(),
-								))
-								.call();
-							}
-						} else {
-							FatalError::new(FatalErrorType::CouldNotLock(
+					if let Ok(mut objectref_object) = objectref_object.lock() {
+						if !objectref_object.set_field(offset, value) {
+							FatalError::new(FatalErrorType::FieldNotFound(
+								format!("at offset {}", offset),
 								objectref_class_name,
-								"put_field".to_string(),
 							))
 							.call();
 						}
 					} else {
-						FatalError::new(FatalErrorType::WrongType(
-							format!("execute_putfield"),
-							format!("Reference"),
+						FatalError::new(FatalErrorType::CouldNotLock(
+							objectref_class_name,
+							"put_field".to_string(),
 						))
 						.call();
 					}
 				}
+				Some(JvmValue::Reference(_, JvmReferenceTargetType::Null, _)) => {
+					self.throw_new("java/lang/NullPointerException", None);
+				}
+				_ => {
+					FatalError::new(FatalErrorType::WrongType(
+						format!("execute_putfield"),
+						format!("Reference"),
+					))
+					.call();
+				}
 			}
 		}
 	}
 
-	fn execute_invokevirtual(
+	/// Resolve the method that the invoke instruction in `bytes` (in a
+	/// method of `class`) names (JVMS 5.4.3.3 and 5.4.3.4). Methods of
+	/// arrays are those of java/lang/Object.
+	///
+	/// # Return value:
+	/// The method's name, its type and the class that declares it, and
+	/// the method. None if resolution threw (NoClassDefFoundError or
+	/// NoSuchMethodError); the exception is pending.
+	fn resolve_invoked_method(
 		&mut self,
 		bytes: &[u8],
-		source_frame: &mut Frame,
-	) -> Option<OpcodeResult> {
-		let class = source_frame.class().unwrap();
-		let _constant_pool = class.get_constant_pool_ref();
-		let method_index = (((bytes[1] as u16) << 8) | (bytes[2] as u16)) as usize;
-
-		// First, find out what method we are supposed to invoke. It is given by the index
-		// to the instruction. That index refers to the constant pool of the class whose
-		// code the JVM is currently executing.
-		if let Some((method_name, method_type, invoked_class_name)) =
-			class.resolve_method_ref(method_index)
-		{
-			let mut invoked_class: Option<Rc<Class>> = None;
-			let mut resolved_method: Option<Rc<Method>> = None;
-			let mut invoked_frame: Frame = Frame::new();
-
-			Debug(
-				format!("Invoke Virtual: {}.{}", invoked_class_name, method_name),
-				&self.debug_level,
-				DebugLevel::Info,
-			);
+		class: &Rc<Class>,
+	) -> Option<(String, String, Rc<Class>, Rc<Method>)> {
+		let method_index = read_u16(bytes, 1) as usize;
+		let (method_name, method_type, invoked_class_name) =
+			match class.resolve_method_ref(method_index) {
+				Some(method_ref) => method_ref,
+				None => {
+					FatalError::new(FatalErrorType::MethodResolutionFailed).call();
+					return None;
+				}
+			};
+		let invoked_class_name = if invoked_class_name.starts_with('[') {
+			"java/lang/Object".to_string()
+		} else {
+			invoked_class_name
+		};
+		Debug(
+			format!(
+				"Invoke: {}.{}{}",
+				invoked_class_name, method_name, method_type
+			),
+			&self.debug_level,
+			DebugLevel::Info,
+		);
 
-			// We know the names of the method (and its class) that we are supposed to execute.
-			// Now we need to resolve those so that we can actually execute them.
-			if let Ok(mut methodarea) = self.methodarea.lock() {
-				(*methodarea).maybe_load_class(&invoked_class_name);
-				invoked_class = (*methodarea).get_class_rc(&invoked_class_name);
-				resolved_method = if let Some(invoked_class) = &invoked_class {
-					(*methodarea).resolve_method(&class, invoked_class, &method_name, &method_type)
-				} else {
-					None
+		let invoked_class = self.resolve_class_from(class, &invoked_class_name)?;
+		let resolved = match self.methodarea.lock() {
+			Ok(mut methodarea) => {
+				(*methodarea).resolve_method(class, &invoked_class, &method_name, &method_type)
+			}
+			_ => None,
+		};
+		match resolved {
+			Some((resolved_class, resolved_method)) => {
+				/*
+				 * The classes in the method's type must be the same for
+				 * the class that references it and for the class that
+				 * declares it (JVMS 5.4.3.3).
+				 */
+				let constrained = match self.methodarea.lock() {
+					Ok(mut methodarea) => (*methodarea).add_loading_constraints(
+						&method_type,
+						class.get_defining_loader(),
+						resolved_class.get_defining_loader(),
+					),
+					_ => Ok(()),
 				};
+				if let Err(message) = constrained {
+					self.throw_new("java/lang/LinkageError", Some(&message));
+					return None;
+				}
+				Some((method_name, method_type, resolved_class, resolved_method))
 			}
+			None => {
+				self.throw_new(
+					"java/lang/NoSuchMethodError",
+					Some(&format!(
+						"{}.{}{}",
+						invoked_class_name.replace('/', "."),
+						method_name,
+						method_type
+					)),
+				);
+				None
+			}
+		}
+	}
 
-			// If that resolution process was successful, we will have a class and a method.
-			if let (Some(invoked_class), Some(resolved_method)) = (invoked_class, resolved_method) {
-				let mut object_class_name: Option<String> = None;
-
-				if resolved_method.access_flags & (MethodAccessFlags::Native as u16) == 0 {
-					// We know how to execute non-native methods.
+	/// Pop the arguments of `method` (and, unless `is_static`, the object
+	/// on which it is invoked, which comes first) off `frame`'s operand
+	/// stack. A null object throws NullPointerException.
+	fn pop_arguments(
+		&mut self,
+		instruction: &str,
+		method: &Rc<Method>,
+		is_static: bool,
+		frame: &mut Frame,
+	) -> Option<Vec<JvmValue>> {
+		let count = method.parameter_count + if is_static { 0 } else { 1 };
+		if frame.operand_stack.len() < count {
+			FatalError::new(FatalErrorType::NotEnough(
+				instruction.to_string(),
+				count,
+				"stack operands".to_string(),
+			))
+			.call();
+			return None;
+		}
+		let first = frame.operand_stack.len() - count;
+		let arguments = frame.operand_stack.split_off(first);
+		if !is_static && arguments[0].is_null() {
+			self.throw_new("java/lang/NullPointerException", None);
+			return None;
+		}
+		Some(arguments)
+	}
 
-					/*
-						* Let's build a frame! Values from the stack will become local variables when we start
-						* executing the invoked method.
-						*/
-					if !move_parameters_to_locals(
-						&resolved_method,
-						source_frame,
-						&mut invoked_frame,
-					) {
-						FatalError::new(FatalErrorType::NotEnough(
-							format!("invokevirtual"),
-							resolved_method.parameter_count,
-							format!("stack operands"),
-						))
-						.call();
-					}
-					/*
-						* The first value on the stack is an object reference. It becomes
-						* the 0th local variable to the invoked method.
-						*/
-					if let Some(top) = source_frame.operand_stack.pop() {
-						if let JvmValue::Reference(JvmReferenceType::Class(ocn), _, _) = &top {
-							object_class_name = Some(ocn.to_string());
-							invoked_frame.locals.insert(0, top);
-						} else {
-							FatalError::new(FatalErrorType::WrongType(
-								format!("execute_invokevirtual"),
-								format!("Reference"),
-							))
-							.call();
-						}
-					} else {
-						// Missing a reference on the top of the stack to an object upon which to invoke this method
-						FatalError::new(FatalErrorType::RequiredStackValueNotFound(format!(
-							"Reference to an object."
-						)))
-						.call();
-					}
+	/// invokevirtual and invokeinterface: invoke the method selected
+	/// (JVMS 5.4.6) by the class of the object.
+	fn execute_invokevirtual(
+		&mut self,
+		bytes: &[u8],
+		source_frame: &mut Frame,
+	) -> Option<OpcodeResult> {
+		let class = source_frame.class().unwrap();
+		let (method_name, method_type, resolved_class, resolved_method) =
+			match self.resolve_invoked_method(bytes, &class) {
+				Some(resolved) => resolved,
+				None => return Some(OpcodeResult::Exception),
+			};
+		let arguments =
+			match self.pop_arguments("invokevirtual", &resolved_method, false, source_frame) {
+				Some(arguments) => arguments,
+				None => return Some(OpcodeResult::Exception),
+			};
 
-					/*
-						* Check to see if the resolved method is private. If so, it's the one
-						* that we invoke.
-						*/
-					if ((MethodAccessFlags::Protected as u16) & resolved_method.access_flags) != 0 {
-						invoked_frame.class = Some(invoked_class);
-						if let Some(v) = self.execute_method(&resolved_method, invoked_frame) {
-							Debug(
-								format!("Returning from a method: {}", resolved_method.clone()),
-								&self.debug_level,
-								DebugLevel::Info,
-							);
-							return Some(OpcodeResult::Value(v));
-						} else {
-							FatalError::new(FatalErrorType::MethodExecutionFailed(method_name))
-								.call();
-						}
-					} else if let Some(object_class_name) = object_class_name {
-						let mut selected_class_method: Option<(Rc<Class>, Rc<Method>)> = None;
-						let mut object_class: Option<Rc<Class>> = None;
-
-						if let Ok(mut methodarea) = self.methodarea.lock() {
-							object_class = (*methodarea).get_class_rc(&object_class_name);
-							selected_class_method = if let Some(object_class) = &object_class {
-								(*methodarea).select_method(
-									&object_class,
-									&method_name,
-									&method_type,
-								)
-							} else {
-								FatalError::new(FatalErrorType::MethodSelectionFailed).call();
-								None
-							};
-						}
+		/*
+		 * A private method is the one that we invoke.
+		 */
+		if resolved_method.access_flags & (MethodAccessFlags::Private as u16) != 0 {
+			return self.invoke_method(
+				resolved_class,
+				resolved_method,
+				arguments,
+				bytes[0],
+				source_frame,
+			);
+		}
 
-						if let Some((selected_class, selected_method)) = selected_class_method {
-							invoked_frame.class = Some(selected_class);
-							if let Some(v) = self.execute_method(&selected_method, invoked_frame) {
-								Debug(
-									format!("Returning from a method: {}", resolved_method.clone()),
-									&self.debug_level,
-									DebugLevel::Info,
-								);
-								return Some(OpcodeResult::Value(v));
-							} else {
-								FatalError::new(FatalErrorType::MethodExecutionFailed(method_name))
-									.call();
-							}
-						}
-					} else {
-						FatalError::new(FatalErrorType::MethodExecutionFailed(method_name)).call();
-					}
-				} else {
-					// We do not know how to execute native methods.
-					NonFatalError::new(NonFatalErrorType::NotImplemented(
-						"Native methods".to_string(),
-					))
-					.call();
-				}
+		let object_class = self.class_of_reference(&arguments[0])?;
+		let selected = match self.methodarea.lock() {
+			Ok(mut methodarea) => (*methodarea).select_method(
+				&object_class,
+				&resolved_class,
+				&method_name,
+				&method_type,
+			),
+			_ => None,
+		};
+		match selected {
+			Some((selected_class, selected_method)) => self.invoke_method(
+				selected_class,
+				selected_method,
+				arguments,
+				bytes[0],
+				source_frame,
+			),
+			None => {
+				self.throw_new(
+					"java/lang/AbstractMethodError",
+					Some(&format!(
+						"{}.{}{}",
+						object_class
+							.get_class_name()
+							.unwrap_or_default()
+							.replace('/', "."),
+						method_name,
+						method_type
+					)),
+				);
+				Some(OpcodeResult::Exception)
 			}
-			/*
-			 * TODO: This is a fatal error, but I'm not sure exactly
-			 * how to qualify it.
-			 */
 		}
-		FatalError::new(FatalErrorType::MethodResolutionFailed).call();
-		None
 	}
 
 	fn execute_invokespecial(
@@ -2623,127 +4993,70 @@ impl JvmThread {
 		source_frame: &mut Frame,
 	) -> Option<OpcodeResult> {
 		let class = source_frame.class().unwrap();
-		let constant_pool = class.get_constant_pool_ref();
-		let method_index = (((bytes[1] as u16) << 8) | (bytes[2] as u16)) as usize;
+		let (method_name, method_type, resolved_class, resolved_method) =
+			match self.resolve_invoked_method(bytes, &class) {
+				Some(resolved) => resolved,
+				None => return Some(OpcodeResult::Exception),
+			};
+		let arguments =
+			match self.pop_arguments("invokespecial", &resolved_method, false, source_frame) {
+				Some(arguments) => arguments,
+				None => return Some(OpcodeResult::Exception),
+			};
 
-		if let Some((method_name, method_type, invoked_class_name)) =
-			class.resolve_method_ref(method_index)
+		/*
+		 * The resolved method is the one that we invoke unless
+		 * the ACC_SUPER flag is set for the current class, the method is
+		 * not an instance initialization method and the class of the
+		 * method is a superclass of the current class. Then, the method
+		 * is looked up starting in the direct superclass of the current
+		 * class (JVMS 6.5, invokespecial).
+		 */
+		let resolved_class_name = resolved_class.get_class_name().unwrap_or_default();
+		let resolved_in_superclass = !resolved_class.is_interface()
+			&& resolved_class_name != class.get_class_name().unwrap_or_default()
+			&& match self.methodarea.lock() {
+				Ok(mut methodarea) => class.is_type_of(&resolved_class_name, &mut *methodarea),
+				_ => false,
+			};
+		if ((ClassAccessFlags::Super as u16) & class.access_flags) != 0
+			&& method_name != "<init>"
+			&& resolved_in_superclass
 		{
-			let mut invoked_class: Option<Rc<Class>> = None;
-			let mut resolved_method: Option<Rc<Method>> = None;
-
-			Debug(
-				format!("Invoke Special: {}.{}", invoked_class_name, method_name),
-				&self.debug_level,
-				DebugLevel::Info,
-			);
-
-			if let Ok(mut methodarea) = self.methodarea.lock() {
-				(*methodarea).maybe_load_class(&invoked_class_name);
-				invoked_class = (*methodarea).get_class_rc(&invoked_class_name);
-				resolved_method = if let Some(invoked_class) = &invoked_class {
-					(*methodarea).resolve_method(&class, &invoked_class, &method_name, &method_type)
-				} else {
-					None
-				}
-			}
-
-			if let (Some(invoked_class), Some(resolved_method)) = (invoked_class, resolved_method) {
-				if ((MethodAccessFlags::Protected as u16) & resolved_method.access_flags) != 0 {
-					FatalError::new(FatalErrorType::Todo(format!("Finally, if the resolved method is protected (§4.6), and it is a member of a superclass of the current class, and the method is not declared in the same run-time package (§5.3) as the current class, then the class of objectref must be either the current class or a subclass of the current class."))).call();
+			let superclass = match class.superclass_name() {
+				Some(superclass_name) => self.resolve_class_from(&class, &superclass_name),
+				None => None,
+			};
+			let selected = match (superclass, self.methodarea.lock()) {
+				(Some(superclass), Ok(mut methodarea)) => {
+					(*methodarea).resolve_method(&class, &superclass, &method_name, &method_type)
 				}
-				/* TODO:
-					Next, the resolved method is selected for invocation unless all of the following conditions are true:
-
-				   The ACC_SUPER flag (Table 4.1) is set for the current class.
-
-					 The resolved method is not an instance initialization method (§2.9).
-				   ...
-				*/
-
-				if ((ClassAccessFlags::Super as u16) & class.access_flags) != 0
-					&& method_name != "<init>"
-				{
-					/*
-						...
-
-						The class of the resolved method is a superclass of the current class.
-
-						If the above conditions are true, the actual method to be invoked is selected by the following lookup procedure. Let C be the direct superclass of the current class:
-
-						If C contains a declaration for an instance method with the same name and descriptor as the resolved method, then this method will be invoked. The lookup procedure terminates.
-
-						Otherwise, if C has a superclass, this same lookup procedure is performed recursively using the direct superclass of C. The method to be invoked is the result of the recursive invocation of this lookup procedure.
-
-						Otherwise, an AbstractMethodError is raised.
-					*/
-					FatalError::new(FatalErrorType::Todo(format!(""))).call();
-				} else if resolved_method.access_flags & (MethodAccessFlags::Native as u16) == 0 {
-					let mut invoked_frame = Frame::new();
-					invoked_frame.class = Some(Rc::clone(&invoked_class));
-
-					/*
-						* The other parameters are on the stack, too. Move the parameters
-						* from the source stack to the invoked stack.
-						*/
-					if !move_parameters_to_locals(
-						&resolved_method,
-						source_frame,
-						&mut invoked_frame,
-					) {
-						FatalError::new(FatalErrorType::NotEnough(
-							"invokespecial".to_string(),
-							resolved_method.parameter_count,
-							"stack operands".to_string(),
-						))
-						.call();
-					}
-					/*
-						* The first value on the stack is an object reference. It becomes
-						* the 0th local variable to the special method.
-						*/
-					if let Some(top) = source_frame.operand_stack.pop() {
-						if let JvmValue::Reference(_, _, _) = top {
-							invoked_frame.locals.insert(0, top);
-						} else {
-							/*
-								* TODO: This is a fatal error: The first value on
-								* the stack at this point must be a reference.
-								*/
-						}
-					}
-
-					Debug(
-						format!("Parameter count: {}", resolved_method.parameter_count),
-						&self.debug_level,
-						DebugLevel::Info,
-					);
-					Debug(
-						format!("invoked_frame: {}", invoked_frame),
-						&self.debug_level,
-						DebugLevel::Info,
+				_ => None,
+			};
+			return match selected {
+				Some((selected_class, selected_method)) => self.invoke_method(
+					selected_class,
+					selected_method,
+					arguments,
+					bytes[0],
+					source_frame,
+				),
+				None => {
+					self.throw_new(
+						"java/lang/AbstractMethodError",
+						Some(&format!("{}{}", method_name, method_type)),
 					);
-
-					if let Some(v) = self.execute_method(&resolved_method, invoked_frame) {
-						Debug(
-							format!("Returning from a method: {}", resolved_method.clone()),
-							&self.debug_level,
-							DebugLevel::Info,
-						);
-						return Some(OpcodeResult::Value(v));
-					} else {
-						FatalError::new(FatalErrorType::MethodExecutionFailed(method_name)).call();
-					}
-				} else {
-					// We do not know how to execute native methods.
-					FatalError::new(FatalErrorType::NotImplemented("Native methods".to_string()))
-						.call();
+					Some(OpcodeResult::Exception)
 				}
-			}
-			FatalError::new(FatalErrorType::ClassNotFound(invoked_class_name.clone())).call()
+			};
 		}
-		FatalError::new(FatalErrorType::MethodResolutionFailed).call();
-		None
+		self.invoke_method(
+			resolved_class,
+			resolved_method,
+			arguments,
+			bytes[0],
+			source_frame,
+		)
 	}
 
 	fn execute_invokestatic(
@@ -2752,168 +5065,32 @@ impl JvmThread {
 		source_frame: &mut Frame,
 	) -> Option<OpcodeResult> {
 		let class = source_frame.class().unwrap();
-		let constant_pool = class.get_constant_pool_ref();
-		let method_index = (((bytes[1] as u16) << 8) | (bytes[2] as u16)) as usize;
-
-		if let Some((method_name, method_type, invoked_class_name)) =
-			class.resolve_method_ref(method_index)
-		{
-			Debug(
-				format!("Invoke Static: {}.{}", invoked_class_name, method_name),
-				&self.debug_level,
-				DebugLevel::Info,
-			);
-			let mut invoked_class: Option<Rc<Class>> = None;
-			if let Ok(mut methodarea) = self.methodarea.lock() {
-				invoked_class = (*methodarea).get_class_rc(&invoked_class_name);
-			}
-			if let Some(invoked_class) = invoked_class {
-				/*
-				 * TODO: We need to follow the method resolution process here. See 5.4.3.3.
-				 */
-				if let Some(method) = invoked_class.get_methods_ref().get_by_name_and_type(
-					&method_name,
-					&method_type,
-					invoked_class.get_constant_pool_ref(),
-				) {
-					Debug(
-						format!("method: {}", method),
-						&self.debug_level,
-						DebugLevel::Info,
-					);
-					/*
-					 * This is an operation that requires the target class
-					 * be initialized.
-					 */
-					self.maybe_initialize_class(&invoked_class);
-
-					if method.access_flags & (MethodAccessFlags::Native as u16) == 0 {
-						// We know how to execute non-native methods.
-
-						let mut invoked_frame = Frame::new();
-						invoked_frame.class = Some(Rc::clone(&invoked_class));
-
-						/*
-							* Move the parameters from the source stack to the
-							* invoked stack.
-							*/
-						let parameter_count = method.parameter_count;
-						for i in 0..parameter_count {
-							if let Some(parameter) = source_frame.operand_stack.pop() {
-								invoked_frame.locals.insert(0, parameter);
-							} else {
-								FatalError::new(FatalErrorType::NotEnough(
-									"invokestatic".to_string(),
-									i,
-									"stack operands".to_string(),
-								))
-								.call();
-							}
-						}
-
-						Debug(
-							format!("Parameter count: {}", parameter_count),
-							&self.debug_level,
-							DebugLevel::Info,
-						);
-						Debug(
-							format!("invoked_frame: {}", invoked_frame),
-							&self.debug_level,
-							DebugLevel::Info,
-						);
+		let (_, _, resolved_class, resolved_method) =
+			match self.resolve_invoked_method(bytes, &class) {
+				Some(resolved) => resolved,
+				None => return Some(OpcodeResult::Exception),
+			};
 
-						if let Some(v) = self.execute_method(&method, invoked_frame) {
-							Debug(
-								format!("Returning from a method{}", method.clone()),
-								&self.debug_level,
-								DebugLevel::Info,
-							);
-							return Some(OpcodeResult::Value(v));
-						}
-					} else {
-						// We do not know how to execute native methods.
-						FatalError::new(FatalErrorType::NotImplemented(
-							"Native methods".to_string(),
-						))
-						.call();
-					}
-				}
-			} else {
-				FatalError::new(FatalErrorType::ClassNotFound(invoked_class_name.clone())).call()
-			}
+		/*
+		 * This is an operation that requires the class that declares
+		 * the method be initialized.
+		 */
+		self.maybe_initialize_class(&resolved_class);
+		if self.pending_exception.is_some() {
+			return Some(OpcodeResult::Exception);
 		}
-		None
-	}
-	fn execute_anewarray(
-		&mut self,
-		type_index: u16,
-		count: u32,
-		frame: &mut Frame,
-	) -> OpcodeResult {
-		let class = frame.class().unwrap();
-		let constant_pool = class.get_constant_pool_ref();
-		let newarray_type = constant_pool.get_constant_ref(type_index as usize);
-
-		if let Constant::Class(_, newarray_class_name_index) = newarray_type {
-			let newarray_class_name_constant =
-				constant_pool.get_constant_ref(*newarray_class_name_index as usize);
-			if let Constant::Utf8(_, _, _, new_array_class_name) = newarray_class_name_constant {
-				// Resolve the class.
-				// TODO: This could throw an exception!
-				let mut new_array_class = if let Ok(mut methodarea) = self.methodarea.lock() {
-					(*methodarea).maybe_load_class(&new_array_class_name);
-					(*methodarea).get_class_rc(&new_array_class_name)
-				} else {
-					FatalError::new(FatalErrorType::CouldNotLock(
-						format!("Class."),
-						format!("execute_anewarray"),
-					))
-					.call();
-					None
-				};
-				if let Some(new_array_class) = new_array_class {
-					// Make the array
-					let mut array = JvmArray::new(count as usize);
-					for i in 0..count {
-						array.push(create_null_value());
-					}
 
-					let jvmtype = JvmType::Reference(JvmReferenceType::Array(
-						Rc::new(JvmType::Reference(JvmReferenceType::Class(
-							new_array_class_name.clone(),
-						))),
-						count,
-					));
-					let v = JvmValue::Reference(
-						JvmReferenceType::Array(Rc::new(jvmtype), count), // type
-						JvmReferenceTargetType::Array(Arc::new(Mutex::new(array))), //target type
-						0,                                                // access,
-					);
-					frame.operand_stack.push(v);
-				} else {
-					FatalError::new(FatalErrorType::ClassNotFound(new_array_class_name.clone()))
-						.call()
-				}
-			} else {
-				FatalError::new(FatalErrorType::WrongType(
-					format!("anewarray"),
-					format!("symbolic reference to class."),
-				))
-				.call();
-			}
-		} else if let Constant::InterfaceMethodref(_, _, _) = newarray_type {
-			FatalError::new(FatalErrorType::NotImplemented(format!(
-				"execute_anewarray of interface/method references."
-			)))
-			.call();
-		} else {
-			// We need to also handle an array. See the description of this type. And then, say that there is an error for the wrong type.
-			FatalError::new(FatalErrorType::WrongType(
-				format!("anewarray"),
-				format!("reference to class, interface/method or array."),
-			))
-			.call();
-		}
-		OpcodeResult::Incr(3)
+		let arguments =
+			match self.pop_arguments("invokestatic", &resolved_method, true, source_frame) {
+				Some(arguments) => arguments,
+				None => return Some(OpcodeResult::Exception),
+			};
+		self.invoke_method(
+			resolved_class,
+			resolved_method,
+			arguments,
+			bytes[0],
+			source_frame,
+		)
 	}
 }
@@ -0,0 +1,125 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::class::Class;
+use jvm::typevalues::create_default_value;
+use jvm::typevalues::JvmType;
+use jvm::typevalues::JvmValue;
+use std::fmt;
+
+/// A FieldSlot describes one instance field and the
+/// value that it has in a newly created object.
+#[derive(Clone)]
+pub struct FieldSlot {
+	/// The name of the class that declares the field.
+	pub class_name: String,
+	pub name: String,
+	pub descriptor: String,
+	pub default: JvmValue,
+}
+
+/// A FieldLayout gives the position of every instance field of
+/// a class, including those that it inherits, in the slots of an
+/// instance of that class. The superclass' fields come first, so a
+/// field has the same offset in instances of every subclass of the
+/// class that declares it. Fields that shadow a field of the same
+/// name in a superclass get their own slot.
+#[derive(Clone, Default)]
+pub struct FieldLayout {
+	slots: Vec<FieldSlot>,
+}
+
+impl FieldLayout {
+	/// Compute the layout of `class`.
+	///
+	/// # Arguments
+	/// `superclass_layout`: The layout of `class`' superclass (None
+	/// if `class` has no superclass).
+	/// `class`: The class whose layout to compute.
+	///
+	/// # Return value:
+	/// The layout or, if one of the class' instance fields has a
+	/// malformed descriptor, the name and descriptor of that field.
+	pub fn new(
+		superclass_layout: Option<&FieldLayout>,
+		class: &Class,
+	) -> Result<FieldLayout, (String, String)> {
+		let mut slots = if let Some(superclass_layout) = superclass_layout {
+			superclass_layout.slots.clone()
+		} else {
+			Vec::new()
+		};
+		let class_name = class.get_class_name().unwrap_or_default();
+		let cp = class.get_constant_pool_ref();
+
+		for field in class.get_fields_ref().iter() {
+			if field.is_static() {
+				continue;
+			}
+			let name = field.get_name(cp).unwrap_or_default();
+			let descriptor = field.get_descriptor(cp).unwrap_or_default();
+			match JvmType::from_field_descriptor(&descriptor) {
+				Some(r#type) => slots.push(FieldSlot {
+					class_name: class_name.clone(),
+					name,
+					default: create_default_value(&r#type, field.access_flags),
+					descriptor,
+				}),
+				None => return Err((name, descriptor)),
+			}
+		}
+		Ok(FieldLayout { slots })
+	}
+
+	/// The offset of the field named `name` with type `descriptor`
+	/// that is declared in the class named `class_name`.
+	pub fn get_offset(&self, class_name: &str, name: &str, descriptor: &str) -> Option<usize> {
+		self.slots.iter().rposition(|slot| {
+			slot.name == name && slot.descriptor == descriptor && slot.class_name == class_name
+		})
+	}
+
+	/// The offset of the most-derived field named `name`, regardless of
+	/// the class that declares it. For use by the VM itself, when it
+	/// needs to access well-known fields of well-known classes.
+	pub fn get_offset_by_name(&self, name: &str) -> Option<usize> {
+		self.slots.iter().rposition(|slot| slot.name == name)
+	}
+
+	/// The values of the fields of a newly created object.
+	pub fn default_values(&self) -> Vec<JvmValue> {
+		self.slots.iter().map(|slot| slot.default.clone()).collect()
+	}
+}
+
+impl fmt::Display for FieldLayout {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut result: fmt::Result = Ok(());
+		for (offset, slot) in self.slots.iter().enumerate() {
+			result = write!(
+				f,
+				"{}: {}.{} ({})\n",
+				offset, slot.class_name, slot.name, slot.descriptor
+			);
+		}
+		result
+	}
+}
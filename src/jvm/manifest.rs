@@ -0,0 +1,130 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use rjar::Jar;
+use std::path::Path;
+
+/// The name of the manifest in a JAR file.
+pub const MANIFEST_NAME: &str = "META-INF/MANIFEST.MF";
+
+/// The main attributes of the manifest of a JAR file.
+#[derive(Default)]
+pub struct Manifest {
+	attributes: Vec<(String, String)>,
+}
+
+impl Manifest {
+	/// Read the main section of the manifest `manifest`: lines of
+	/// `Name: value`, where a line that starts with a space continues
+	/// the value of the line before it.
+	pub fn parse(manifest: &str) -> Manifest {
+		let mut attributes = Vec::<(String, String)>::new();
+		for line in manifest.lines() {
+			let line = line.trim_end_matches('\r');
+			/*
+			 * The main section ends at the first empty line.
+			 */
+			if line.is_empty() {
+				break;
+			}
+			if line.starts_with(' ') {
+				if let Some((_, value)) = attributes.last_mut() {
+					value.push_str(&line[1..]);
+				}
+				continue;
+			}
+			if let Some(separator) = line.find(':') {
+				let value = &line[separator + 1..];
+				attributes.push((
+					line[..separator].to_string(),
+					value.strip_prefix(' ').unwrap_or(value).to_string(),
+				));
+			}
+		}
+		Manifest { attributes }
+	}
+
+	/// Read the manifest of the (open) JAR file `jar`.
+	pub fn from_open_jar(jar: &mut Jar) -> Option<Manifest> {
+		let bytes = jar.file_contents_by_name(&MANIFEST_NAME.to_string()).ok()?;
+		Some(Manifest::parse(&String::from_utf8_lossy(&bytes)))
+	}
+
+	/// The value of the main attribute `name` (whose case does not
+	/// matter).
+	pub fn attribute(&self, name: &str) -> Option<&str> {
+		self.attributes
+			.iter()
+			.find(|(attribute, _)| attribute.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value.trim())
+	}
+
+	/// The binary name of the Main-Class, e.g., com/acme/Tool.
+	pub fn main_class(&self) -> Option<String> {
+		self.attribute("Main-Class")
+			.filter(|main_class| !main_class.is_empty())
+			.map(|main_class| main_class.replace('.', "/"))
+	}
+
+	/// The entries of the Class-Path of the JAR file `jar_file`, as
+	/// paths relative to its directory.
+	pub fn class_path(&self, jar_file: &str) -> Vec<String> {
+		let directory = Path::new(jar_file).parent().unwrap_or(Path::new(""));
+		self.attribute("Class-Path")
+			.unwrap_or("")
+			.split_whitespace()
+			.map(|url| {
+				let path = decode_url_path(url.trim_start_matches("file:"));
+				if Path::new(&path).is_absolute() {
+					path
+				} else {
+					directory.join(&path).to_string_lossy().into_owned()
+				}
+			})
+			.collect()
+	}
+
+	/// Whether the JAR file is a multi-release JAR file.
+	pub fn multi_release(&self) -> bool {
+		self.attribute("Multi-Release")
+			.map_or(false, |multi_release| {
+				multi_release.eq_ignore_ascii_case("true")
+			})
+	}
+}
+
+/// The path of the relative URL `url`: %XX escapes are decoded.
+fn decode_url_path(url: &str) -> String {
+	let mut bytes = Vec::<u8>::new();
+	let mut rest = url.as_bytes();
+	while let Some((&byte, tail)) = rest.split_first() {
+		if byte == b'%' && tail.len() >= 2 {
+			if let Ok(decoded) = u8::from_str_radix(&String::from_utf8_lossy(&tail[..2]), 16) {
+				bytes.push(decoded);
+				rest = &tail[2..];
+				continue;
+			}
+		}
+		bytes.push(byte);
+		rest = tail;
+	}
+	String::from_utf8_lossy(&bytes).into_owned()
+}
@@ -19,12 +19,15 @@
  * You should have received a copy of the GNU General Public License
  * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
  */
+use jvm::attribute::codeattributes::CodeAttribute;
+use jvm::attribute::linenumbertable::LineNumberTable;
 use jvm::attribute::Attributes;
 use jvm::constant::Constant;
 use jvm::constant::Utf8Reserved;
 use jvm::constantpool::ConstantPool;
 use jvm::error::FatalError;
 use jvm::error::FatalErrorType;
+use jvm::exceptions::ExceptionTable;
 use jvm::typevalues::JvmPrimitiveType;
 use jvm::typevalues::JvmType;
 use std::fmt;
@@ -59,9 +62,60 @@ pub struct Method {
 	pub parameter_count: usize,
 	pub return_type: JvmType,
 	pub attributes: Attributes,
+	/// The handlers of the method's code (JVMS 4.7.3), parsed when the
+	/// method is loaded. None for a method without code.
+	exception_table: Option<ExceptionTable>,
+}
+
+/// The field descriptors of the parameters in the method descriptor
+/// `descriptor` (JVMS 4.3.3), e.g., I and [Ljava/lang/String; for
+/// (I[Ljava/lang/String;)V.
+pub fn parameter_descriptors(descriptor: &str) -> Vec<String> {
+	let mut parameters = Vec::new();
+	let signature = descriptor.as_bytes();
+	if signature.first() != Some(&b'(') {
+		return parameters;
+	}
+	let mut i = 1;
+	while i < signature.len() && signature[i] != b')' {
+		let start = i;
+		while i < signature.len() && signature[i] == b'[' {
+			i += 1;
+		}
+		if i < signature.len() && signature[i] == b'L' {
+			/*
+			 * Lsome/class/name;
+			 * means a reference to a class of that name.
+			 */
+			while i < signature.len() && signature[i] != b';' {
+				i += 1;
+			}
+		}
+		i += 1;
+		parameters.push(descriptor[start..i.min(descriptor.len())].to_string());
+	}
+	parameters
 }
 
 impl Method {
+	pub fn get_name(&self, cp: &ConstantPool) -> Option<String> {
+		if let Constant::Utf8(_, _, _, name) = cp.get_constant_ref(self.name_index as usize) {
+			Some(name.clone())
+		} else {
+			None
+		}
+	}
+
+	pub fn get_descriptor(&self, cp: &ConstantPool) -> Option<String> {
+		if let Constant::Utf8(_, _, _, descriptor) =
+			cp.get_constant_ref(self.descriptor_index as usize)
+		{
+			Some(descriptor.clone())
+		} else {
+			None
+		}
+	}
+
 	pub fn get_code(&self, cp: &ConstantPool) -> Option<&[u8]> {
 		for i in 0..self.attributes.len() {
 			let attribute = self.attributes.get_ref(i);
@@ -76,6 +130,38 @@ impl Method {
 		None
 	}
 
+	/// The method's exception handlers (JVMS 4.7.3).
+	pub fn get_exception_table(&self) -> Option<&ExceptionTable> {
+		self.exception_table.as_ref()
+	}
+
+	/// The line of the source file that the instruction at `pc` comes
+	/// from, if the method's code has a LineNumberTable (JVMS 4.7.12).
+	pub fn get_line_number(&self, cp: &ConstantPool, pc: usize) -> Option<u16> {
+		for i in 0..self.attributes.len() {
+			let attribute = self.attributes.get_ref(i);
+			if let Constant::Utf8(_, Utf8Reserved::Code, _, _) =
+				cp.get_constant_ref(attribute.attribute_name_index as usize)
+			{
+				let code = CodeAttribute::from(attribute.info.clone());
+				let code_attributes = code.attributes();
+				for j in 0..code_attributes.len() {
+					let code_attribute = code_attributes.get_ref(j);
+					if let Constant::Utf8(_, Utf8Reserved::LineNumberTable, _, _) =
+						cp.get_constant_ref(code_attribute.attribute_name_index as usize)
+					{
+						if let Some(line_number) =
+							LineNumberTable::from(&code_attribute.info).line_number(pc)
+						{
+							return Some(line_number);
+						}
+					}
+				}
+			}
+		}
+		None
+	}
+
 	pub fn byte_len(&self) -> usize {
 		self.byte_len
 	}
@@ -92,6 +178,7 @@ impl<'l> From<(&'l Vec<u8>, &'l ConstantPool)> for Method {
 		let max_locals: usize;
 		let parameter_count: usize;
 		let return_type: JvmType;
+		let exception_table: Option<ExceptionTable>;
 
 		access_flags = (bytes[offset] as u16) << 8 | (bytes[offset + 1] as u16) << 0;
 		offset += 2;
@@ -122,32 +209,32 @@ impl<'l> From<(&'l Vec<u8>, &'l ConstantPool)> for Method {
 			max_locals
 		};
 
+		/*
+		 * Get the exception handlers.
+		 */
+		exception_table = {
+			let mut exception_table: Option<ExceptionTable> = None;
+			for i in 0..attributes.len() {
+				let attribute = attributes.get_ref(i);
+				if let Constant::Utf8(_, Utf8Reserved::Code, _, _) =
+					cp.get_constant_ref(attribute.attribute_name_index as usize)
+				{
+					exception_table = Some(
+						CodeAttribute::from(attribute.info.clone())
+							.exception_table()
+							.clone(),
+					);
+				}
+			}
+			exception_table
+		};
+
 		/*
 		 * Get the parameter count.
 		 */
 		parameter_count =
 			if let Constant::Utf8(_, _, _, s) = cp.get_constant_ref(descriptor_index as usize) {
-				let mut parameter_count: usize = 0;
-				let signature = s.as_bytes();
-				if signature[0] == '(' as u8 {
-					let mut i = 1;
-					while i < signature.len() && signature[i] != ')' as u8 {
-						if signature[i] == 'L' as u8 {
-							/*
-							 * Lsome/class/name;
-							 * means a reference to a class of that name.
-							 */
-							while signature[i] != ';' as u8 {
-								i = i + 1;
-							}
-						} else if signature[i] == '[' as u8 {
-							i = i + 1;
-						}
-						i = i + 1;
-						parameter_count += 1;
-					}
-				}
-				parameter_count
+				parameter_descriptors(s).len()
 			} else {
 				0
 			};
@@ -177,6 +264,7 @@ impl<'l> From<(&'l Vec<u8>, &'l ConstantPool)> for Method {
 			parameter_count: parameter_count,
 			return_type: return_type,
 			attributes,
+			exception_table,
 		}
 	}
 }
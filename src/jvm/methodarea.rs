@@ -30,6 +30,8 @@ use jvm::error::FatalError;
 use jvm::error::FatalErrorType;
 use jvm::method::Method;
 use jvm::method::MethodAccessFlags;
+use jvm::typevalues::class_names_of_descriptor;
+use jvm::typevalues::JvmReferenceTargetType;
 use jvm::typevalues::JvmValue;
 use rjar::Jar;
 use std::collections::HashMap;
@@ -42,6 +44,16 @@ use std::sync::LockResult;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
 
+/// Identifies a class loader: the bootstrap class loader, which loads
+/// the classes of the class path, or a java.lang.ClassLoader object (by
+/// its identity; see MethodArea::register_class_loader). A class is
+/// known by its name and the loader that defined it, so classes with
+/// the same name coexist in different loaders.
+pub type ClassLoaderId = usize;
+
+/// The bootstrap class loader (null in Java).
+pub const BOOTSTRAP_LOADER: ClassLoaderId = 0;
+
 /// A LoadedClass holds *the* first reference to
 /// `class` and can be used to determine whether the
 /// `class` has been initialized.
@@ -68,7 +80,45 @@ impl LoadedClass {
 pub struct MethodArea {
 	debug_level: DebugLevel,
 	environment: Environment,
-	classes: HashMap<String, Arc<LoadedClass>>,
+	/// The loaded classes, keyed by their name and defining loader.
+	classes: HashMap<(String, ClassLoaderId), Arc<LoadedClass>>,
+	/// The defining loader of each class that a class loader other than
+	/// the bootstrap class loader initiated the loading of (JVMS 5.3),
+	/// keyed by the class' name and the initiating loader.
+	initiated_classes: HashMap<(String, ClassLoaderId), ClassLoaderId>,
+	/// The ClassLoader object of each class loader that has been used.
+	class_loaders: HashMap<ClassLoaderId, JvmValue>,
+	/// The loading constraints (JVMS 5.3.4): the two loaders must load
+	/// the same class with the name.
+	loading_constraints: Vec<(String, ClassLoaderId, ClassLoaderId)>,
+	/// The pool of interned strings (JVMS 5.1), keyed by their contents.
+	interned_strings: HashMap<String, JvmValue>,
+	/// The Class object of each type that has one, keyed by the type's
+	/// name (see typevalues::type_name_of_descriptor) and the class
+	/// loader that defined it.
+	mirrors: HashMap<(String, ClassLoaderId), JvmValue>,
+	/// The type (and its defining loader) of each Class object, keyed by
+	/// the object's identity.
+	mirrored_types: HashMap<usize, (String, ClassLoaderId)>,
+}
+
+/// The name of the (run-time) package of the class named `class_name`.
+pub fn package_name(class_name: &str) -> &str {
+	match class_name.rfind('/') {
+		Some(index) => &class_name[..index],
+		None => "",
+	}
+}
+
+/// The identity of the object that `value` references, if it
+/// references one.
+fn object_identity(value: &JvmValue) -> Option<usize> {
+	match value {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+			Some(Arc::as_ptr(object) as usize)
+		}
+		_ => None,
+	}
 }
 
 impl MethodArea {
@@ -77,10 +127,126 @@ impl MethodArea {
 			debug_level,
 			environment: environment,
 			classes: HashMap::new(),
+			initiated_classes: HashMap::new(),
+			class_loaders: HashMap::new(),
+			loading_constraints: Vec::new(),
+			interned_strings: HashMap::new(),
+			mirrors: HashMap::new(),
+			mirrored_types: HashMap::new(),
 		};
 		result
 	}
 
+	/// The command-line arguments to the Java program.
+	pub fn get_arguments(&self) -> Vec<String> {
+		self.environment.arguments.clone()
+	}
+
+	/// Where the class named `class_name` is on the class path.
+	pub fn get_class_location(&self, class_name: &str) -> Option<ClassLocation> {
+		self.environment.class_location_for_class(class_name)
+	}
+
+	/// The entries of the class path.
+	pub fn get_classpath_entries(&self) -> Vec<String> {
+		self.environment.classpath_entries.clone()
+	}
+
+	/// The entries of the boot class path.
+	pub fn get_boot_classpath_entries(&self) -> Vec<String> {
+		self.environment.boot_classpath_entries.clone()
+	}
+
+	/// Get the reference to the interned String object whose contents
+	/// are `value`, if there is one.
+	/// This must be called with the methodarea locked.
+	pub fn get_interned_string(&self, value: &str) -> Option<JvmValue> {
+		if let Some(string) = self.interned_strings.get(value) {
+			Some(string.clone())
+		} else {
+			None
+		}
+	}
+
+	/// Intern `string`, a reference to a String object whose contents
+	/// are `value`. If a String with the same contents was interned
+	/// first, that one wins and is returned instead.
+	/// This must be called with the methodarea locked.
+	pub fn intern_string(&mut self, value: &str, string: JvmValue) -> JvmValue {
+		self.interned_strings
+			.entry(value.to_string())
+			.or_insert(string)
+			.clone()
+	}
+
+	/// The Class object of the type named `type_name` that `loader`
+	/// defined, if it has one.
+	/// This must be called with the methodarea locked.
+	pub fn get_mirror(&self, type_name: &str, loader: ClassLoaderId) -> Option<JvmValue> {
+		self.mirrors.get(&(type_name.to_string(), loader)).cloned()
+	}
+
+	/// Make `mirror` the Class object of the type named `type_name` that
+	/// `loader` defined. If the type got one first, that one wins and is
+	/// returned instead.
+	/// This must be called with the methodarea locked.
+	pub fn add_mirror(
+		&mut self,
+		type_name: &str,
+		loader: ClassLoaderId,
+		mirror: JvmValue,
+	) -> JvmValue {
+		let key = (type_name.to_string(), loader);
+		if let Some(existing) = self.mirrors.get(&key) {
+			return existing.clone();
+		}
+		if let Some(identity) = object_identity(&mirror) {
+			self.mirrored_types.insert(identity, key.clone());
+		}
+		self.mirrors.insert(key, mirror.clone());
+		mirror
+	}
+
+	/// The name of the type whose Class object is `mirror`. None if
+	/// `mirror` is not a Class object.
+	/// This must be called with the methodarea locked.
+	pub fn mirrored_type(&self, mirror: &JvmValue) -> Option<String> {
+		self.mirrored_types
+			.get(&object_identity(mirror)?)
+			.map(|(type_name, _)| type_name.clone())
+	}
+
+	/// The loader that defined the type whose Class object is `mirror`.
+	/// This must be called with the methodarea locked.
+	pub fn mirrored_loader(&self, mirror: &JvmValue) -> Option<ClassLoaderId> {
+		self.mirrored_types
+			.get(&object_identity(mirror)?)
+			.map(|(_, loader)| *loader)
+	}
+
+	/// The identity of the class loader whose ClassLoader object is
+	/// `class_loader` (the bootstrap class loader for null). The method
+	/// area keeps the object, so its identity stays unique.
+	/// This must be called with the methodarea locked.
+	pub fn register_class_loader(&mut self, class_loader: &JvmValue) -> ClassLoaderId {
+		match object_identity(class_loader) {
+			Some(identity) => {
+				self.class_loaders
+					.entry(identity)
+					.or_insert_with(|| class_loader.clone());
+				identity
+			}
+			None => BOOTSTRAP_LOADER,
+		}
+	}
+
+	/// The ClassLoader object of `loader`; None for the bootstrap class
+	/// loader.
+	/// This must be called with the methodarea locked.
+	pub fn class_loader_object(&self, loader: ClassLoaderId) -> Option<JvmValue> {
+		self.class_loaders.get(&loader).cloned()
+	}
+
 	/// If the class named `class_name` is loaded into the method area,
 	/// this function will increase its reference count by one and move
 	/// that reference count to the caller.
@@ -91,18 +257,36 @@ impl MethodArea {
 	/// Optionally, a reference to the class named `class_name`. None if
 	/// the class is not loaded into the methodarea.
 	pub fn get_class_rc(&self, class_name: &String) -> Option<Rc<Class>> {
-		if let Some(loaded_class) = self.classes.get(class_name) {
+		if let Some(loaded_class) = self
+			.classes
+			.get(&(class_name.to_string(), BOOTSTRAP_LOADER))
+		{
 			Some(Rc::clone(&(loaded_class.class)))
 		} else {
 			None
 		}
 	}
 
+	/// Whether the class named `class_name` is loaded or can be found
+	/// on the class path.
+	/// This must be called with the methodarea locked.
+	pub fn can_load_class(&self, class_name: &String) -> bool {
+		self.classes
+			.contains_key(&(class_name.to_string(), BOOTSTRAP_LOADER))
+			|| self
+				.environment
+				.class_location_for_class(class_name)
+				.is_some()
+	}
+
 	///
 	/// Must call this with the methodarea locked.
 	///
 	pub fn maybe_load_class(&mut self, class_name: &String) {
-		if let None = self.classes.get(class_name) {
+		if let None = self
+			.classes
+			.get(&(class_name.to_string(), BOOTSTRAP_LOADER))
+		{
 			match self.environment.class_location_for_class(class_name) {
 				Some(ClassLocation::ClassFile(location)) => {
 					self.load_class_from_file(&location);
@@ -114,15 +298,53 @@ impl MethodArea {
 						}
 					}
 				}
+				Some(ClassLocation::Runtime(bytes)) => {
+					self.load_class_from_bytes(bytes.to_vec());
+				}
+				Some(ClassLocation::JmodFile(jmodfile, location)) => {
+					if let Ok(mut jmod) = Jar::open(&jmodfile) {
+						if let Ok(bytes) = jmod.file_contents_by_name(&location) {
+							self.load_class_from_bytes(bytes);
+						}
+					}
+				}
+				Some(ClassLocation::JImage(image, resource)) => {
+					/*
+					 * The resources of a jimage linked with jlink
+					 * --compress cannot be read.
+					 */
+					if resource.compressed {
+						FatalError::new(FatalErrorType::CompressedResource(
+							class_name.to_string(),
+							image.path().to_string(),
+						))
+						.call();
+					}
+					if let Some(bytes) = image.read_resource(&resource) {
+						self.load_class_from_bytes(bytes);
+					}
+				}
+				Some(ClassLocation::Source(source, class_name)) => {
+					if let Some(bytes) = source.class_bytes(&class_name) {
+						self.load_class_from_bytes(bytes);
+					}
+				}
 				None => {
-					println!("error: no path to {}", class_name);
+					Debug(
+						format!("No path to {}", class_name),
+						&self.debug_level,
+						DebugLevel::Info,
+					);
 				}
 			}
 		}
 	}
 
 	pub fn get_loaded_class(&mut self, class_name: &String) -> Option<Arc<LoadedClass>> {
-		if let Some(loaded_class) = self.classes.get(class_name) {
+		if let Some(loaded_class) = self
+			.classes
+			.get(&(class_name.to_string(), BOOTSTRAP_LOADER))
+		{
 			Some(Arc::clone(loaded_class))
 		} else {
 			FatalError::new(FatalErrorType::ClassNotFound(class_name.to_string())).call();
@@ -130,81 +352,336 @@ impl MethodArea {
 		}
 	}
 
+	/// The LoadedClass of `class` (which its defining loader loaded).
+	/// This must be called with the methodarea locked.
+	pub fn loaded_class_of(&self, class: &Rc<Class>) -> Option<Arc<LoadedClass>> {
+		self.classes
+			.get(&(
+				class.get_class_name().unwrap_or_default(),
+				class.get_defining_loader(),
+			))
+			.cloned()
+	}
+
+	/// The class named `class_name` that `loader` has loaded: that it
+	/// defined or initiated the loading of. None if it has not (yet).
+	/// This must be called with the methodarea locked.
+	pub fn find_class(&self, class_name: &str, loader: ClassLoaderId) -> Option<Rc<Class>> {
+		let key = (class_name.to_string(), loader);
+		let defining_loader = self.initiated_classes.get(&key).cloned().unwrap_or(loader);
+		self.classes
+			.get(&(class_name.to_string(), defining_loader))
+			.map(|loaded_class| Rc::clone(&loaded_class.class))
+	}
+
+	/// The class named `class_name` in the namespace of `loader`: the
+	/// bootstrap class loader loads it from the class path if need be;
+	/// any other loader must have loaded it already (the VM asks it to
+	/// with ClassLoader.loadClass; see JvmThread::load_class).
+	/// This must be called with the methodarea locked.
+	pub fn find_or_load_class(
+		&mut self,
+		class_name: &String,
+		loader: ClassLoaderId,
+	) -> Option<Rc<Class>> {
+		if loader != BOOTSTRAP_LOADER {
+			return self.find_class(class_name, loader);
+		}
+		if !self.can_load_class(class_name) {
+			return None;
+		}
+		self.maybe_load_class(class_name);
+		self.get_class_rc(class_name)
+	}
+
+	/// Record that `loader` initiated the loading of `class` (i.e., its
+	/// loadClass returned it), so that it resolves `class`' name to
+	/// `class` from now on.
+	///
+	/// # Return value:
+	/// The message of the LinkageError to throw if that violates a
+	/// loading constraint (or `loader` already has another class with
+	/// the name).
+	/// This must be called with the methodarea locked.
+	pub fn record_initiating_loader(
+		&mut self,
+		class: &Rc<Class>,
+		loader: ClassLoaderId,
+	) -> Result<(), String> {
+		let class_name = class.get_class_name().unwrap_or_default();
+		if loader == BOOTSTRAP_LOADER || loader == class.get_defining_loader() {
+			return Ok(());
+		}
+		if let Some(existing) = self.find_class(&class_name, loader) {
+			if Rc::ptr_eq(&existing, class) {
+				return Ok(());
+			}
+			return Err(format!(
+				"loader {:#x} attempted duplicate class definition for {}",
+				loader,
+				class_name.replace('/', ".")
+			));
+		}
+		self.check_loading_constraints(&class_name, loader, class.get_defining_loader())?;
+		self.initiated_classes
+			.insert((class_name, loader), class.get_defining_loader());
+		Ok(())
+	}
+
+	/// Whether loading the class named `class_name` defined by
+	/// `defining_loader` into `loader` keeps every loading constraint on
+	/// the name and `loader`: the other loader of the constraint must not
+	/// have loaded a different class with the name.
+	fn check_loading_constraints(
+		&self,
+		class_name: &str,
+		loader: ClassLoaderId,
+		defining_loader: ClassLoaderId,
+	) -> Result<(), String> {
+		for (constrained_name, first, second) in &self.loading_constraints {
+			if constrained_name != class_name {
+				continue;
+			}
+			let other = if *first == loader {
+				*second
+			} else if *second == loader {
+				*first
+			} else {
+				continue;
+			};
+			if let Some(other_class) = self.find_class(class_name, other) {
+				if other_class.get_defining_loader() != defining_loader {
+					return Err(format!(
+						"loader constraint violation: loaders {:#x} and {:#x} have different classes named {}",
+						loader,
+						other,
+						class_name.replace('/', ".")
+					));
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Impose the loading constraints (JVMS 5.3.4) that referencing a
+	/// method or field of type `descriptor` across the loaders `first`
+	/// and `second` requires: they must load the same classes with the
+	/// names in `descriptor`.
+	///
+	/// # Return value:
+	/// The message of the LinkageError to throw if they already load
+	/// different classes with one of the names.
+	/// This must be called with the methodarea locked.
+	pub fn add_loading_constraints(
+		&mut self,
+		descriptor: &str,
+		first: ClassLoaderId,
+		second: ClassLoaderId,
+	) -> Result<(), String> {
+		if first == second {
+			return Ok(());
+		}
+		for class_name in class_names_of_descriptor(descriptor) {
+			if let (Some(first_class), Some(second_class)) = (
+				self.find_class(&class_name, first),
+				self.find_class(&class_name, second),
+			) {
+				if !Rc::ptr_eq(&first_class, &second_class) {
+					return Err(format!(
+						"loader constraint violation: loaders {:#x} and {:#x} have different classes named {}",
+						first,
+						second,
+						class_name.replace('/', ".")
+					));
+				}
+			}
+			let constraint = (class_name, first, second);
+			if !self.loading_constraints.contains(&constraint) {
+				self.loading_constraints.push(constraint);
+			}
+		}
+		Ok(())
+	}
+
+	/// Define `class` (parsed from a class file) with `loader` as its
+	/// defining loader (i.e., ClassLoader.defineClass). `loader` must have
+	/// loaded its superclass already.
+	///
+	/// # Return value:
+	/// The class, or the name of the exception to throw and its message.
+	/// This must be called with the methodarea locked.
+	pub fn define_class_with_loader(
+		&mut self,
+		mut class: Class,
+		loader: ClassLoaderId,
+	) -> Result<Rc<Class>, (&'static str, String)> {
+		let class_name = class.get_class_name().unwrap_or_default();
+		if self.find_class(&class_name, loader).is_some() {
+			return Err((
+				"java/lang/LinkageError",
+				format!(
+					"loader {:#x} attempted duplicate class definition for {}",
+					loader,
+					class_name.replace('/', ".")
+				),
+			));
+		}
+		if let Err(message) = self.check_loading_constraints(&class_name, loader, loader) {
+			return Err(("java/lang/LinkageError", message));
+		}
+		class.set_defining_loader(loader);
+		match self.define_class(class) {
+			Some(class) => Ok(class),
+			None => Err(("java/lang/NoClassDefFoundError", class_name)),
+		}
+	}
+
+	/// Select the method to invoke (JVMS 5.4.6) when `resolved_method`,
+	/// declared in `resolved_class`, is invoked on an instance of
+	/// `invoked_class`: the method that overrides it, in `invoked_class`
+	/// or the nearest of its superclasses that has one, or else a default
+	/// method of one of their superinterfaces.
+	///
+	/// # Return value:
+	/// The class that declares the selected method and the method.
 	pub fn select_method(
 		&mut self,
 		invoked_class: &Rc<Class>,
+		resolved_class: &Rc<Class>,
 		method_name: &String,
 		method_type: &String,
 	) -> Option<(Rc<Class>, Rc<Method>)> {
-		let mut target_class = Rc::clone(invoked_class);
-		let mut result: Option<(Rc<Class>, Rc<Method>)> = None;
+		let resolved_class_name = resolved_class.get_class_name().unwrap_or_default();
+		let resolved_package = package_name(&resolved_class_name);
+		let mut target_class = Some(Rc::clone(invoked_class));
 
-		while {
-			if let Some(target_method) = target_class.get_methods_ref().get_by_name_and_type(
+		while let Some(class) = target_class {
+			if let Some(target_method) = class.get_methods_ref().get_by_name_and_type(
 				method_name,
 				method_type,
-				target_class.get_constant_pool_ref(),
+				class.get_constant_pool_ref(),
 			) {
-				if (target_method.access_flags & (MethodAccessFlags::Private as u16) == 0)
-					&& ((target_method.access_flags & (MethodAccessFlags::Public as u16) != 0)
-						|| (target_method.access_flags & (MethodAccessFlags::Protected as u16)
-							!= 0) || false/* TODO: mA is marked neither ACC_PUBLIC nor ACC_PROTECTED nor ACC_PRIVATE, and either (a) the declaration of mA appears in the same run-time package as the declaration of mC, or (b) if mA is declared in a class A and mC is declared in a class C, then there exists a method mB declared in a class B such that C is a subclass of B and B is a subclass of A and mC can override mB and mB can override mA. */)
+				/*
+				 * A package-private method only overrides one that is
+				 * declared in the same run-time package (the rest of
+				 * the transitive overriding rules are not checked).
+				 */
+				let access_flags = target_method.access_flags;
+				let package_private = access_flags
+					& ((MethodAccessFlags::Public as u16)
+						| (MethodAccessFlags::Protected as u16)
+						| (MethodAccessFlags::Private as u16))
+					== 0;
+				let class_name = class.get_class_name().unwrap_or_default();
+				if (access_flags & (MethodAccessFlags::Private as u16) == 0
+					&& (!package_private || package_name(&class_name) == resolved_package))
+					|| Rc::ptr_eq(&class, resolved_class)
 				{
 					Debug(
-						format!(
-							"Method {} selected to {}.",
-							method_name,
-							target_class.get_class_name().unwrap()
-						),
+						format!("Method {} selected to {}.", method_name, class_name),
 						&self.debug_level,
 						DebugLevel::Info,
 					);
-					result = Some((target_class.clone(), target_method));
+					return Some((class, target_method));
 				}
 			}
 
 			/*
-			 * If we did not find a result (either because it doesn't exist or
-			 * because it did not qualify as an override), then we are going
-			 * to set ourselves up to look in the superclass.
+			 * It either doesn't exist or it does not qualify as an
+			 * override: look in the superclass.
 			 */
-			match result {
-				None => {
-					let mut traversed_to_superclass = false;
-					if let Some(superclass_name) = target_class.resolve_superclass() {
-						if let Some(superclass) = self.get_class_rc(&superclass_name) {
-							target_class = superclass;
-							traversed_to_superclass = true;
-						}
-					} else {
-						/*
-						 * TODO: We walked to the top of the class hierarchy
-						 * and couldn't find a method. This will not happen because
-						 * we already resolved a method that the compiler guaranteed
-						 * is somewhere in the hierarchy and we are now just looking
-						 * for something that overrides it. So, it is a fatal error.
-						 */
-					}
-					traversed_to_superclass
+			target_class = match class.resolve_superclass() {
+				Some(superclass_name) => {
+					self.find_or_load_class(&superclass_name, class.get_defining_loader())
 				}
-				_ => false,
-			}
-		} {}
+				None => None,
+			};
+		}
 
 		/*
-		 * If we didn't find anything there, then let's look in
-		 * the superinterfaces:
-		 * Otherwise, the maximally-specific superinterface methods of C are determined (§5.4.3.3). If exactly one matches mR's name and descriptor and is not abstract, then it is the selected method.
+		 * Otherwise, the maximally-specific superinterface methods of C
+		 * are determined (§5.4.3.3). If exactly one matches mR's name and
+		 * descriptor and is not abstract, then it is the selected method.
 		 */
-		match result {
-			None => assert!(false, "TODO: Look in the superinterfaces."),
-			_ => {}
+		self.find_superinterface_method(invoked_class, method_name, method_type, true)
+	}
+
+	/// Find a method named `method_name` with type `method_type` in the
+	/// superinterfaces of `class` (and of its superclasses), nearest
+	/// first. If `concrete`, only a method with code (i.e., a default
+	/// method) qualifies.
+	fn find_superinterface_method(
+		&mut self,
+		class: &Rc<Class>,
+		method_name: &String,
+		method_type: &String,
+		concrete: bool,
+	) -> Option<(Rc<Class>, Rc<Method>)> {
+		/*
+		 * The name of each superinterface is resolved by the loader of
+		 * the class (or interface) that names it.
+		 */
+		let mut pending: Vec<(String, ClassLoaderId)> = Vec::new();
+		let mut target_class = Some(Rc::clone(class));
+		while let Some(class) = target_class {
+			let loader = class.get_defining_loader();
+			pending.extend(
+				class
+					.get_interface_names()
+					.into_iter()
+					.map(|interface_name| (interface_name, loader)),
+			);
+			target_class = match class.resolve_superclass() {
+				Some(superclass_name) => self.find_or_load_class(&superclass_name, loader),
+				None => None,
+			};
 		}
-		result
+
+		let mut visited: Vec<(String, ClassLoaderId)> = Vec::new();
+		let mut index = 0;
+		while index < pending.len() {
+			let entry = pending[index].clone();
+			index += 1;
+			if visited.contains(&entry) {
+				continue;
+			}
+			visited.push(entry.clone());
+			let (interface_name, loader) = entry;
+			let interface = match self.find_or_load_class(&interface_name, loader) {
+				Some(interface) => interface,
+				None => continue,
+			};
+			if let Some(method) = interface.get_methods_ref().get_by_name_and_type(
+				method_name,
+				method_type,
+				interface.get_constant_pool_ref(),
+			) {
+				let flags = method.access_flags;
+				if !concrete
+					|| (flags
+						& ((MethodAccessFlags::Abstract as u16)
+							| (MethodAccessFlags::Static as u16)
+							| (MethodAccessFlags::Private as u16))
+						== 0)
+				{
+					return Some((interface, method));
+				}
+			}
+			let loader = interface.get_defining_loader();
+			pending.extend(
+				interface
+					.get_interface_names()
+					.into_iter()
+					.map(|interface_name| (interface_name, loader)),
+			);
+		}
+		None
 	}
 
-	// Return the name of the class that contains the referenced field.
-	// field_class: The class where the reference originally occured.
+	// Return the class that contains the referenced field
+	// (JVMS 5.4.3.2): the class itself, then (recursively) its direct
+	// superinterfaces and, finally, (recursively) its superclass.
+	// field_class: The class named by the field reference.
 	// field_name: The name of the field.
 	// field_type: The type of the field.
 	pub fn resolve_field(
@@ -212,117 +689,136 @@ impl MethodArea {
 		field_class: &Rc<Class>,
 		field_name: &String,
 		field_type: &String,
-	) -> Option<String> {
-		let mut target_class = field_class;
-		while {
-			if target_class
-				.get_fields_ref()
-				.contains_field_with_name_and_type(
-					field_name,
-					field_type,
-					target_class.get_constant_pool_ref(),
-				) {
-				return target_class.get_class_name();
-			} else {
-				assert!(false, "Must look in the super class for a field.");
-				target_class = target_class;
-				true // go again
+	) -> Option<Rc<Class>> {
+		if field_class
+			.get_fields_ref()
+			.contains_field_with_name_and_type(
+				field_name,
+				field_type,
+				field_class.get_constant_pool_ref(),
+			) {
+			return Some(Rc::clone(field_class));
+		}
+
+		let loader = field_class.get_defining_loader();
+		for interface_name in field_class.get_interface_names() {
+			if let Some(interface) = self.find_or_load_class(&interface_name, loader) {
+				if let Some(resolved) = self.resolve_field(&interface, field_name, field_type) {
+					return Some(resolved);
+				}
+			}
+		}
+
+		if let Some(superclass_name) = field_class.resolve_superclass() {
+			if let Some(superclass) = self.find_or_load_class(&superclass_name, loader) {
+				return self.resolve_field(&superclass, field_name, field_type);
 			}
-		} {}
+		}
 		None
 	}
 
+	/// Resolve the method named `method_name` with type `method_type`
+	/// that `invoking_class` references in `invoked_class` (JVMS 5.4.3.3
+	/// and, for an interface, 5.4.3.4): look in `invoked_class` and its
+	/// superclasses (for an interface, in java/lang/Object) and then in
+	/// its superinterfaces.
+	///
+	/// # Return value:
+	/// The class that declares the resolved method and the method. None
+	/// if there is no such method.
 	pub fn resolve_method(
 		&mut self,
 		invoking_class: &Rc<Class>,
 		invoked_class: &Rc<Class>,
 		method_name: &String,
 		method_type: &String,
-	) -> Option<Rc<Method>> {
-		let mut target_class = invoked_class;
-		let mut result: Option<Rc<Method>> = None;
-
+	) -> Option<(Rc<Class>, Rc<Method>)> {
 		/*
-		 * TODO: Check whether class is an interface. This is an
-		 * IncompatibleClassChangeError.
+		 * TODO:
+		 * If [target_class] declares exactly one method with the name
+		 * specified by the method reference, and the declaration
+		 * is a signature polymorphic method (§2.9), then method
+		 * lookup succeeds. All the class names mentioned in the
+		 * descriptor are resolved (§5.4.3.1).
 		 */
-
-		while {
-			/*
-			 * TODO:
-			 * If [target_class] declares exactly one method with the name
-			 * specified by the method reference, and the declaration
-			 * is a signature polymorphic method (§2.9), then method
-			 * lookup succeeds. All the class names mentioned in the
-			 * descriptor are resolved (§5.4.3.1).
-			 */
-
+		let mut target_class = Some(Rc::clone(invoked_class));
+		while let Some(class) = target_class {
 			/*
 			 * ... if [class] declares a method with the name and
 			 * descriptor specified by the method reference, method
 			 * lookup succeeds.
 			 */
-			if let Some(target_method) = target_class.get_methods_ref().get_by_name_and_type(
+			if let Some(target_method) = class.get_methods_ref().get_by_name_and_type(
 				method_name,
 				method_type,
-				target_class.get_constant_pool_ref(),
+				class.get_constant_pool_ref(),
 			) {
 				Debug(
 					format!(
 						"Method {} resolved to {}.",
 						method_name,
-						target_class.get_class_name().unwrap()
+						class.get_class_name().unwrap()
 					),
 					&self.debug_level,
 					DebugLevel::Info,
 				);
-
-				result = Some(target_method);
-				false /* this will break the loop */
-			} else {
 				/*
-				 * ...  if [target_class] has a superclass, step 2 of method
-				 * lookup is recursively invoked on the direct superclass
-				 * of [class].
+				 * The caller checks the loading constraints (see
+				 * add_loading_constraints).
 				 */
-				assert!(false, "Must look in the super class for a method");
-				target_class = target_class;
-				true
+				return Some((class, target_method));
 			}
-		} {}
+
+			/*
+			 * ...  if [target_class] has a superclass, step 2 of method
+			 * lookup is recursively invoked on the direct superclass
+			 * of [class]. An interface's "superclass" is Object.
+			 */
+			let superclass_name = if class.is_interface() {
+				if class.get_class_name().as_deref() == Some("java/lang/Object") {
+					None
+				} else {
+					Some("java/lang/Object".to_string())
+				}
+			} else {
+				class.resolve_superclass()
+			};
+			target_class = match superclass_name {
+				Some(superclass_name) => {
+					self.find_or_load_class(&superclass_name, class.get_defining_loader())
+				}
+				None => None,
+			};
+		}
 
 		/*
 		 * If we didn't find anything there, then let's look in
 		 * the superinterfaces.
 		 */
-		match result {
-			None => assert!(false, "TODO: Look in the superinterfaces."),
-			_ => {}
+		let result = self
+			.find_superinterface_method(invoked_class, method_name, method_type, true)
+			.or_else(|| {
+				self.find_superinterface_method(invoked_class, method_name, method_type, false)
+			});
+		if result.is_none() {
+			Debug(
+				format!(
+					"Method {}{} of {} (referenced from {}) could not be resolved.",
+					method_name,
+					method_type,
+					invoked_class.get_class_name().unwrap_or_default(),
+					invoking_class.get_class_name().unwrap_or_default()
+				),
+				&self.debug_level,
+				DebugLevel::Info,
+			);
 		}
-
-		/*
-		 * TODO: Check loading constraints!
-		 */
-
 		result
 	}
+
 	pub fn load_class_from_bytes(&mut self, class_bytes: Vec<u8>) -> Option<Rc<Class>> {
 		if let Some(class) = Class::load_from_bytes(class_bytes) {
-			if let Some(class_name) = class.get_class_name() {
-				if let Some(_) = self
-					.classes
-					.insert(class_name.to_string(), Arc::new(LoadedClass::new(class)))
-				{
-					/*
-					 * This is a fatal error -- loading the same class twice!
-					 */
-				}
-				/*
-				 * loaded_class is an Arc
-				 */
-				let loaded_class = self.classes.get(&class_name).unwrap();
-				return Some(Rc::clone(&loaded_class.class));
-			}
+			return self.define_class(class);
 		}
 		None
 	}
@@ -330,21 +826,62 @@ impl MethodArea {
 	pub fn load_class_from_file(&mut self, class_filename: &String) -> Option<Rc<Class>> {
 		if let Some(class) = Class::load_from_file(class_filename) {
 			if let Some(class_name) = class.get_class_name() {
-				println!("load_class_from_file: {}", class_name);
-				if let Some(_) = self
-					.classes
-					.insert(class_name.to_string(), Arc::new(LoadedClass::new(class)))
-				{
-					/*
-					 * This is a fatal error -- loading the same class twice!
-					 */
+				Debug(
+					format!("load_class_from_file: {}", class_name),
+					&self.debug_level,
+					DebugLevel::Info,
+				);
+			}
+			return self.define_class(class);
+		}
+		None
+	}
+
+	/// Prepare and link `class` and then add it to the method area.
+	/// Linking loads the superclass (to lay out the fields that
+	/// `class` inherits from it).
+	fn define_class(&mut self, mut class: Class) -> Option<Rc<Class>> {
+		if let Some(class_name) = class.get_class_name() {
+			if !class.prepare() {
+				FatalError::new(FatalErrorType::ClassInstantiationFailed(class_name)).call();
+				return None;
+			}
+
+			let loader = class.get_defining_loader();
+			let superclass_layout = if let Some(superclass_name) = class.resolve_superclass() {
+				if let Some(superclass) = self.find_or_load_class(&superclass_name, loader) {
+					Some(superclass.get_layout())
+				} else {
+					FatalError::new(FatalErrorType::ClassNotLoaded(superclass_name)).call();
+					return None;
 				}
+			} else {
+				None
+			};
+			if !class.link(superclass_layout.as_ref().map(|layout| &**layout)) {
+				FatalError::new(FatalErrorType::ClassInstantiationFailed(class_name)).call();
+				return None;
+			}
+			Debug(
+				format!("Layout of {}:\n{}", class_name, class.get_layout()),
+				&self.debug_level,
+				DebugLevel::Info,
+			);
+
+			let key = (class_name.to_string(), loader);
+			if let Some(_) = self
+				.classes
+				.insert(key.clone(), Arc::new(LoadedClass::new(class)))
+			{
 				/*
-				 * loaded_class is an Arc
+				 * This is a fatal error -- loading the same class twice!
 				 */
-				let loaded_class = self.classes.get(&class_name).unwrap();
-				return Some(Rc::clone(&loaded_class.class));
 			}
+			/*
+			 * loaded_class is an Arc
+			 */
+			let loaded_class = self.classes.get(&key).unwrap();
+			return Some(Rc::clone(&loaded_class.class));
 		}
 		None
 	}
@@ -22,9 +22,11 @@
 use std::fmt;
 mod array;
 mod attribute;
+mod builder;
 mod class;
 mod classpath;
 mod comparison;
+mod console;
 mod constant;
 mod constantpool;
 pub mod debug;
@@ -32,59 +34,362 @@ mod environment;
 mod error;
 mod exceptions;
 mod field;
+mod filesystem;
 mod frame;
+mod heap;
+mod jimage;
 mod jvmthread;
+mod layout;
+mod manifest;
 mod method;
 mod methodarea;
+mod native;
+mod nativememory;
 mod object;
 mod opcodes;
+mod runtime;
+mod stacktrace;
+mod trace;
 mod typevalues;
+mod vm;
 
+pub use jvm::builder::JvmBuilder;
+pub use jvm::classpath::{ClassSource, DirectorySource, JarSource, MapSource, DEFAULT_RELEASE};
+pub use jvm::console::Charset;
+use jvm::console::{StandardStream, StandardStreams};
 use jvm::debug::Debug;
 use jvm::debug::DebugLevel;
-use jvm::methodarea::MethodArea;
+pub use jvm::filesystem::{AllowAll, FileSystemPolicy, OpenMode};
+pub use jvm::heap::parse_memory_size;
+use jvm::heap::Heap;
+pub use jvm::heap::DEFAULT_MAX_HEAP_SIZE;
+pub use jvm::jvmthread::JvmThread;
+pub use jvm::jvmthread::DEFAULT_MAX_STACK_DEPTH;
+pub use jvm::jvmthread::INSTRUCTION_BUDGET_EXIT_STATUS;
+pub use jvm::jvmthread::STACK_FRAME_SIZE;
+pub use jvm::jvmthread::UNCAUGHT_EXCEPTION_EXIT_STATUS;
+use jvm::manifest::Manifest;
+pub use jvm::methodarea::MethodArea;
+pub use jvm::native::{NativeMethod, NativeRegistry};
+pub use jvm::trace::TraceHook;
+pub use jvm::typevalues::{JvmPrimitiveType, JvmValue};
+pub use jvm::vm::{CallError, JavaException, JavaObject, JavaValue, Vm};
+use rjar::Jar;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::thread;
 
+/// The size of the native stack of the thread that runs the VM. Java
+/// frames live on the JvmThread's own stack; the native stack only
+/// grows when the VM itself calls into Java (e.g., to run <clinit>)
+/// while it is executing an instruction.
+const NATIVE_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// The exit status when the VM itself fails (i.e., with a FatalError)
+/// or cannot start. EX_SOFTWARE, from sysexits.h.
+pub const VM_ERROR_EXIT_STATUS: i32 = 70;
+
+/// The exit status when the program cannot be launched (e.g., its JAR
+/// file has no Main-Class), as java's.
+pub const LAUNCH_ERROR_EXIT_STATUS: i32 = 1;
+
+#[derive(Clone)]
 pub struct Jvm {
 	debug_level: DebugLevel,
+	max_heap_size: usize,
+	max_stack_depth: usize,
+	/// The Java SE release whose entries of multi-release JAR files
+	/// the VM uses.
+	release: u32,
+	natives: NativeRegistry,
+	/// The encoding of text printed to the standard streams; by
+	/// default, the codeset of the host's locale.
+	console_charset: Option<Charset>,
+	/// Decides which files the program may open.
+	file_system_policy: Arc<dyn FileSystemPolicy>,
+	/// Where to find classes that are not on the class path.
+	class_sources: Vec<Arc<dyn ClassSource>>,
+	/// The entries of the class path.
+	classpath: Vec<String>,
+	/// The entries of the boot class path, which come before those of
+	/// the class path.
+	boot_classpath: Vec<String>,
+	/// The system properties that override the VM's standard ones.
+	system_properties: Vec<(String, String)>,
+	/// Where the program's standard streams go.
+	standard_streams: StandardStreams,
+	trace_hooks: Vec<Arc<dyn TraceHook>>,
+	/// The number of instructions that a program may execute, if it is
+	/// limited.
+	instruction_budget: Option<u64>,
 }
 
 impl Jvm {
 	pub fn new(debug_level: DebugLevel) -> Option<Jvm> {
 		Some(Jvm {
 			debug_level: debug_level,
+			max_heap_size: DEFAULT_MAX_HEAP_SIZE,
+			max_stack_depth: DEFAULT_MAX_STACK_DEPTH,
+			release: DEFAULT_RELEASE,
+			natives: NativeRegistry::new(),
+			console_charset: None,
+			file_system_policy: Arc::new(AllowAll),
+			class_sources: Vec::new(),
+			classpath: Vec::new(),
+			boot_classpath: Vec::new(),
+			system_properties: Vec::new(),
+			standard_streams: StandardStreams::default(),
+			trace_hooks: Vec::new(),
+			instruction_budget: None,
 		})
 	}
 
-	pub fn run(
-		&self,
-		start_class: &String,
-		start_function: &String,
-		classpath: &[&str],
-		args: &[&str],
-	) -> bool {
+	/// Set the entries of the class path: directories, JAR files, jmod
+	/// files, jimages and dir/* (the JAR files in dir).
+	pub fn set_classpath(&mut self, classpath: &[&str]) {
+		self.classpath = classpath.iter().map(|entry| entry.to_string()).collect();
+	}
+
+	/// Set the maximum size (in bytes) of the Java heap.
+	pub fn set_max_heap_size(&mut self, max_heap_size: usize) {
+		self.max_heap_size = max_heap_size;
+	}
+
+	/// Set the maximum number of frames on the Java stack. A program
+	/// that goes deeper gets a StackOverflowError.
+	pub fn set_max_stack_depth(&mut self, max_stack_depth: usize) {
+		self.max_stack_depth = max_stack_depth;
+	}
+
+	/// Set the Java SE release whose versioned entries (and those of
+	/// earlier releases) of multi-release JAR files override their
+	/// base entries.
+	pub fn set_release(&mut self, release: u32) {
+		self.release = release;
+	}
+
+	/// Set the encoding of text that the program prints to System.out
+	/// and System.err.
+	pub fn set_console_charset(&mut self, console_charset: Charset) {
+		self.console_charset = Some(console_charset);
+	}
+
+	/// Set the policy that decides which files the program may open
+	/// (and where they are).
+	pub fn set_file_system_policy<P>(&mut self, policy: P)
+	where
+		P: FileSystemPolicy + 'static,
+	{
+		self.file_system_policy = Arc::new(policy);
+	}
+
+	/// Look for classes that are not on the class path in `source` (after
+	/// the sources added before it).
+	pub fn add_class_source<S>(&mut self, source: S)
+	where
+		S: ClassSource + 'static,
+	{
+		self.class_sources.push(Arc::new(source));
+	}
+
+	/// Implement the native method `method_name` with type `descriptor`
+	/// of the class named `class_name` with `native`, in place of the
+	/// VM's own implementation (if it has one).
+	pub fn register_native<N>(
+		&mut self,
+		class_name: &str,
+		method_name: &str,
+		descriptor: &str,
+		native: N,
+	) where
+		N: NativeMethod + 'static,
+	{
+		self.natives
+			.register(class_name, method_name, descriptor, native);
+	}
+
+	/// Run the Main-Class of the executable JAR file `jar_file`, with
+	/// the JAR file and the entries of its Class-Path as the class path.
+	pub fn run_jar(&self, jar_file: &str, args: &[&str]) -> i32 {
+		let mut jar = match Jar::open(&jar_file.to_string()) {
+			Ok(jar) => jar,
+			Err(_) => {
+				self.report_error(&format!("Error: Invalid or corrupt jarfile {}", jar_file));
+				return LAUNCH_ERROR_EXIT_STATUS;
+			}
+		};
 		/*
-		 * Create a VM and start running!
+		 * As in java, a JAR file without a manifest has no Main-Class.
 		 */
-		let env = environment::Environment::new(classpath, args, self.debug_level.clone());
+		let manifest = Manifest::from_open_jar(&mut jar).unwrap_or_default();
+		let main_class = match manifest.main_class() {
+			Some(main_class) => main_class,
+			None => {
+				self.report_error(&format!("no main manifest attribute, in {}", jar_file));
+				return LAUNCH_ERROR_EXIT_STATUS;
+			}
+		};
+		let mut jvm = self.clone();
+		jvm.classpath = vec![jar_file.to_string()];
+		jvm.classpath.extend(manifest.class_path(jar_file));
+		Debug(
+			format!(
+				"Running {} from {} with class path {}",
+				main_class,
+				jar_file,
+				jvm.classpath.join(":")
+			),
+			&self.debug_level,
+			DebugLevel::Info,
+		);
+		jvm.run(&main_class, "main", args)
+	}
+
+	/// Boot a VM that runs Java code on the calling thread when Rust
+	/// code calls into it. The VM's native stack is the calling
+	/// thread's, which may be smaller than the one that Jvm::run gives
+	/// a program (NATIVE_STACK_SIZE): see with_vm.
+	pub fn start(&self) -> Result<Vm, CallError> {
+		Vm::new(self.create_thread(&[]))
+	}
+
+	/// Boot a VM on a thread of its own, whose native stack is as large
+	/// as the one that Jvm::run gives a program, and call `f` with it
+	/// there. The VM (and what it returns) cannot leave that thread, so
+	/// `f` returns what the caller needs of it.
+	pub fn with_vm<F, R>(&self, f: F) -> Result<R, CallError>
+	where
+		F: FnOnce(&mut Vm) -> R + Send + 'static,
+		R: Send + 'static,
+	{
+		let jvm = self.clone();
+		let vm = thread::Builder::new()
+			.name("vm".to_string())
+			.stack_size(NATIVE_STACK_SIZE)
+			.spawn(move || jvm.start().map(|mut vm| f(&mut vm)));
+		match vm.map(|vm| vm.join()) {
+			Ok(Ok(result)) => result,
+			/*
+			 * Only `f` itself can have panicked: the Vm catches its own
+			 * failures.
+			 */
+			Ok(Err(_)) => Err(CallError::VmError("the caller panicked".to_string())),
+			Err(err) => Err(CallError::VmError(format!(
+				"Could not start the VM: {}",
+				err
+			))),
+		}
+	}
+
+	/// Run the static method `start_function` of the class named
+	/// `start_class` (e.g., main) with `args` as the program's
+	/// arguments, on a thread of its own in a VM of its own.
+	///
+	/// # Return value:
+	/// The program's exit status.
+	pub fn run(&self, start_class: &str, start_function: &str, args: &[&str]) -> i32 {
+		let jvm = self.clone();
+		let start_class = start_class.to_string();
+		let start_function = start_function.to_string();
+		let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+
+		let vm = thread::Builder::new()
+			.name("main".to_string())
+			.stack_size(NATIVE_STACK_SIZE)
+			.spawn(move || {
+				/*
+				 * Create a VM and start running!
+				 */
+				let args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+				let mut thread = jvm.create_thread(&args);
+				let exit_status = thread.run(&start_class, &start_function);
+				Debug(
+					format!(
+						"Running {}.{} exited with status {}",
+						start_class, start_function, exit_status
+					),
+					&jvm.debug_level,
+					DebugLevel::Info,
+				);
+				exit_status
+			});
+
+		match vm.map(|vm| vm.join()) {
+			Ok(Ok(exit_status)) => exit_status,
+			/*
+			 * The VM failed; the FatalError has been reported already.
+			 */
+			Ok(Err(_)) => VM_ERROR_EXIT_STATUS,
+			Err(err) => {
+				self.report_error(&format!("Could not start the VM: {}", err));
+				VM_ERROR_EXIT_STATUS
+			}
+		}
+	}
+
+	/// Report `message` (and a line separator) on the standard error.
+	fn report_error(&self, message: &str) {
+		let _ = self
+			.standard_streams
+			.write(StandardStream::Err, format!("{}\n", message).as_bytes());
+	}
+
+	/// A thread of a new VM configured as this one, with `args` as the
+	/// program's arguments.
+	fn create_thread(&self, args: &[&str]) -> JvmThread {
+		let boot_classpath: Vec<&str> = self
+			.boot_classpath
+			.iter()
+			.map(|entry| entry.as_str())
+			.collect();
+		let classpath: Vec<&str> = self.classpath.iter().map(|entry| entry.as_str()).collect();
+		let env = environment::Environment::new(
+			&boot_classpath,
+			&classpath,
+			&self.class_sources,
+			args,
+			self.release,
+			self.debug_level.clone(),
+		);
 		let methodarea = Arc::new(Mutex::new(MethodArea::new(self.debug_level.clone(), env)));
-		let mut thread = jvmthread::JvmThread::new(self.debug_level.clone(), methodarea);
-		if thread.run(start_class, start_function) {
-			Debug(
-				format!("Success running {}.{}", start_class, start_function),
-				&self.debug_level,
-				DebugLevel::Info,
-			);
-			return true;
+		let heap = Arc::new(Mutex::new(Heap::new(
+			self.max_heap_size,
+			self.debug_level.clone(),
+		)));
+		let mut thread = JvmThread::new(
+			self.debug_level.clone(),
+			methodarea,
+			heap,
+			self.natives.clone(),
+			self.max_stack_depth,
+		);
+		if let Some(console_charset) = self.console_charset {
+			thread.set_console_charset(console_charset);
+		}
+		thread.set_file_system_policy(Arc::clone(&self.file_system_policy));
+		thread
+			.files()
+			.set_standard_streams(self.standard_streams.clone());
+		for (key, value) in &self.system_properties {
+			thread.set_system_property(key, value);
+		}
+		for hook in &self.trace_hooks {
+			thread.add_trace_hook(Arc::clone(hook));
+		}
+		if let Some(instruction_budget) = self.instruction_budget {
+			thread.set_instruction_budget(instruction_budget);
 		}
-		eprintln!("Failure running {}.{}", start_class, start_function);
-		false
+		thread
 	}
 }
 
 impl fmt::Display for Jvm {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "debug_level: {}\n", &self.debug_level)
+		write!(f, "debug_level: {}\n", &self.debug_level)?;
+		write!(f, "max_heap_size: {}\n", self.max_heap_size)?;
+		write!(f, "max_stack_depth: {}\n", self.max_stack_depth)?;
+		write!(f, "classpath: {}\n", self.classpath.join(":"))?;
+		write!(f, "boot_classpath: {}\n", self.boot_classpath.join(":"))?;
+		write!(f, "release: {}\n", self.release)
 	}
 }
@@ -0,0 +1,932 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::array::JvmArray;
+use jvm::filesystem::{error_message, OpenMode};
+use jvm::jvmthread::JvmThread;
+use jvm::methodarea::MethodArea;
+use jvm::native::{
+	boolean_value, double_argument, float_argument, int_argument, int_of, long_argument, null,
+	void, NativeRegistry,
+};
+use jvm::object::{get_string_characters, get_string_value};
+use jvm::typevalues::JvmPrimitiveType;
+use jvm::typevalues::JvmReferenceTargetType;
+use jvm::typevalues::JvmReferenceType;
+use jvm::typevalues::JvmType;
+use jvm::typevalues::JvmValue;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::UNIX_EPOCH;
+
+const PRINT_STREAM: &str = "java/io/PrintStream";
+
+/// The value of the fd field of a closed FileDescriptor.
+const CLOSED_FD: i32 = -1;
+
+/// The flag of RandomAccessFile's open0 mode for reading and writing.
+const RANDOM_ACCESS_FILE_O_RDWR: i32 = 2;
+
+/// The bits of UnixFileSystem's boolean attributes (see
+/// java.io.FileSystem).
+const BA_EXISTS: i32 = 0x01;
+const BA_REGULAR: i32 = 0x02;
+const BA_DIRECTORY: i32 = 0x04;
+const BA_HIDDEN: i32 = 0x08;
+
+/// The bits of checkAccess' access mode (see java.io.FileSystem).
+const ACCESS_READ: i32 = 0x04;
+const ACCESS_WRITE: i32 = 0x02;
+const ACCESS_EXECUTE: i32 = 0x01;
+
+/// The text that a print method prints, from its arguments. None if its
+/// argument is (illegally) null.
+type Text = fn(&[JvmValue]) -> Option<Vec<u16>>;
+
+/// Register the natives of the java.io package.
+pub fn register(registry: &mut NativeRegistry) {
+	registry.register("java/io/FileDescriptor", "initIDs", "()V", initialize_ids);
+	registry.register(
+		"java/io/FileDescriptor",
+		"sync",
+		"()V",
+		file_descriptor_sync,
+	);
+	registry.register("java/io/FileDescriptor", "close0", "()V", close);
+	registry.register(
+		"java/io/FileDescriptor",
+		"getHandle",
+		"(I)J",
+		file_descriptor_get_handle,
+	);
+	registry.register(
+		"java/io/FileDescriptor",
+		"getAppend",
+		"(I)Z",
+		file_descriptor_get_append,
+	);
+
+	let file_input_stream = "java/io/FileInputStream";
+	registry.register(file_input_stream, "initIDs", "()V", initialize_ids);
+	registry.register(
+		file_input_stream,
+		"open0",
+		"(Ljava/lang/String;)V",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			open(thread, arguments, OpenMode::Read)
+		},
+	);
+	registry.register(file_input_stream, "read0", "()I", read);
+	registry.register(file_input_stream, "readBytes", "([BII)I", read_bytes);
+	registry.register(file_input_stream, "skip0", "(J)J", file_input_stream_skip);
+	registry.register(
+		file_input_stream,
+		"available0",
+		"()I",
+		file_input_stream_available,
+	);
+	registry.register(file_input_stream, "close0", "()V", close);
+
+	let file_output_stream = "java/io/FileOutputStream";
+	registry.register(file_output_stream, "initIDs", "()V", initialize_ids);
+	registry.register(
+		file_output_stream,
+		"open0",
+		"(Ljava/lang/String;Z)V",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			if int_argument(arguments, 2) != 0 {
+				open(thread, arguments, OpenMode::Append)
+			} else {
+				open(thread, arguments, OpenMode::Write)
+			}
+		},
+	);
+	registry.register(file_output_stream, "write", "(IZ)V", write);
+	registry.register(file_output_stream, "writeBytes", "([BIIZ)V", write_bytes);
+	registry.register(file_output_stream, "close0", "()V", close);
+
+	let random_access_file = "java/io/RandomAccessFile";
+	registry.register(random_access_file, "initIDs", "()V", initialize_ids);
+	registry.register(
+		random_access_file,
+		"open0",
+		"(Ljava/lang/String;I)V",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			if int_argument(arguments, 2) & RANDOM_ACCESS_FILE_O_RDWR != 0 {
+				open(thread, arguments, OpenMode::ReadWrite)
+			} else {
+				open(thread, arguments, OpenMode::Read)
+			}
+		},
+	);
+	registry.register(random_access_file, "read0", "()I", read);
+	registry.register(random_access_file, "readBytes", "([BII)I", read_bytes);
+	registry.register(random_access_file, "write0", "(I)V", write);
+	registry.register(random_access_file, "writeBytes", "([BII)V", write_bytes);
+	registry.register(
+		random_access_file,
+		"getFilePointer",
+		"()J",
+		random_access_file_get_file_pointer,
+	);
+	registry.register(random_access_file, "seek0", "(J)V", random_access_file_seek);
+	registry.register(
+		random_access_file,
+		"length",
+		"()J",
+		random_access_file_length,
+	);
+	registry.register(
+		random_access_file,
+		"length0",
+		"()J",
+		random_access_file_length,
+	);
+	registry.register(
+		random_access_file,
+		"setLength",
+		"(J)V",
+		random_access_file_set_length,
+	);
+	registry.register(
+		random_access_file,
+		"setLength0",
+		"(J)V",
+		random_access_file_set_length,
+	);
+	registry.register(random_access_file, "close0", "()V", close);
+
+	/*
+	 * UnixFileSystem queries the host's file system directly: the
+	 * FileSystemPolicy decides only which files the program may open.
+	 */
+	let unix_file_system = "java/io/UnixFileSystem";
+	registry.register(unix_file_system, "initIDs", "()V", initialize_ids);
+	registry.register(
+		unix_file_system,
+		"canonicalize0",
+		"(Ljava/lang/String;)Ljava/lang/String;",
+		unix_file_system_canonicalize0,
+	);
+	registry.register(
+		unix_file_system,
+		"getBooleanAttributes0",
+		"(Ljava/io/File;)I",
+		unix_file_system_get_boolean_attributes0,
+	);
+	registry.register(
+		unix_file_system,
+		"checkAccess",
+		"(Ljava/io/File;I)Z",
+		unix_file_system_check_access,
+	);
+	registry.register(
+		unix_file_system,
+		"getLastModifiedTime",
+		"(Ljava/io/File;)J",
+		unix_file_system_get_last_modified_time,
+	);
+	registry.register(
+		unix_file_system,
+		"getLength",
+		"(Ljava/io/File;)J",
+		unix_file_system_get_length,
+	);
+	registry.register(
+		unix_file_system,
+		"list",
+		"(Ljava/io/File;)[Ljava/lang/String;",
+		unix_file_system_list,
+	);
+
+	/*
+	 * System.out and System.err are PrintStreams that the VM creates (see
+	 * JvmThread::initialize_standard_streams) and these intrinsics write
+	 * to the host's standard streams. They decline to print to any
+	 * other PrintStream.
+	 */
+	let printers: [(&str, Text); 8] = [
+		("(Ljava/lang/String;)V", string_text),
+		("([C)V", character_array_text),
+		("(Z)V", boolean_text),
+		("(C)V", character_text),
+		("(I)V", int_text),
+		("(J)V", long_text),
+		("(F)V", float_text),
+		("(D)V", double_text),
+	];
+	for (descriptor, text) in printers.iter().cloned() {
+		registry.register_intrinsic(
+			PRINT_STREAM,
+			"print",
+			descriptor,
+			move |thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+				print(thread, arguments, text, false)
+			},
+		);
+		registry.register_intrinsic(
+			PRINT_STREAM,
+			"println",
+			descriptor,
+			move |thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+				print(thread, arguments, text, true)
+			},
+		);
+	}
+	registry.register_intrinsic(PRINT_STREAM, "println", "()V", print_stream_new_line);
+	registry.register_intrinsic(PRINT_STREAM, "newLine", "()V", print_stream_new_line);
+	registry.register_intrinsic(PRINT_STREAM, "write", "(I)V", print_stream_write);
+	registry.register_intrinsic(PRINT_STREAM, "write", "([BII)V", print_stream_write_bytes);
+	registry.register_intrinsic(PRINT_STREAM, "flush", "()V", print_stream_flush);
+}
+
+/// Java's string representation of the floating-point number `value`
+/// (Float.toString or Double.toString): shortest decimal that identifies
+/// it, in scientific notation when it is small or large.
+pub fn floating_point_string<F: fmt::Display + fmt::LowerExp>(value: F, magnitude: f64) -> String {
+	if magnitude.is_nan() {
+		return "NaN".to_string();
+	}
+	if magnitude.is_infinite() {
+		return if magnitude > 0.0 {
+			"Infinity".to_string()
+		} else {
+			"-Infinity".to_string()
+		};
+	}
+	let magnitude = magnitude.abs();
+	if magnitude == 0.0 || (magnitude >= 1e-3 && magnitude < 1e7) {
+		let string = format!("{}", value);
+		if string.contains('.') {
+			string
+		} else {
+			string + ".0"
+		}
+	} else {
+		let string = format!("{:e}", value);
+		let mut parts = string.splitn(2, 'e');
+		let mantissa = parts.next().unwrap_or_default();
+		let exponent = parts.next().unwrap_or_default();
+		if mantissa.contains('.') {
+			format!("{}E{}", mantissa, exponent)
+		} else {
+			format!("{}.0E{}", mantissa, exponent)
+		}
+	}
+}
+
+fn string_text(arguments: &[JvmValue]) -> Option<Vec<u16>> {
+	Some(get_string_characters(&arguments[1]).unwrap_or_else(|| "null".encode_utf16().collect()))
+}
+
+fn character_array_text(arguments: &[JvmValue]) -> Option<Vec<u16>> {
+	match &arguments[1] {
+		JvmValue::Reference(_, JvmReferenceTargetType::Array(characters), _) => {
+			let characters = characters.lock().ok()?;
+			Some(
+				characters
+					.get_range(0, characters.dimension())
+					.iter()
+					.map(|character| character.as_ref().map(int_of).unwrap_or(0) as u16)
+					.collect(),
+			)
+		}
+		_ => None,
+	}
+}
+
+fn boolean_text(arguments: &[JvmValue]) -> Option<Vec<u16>> {
+	let text = if int_argument(arguments, 1) != 0 {
+		"true"
+	} else {
+		"false"
+	};
+	Some(text.encode_utf16().collect())
+}
+
+fn character_text(arguments: &[JvmValue]) -> Option<Vec<u16>> {
+	Some(vec![int_argument(arguments, 1) as u16])
+}
+
+fn int_text(arguments: &[JvmValue]) -> Option<Vec<u16>> {
+	Some(
+		int_argument(arguments, 1)
+			.to_string()
+			.encode_utf16()
+			.collect(),
+	)
+}
+
+fn long_text(arguments: &[JvmValue]) -> Option<Vec<u16>> {
+	Some(
+		long_argument(arguments, 1)
+			.to_string()
+			.encode_utf16()
+			.collect(),
+	)
+}
+
+fn float_text(arguments: &[JvmValue]) -> Option<Vec<u16>> {
+	let value = float_argument(arguments, 1);
+	Some(
+		floating_point_string(value, value as f64)
+			.encode_utf16()
+			.collect(),
+	)
+}
+
+fn double_text(arguments: &[JvmValue]) -> Option<Vec<u16>> {
+	let value = double_argument(arguments, 1);
+	Some(floating_point_string(value, value).encode_utf16().collect())
+}
+
+/// Print the text of `arguments` (and, maybe, a line separator) in the
+/// console's charset to the standard stream that `arguments[0]` is.
+fn print(
+	thread: &mut JvmThread,
+	arguments: &[JvmValue],
+	text: Text,
+	new_line: bool,
+) -> Option<JvmValue> {
+	let stream = thread.standard_stream(&arguments[0])?;
+	let mut characters = match text(arguments) {
+		Some(characters) => characters,
+		None => {
+			thread.throw_new("java/lang/NullPointerException", None);
+			return None;
+		}
+	};
+	if new_line {
+		characters.push('\n' as u16);
+	}
+	/*
+	 * A PrintStream never throws an IOException.
+	 */
+	let _ = thread.write_standard_stream(stream, &thread.console_charset().encode(&characters));
+	void()
+}
+
+fn print_stream_new_line(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let stream = thread.standard_stream(&arguments[0])?;
+	let _ = thread.write_standard_stream(stream, b"\n");
+	void()
+}
+
+fn print_stream_write(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let stream = thread.standard_stream(&arguments[0])?;
+	let _ = thread.write_standard_stream(stream, &[int_argument(arguments, 1) as u8]);
+	void()
+}
+
+fn print_stream_write_bytes(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let stream = thread.standard_stream(&arguments[0])?;
+	let bytes = array_bytes(
+		thread,
+		&arguments[1],
+		int_argument(arguments, 2),
+		int_argument(arguments, 3),
+	)?;
+	let _ = thread.write_standard_stream(stream, &bytes);
+	void()
+}
+
+/// The standard streams are not buffered.
+fn print_stream_flush(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	thread.standard_stream(&arguments[0])?;
+	void()
+}
+
+fn initialize_ids(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	void()
+}
+
+/// The byte array `array`, if `offset` and `length` are in its bounds.
+/// None (after throwing) if `array` is null or too short.
+fn byte_array(
+	thread: &mut JvmThread,
+	array: &JvmValue,
+	offset: i32,
+	length: i32,
+) -> Option<Arc<Mutex<JvmArray>>> {
+	let array = match array {
+		JvmValue::Reference(_, JvmReferenceTargetType::Array(array), _) => Arc::clone(array),
+		_ => {
+			thread.throw_new("java/lang/NullPointerException", None);
+			return None;
+		}
+	};
+	let dimension = match array.lock() {
+		Ok(array) => array.dimension(),
+		_ => 0,
+	};
+	if offset < 0 || length < 0 || offset as usize + length as usize > dimension {
+		thread.throw_new("java/lang/IndexOutOfBoundsException", None);
+		return None;
+	}
+	Some(array)
+}
+
+/// The `length` bytes of the byte array `array` that start at `offset`.
+/// None (after throwing) if `array` is null or too short.
+pub fn array_bytes(
+	thread: &mut JvmThread,
+	array: &JvmValue,
+	offset: i32,
+	length: i32,
+) -> Option<Vec<u8>> {
+	let array = byte_array(thread, array, offset, length)?;
+	let array = array.lock().ok()?;
+	Some(
+		array
+			.get_range(offset as usize, length as usize)
+			.iter()
+			.map(|byte| byte.as_ref().map(int_of).unwrap_or(0) as u8)
+			.collect(),
+	)
+}
+
+/// Store `bytes` in `array`, starting at `offset`.
+fn store_bytes(array: &Arc<Mutex<JvmArray>>, offset: usize, bytes: &[u8]) {
+	if let Ok(mut array) = array.lock() {
+		array.set_range(
+			offset,
+			bytes
+				.iter()
+				.map(|byte| {
+					Some(JvmValue::Primitive(
+						JvmPrimitiveType::Byte,
+						0,
+						*byte as i8 as i32 as u32,
+						0,
+					))
+				})
+				.collect(),
+		);
+	}
+}
+
+/// The descriptor of the file that `stream` (a FileInputStream,
+/// FileOutputStream or RandomAccessFile -- or a FileDescriptor) reads
+/// or writes: the fd field of its FileDescriptor. -1 if it is closed.
+fn descriptor(stream: &JvmValue) -> i32 {
+	let fd = match stream {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(stream), _) => stream
+			.lock()
+			.ok()
+			.and_then(|stream| stream.get_field_by_name("fd")),
+		_ => None,
+	};
+	match fd {
+		Some(JvmValue::Reference(_, JvmReferenceTargetType::Object(fd), _)) => fd
+			.lock()
+			.ok()
+			.and_then(|fd| fd.get_field_by_name("fd"))
+			.map(|fd| int_of(&fd))
+			.unwrap_or(CLOSED_FD),
+		Some(fd @ JvmValue::Primitive(_, _, _, _)) => int_of(&fd),
+		_ => CLOSED_FD,
+	}
+}
+
+/// Set the descriptor of the file that `stream` reads or writes.
+fn set_descriptor(stream: &JvmValue, fd: i32) {
+	let fd_value = JvmValue::int(fd);
+	if let JvmValue::Reference(_, JvmReferenceTargetType::Object(stream), _) = stream {
+		if let Ok(mut stream) = stream.lock() {
+			match stream.get_field_by_name("fd") {
+				Some(JvmValue::Reference(_, JvmReferenceTargetType::Object(fd), _)) => {
+					if let Ok(mut fd) = fd.lock() {
+						fd.set_field_by_name("fd", fd_value);
+					}
+				}
+				_ => {
+					stream.set_field_by_name("fd", fd_value);
+				}
+			}
+		}
+	}
+}
+
+/// Throw an IOException for `err`.
+fn throw_io_exception(thread: &mut JvmThread, err: &io::Error) -> Option<JvmValue> {
+	thread.throw_new("java/io/IOException", Some(&error_message(err)));
+	None
+}
+
+fn open(thread: &mut JvmThread, arguments: &[JvmValue], mode: OpenMode) -> Option<JvmValue> {
+	let path = match get_string_value(&arguments[1]) {
+		Some(path) => path,
+		None => {
+			thread.throw_new("java/lang/NullPointerException", None);
+			return None;
+		}
+	};
+	match thread.files().open(&path, mode) {
+		Ok(fd) => {
+			set_descriptor(&arguments[0], fd);
+			void()
+		}
+		Err(err) => {
+			thread.throw_new(
+				"java/io/FileNotFoundException",
+				Some(&format!("{} ({})", path, error_message(&err))),
+			);
+			None
+		}
+	}
+}
+
+fn read(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let mut byte = [0u8];
+	match thread.files().read(descriptor(&arguments[0]), &mut byte) {
+		Ok(0) => Some(JvmValue::int(-1)),
+		Ok(_) => Some(JvmValue::int(byte[0] as i32)),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn read_bytes(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let offset = int_argument(arguments, 2);
+	let length = int_argument(arguments, 3);
+	let array = byte_array(thread, &arguments[1], offset, length)?;
+	if length == 0 {
+		return Some(JvmValue::int(0));
+	}
+	let mut buffer = vec![0u8; length as usize];
+	match thread.files().read(descriptor(&arguments[0]), &mut buffer) {
+		Ok(0) => Some(JvmValue::int(-1)),
+		Ok(read) => {
+			store_bytes(&array, offset as usize, &buffer[..read]);
+			Some(JvmValue::int(read as i32))
+		}
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn file_input_stream_skip(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match thread
+		.files()
+		.skip(descriptor(&arguments[0]), long_argument(arguments, 1))
+	{
+		Ok(skipped) => Some(JvmValue::long(skipped)),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn file_input_stream_available(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match thread.files().available(descriptor(&arguments[0])) {
+		Ok(available) => Some(JvmValue::int(available.min(i32::MAX as u64) as i32)),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+/// Close the file, unless it is already closed.
+fn close(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let fd = descriptor(&arguments[0]);
+	if fd == CLOSED_FD {
+		return void();
+	}
+	set_descriptor(&arguments[0], CLOSED_FD);
+	match thread.files().close(fd) {
+		Ok(()) => void(),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn write(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let byte = [int_argument(arguments, 1) as u8];
+	match thread.files().write(descriptor(&arguments[0]), &byte) {
+		Ok(()) => void(),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn write_bytes(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let bytes = array_bytes(
+		thread,
+		&arguments[1],
+		int_argument(arguments, 2),
+		int_argument(arguments, 3),
+	)?;
+	match thread.files().write(descriptor(&arguments[0]), &bytes) {
+		Ok(()) => void(),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn random_access_file_get_file_pointer(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match thread.files().position(descriptor(&arguments[0])) {
+		Ok(position) => Some(JvmValue::long(position as i64)),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn random_access_file_seek(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let position = long_argument(arguments, 1);
+	if position < 0 {
+		thread.throw_new("java/io/IOException", Some("Negative seek offset"));
+		return None;
+	}
+	match thread
+		.files()
+		.seek(descriptor(&arguments[0]), position as u64)
+	{
+		Ok(()) => void(),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn random_access_file_length(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match thread.files().length(descriptor(&arguments[0])) {
+		Ok(length) => Some(JvmValue::long(length as i64)),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn random_access_file_set_length(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let length = long_argument(arguments, 1);
+	if length < 0 {
+		thread.throw_new("java/io/IOException", Some("Negative length"));
+		return None;
+	}
+	match thread
+		.files()
+		.set_length(descriptor(&arguments[0]), length as u64)
+	{
+		Ok(()) => void(),
+		Err(err) => throw_io_exception(thread, &err),
+	}
+}
+
+fn file_descriptor_sync(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match thread.files().sync(descriptor(&arguments[0])) {
+		Ok(()) => void(),
+		Err(_) => {
+			thread.throw_new("java/io/SyncFailedException", Some("sync failed"));
+			None
+		}
+	}
+}
+
+/// FileDescriptors have no (Windows) handles.
+fn file_descriptor_get_handle(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::long(-1))
+}
+
+/// The host's standard streams are not in append mode.
+fn file_descriptor_get_append(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::int(0))
+}
+
+/// The path of a java.io.File.
+fn file_path(file: &JvmValue) -> Option<String> {
+	match file {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(file), _) => {
+			get_string_value(&file.lock().ok()?.get_field_by_name("path")?)
+		}
+		_ => None,
+	}
+}
+
+/// The canonical form of `path`: absolute, without . and .. (and with
+/// symbolic links resolved, as far as the path exists).
+fn canonicalize(path: &str) -> PathBuf {
+	let mut absolute = std::env::current_dir().unwrap_or_default();
+	for component in Path::new(path).components() {
+		match component {
+			Component::RootDir => absolute = PathBuf::from("/"),
+			Component::ParentDir => {
+				absolute.pop();
+			}
+			Component::Normal(name) => absolute.push(name),
+			_ => (),
+		}
+	}
+	if let Ok(canonical) = fs::canonicalize(&absolute) {
+		return canonical;
+	}
+	/*
+	 * Resolve the longest prefix that exists.
+	 */
+	let mut missing = Vec::new();
+	let mut existing = absolute.clone();
+	while let Some(name) = existing.file_name().map(|name| name.to_owned()) {
+		missing.push(name);
+		existing.pop();
+		if let Ok(mut canonical) = fs::canonicalize(&existing) {
+			for name in missing.iter().rev() {
+				canonical.push(name);
+			}
+			return canonical;
+		}
+	}
+	absolute
+}
+
+fn unix_file_system_canonicalize0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let path = get_string_value(&arguments[1]).unwrap_or_default();
+	thread.create_string(&canonicalize(&path).to_string_lossy())
+}
+
+fn unix_file_system_get_boolean_attributes0(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let path = file_path(&arguments[1]).unwrap_or_default();
+	let attributes = match fs::metadata(&path) {
+		Ok(metadata) => {
+			let mut attributes = BA_EXISTS;
+			if metadata.is_file() {
+				attributes |= BA_REGULAR;
+			}
+			if metadata.is_dir() {
+				attributes |= BA_DIRECTORY;
+			}
+			let hidden = Path::new(&path)
+				.file_name()
+				.map_or(false, |name| name.to_string_lossy().starts_with('.'));
+			if hidden {
+				attributes |= BA_HIDDEN;
+			}
+			attributes
+		}
+		Err(_) => 0,
+	};
+	Some(JvmValue::int(attributes))
+}
+
+/// Whether the owner of the file (approximately, the user) may access it
+/// the way `access` (the bits of java.io.FileSystem) asks.
+fn unix_file_system_check_access(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	use std::os::unix::fs::PermissionsExt;
+	let path = file_path(&arguments[1]).unwrap_or_default();
+	let access = int_argument(arguments, 2);
+	let mode = match fs::metadata(&path) {
+		Ok(metadata) => metadata.permissions().mode(),
+		Err(_) => return boolean_value(false),
+	};
+	let mut allowed = true;
+	for (bit, owner_bit) in &[
+		(ACCESS_READ, 0o400),
+		(ACCESS_WRITE, 0o200),
+		(ACCESS_EXECUTE, 0o100),
+	] {
+		if access & bit != 0 && mode & owner_bit == 0 {
+			allowed = false;
+		}
+	}
+	boolean_value(allowed)
+}
+
+fn unix_file_system_get_last_modified_time(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let path = file_path(&arguments[1]).unwrap_or_default();
+	let modified = fs::metadata(&path)
+		.and_then(|metadata| metadata.modified())
+		.ok()
+		.and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+		.map_or(0, |modified| modified.as_millis() as i64);
+	Some(JvmValue::long(modified))
+}
+
+fn unix_file_system_get_length(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let path = file_path(&arguments[1]).unwrap_or_default();
+	Some(JvmValue::long(
+		fs::metadata(&path).map_or(0, |metadata| metadata.len() as i64),
+	))
+}
+
+/// The names of the directory's entries; null if it is not a directory
+/// (or cannot be read).
+fn unix_file_system_list(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let path = file_path(&arguments[1]).unwrap_or_default();
+	let entries = match fs::read_dir(&path) {
+		Ok(entries) => entries,
+		Err(_) => return null(),
+	};
+	let mut names = Vec::new();
+	for entry in entries.flatten() {
+		names.push(thread.create_string(&entry.file_name().to_string_lossy())?);
+	}
+	thread.create_array(
+		JvmType::Reference(JvmReferenceType::Class("java/lang/String".to_string())),
+		names,
+	)
+}
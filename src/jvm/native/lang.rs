@@ -0,0 +1,1193 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::array::{array_element, JvmArray};
+use jvm::classpath::ClassLocation;
+use jvm::jvmthread::JvmThread;
+use jvm::methodarea::MethodArea;
+use jvm::native::io::{array_bytes, floating_point_string};
+use jvm::native::{
+	boolean_value, double_argument, float_argument, int_argument, long_argument, mirrored_class,
+	null, void, NativeRegistry,
+};
+use jvm::object::get_string_value;
+use jvm::typevalues::JvmReferenceTargetType;
+use jvm::typevalues::JvmReferenceType;
+use jvm::typevalues::JvmValue;
+use jvm::typevalues::{JvmPrimitiveType, JvmType};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Register the natives of the java.lang package.
+pub fn register(registry: &mut NativeRegistry) {
+	registry.register(
+		"java/lang/Object",
+		"registerNatives",
+		"()V",
+		register_natives,
+	);
+	registry.register("java/lang/Object", "hashCode", "()I", object_hash_code);
+	registry.register("java/lang/Object", "notify", "()V", object_notify);
+	registry.register("java/lang/Object", "notifyAll", "()V", object_notify);
+
+	registry.register(
+		"java/lang/System",
+		"registerNatives",
+		"()V",
+		register_natives,
+	);
+	registry.register(
+		"java/lang/System",
+		"currentTimeMillis",
+		"()J",
+		system_current_time_millis,
+	);
+	registry.register("java/lang/System", "nanoTime", "()J", system_nano_time);
+	registry.register(
+		"java/lang/System",
+		"arraycopy",
+		"(Ljava/lang/Object;ILjava/lang/Object;II)V",
+		system_arraycopy,
+	);
+	registry.register(
+		"java/lang/System",
+		"identityHashCode",
+		"(Ljava/lang/Object;)I",
+		system_identity_hash_code,
+	);
+	/*
+	 * In runtimes where System.exit (or Runtime.exit) is native, it
+	 * runs the shutdown hooks itself.
+	 */
+	registry.register("java/lang/System", "exit", "(I)V", exit);
+	registry.register("java/lang/Runtime", "exit", "(I)V", exit);
+	registry.register("java/lang/Shutdown", "halt0", "(I)V", shutdown_halt0);
+	registry.register("java/lang/Shutdown", "beforeHalt", "()V", register_natives);
+
+	/*
+	 * The class library implements Math with StrictMath, whose methods
+	 * are native. A smaller class library may have native Math methods.
+	 */
+	let unary: [(&str, fn(f64) -> f64); 19] = [
+		("sin", f64::sin),
+		("cos", f64::cos),
+		("tan", f64::tan),
+		("asin", f64::asin),
+		("acos", f64::acos),
+		("atan", f64::atan),
+		("exp", f64::exp),
+		("log", f64::ln),
+		("log10", f64::log10),
+		("sqrt", f64::sqrt),
+		("cbrt", f64::cbrt),
+		("sinh", f64::sinh),
+		("cosh", f64::cosh),
+		("tanh", f64::tanh),
+		("expm1", f64::exp_m1),
+		("log1p", f64::ln_1p),
+		("floor", f64::floor),
+		("ceil", f64::ceil),
+		("rint", f64::round_ties_even),
+	];
+	let binary: [(&str, fn(f64, f64) -> f64); 4] = [
+		("atan2", f64::atan2),
+		("pow", f64::powf),
+		("hypot", f64::hypot),
+		("IEEEremainder", ieee_remainder),
+	];
+	for class_name in &["java/lang/StrictMath", "java/lang/Math"] {
+		for (method_name, function) in unary.iter().cloned() {
+			registry.register(
+				class_name,
+				method_name,
+				"(D)D",
+				move |_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+					Some(JvmValue::double(function(double_argument(arguments, 0))))
+				},
+			);
+		}
+		for (method_name, function) in binary.iter().cloned() {
+			registry.register(
+				class_name,
+				method_name,
+				"(DD)D",
+				move |_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+					Some(JvmValue::double(function(
+						double_argument(arguments, 0),
+						double_argument(arguments, 1),
+					)))
+				},
+			);
+		}
+	}
+
+	registry.register(
+		"java/lang/Float",
+		"floatToRawIntBits",
+		"(F)I",
+		float_to_raw_int_bits,
+	);
+	registry.register(
+		"java/lang/Float",
+		"intBitsToFloat",
+		"(I)F",
+		int_bits_to_float,
+	);
+	registry.register(
+		"java/lang/Double",
+		"doubleToRawLongBits",
+		"(D)J",
+		double_to_raw_long_bits,
+	);
+	registry.register(
+		"java/lang/Double",
+		"longBitsToDouble",
+		"(J)D",
+		long_bits_to_double,
+	);
+
+	/*
+	 * The VM's runtime converts floating-point numbers to and from
+	 * strings natively.
+	 */
+	registry.register(
+		"java/lang/Float",
+		"toString",
+		"(F)Ljava/lang/String;",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			let value = float_argument(arguments, 0);
+			thread.create_string(&floating_point_string(value, value as f64))
+		},
+	);
+	registry.register(
+		"java/lang/Double",
+		"toString",
+		"(D)Ljava/lang/String;",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			let value = double_argument(arguments, 0);
+			thread.create_string(&floating_point_string(value, value))
+		},
+	);
+	registry.register(
+		"java/lang/Double",
+		"parseDouble0",
+		"(Ljava/lang/String;)D",
+		double_parse_double0,
+	);
+
+	registry.register(
+		"java/lang/String",
+		"intern",
+		"()Ljava/lang/String;",
+		string_intern,
+	);
+
+	registry.register(
+		"java/lang/Throwable",
+		"fillInStackTrace",
+		"(I)Ljava/lang/Throwable;",
+		throwable_fill_in_stack_trace,
+	);
+	registry.register(
+		"java/lang/Throwable",
+		"getStackTraceDepth",
+		"()I",
+		throwable_get_stack_trace_depth,
+	);
+	registry.register(
+		"java/lang/Throwable",
+		"getStackTraceElement",
+		"(I)Ljava/lang/StackTraceElement;",
+		throwable_get_stack_trace_element,
+	);
+
+	registry.register(
+		"java/lang/Object",
+		"getClass",
+		"()Ljava/lang/Class;",
+		object_get_class,
+	);
+	registry.register(
+		"java/lang/Object",
+		"clone",
+		"()Ljava/lang/Object;",
+		object_clone,
+	);
+	registry.register("java/lang/Object", "wait", "(J)V", object_wait);
+
+	let thread = "java/lang/Thread";
+	registry.register(thread, "registerNatives", "()V", register_natives);
+	registry.register(
+		thread,
+		"currentThread",
+		"()Ljava/lang/Thread;",
+		thread_current_thread,
+	);
+	registry.register(thread, "isAlive", "()Z", thread_is_alive);
+	registry.register(thread, "start0", "()V", thread_start0);
+	registry.register(thread, "setPriority0", "(I)V", register_natives);
+	registry.register(thread, "interrupt0", "()V", register_natives);
+	registry.register(thread, "yield", "()V", register_natives);
+	registry.register(thread, "isInterrupted", "(Z)Z", thread_is_interrupted);
+	registry.register(
+		thread,
+		"holdsLock",
+		"(Ljava/lang/Object;)Z",
+		thread_holds_lock,
+	);
+	registry.register(thread, "sleep", "(J)V", thread_sleep);
+
+	registry.register(
+		"java/lang/System",
+		"initProperties",
+		"(Ljava/util/Properties;)Ljava/util/Properties;",
+		system_init_properties,
+	);
+	for (method_name, field_name, field_type) in &[
+		("setIn0", "in", "Ljava/io/InputStream;"),
+		("setOut0", "out", "Ljava/io/PrintStream;"),
+		("setErr0", "err", "Ljava/io/PrintStream;"),
+	] {
+		registry.register(
+			"java/lang/System",
+			method_name,
+			&format!("({})V", field_type),
+			move |thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+				system_set_stream(thread, field_name, field_type, &arguments[0])
+			},
+		);
+	}
+	registry.register(
+		"java/lang/System",
+		"environ",
+		"()[Ljava/lang/String;",
+		system_environ,
+	);
+	registry.register(
+		"java/lang/ProcessEnvironment",
+		"environ",
+		"()[[B",
+		process_environment_environ,
+	);
+	registry.register(
+		"java/lang/System",
+		"mapLibraryName",
+		"(Ljava/lang/String;)Ljava/lang/String;",
+		system_map_library_name,
+	);
+
+	let runtime = "java/lang/Runtime";
+	registry.register(
+		runtime,
+		"availableProcessors",
+		"()I",
+		runtime_available_processors,
+	);
+	registry.register(runtime, "freeMemory", "()J", runtime_free_memory);
+	registry.register(runtime, "totalMemory", "()J", runtime_max_memory);
+	registry.register(runtime, "maxMemory", "()J", runtime_max_memory);
+	registry.register(runtime, "gc", "()V", runtime_gc);
+
+	let class_loader = "java/lang/ClassLoader";
+	registry.register(class_loader, "registerNatives", "()V", register_natives);
+	registry.register(
+		class_loader,
+		"findBuiltinLib",
+		"(Ljava/lang/String;)Ljava/lang/String;",
+		class_loader_find_builtin_lib,
+	);
+	registry.register(
+		class_loader,
+		"findLoadedClass0",
+		"(Ljava/lang/String;)Ljava/lang/Class;",
+		class_loader_find_loaded_class0,
+	);
+	registry.register(
+		class_loader,
+		"defineClass0",
+		"(Ljava/lang/String;[BIILjava/security/ProtectionDomain;)Ljava/lang/Class;",
+		class_loader_define_class,
+	);
+	registry.register(
+		class_loader,
+		"defineClass1",
+		"(Ljava/lang/String;[BIILjava/security/ProtectionDomain;Ljava/lang/String;)Ljava/lang/Class;",
+		class_loader_define_class,
+	);
+	registry.register(
+		class_loader,
+		"findBootstrapClass",
+		"(Ljava/lang/String;)Ljava/lang/Class;",
+		class_loader_find_bootstrap_class,
+	);
+	let native_library = "java/lang/ClassLoader$NativeLibrary";
+	registry.register(
+		native_library,
+		"load",
+		"(Ljava/lang/String;Z)V",
+		native_library_load,
+	);
+	registry.register(
+		native_library,
+		"load",
+		"(Ljava/lang/String;)V",
+		native_library_load,
+	);
+	registry.register(
+		native_library,
+		"find",
+		"(Ljava/lang/String;)J",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| Some(JvmValue::long(0)),
+	);
+	registry.register(
+		native_library,
+		"unload",
+		"(Ljava/lang/String;Z)V",
+		register_natives,
+	);
+}
+
+/// The identity of the object (or array) that `value` references; 0 for null.
+fn identity_hash_code(value: &JvmValue) -> i32 {
+	let address = match value {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+			Arc::as_ptr(object) as usize
+		}
+		JvmValue::Reference(_, JvmReferenceTargetType::Array(array), _) => {
+			Arc::as_ptr(array) as usize
+		}
+		_ => 0,
+	};
+	/*
+	 * Objects are aligned, so the low bits say nothing.
+	 */
+	(address >> 3) as i32
+}
+
+/// IEEE 754 remainder: `dividend` less the multiple of `divisor`
+/// that is nearest to it (the even one, in a tie).
+fn ieee_remainder(dividend: f64, divisor: f64) -> f64 {
+	dividend - (dividend / divisor).round_ties_even() * divisor
+}
+
+fn register_natives(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	void()
+}
+
+fn object_hash_code(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::int(identity_hash_code(&arguments[0])))
+}
+
+/// There is only one thread, so there is never another to notify.
+fn object_notify(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	void()
+}
+
+fn system_current_time_millis(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default();
+	Some(JvmValue::long(now.as_millis() as i64))
+}
+
+fn system_nano_time(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	static ORIGIN: OnceLock<Instant> = OnceLock::new();
+	Some(JvmValue::long(
+		ORIGIN.get_or_init(Instant::now).elapsed().as_nanos() as i64,
+	))
+}
+
+fn system_arraycopy(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let (source, destination) = match (&arguments[0], &arguments[2]) {
+		(
+			JvmValue::Reference(_, JvmReferenceTargetType::Array(source), _),
+			JvmValue::Reference(_, JvmReferenceTargetType::Array(destination), _),
+		) => (Arc::clone(source), Arc::clone(destination)),
+		(JvmValue::Reference(_, JvmReferenceTargetType::Null, _), _)
+		| (_, JvmValue::Reference(_, JvmReferenceTargetType::Null, _)) => {
+			thread.throw_new("java/lang/NullPointerException", None);
+			return None;
+		}
+		_ => {
+			thread.throw_new("java/lang/ArrayStoreException", None);
+			return None;
+		}
+	};
+	let source_position = int_argument(arguments, 1);
+	let destination_position = int_argument(arguments, 3);
+	let length = int_argument(arguments, 4);
+
+	let in_bounds = |array: &Arc<Mutex<JvmArray>>, position: i32| {
+		let dimension = match array.lock() {
+			Ok(array) => array.dimension(),
+			_ => 0,
+		};
+		position >= 0 && length >= 0 && (position as usize) + (length as usize) <= dimension
+	};
+	if !in_bounds(&source, source_position) || !in_bounds(&destination, destination_position) {
+		thread.throw_new("java/lang/ArrayIndexOutOfBoundsException", None);
+		return None;
+	}
+
+	/*
+	 * Copy out of the source before copying in to the destination:
+	 * they may be the same array.
+	 */
+	let values = match source.lock() {
+		Ok(source) => source.get_range(source_position as usize, length as usize),
+		_ => return void(),
+	};
+	if let Ok(mut destination) = destination.lock() {
+		destination.set_range(destination_position as usize, values);
+	}
+	void()
+}
+
+fn system_identity_hash_code(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::int(identity_hash_code(&arguments[0])))
+}
+
+/// System.exit (and Runtime.exit, whose exit status follows the Runtime).
+fn exit(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let exit_status = int_argument(arguments, arguments.len() - 1);
+	thread.run_shutdown_hooks();
+	thread.halt(exit_status);
+	void()
+}
+
+fn shutdown_halt0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	thread.halt(int_argument(arguments, 0));
+	void()
+}
+
+fn float_to_raw_int_bits(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::int(float_argument(arguments, 0).to_bits() as i32))
+}
+
+/// Parse `string` as Double.parseDouble does: a decimal number, with
+/// an optional exponent and type suffix, NaN or (signed) Infinity.
+fn parse_java_double(string: &str) -> Option<f64> {
+	let unsigned = string.trim_start_matches(|c| c == '+' || c == '-');
+	if unsigned == "NaN" || unsigned == "Infinity" {
+		return string.parse::<f64>().ok();
+	}
+	let number = string.trim_end_matches(|c| "fFdD".contains(c));
+	if number.len() + 1 < string.len()
+		|| !number
+			.chars()
+			.all(|c| c.is_ascii_digit() || "+-.eE".contains(c))
+	{
+		return None;
+	}
+	number.parse::<f64>().ok()
+}
+
+fn double_parse_double0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let string = get_string_value(&arguments[0]).unwrap_or_default();
+	match parse_java_double(&string) {
+		Some(value) => Some(JvmValue::double(value)),
+		None => {
+			thread.throw_new(
+				"java/lang/NumberFormatException",
+				Some(&format!("For input string: \"{}\"", string)),
+			);
+			None
+		}
+	}
+}
+
+fn int_bits_to_float(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::float(f32::from_bits(
+		int_argument(arguments, 0) as u32
+	)))
+}
+
+fn double_to_raw_long_bits(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::long(
+		double_argument(arguments, 0).to_bits() as i64
+	))
+}
+
+fn long_bits_to_double(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::double(f64::from_bits(
+		long_argument(arguments, 0) as u64,
+	)))
+}
+
+/// If a String with the same contents is interned, return it. Otherwise,
+/// intern this one.
+fn string_intern(
+	_: &mut JvmThread,
+	methodarea: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let value = get_string_value(&arguments[0]).unwrap_or_default();
+	match methodarea.lock() {
+		Ok(mut methodarea) => Some((*methodarea).intern_string(&value, arguments[0].clone())),
+		_ => Some(arguments[0].clone()),
+	}
+}
+
+fn throwable_fill_in_stack_trace(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	thread.fill_in_stack_trace(&arguments[0]);
+	Some(arguments[0].clone())
+}
+
+/// The StackTraceElements that fillInStackTrace recorded in `throwable`.
+fn stack_trace_elements(throwable: &JvmValue) -> Vec<JvmValue> {
+	match throwable {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(throwable), _) => {
+			JvmThread::get_stack_trace_elements(throwable)
+		}
+		_ => Vec::new(),
+	}
+}
+
+fn throwable_get_stack_trace_depth(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::int(
+		stack_trace_elements(&arguments[0]).len() as i32
+	))
+}
+
+fn throwable_get_stack_trace_element(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let elements = stack_trace_elements(&arguments[0]);
+	let index = int_argument(arguments, 1);
+	if index < 0 || index as usize >= elements.len() {
+		thread.throw_new("java/lang/IndexOutOfBoundsException", None);
+		return None;
+	}
+	Some(elements[index as usize].clone())
+}
+
+fn object_get_class(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match &arguments[0] {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+			let class = object.lock().ok()?.get_class();
+			thread.class_object(&class)
+		}
+		_ => {
+			let type_name = arguments[0].type_name().unwrap_or_default();
+			thread.class_mirror(&type_name)
+		}
+	}
+}
+
+/// A shallow copy of the object or array. An object's class must
+/// implement Cloneable.
+fn object_clone(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match &arguments[0] {
+		JvmValue::Reference(
+			JvmReferenceType::Array(component, length),
+			JvmReferenceTargetType::Array(array),
+			access,
+		) => {
+			let copy = {
+				let array = array.lock().ok()?;
+				let mut copy = JvmArray::new(array.dimension());
+				copy.set_range(0, array.get_range(0, array.dimension()));
+				copy
+			};
+			Some(JvmValue::Reference(
+				JvmReferenceType::Array(Rc::clone(component), *length),
+				JvmReferenceTargetType::Array(thread.allocate_array(copy)?),
+				*access,
+			))
+		}
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+			let class = object.lock().ok()?.get_class();
+			let class_name = class.get_class_name().unwrap_or_default();
+			if !thread.is_assignable(&class_name, "java/lang/Cloneable") {
+				thread.throw_new(
+					"java/lang/CloneNotSupportedException",
+					Some(&class_name.replace('/', ".")),
+				);
+				return None;
+			}
+			let copy = thread.create_object(class)?;
+			if let JvmValue::Reference(_, JvmReferenceTargetType::Object(copy_object), _) = &copy {
+				let values: Vec<JvmValue> = object.lock().ok()?.values().cloned().collect();
+				let mut copy_object = copy_object.lock().ok()?;
+				for (offset, value) in values.into_iter().enumerate() {
+					copy_object.set_field(offset, value);
+				}
+			}
+			Some(copy)
+		}
+		_ => {
+			thread.throw_new("java/lang/NullPointerException", None);
+			None
+		}
+	}
+}
+
+/// There is only one thread, so there is never another to notify the
+/// waiting one: wait until the timeout (if there is one) elapses.
+fn object_wait(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let timeout = long_argument(arguments, 1);
+	if timeout < 0 {
+		thread.throw_new(
+			"java/lang/IllegalArgumentException",
+			Some("timeout value is negative"),
+		);
+		return None;
+	}
+	thread::sleep(Duration::from_millis(timeout as u64));
+	void()
+}
+
+fn thread_current_thread(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(thread.current_thread())
+}
+
+fn thread_is_alive(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	boolean_value(arguments[0].is_same_reference(&thread.current_thread()))
+}
+
+/// The VM runs only the thread that runs main: a thread that the
+/// program starts never runs.
+fn thread_start0(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	void()
+}
+
+/// No thread is ever interrupted: there is no other to interrupt it.
+fn thread_is_interrupted(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	boolean_value(false)
+}
+
+/// Monitors are never contended, so the only thread holds every lock.
+fn thread_holds_lock(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	if arguments[0].is_null() {
+		thread.throw_new("java/lang/NullPointerException", None);
+		return None;
+	}
+	boolean_value(true)
+}
+
+fn thread_sleep(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let duration = long_argument(arguments, 0);
+	if duration < 0 {
+		thread.throw_new(
+			"java/lang/IllegalArgumentException",
+			Some("timeout value is negative"),
+		);
+		return None;
+	}
+	thread::sleep(Duration::from_millis(duration as u64));
+	void()
+}
+
+/// The system properties that describe the VM and the host.
+pub fn standard_properties(
+	thread: &JvmThread,
+	methodarea: &Arc<Mutex<MethodArea>>,
+) -> Vec<(String, String)> {
+	let (classpath_entries, boot_classpath_entries, object_location) = match methodarea.lock() {
+		Ok(methodarea) => (
+			methodarea.get_classpath_entries(),
+			methodarea.get_boot_classpath_entries(),
+			methodarea.get_class_location("java/lang/Object"),
+		),
+		_ => (Vec::new(), Vec::new(), None),
+	};
+	let user_home = env::var("HOME").unwrap_or_else(|_| "?".to_string());
+	let user_dir = env::current_dir()
+		.map(|directory| directory.to_string_lossy().into_owned())
+		.unwrap_or_else(|_| "?".to_string());
+	let encoding = thread.console_charset().to_string();
+	let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+		.iter()
+		.filter_map(|variable| env::var(variable).ok())
+		.find(|locale| !locale.is_empty())
+		.unwrap_or_default();
+	let (language, country) = locale_language_and_country(&locale);
+	let mut properties: Vec<(&str, String)> = vec![
+		("java.version", "1.8.0".to_string()),
+		("java.vendor", "Rust-JVM".to_string()),
+		(
+			"java.vendor.url",
+			"https://github.com/hawkinsw/rust_jvm".to_string(),
+		),
+		("java.specification.version", "1.8".to_string()),
+		(
+			"java.specification.name",
+			"Java Platform API Specification".to_string(),
+		),
+		(
+			"java.specification.vendor",
+			"Oracle Corporation".to_string(),
+		),
+		("java.vm.specification.version", "1.8".to_string()),
+		(
+			"java.vm.specification.name",
+			"Java Virtual Machine Specification".to_string(),
+		),
+		(
+			"java.vm.specification.vendor",
+			"Oracle Corporation".to_string(),
+		),
+		("java.vm.name", "Rust-JVM".to_string()),
+		("java.vm.version", env!("CARGO_PKG_VERSION").to_string()),
+		("java.vm.vendor", "Rust-JVM".to_string()),
+		("java.vm.info", "interpreted mode".to_string()),
+		("java.class.version", "52.0".to_string()),
+		("java.class.path", classpath_entries.join(":")),
+		("java.library.path", String::new()),
+		("os.name", os_name().to_string()),
+		("os.arch", env::consts::ARCH.to_string()),
+		("os.version", os_version()),
+		("file.separator", "/".to_string()),
+		("path.separator", ":".to_string()),
+		("line.separator", "\n".to_string()),
+		(
+			"user.name",
+			env::var("USER").unwrap_or_else(|_| "?".to_string()),
+		),
+		("user.home", user_home),
+		("user.dir", user_dir),
+		(
+			"java.io.tmpdir",
+			env::temp_dir().to_string_lossy().into_owned(),
+		),
+		("user.language", language.to_string()),
+		("user.country", country.to_string()),
+		("file.encoding", encoding.clone()),
+		("sun.jnu.encoding", encoding),
+		(
+			"sun.arch.data.model",
+			(8 * std::mem::size_of::<usize>()).to_string(),
+		),
+		(
+			"sun.cpu.endian",
+			if cfg!(target_endian = "little") {
+				"little".to_string()
+			} else {
+				"big".to_string()
+			},
+		),
+	];
+	/*
+	 * If the class library is that of a JDK (in its lib/rt.jar), it
+	 * looks for the rest of the JDK around it.
+	 */
+	if let Some(ClassLocation::JarFile(jar, _)) = &object_location {
+		let jar = Path::new(jar);
+		if jar.file_name().map_or(false, |name| name == "rt.jar") {
+			if let Some(lib) = jar.parent() {
+				if let Some(home) = lib.parent() {
+					properties.push(("java.home", home.to_string_lossy().into_owned()));
+				}
+				properties.push((
+					"sun.boot.library.path",
+					lib.join(env::consts::ARCH).to_string_lossy().into_owned(),
+				));
+			}
+			properties.push(("sun.boot.class.path", jar.to_string_lossy().into_owned()));
+		}
+	}
+	/*
+	 * Likewise, if it is that of a JDK 9+ (in its lib/modules).
+	 */
+	if let Some(ClassLocation::JImage(image, _)) = object_location {
+		let image = Path::new(image.path());
+		if image.file_name().map_or(false, |name| name == "modules") {
+			if let Some(lib) = image.parent() {
+				if let Some(home) = lib.parent() {
+					properties.push(("java.home", home.to_string_lossy().into_owned()));
+				}
+				properties.push(("sun.boot.library.path", lib.to_string_lossy().into_owned()));
+			}
+		}
+	}
+	/*
+	 * The boot class path that the embedder set, though, is what it is.
+	 */
+	if !boot_classpath_entries.is_empty() {
+		properties.push(("sun.boot.class.path", boot_classpath_entries.join(":")));
+	}
+	properties
+		.into_iter()
+		.map(|(key, value)| (key.to_string(), value))
+		.collect()
+}
+
+/// The name of the host's operating system, as Java knows it.
+/// The version of the host's operating system (its kernel's release),
+/// if the VM can find out.
+fn os_version() -> String {
+	fs::read_to_string("/proc/sys/kernel/osrelease")
+		.map(|release| release.trim().to_string())
+		.unwrap_or_default()
+}
+
+/// The language and country of the locale `locale` (e.g., en and US for
+/// en_US.UTF-8). The C (or POSIX) locale, like no locale, is English.
+fn locale_language_and_country(locale: &str) -> (&str, &str) {
+	let name = locale.split(|c| c == '.' || c == '@').next().unwrap_or("");
+	if name.is_empty() || name == "C" || name == "POSIX" {
+		return ("en", "");
+	}
+	let mut parts = name.splitn(2, '_');
+	(parts.next().unwrap_or("en"), parts.next().unwrap_or(""))
+}
+
+fn os_name() -> &'static str {
+	match env::consts::OS {
+		"linux" => "Linux",
+		"macos" => "Mac OS X",
+		"windows" => "Windows",
+		"freebsd" => "FreeBSD",
+		"solaris" => "SunOS",
+		os => os,
+	}
+}
+
+/// Set the system properties (see standard_properties) in `props`
+/// (a java.util.Properties).
+fn system_init_properties(
+	thread: &mut JvmThread,
+	methodarea: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let properties = &arguments[0];
+	let mut system_properties = standard_properties(thread, methodarea);
+	system_properties.extend(thread.system_properties().iter().cloned());
+	for (key, value) in system_properties {
+		let key = thread.create_string(&key)?;
+		let value = thread.create_string(&value)?;
+		thread.call_virtual(
+			properties,
+			"setProperty",
+			"(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/Object;",
+			vec![key, value],
+		)?;
+	}
+	Some(properties.clone())
+}
+
+/// Set System.in, out or err (which are final).
+fn system_set_stream(
+	thread: &mut JvmThread,
+	field_name: &str,
+	field_type: &str,
+	stream: &JvmValue,
+) -> Option<JvmValue> {
+	let system_class = thread.resolve_class("java/lang/System")?;
+	thread.set_static(&system_class, field_name, field_type, stream.clone());
+	void()
+}
+
+/// The names and values of the variables of the host's environment,
+/// alternately (System.getenv reads them).
+fn system_environ(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	let mut strings = Vec::new();
+	for (name, value) in env::vars_os() {
+		strings.push(thread.create_string(&name.to_string_lossy())?);
+		strings.push(thread.create_string(&value.to_string_lossy())?);
+	}
+	thread.create_array(
+		JvmType::Reference(JvmReferenceType::Class("java/lang/String".to_string())),
+		strings,
+	)
+}
+
+/// ProcessEnvironment.environ (that of OpenJDK on Unix): the names and
+/// values of the variables of the host's environment, alternately, as
+/// arrays of bytes.
+fn process_environment_environ(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	let byte = JvmType::Primitive(JvmPrimitiveType::Byte);
+	let mut arrays = Vec::new();
+	for (name, value) in env::vars_os() {
+		for string in &[name, value] {
+			let bytes = string
+				.to_string_lossy()
+				.bytes()
+				.map(|b| array_element(&byte, JvmValue::int(b as i8 as i32)))
+				.collect();
+			arrays.push(thread.create_array(byte.clone(), bytes)?);
+		}
+	}
+	thread.create_array(JvmType::from_field_descriptor("[B")?, arrays)
+}
+
+fn system_map_library_name(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match get_string_value(&arguments[0]) {
+		Some(name) => thread.create_string(&format!(
+			"{}{}{}",
+			env::consts::DLL_PREFIX,
+			name,
+			env::consts::DLL_SUFFIX
+		)),
+		None => {
+			thread.throw_new("java/lang/NullPointerException", None);
+			None
+		}
+	}
+}
+
+fn runtime_available_processors(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	let processors = thread::available_parallelism().map_or(1, |processors| processors.get());
+	Some(JvmValue::int(processors as i32))
+}
+
+fn runtime_free_memory(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	let (allocated, max_size) = thread.heap_usage();
+	Some(JvmValue::long(max_size.saturating_sub(allocated) as i64))
+}
+
+/// The heap never grows: its total size is its maximum size.
+fn runtime_max_memory(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::long(thread.heap_usage().1 as i64))
+}
+
+fn runtime_gc(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	thread.collect_garbage();
+	void()
+}
+
+/// The natives of the class library are all built into the VM, so
+/// every library is built in: its name is that of the library file
+/// without its prefix and suffix.
+fn class_loader_find_builtin_lib(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let library = get_string_value(&arguments[0]).unwrap_or_default();
+	let library = library
+		.strip_prefix(env::consts::DLL_PREFIX)
+		.unwrap_or(&library);
+	let library = library
+		.strip_suffix(env::consts::DLL_SUFFIX)
+		.unwrap_or(library);
+	thread.create_string(library)
+}
+
+fn class_loader_find_bootstrap_class(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let class_name = get_string_value(&arguments[1])
+		.unwrap_or_default()
+		.replace('.', "/");
+	if !thread.can_load_class(&class_name) {
+		return null();
+	}
+	let mirror = thread.class_mirror(&class_name)?;
+	match mirrored_class(thread, &mirror) {
+		Some(_) => Some(mirror),
+		None => null(),
+	}
+}
+
+/// The class with the binary name in arguments[1] that the class loader
+/// has loaded (that it defined or that its loadClass returned), or null.
+fn class_loader_find_loaded_class0(
+	thread: &mut JvmThread,
+	methodarea: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let class_name = get_string_value(&arguments[1])
+		.unwrap_or_default()
+		.replace('.', "/");
+	let loader = thread.register_class_loader(&arguments[0]);
+	let class = match methodarea.lock() {
+		Ok(methodarea) => (*methodarea).find_class(&class_name, loader),
+		_ => None,
+	};
+	match class {
+		Some(class) => thread.class_object(&class),
+		None => null(),
+	}
+}
+
+/// Define a class with the class loader: the bytes of its class file
+/// are arguments[4] bytes of the array arguments[2] from arguments[3].
+/// The protection domain (and source) are ignored.
+fn class_loader_define_class(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let class_name = get_string_value(&arguments[1]).map(|name| name.replace('.', "/"));
+	let class_bytes = array_bytes(
+		thread,
+		&arguments[2],
+		int_argument(arguments, 3),
+		int_argument(arguments, 4),
+	)?;
+	let loader = thread.register_class_loader(&arguments[0]);
+	let class = thread.define_class(class_name.as_deref(), class_bytes, loader)?;
+	thread.class_object(&class)
+}
+
+/// Loading a (built-in) library always succeeds.
+fn native_library_load(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	if let JvmValue::Reference(_, JvmReferenceTargetType::Object(library), _) = &arguments[0] {
+		if let Ok(mut library) = library.lock() {
+			library.set_field_by_name("loaded", JvmValue::int(1));
+			library.set_field_by_name("handle", JvmValue::long(1));
+		}
+	}
+	void()
+}
@@ -0,0 +1,863 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::array::array_element;
+use jvm::array::JvmArray;
+use jvm::jvmthread::JvmThread;
+use jvm::methodarea::MethodArea;
+use jvm::native::NativeRegistry;
+use jvm::native::{boolean_value, int_argument, int_of, long_argument};
+use jvm::native::{mirrored_class, null, void};
+use jvm::object::get_string_value;
+use jvm::object::JvmObject;
+use jvm::typevalues::create_default_value;
+use jvm::typevalues::JvmPrimitiveType;
+use jvm::typevalues::JvmReferenceTargetType;
+use jvm::typevalues::JvmReferenceType;
+use jvm::typevalues::JvmType;
+use jvm::typevalues::JvmValue;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The offset (as Unsafe sees it) of the first element of an array.
+const ARRAY_BASE_OFFSET: i64 = 16;
+
+/// The offsets of static fields start here: a static field's offset is
+/// this plus its index in its class' fields table. The offsets of
+/// instance fields (their slots in the object's layout) are far below.
+const STATIC_FIELD_OFFSET: i64 = 1 << 32;
+
+/// The kinds of values that Unsafe reads and writes.
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+	Boolean,
+	Byte,
+	Short,
+	Char,
+	Int,
+	Long,
+	Float,
+	Double,
+	Object,
+}
+
+impl Kind {
+	/// The kinds, with the names that Unsafe gives their accessors
+	/// and their descriptors.
+	fn all() -> Vec<(Kind, &'static str, &'static str)> {
+		vec![
+			(Kind::Boolean, "Boolean", "Z"),
+			(Kind::Byte, "Byte", "B"),
+			(Kind::Short, "Short", "S"),
+			(Kind::Char, "Char", "C"),
+			(Kind::Int, "Int", "I"),
+			(Kind::Long, "Long", "J"),
+			(Kind::Float, "Float", "F"),
+			(Kind::Double, "Double", "D"),
+			(Kind::Object, "Object", "Ljava/lang/Object;"),
+		]
+	}
+
+	/// The number of bytes that a value of this kind occupies in native
+	/// memory. A reference occupies 4 in an array.
+	fn size(self) -> usize {
+		match self {
+			Kind::Boolean | Kind::Byte => 1,
+			Kind::Short | Kind::Char => 2,
+			Kind::Int | Kind::Float | Kind::Object => 4,
+			Kind::Long | Kind::Double => 8,
+		}
+	}
+
+	/// `value`, as a native method's result of this kind.
+	fn result(self, value: JvmValue) -> JvmValue {
+		match self {
+			Kind::Boolean => JvmValue::int(int_of(&value) & 1),
+			Kind::Byte => JvmValue::int(int_of(&value) as i8 as i32),
+			Kind::Short => JvmValue::int(int_of(&value) as i16 as i32),
+			Kind::Char => JvmValue::int(int_of(&value) as u16 as i32),
+			Kind::Int | Kind::Float => JvmValue::int(int_of(&value)),
+			Kind::Long | Kind::Double => match value {
+				JvmValue::Primitive(_, value, _, _) => JvmValue::long(value as i64),
+				_ => JvmValue::long(0),
+			},
+			Kind::Object => value,
+		}
+	}
+
+	/// Decode a value of this kind from (little-endian) native memory.
+	fn from_bytes(self, bytes: &[u8]) -> JvmValue {
+		let mut raw = 0u64;
+		for (index, byte) in bytes.iter().enumerate() {
+			raw |= (*byte as u64) << (8 * index);
+		}
+		match self {
+			Kind::Long | Kind::Double => JvmValue::long(raw as i64),
+			_ => self.result(JvmValue::int(raw as u32 as i32)),
+		}
+	}
+
+	/// Encode `value` as this kind for (little-endian) native memory.
+	fn to_bytes(self, value: &JvmValue) -> Vec<u8> {
+		let raw = match (self, value) {
+			(Kind::Long, JvmValue::Primitive(_, value, _, _))
+			| (Kind::Double, JvmValue::Primitive(_, value, _, _)) => *value,
+			_ => int_of(value) as u32 as u64,
+		};
+		(0..self.size())
+			.map(|index| (raw >> (8 * index)) as u8)
+			.collect()
+	}
+
+	/// `value` (an argument of this kind) as it is held in a field.
+	fn field_value(self, value: &JvmValue) -> JvmValue {
+		match self {
+			Kind::Long | Kind::Double | Kind::Object => value.clone(),
+			Kind::Float => JvmValue::Primitive(JvmPrimitiveType::Float, 0, int_of(value) as u32, 0),
+			_ => self.result(value.clone()),
+		}
+	}
+}
+
+/// A place that Unsafe reads or writes.
+enum Location {
+	/// The field in `slot` of an object.
+	Field(Arc<Mutex<JvmObject>>, usize),
+	/// A static field.
+	Static(Arc<Mutex<Option<JvmValue>>>, JvmType),
+	/// The element at `index` of an array of `component`s.
+	Element(Arc<Mutex<JvmArray>>, Rc<JvmType>, usize),
+	/// Native memory, at an address.
+	Memory(u64),
+}
+
+/// The location that `base` and `offset` -- the arguments of Unsafe's
+/// accessors -- name. `base` is an object (with the offset of one of its
+/// fields), an array (with that of one of its elements), a Class (with
+/// that of one of its static fields) or null (with an address).
+fn locate(thread: &mut JvmThread, base: &JvmValue, offset: i64) -> Option<Location> {
+	match base {
+		JvmValue::Reference(_, JvmReferenceTargetType::Null, _) => {
+			Some(Location::Memory(offset as u64))
+		}
+		JvmValue::Reference(
+			JvmReferenceType::Array(component, _),
+			JvmReferenceTargetType::Array(array),
+			_,
+		) => {
+			let scale = element_kind(component).size() as i64;
+			if offset < ARRAY_BASE_OFFSET || (offset - ARRAY_BASE_OFFSET) % scale != 0 {
+				return None;
+			}
+			let index = ((offset - ARRAY_BASE_OFFSET) / scale) as usize;
+			if !array.lock().ok()?.inbounds(index) {
+				return None;
+			}
+			Some(Location::Element(
+				Arc::clone(array),
+				Rc::clone(component),
+				index,
+			))
+		}
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(_), _)
+			if offset >= STATIC_FIELD_OFFSET =>
+		{
+			let class = mirrored_class(thread, base)?;
+			let index = (offset - STATIC_FIELD_OFFSET) as usize;
+			if index >= class.get_fields_ref().fields_count() as usize {
+				return None;
+			}
+			let field = class.get_fields_ref().get(index);
+			let field_type = JvmType::from_field_descriptor(
+				&field.get_descriptor(class.get_constant_pool_ref())?,
+			)?;
+			Some(Location::Static(field.value, field_type))
+		}
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+			if offset < 0 {
+				return None;
+			}
+			Some(Location::Field(Arc::clone(object), offset as usize))
+		}
+		_ => None,
+	}
+}
+
+/// The kind of the elements of an array of `component`s.
+fn element_kind(component: &JvmType) -> Kind {
+	match component {
+		JvmType::Primitive(JvmPrimitiveType::Boolean) => Kind::Boolean,
+		JvmType::Primitive(JvmPrimitiveType::Byte) => Kind::Byte,
+		JvmType::Primitive(JvmPrimitiveType::Short) => Kind::Short,
+		JvmType::Primitive(JvmPrimitiveType::Char) => Kind::Char,
+		JvmType::Primitive(JvmPrimitiveType::Integer) => Kind::Int,
+		JvmType::Primitive(JvmPrimitiveType::LongInteger) => Kind::Long,
+		JvmType::Primitive(JvmPrimitiveType::Float) => Kind::Float,
+		JvmType::Primitive(JvmPrimitiveType::Double) => Kind::Double,
+		_ => Kind::Object,
+	}
+}
+
+/// Read a value of `kind` from `location`.
+fn read(thread: &mut JvmThread, location: &Location, kind: Kind) -> Option<JvmValue> {
+	let value = match location {
+		Location::Field(object, slot) => object.lock().ok()?.get_field(*slot)?,
+		Location::Static(value, field_type) => value
+			.lock()
+			.ok()?
+			.clone()
+			.unwrap_or_else(|| create_default_value(field_type, 0)),
+		Location::Element(array, component, index) => array
+			.lock()
+			.ok()?
+			.get_at(*index)
+			.clone()
+			.unwrap_or_else(|| create_default_value(component, 0)),
+		Location::Memory(address) => {
+			if kind == Kind::Object {
+				return None;
+			}
+			return Some(kind.from_bytes(&thread.memory().read(*address, kind.size())?));
+		}
+	};
+	Some(kind.result(value))
+}
+
+/// Write `value`, of `kind`, to `location`.
+fn write(thread: &mut JvmThread, location: &Location, kind: Kind, value: &JvmValue) -> Option<()> {
+	match location {
+		Location::Field(object, slot) => {
+			if !object
+				.lock()
+				.ok()?
+				.set_field(*slot, kind.field_value(value))
+			{
+				return None;
+			}
+		}
+		Location::Static(field_value, _) => {
+			*field_value.lock().ok()? = Some(kind.field_value(value))
+		}
+		Location::Element(array, component, index) => {
+			let element = array_element(component, kind.field_value(value));
+			array.lock().ok()?.set_at(*index, element);
+		}
+		Location::Memory(address) => {
+			if kind == Kind::Object || !thread.memory().write(*address, &kind.to_bytes(value)) {
+				return None;
+			}
+		}
+	}
+	Some(())
+}
+
+/// Throw the error that HotSpot throws for a bad access.
+fn throw_fault(thread: &mut JvmThread) {
+	thread.throw_new(
+		"java/lang/InternalError",
+		Some("a fault occurred in an unsafe memory access operation"),
+	);
+}
+
+fn get(thread: &mut JvmThread, base: &JvmValue, offset: i64, kind: Kind) -> Option<JvmValue> {
+	let value = match locate(thread, base, offset) {
+		Some(location) => read(thread, &location, kind),
+		None => None,
+	};
+	if value.is_none() {
+		throw_fault(thread);
+	}
+	value
+}
+
+fn put(
+	thread: &mut JvmThread,
+	base: &JvmValue,
+	offset: i64,
+	kind: Kind,
+	value: &JvmValue,
+) -> Option<JvmValue> {
+	let written = match locate(thread, base, offset) {
+		Some(location) => write(thread, &location, kind, value),
+		None => None,
+	};
+	if written.is_none() {
+		throw_fault(thread);
+		return None;
+	}
+	void()
+}
+
+/// Whether `current` (read from a location of `kind`) is `expected`.
+fn is_expected(kind: Kind, current: &JvmValue, expected: &JvmValue) -> bool {
+	match kind {
+		Kind::Object => {
+			current.is_same_reference(expected) || current.is_null() && expected.is_null()
+		}
+		Kind::Long => current.as_long() == expected.as_long(),
+		_ => int_of(current) == int_of(expected),
+	}
+}
+
+/// Set the location (see locate) to `value` if it holds `expected`.
+/// Threads never run concurrently, so this is trivially atomic.
+fn compare_and_swap(
+	thread: &mut JvmThread,
+	arguments: &[JvmValue],
+	kind: Kind,
+) -> Option<JvmValue> {
+	let offset = long_argument(arguments, 2);
+	let current = get(thread, &arguments[1], offset, kind)?;
+	if !is_expected(kind, &current, &arguments[3]) {
+		return boolean_value(false);
+	}
+	put(thread, &arguments[1], offset, kind, &arguments[4])?;
+	boolean_value(true)
+}
+
+/// Register the natives of the sun.misc package (and of the class
+/// library's other users of VM internals).
+pub fn register(registry: &mut NativeRegistry) {
+	let r#unsafe = "sun/misc/Unsafe";
+	registry.register(
+		r#unsafe,
+		"registerNatives",
+		"()V",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| void(),
+	);
+	for (kind, name, descriptor) in Kind::all() {
+		let getter =
+			move |thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+				get(thread, &arguments[1], long_argument(arguments, 2), kind)
+			};
+		let setter =
+			move |thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+				put(
+					thread,
+					&arguments[1],
+					long_argument(arguments, 2),
+					kind,
+					&arguments[3],
+				)
+			};
+		let get_descriptor = format!("(Ljava/lang/Object;J){}", descriptor);
+		let put_descriptor = format!("(Ljava/lang/Object;J{})V", descriptor);
+		registry.register(r#unsafe, &format!("get{}", name), &get_descriptor, getter);
+		registry.register(r#unsafe, &format!("put{}", name), &put_descriptor, setter);
+		/*
+		 * Threads never run concurrently: every access is volatile.
+		 */
+		registry.register(
+			r#unsafe,
+			&format!("get{}Volatile", name),
+			&get_descriptor,
+			getter,
+		);
+		registry.register(
+			r#unsafe,
+			&format!("put{}Volatile", name),
+			&put_descriptor,
+			setter,
+		);
+		if kind == Kind::Int || kind == Kind::Long || kind == Kind::Object {
+			registry.register(
+				r#unsafe,
+				&format!("putOrdered{}", name),
+				&put_descriptor,
+				setter,
+			);
+			registry.register(
+				r#unsafe,
+				&format!("compareAndSwap{}", name),
+				&format!("(Ljava/lang/Object;J{}{})Z", descriptor, descriptor),
+				move |thread: &mut JvmThread,
+				      _: &Arc<Mutex<MethodArea>>,
+				      arguments: &[JvmValue]| { compare_and_swap(thread, arguments, kind) },
+			);
+		}
+		if kind != Kind::Boolean && kind != Kind::Object {
+			registry.register(
+				r#unsafe,
+				&format!("get{}", name),
+				&format!("(J){}", descriptor),
+				move |thread: &mut JvmThread,
+				      _: &Arc<Mutex<MethodArea>>,
+				      arguments: &[JvmValue]| {
+					get(thread, &null().unwrap(), long_argument(arguments, 1), kind)
+				},
+			);
+			registry.register(
+				r#unsafe,
+				&format!("put{}", name),
+				&format!("(J{})V", descriptor),
+				move |thread: &mut JvmThread,
+				      _: &Arc<Mutex<MethodArea>>,
+				      arguments: &[JvmValue]| {
+					put(
+						thread,
+						&null().unwrap(),
+						long_argument(arguments, 1),
+						kind,
+						&arguments[2],
+					)
+				},
+			);
+		}
+	}
+	registry.register(
+		r#unsafe,
+		"getAddress",
+		"(J)J",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			get(
+				thread,
+				&null().unwrap(),
+				long_argument(arguments, 1),
+				Kind::Long,
+			)
+		},
+	);
+	registry.register(
+		r#unsafe,
+		"putAddress",
+		"(JJ)V",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			put(
+				thread,
+				&null().unwrap(),
+				long_argument(arguments, 1),
+				Kind::Long,
+				&arguments[2],
+			)
+		},
+	);
+	registry.register(r#unsafe, "allocateMemory", "(J)J", unsafe_allocate_memory);
+	registry.register(
+		r#unsafe,
+		"reallocateMemory",
+		"(JJ)J",
+		unsafe_reallocate_memory,
+	);
+	registry.register(r#unsafe, "freeMemory", "(J)V", unsafe_free_memory);
+	registry.register(
+		r#unsafe,
+		"setMemory",
+		"(Ljava/lang/Object;JJB)V",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			set_memory(
+				thread,
+				&arguments[1],
+				long_argument(arguments, 2),
+				long_argument(arguments, 3),
+				&arguments[4],
+			)
+		},
+	);
+	registry.register(
+		r#unsafe,
+		"copyMemory",
+		"(Ljava/lang/Object;JLjava/lang/Object;JJ)V",
+		unsafe_copy_memory,
+	);
+	registry.register(
+		r#unsafe,
+		"objectFieldOffset",
+		"(Ljava/lang/reflect/Field;)J",
+		unsafe_object_field_offset,
+	);
+	registry.register(
+		r#unsafe,
+		"staticFieldOffset",
+		"(Ljava/lang/reflect/Field;)J",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			match reflected_field(&arguments[1]) {
+				Some((_, slot)) => Some(JvmValue::long(STATIC_FIELD_OFFSET + slot as i64)),
+				None => {
+					thread.throw_new("java/lang/NullPointerException", None);
+					None
+				}
+			}
+		},
+	);
+	registry.register(
+		r#unsafe,
+		"staticFieldBase",
+		"(Ljava/lang/reflect/Field;)Ljava/lang/Object;",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			match reflected_field(&arguments[1]) {
+				Some((class, _)) => Some(class),
+				None => {
+					thread.throw_new("java/lang/NullPointerException", None);
+					None
+				}
+			}
+		},
+	);
+	registry.register(
+		r#unsafe,
+		"shouldBeInitialized",
+		"(Ljava/lang/Class;)Z",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			match mirrored_class(thread, &arguments[1]) {
+				Some(class) => boolean_value(!thread.is_initialized(&class)),
+				None => boolean_value(false),
+			}
+		},
+	);
+	registry.register(
+		r#unsafe,
+		"ensureClassInitialized",
+		"(Ljava/lang/Class;)V",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			if let Some(class) = mirrored_class(thread, &arguments[1]) {
+				thread.maybe_initialize_class(&class);
+			}
+			if thread.exception_pending() {
+				return None;
+			}
+			void()
+		},
+	);
+	registry.register(
+		r#unsafe,
+		"arrayBaseOffset",
+		"(Ljava/lang/Class;)I",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| {
+			Some(JvmValue::int(ARRAY_BASE_OFFSET as i32))
+		},
+	);
+	registry.register(
+		r#unsafe,
+		"arrayIndexScale",
+		"(Ljava/lang/Class;)I",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			let type_name = thread.mirrored_type(&arguments[1]).unwrap_or_default();
+			let component = type_name
+				.get(1..)
+				.and_then(|component| JvmType::from_field_descriptor(component));
+			match component {
+				Some(component) => Some(JvmValue::int(element_kind(&component).size() as i32)),
+				None => {
+					thread.throw_new("java/lang/IllegalArgumentException", None);
+					None
+				}
+			}
+		},
+	);
+	registry.register(
+		r#unsafe,
+		"addressSize",
+		"()I",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| Some(JvmValue::int(8)),
+	);
+	registry.register(
+		r#unsafe,
+		"pageSize",
+		"()I",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| Some(JvmValue::int(4096)),
+	);
+	registry.register(
+		r#unsafe,
+		"allocateInstance",
+		"(Ljava/lang/Class;)Ljava/lang/Object;",
+		unsafe_allocate_instance,
+	);
+	registry.register(
+		r#unsafe,
+		"throwException",
+		"(Ljava/lang/Throwable;)V",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			thread.throw(arguments[1].clone());
+			None
+		},
+	);
+	registry.register(r#unsafe, "park", "(ZJ)V", unsafe_park);
+	/*
+	 * There is only ever one thread: none is ever parked, waiting to
+	 * be unparked, and no monitor is ever contended.
+	 */
+	registry.register(
+		r#unsafe,
+		"unpark",
+		"(Ljava/lang/Object;)V",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| void(),
+	);
+	for fence in &["loadFence", "storeFence", "fullFence"] {
+		registry.register(
+			r#unsafe,
+			fence,
+			"()V",
+			|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| void(),
+		);
+	}
+	for monitor in &["monitorEnter", "monitorExit"] {
+		registry.register(
+			r#unsafe,
+			monitor,
+			"(Ljava/lang/Object;)V",
+			|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| void(),
+		);
+	}
+	registry.register(
+		r#unsafe,
+		"tryMonitorEnter",
+		"(Ljava/lang/Object;)Z",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| boolean_value(true),
+	);
+	registry.register(
+		r#unsafe,
+		"getLoadAverage",
+		"([DI)I",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| Some(JvmValue::int(-1)),
+	);
+
+	registry.register(
+		"sun/misc/VM",
+		"initialize",
+		"()V",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| void(),
+	);
+	registry.register(
+		"sun/misc/Signal",
+		"findSignal",
+		"(Ljava/lang/String;)I",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			Some(JvmValue::int(signal_number(
+				&get_string_value(&arguments[0]).unwrap_or_default(),
+			)))
+		},
+	);
+	/*
+	 * Signals are not delivered to Java code: installing a handler
+	 * succeeds, but it never runs.
+	 */
+	registry.register(
+		"sun/misc/Signal",
+		"handle0",
+		"(IJ)J",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| Some(JvmValue::long(0)),
+	);
+	registry.register(
+		"java/util/concurrent/atomic/AtomicLong",
+		"VMSupportsCS8",
+		"()Z",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| boolean_value(true),
+	);
+}
+
+/// The number of the signal named `name` (e.g., INT), or -1 if
+/// there is no such signal.
+fn signal_number(name: &str) -> i32 {
+	match name {
+		"HUP" => 1,
+		"INT" => 2,
+		"QUIT" => 3,
+		"ILL" => 4,
+		"TRAP" => 5,
+		"ABRT" => 6,
+		"BUS" => 7,
+		"FPE" => 8,
+		"KILL" => 9,
+		"USR1" => 10,
+		"SEGV" => 11,
+		"USR2" => 12,
+		"PIPE" => 13,
+		"ALRM" => 14,
+		"TERM" => 15,
+		_ => -1,
+	}
+}
+
+/// The Class and the slot (its index in its class' fields table) of the
+/// field that a java.lang.reflect.Field reflects.
+fn reflected_field(field: &JvmValue) -> Option<(JvmValue, usize)> {
+	match field {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(field), _) => {
+			let field = field.lock().ok()?;
+			let class = field.get_field_by_name("clazz")?;
+			let slot = int_of(&field.get_field_by_name("slot")?) as usize;
+			Some((class, slot))
+		}
+		_ => None,
+	}
+}
+
+/// The offset of an instance field: its slot in the layout of the
+/// objects of its class.
+fn unsafe_object_field_offset(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let offset = reflected_field(&arguments[1]).and_then(|(mirror, slot)| {
+		let class = mirrored_class(thread, &mirror)?;
+		let cp = class.get_constant_pool_ref();
+		if slot >= class.get_fields_ref().fields_count() as usize {
+			return None;
+		}
+		let field = class.get_fields_ref().get(slot);
+		class.get_layout().get_offset(
+			&class.get_class_name()?,
+			&field.get_name(cp)?,
+			&field.get_descriptor(cp)?,
+		)
+	});
+	match offset {
+		Some(offset) => Some(JvmValue::long(offset as i64)),
+		None => {
+			thread.throw_new("java/lang/IllegalArgumentException", None);
+			None
+		}
+	}
+}
+
+fn unsafe_allocate_memory(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let size = long_argument(arguments, 1);
+	if size < 0 {
+		thread.throw_new("java/lang/IllegalArgumentException", None);
+		return None;
+	}
+	Some(JvmValue::long(
+		thread.memory().allocate(size as usize) as i64
+	))
+}
+
+fn unsafe_reallocate_memory(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let address = long_argument(arguments, 1) as u64;
+	let size = long_argument(arguments, 2);
+	if size < 0 {
+		thread.throw_new("java/lang/IllegalArgumentException", None);
+		return None;
+	}
+	match thread.memory().reallocate(address, size as usize) {
+		Some(address) => Some(JvmValue::long(address as i64)),
+		None => {
+			throw_fault(thread);
+			None
+		}
+	}
+}
+
+fn unsafe_free_memory(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let address = long_argument(arguments, 1) as u64;
+	if address != 0 && !thread.memory().free(address) {
+		throw_fault(thread);
+		return None;
+	}
+	void()
+}
+
+/// Set `count` bytes, starting at the location that `base` and `offset`
+/// name (see locate), to `value`.
+fn set_memory(
+	thread: &mut JvmThread,
+	base: &JvmValue,
+	offset: i64,
+	count: i64,
+	value: &JvmValue,
+) -> Option<JvmValue> {
+	for index in 0..count {
+		put(thread, base, offset + index, Kind::Byte, value)?;
+	}
+	void()
+}
+
+/// Copy bytes between native memory and arrays of bytes (or booleans).
+fn unsafe_copy_memory(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let source_offset = long_argument(arguments, 2);
+	let destination_offset = long_argument(arguments, 4);
+	let count = long_argument(arguments, 5);
+	let bytes = (0..count)
+		.map(|index| get(thread, &arguments[1], source_offset + index, Kind::Byte))
+		.collect::<Option<Vec<JvmValue>>>()?;
+	for (index, byte) in bytes.iter().enumerate() {
+		put(
+			thread,
+			&arguments[3],
+			destination_offset + index as i64,
+			Kind::Byte,
+			byte,
+		)?;
+	}
+	void()
+}
+
+/// Create an instance of the class without running a constructor.
+fn unsafe_allocate_instance(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let class = match mirrored_class(thread, &arguments[1]) {
+		Some(class) if !class.is_interface() => class,
+		_ => {
+			thread.throw_new("java/lang/InstantiationException", None);
+			return None;
+		}
+	};
+	thread.maybe_initialize_class(&class);
+	if thread.exception_pending() {
+		return None;
+	}
+	thread.create_object(class)
+}
+
+/// Block the thread until its timeout -- an absolute time (in
+/// milliseconds since the epoch) or a relative one (in nanoseconds).
+/// There is no other thread to unpark it, so without a timeout it
+/// returns at once, as park is allowed to do spuriously.
+fn unsafe_park(
+	_: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let is_absolute = int_argument(arguments, 1) != 0;
+	let time = long_argument(arguments, 2);
+	let timeout = if is_absolute {
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|now| now.as_millis() as i64)
+			.unwrap_or(0);
+		Duration::from_millis((time - now).max(0) as u64)
+	} else {
+		Duration::from_nanos(time.max(0) as u64)
+	};
+	thread::sleep(timeout);
+	void()
+}
@@ -0,0 +1,218 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::class::Class;
+use jvm::jvmthread::JvmThread;
+use jvm::methodarea::MethodArea;
+use jvm::typevalues::create_null_value;
+use jvm::typevalues::JvmPrimitiveType;
+use jvm::typevalues::JvmValue;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+pub mod io;
+pub mod lang;
+pub mod misc;
+pub mod reflect;
+pub mod security;
+
+/// A NativeMethod implements a Java method that is declared native.
+///
+/// It gets the thread that invoked it, the method area and the method's
+/// arguments (for an instance method, the object is the first). It returns
+/// the method's result: a Void value for a void method. To throw, it makes
+/// the exception pending (e.g., with JvmThread::throw or throw_new) and
+/// returns None.
+///
+/// Any closure with the same signature as invoke is a NativeMethod.
+pub trait NativeMethod: Send + Sync {
+	fn invoke(
+		&self,
+		thread: &mut JvmThread,
+		methodarea: &Arc<Mutex<MethodArea>>,
+		arguments: &[JvmValue],
+	) -> Option<JvmValue>;
+}
+
+impl<F> NativeMethod for F
+where
+	F: Fn(&mut JvmThread, &Arc<Mutex<MethodArea>>, &[JvmValue]) -> Option<JvmValue> + Send + Sync,
+{
+	fn invoke(
+		&self,
+		thread: &mut JvmThread,
+		methodarea: &Arc<Mutex<MethodArea>>,
+		arguments: &[JvmValue],
+	) -> Option<JvmValue> {
+		self(thread, methodarea, arguments)
+	}
+}
+
+/// The NativeRegistry maps each native method -- by the name of its class,
+/// its name and its descriptor -- to the NativeMethod that implements it.
+///
+/// It also holds intrinsics: NativeMethods that replace methods that
+/// have bytecode. An intrinsic that returns None without throwing
+/// declines to execute and the method's bytecode runs instead.
+#[derive(Clone)]
+pub struct NativeRegistry {
+	natives: HashMap<(String, String, String), Arc<dyn NativeMethod>>,
+	intrinsics: HashSet<(String, String, String)>,
+}
+
+impl NativeRegistry {
+	/// A registry of the VM's built-in natives.
+	pub fn new() -> Self {
+		let mut registry = NativeRegistry {
+			natives: HashMap::new(),
+			intrinsics: HashSet::new(),
+		};
+		lang::register(&mut registry);
+		io::register(&mut registry);
+		reflect::register(&mut registry);
+		security::register(&mut registry);
+		misc::register(&mut registry);
+		registry
+	}
+
+	/// Implement the native method `method_name` with type `descriptor`
+	/// of the class named `class_name` (e.g., java/lang/Object) with
+	/// `native`. It replaces any NativeMethod already registered for it.
+	pub fn register<N>(&mut self, class_name: &str, method_name: &str, descriptor: &str, native: N)
+	where
+		N: NativeMethod + 'static,
+	{
+		self.natives.insert(
+			(
+				class_name.to_string(),
+				method_name.to_string(),
+				descriptor.to_string(),
+			),
+			Arc::new(native),
+		);
+	}
+
+	/// Replace the method `method_name` with type `descriptor` of the
+	/// class named `class_name` with the intrinsic `native`.
+	pub fn register_intrinsic<N>(
+		&mut self,
+		class_name: &str,
+		method_name: &str,
+		descriptor: &str,
+		native: N,
+	) where
+		N: NativeMethod + 'static,
+	{
+		self.register(class_name, method_name, descriptor, native);
+		self.intrinsics.insert((
+			class_name.to_string(),
+			method_name.to_string(),
+			descriptor.to_string(),
+		));
+	}
+
+	/// The intrinsic that replaces a method, if one is registered.
+	pub fn get_intrinsic(
+		&self,
+		class_name: &str,
+		method_name: &str,
+		descriptor: &str,
+	) -> Option<Arc<dyn NativeMethod>> {
+		let key = (
+			class_name.to_string(),
+			method_name.to_string(),
+			descriptor.to_string(),
+		);
+		if self.intrinsics.contains(&key) {
+			self.natives.get(&key).cloned()
+		} else {
+			None
+		}
+	}
+
+	/// The NativeMethod that implements a native method, if one is registered.
+	pub fn get(
+		&self,
+		class_name: &str,
+		method_name: &str,
+		descriptor: &str,
+	) -> Option<Arc<dyn NativeMethod>> {
+		self.natives
+			.get(&(
+				class_name.to_string(),
+				method_name.to_string(),
+				descriptor.to_string(),
+			))
+			.cloned()
+	}
+}
+
+/// The value that a void native method returns.
+pub fn void() -> Option<JvmValue> {
+	Some(JvmValue::Primitive(JvmPrimitiveType::Void, 0, 0, 0))
+}
+
+/// The null reference, as a native method's result.
+pub fn null() -> Option<JvmValue> {
+	Some(create_null_value())
+}
+
+pub fn boolean_value(value: bool) -> Option<JvmValue> {
+	Some(JvmValue::int(value as i32))
+}
+
+/// The int (or boolean, byte, char or short) argument at `index`.
+pub fn int_argument(arguments: &[JvmValue], index: usize) -> i32 {
+	arguments.get(index).map(int_of).unwrap_or(0)
+}
+
+/// The int (or boolean, byte, char or short) that `value` holds. Chars
+/// in arrays hold their value in the 64-bit slot.
+pub fn int_of(value: &JvmValue) -> i32 {
+	match value {
+		JvmValue::Primitive(JvmPrimitiveType::Char, value, 0, _) => *value as i32,
+		JvmValue::Primitive(_, _, value, _) => *value as i32,
+		_ => 0,
+	}
+}
+
+pub fn long_argument(arguments: &[JvmValue], index: usize) -> i64 {
+	match arguments.get(index) {
+		Some(JvmValue::Primitive(_, value, _, _)) => *value as i64,
+		_ => 0,
+	}
+}
+
+pub fn float_argument(arguments: &[JvmValue], index: usize) -> f32 {
+	f32::from_bits(int_argument(arguments, index) as u32)
+}
+
+pub fn double_argument(arguments: &[JvmValue], index: usize) -> f64 {
+	f64::from_bits(long_argument(arguments, index) as u64)
+}
+
+/// The class whose Class object is `mirror`, loaded. None for the Class
+/// object of a primitive type or of an array type (or if `mirror` is
+/// not a Class object).
+pub fn mirrored_class(thread: &mut JvmThread, mirror: &JvmValue) -> Option<Rc<Class>> {
+	thread.mirrored_class(mirror)
+}
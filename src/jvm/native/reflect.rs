@@ -0,0 +1,900 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::class::Class;
+use jvm::class::ClassAccessFlags;
+use jvm::field::FieldAccessFlags;
+use jvm::frame::Frame;
+use jvm::jvmthread::JvmThread;
+use jvm::method::parameter_descriptors;
+use jvm::method::Method;
+use jvm::method::MethodAccessFlags;
+use jvm::methodarea::MethodArea;
+use jvm::methodarea::BOOTSTRAP_LOADER;
+use jvm::native::NativeRegistry;
+use jvm::native::{boolean_value, int_argument, int_of, mirrored_class, null, void};
+use jvm::object::get_string_value;
+use jvm::typevalues::create_null_value;
+use jvm::typevalues::descriptor_of_type_name;
+use jvm::typevalues::type_name_of_descriptor;
+use jvm::typevalues::JvmReferenceTargetType;
+use jvm::typevalues::JvmReferenceType;
+use jvm::typevalues::JvmType;
+use jvm::typevalues::JvmValue;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// The modifiers of the Class objects of primitive types: public,
+/// final and abstract.
+const PRIMITIVE_MODIFIERS: i32 = 0x411;
+
+/// Register the natives of reflection: those of java.lang.Class and of
+/// the java.lang.reflect and sun.reflect packages.
+pub fn register(registry: &mut NativeRegistry) {
+	let class = "java/lang/Class";
+	registry.register(
+		class,
+		"registerNatives",
+		"()V",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| void(),
+	);
+	registry.register(
+		class,
+		"getPrimitiveClass",
+		"(Ljava/lang/String;)Ljava/lang/Class;",
+		class_get_primitive_class,
+	);
+	registry.register(
+		class,
+		"desiredAssertionStatus0",
+		"(Ljava/lang/Class;)Z",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| boolean_value(false),
+	);
+	registry.register(class, "getName0", "()Ljava/lang/String;", class_get_name0);
+	registry.register(
+		class,
+		"forName0",
+		"(Ljava/lang/String;ZLjava/lang/ClassLoader;Ljava/lang/Class;)Ljava/lang/Class;",
+		class_for_name0,
+	);
+	registry.register(class, "isInterface", "()Z", class_is_interface);
+	registry.register(class, "isArray", "()Z", class_is_array);
+	registry.register(class, "isPrimitive", "()Z", class_is_primitive);
+	registry.register(
+		class,
+		"getSuperclass",
+		"()Ljava/lang/Class;",
+		class_get_superclass,
+	);
+	registry.register(
+		class,
+		"getInterfaces0",
+		"()[Ljava/lang/Class;",
+		class_get_interfaces0,
+	);
+	registry.register(class, "getModifiers", "()I", class_get_modifiers);
+	registry.register(
+		class,
+		"getComponentType",
+		"()Ljava/lang/Class;",
+		class_get_component_type,
+	);
+	registry.register(
+		class,
+		"isAssignableFrom",
+		"(Ljava/lang/Class;)Z",
+		class_is_assignable_from,
+	);
+	registry.register(
+		class,
+		"isInstance",
+		"(Ljava/lang/Object;)Z",
+		class_is_instance,
+	);
+	registry.register(
+		class,
+		"getDeclaredFields0",
+		"(Z)[Ljava/lang/reflect/Field;",
+		class_get_declared_fields0,
+	);
+	registry.register(
+		class,
+		"getDeclaredMethods0",
+		"(Z)[Ljava/lang/reflect/Method;",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			declared_executables(thread, arguments, false)
+		},
+	);
+	registry.register(
+		class,
+		"getDeclaredConstructors0",
+		"(Z)[Ljava/lang/reflect/Constructor;",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, arguments: &[JvmValue]| {
+			declared_executables(thread, arguments, true)
+		},
+	);
+	registry.register(
+		class,
+		"getDeclaredClasses0",
+		"()[Ljava/lang/Class;",
+		|thread: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| {
+			class_array(thread, Vec::new())
+		},
+	);
+	registry.register(
+		class,
+		"getClassLoader0",
+		"()Ljava/lang/ClassLoader;",
+		class_get_class_loader0,
+	);
+	/*
+	 * The VM keeps none of the optional metadata that these return.
+	 */
+	for (method_name, descriptor) in &[
+		("getEnclosingMethod0", "()[Ljava/lang/Object;"),
+		("getDeclaringClass0", "()Ljava/lang/Class;"),
+		("getGenericSignature0", "()Ljava/lang/String;"),
+		("getRawAnnotations", "()[B"),
+		("getRawTypeAnnotations", "()[B"),
+		("getConstantPool", "()Lsun/reflect/ConstantPool;"),
+		("getProtectionDomain0", "()Ljava/security/ProtectionDomain;"),
+		("getSigners", "()[Ljava/lang/Object;"),
+	] {
+		registry.register(
+			class,
+			method_name,
+			descriptor,
+			|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| null(),
+		);
+	}
+
+	let reflection = "sun/reflect/Reflection";
+	registry.register(
+		reflection,
+		"getCallerClass",
+		"()Ljava/lang/Class;",
+		reflection_get_caller_class,
+	);
+	registry.register(
+		reflection,
+		"getCallerClass",
+		"(I)Ljava/lang/Class;",
+		reflection_get_caller_class_at,
+	);
+	registry.register(
+		reflection,
+		"getClassAccessFlags",
+		"(Ljava/lang/Class;)I",
+		reflection_get_class_access_flags,
+	);
+	registry.register(
+		"sun/reflect/NativeConstructorAccessorImpl",
+		"newInstance0",
+		"(Ljava/lang/reflect/Constructor;[Ljava/lang/Object;)Ljava/lang/Object;",
+		native_constructor_accessor_new_instance0,
+	);
+	registry.register(
+		"sun/reflect/NativeMethodAccessorImpl",
+		"invoke0",
+		"(Ljava/lang/reflect/Method;Ljava/lang/Object;[Ljava/lang/Object;)Ljava/lang/Object;",
+		native_method_accessor_invoke0,
+	);
+
+	let array = "java/lang/reflect/Array";
+	registry.register(
+		array,
+		"newArray",
+		"(Ljava/lang/Class;I)Ljava/lang/Object;",
+		array_new_array,
+	);
+	registry.register(
+		array,
+		"getLength",
+		"(Ljava/lang/Object;)I",
+		array_get_length,
+	);
+}
+
+/// The name of the type whose Class object is `mirror` (see
+/// JvmThread::mirrored_type).
+fn mirrored_type(thread: &JvmThread, mirror: &JvmValue) -> String {
+	thread.mirrored_type(mirror).unwrap_or_default()
+}
+
+fn is_primitive(type_name: &str) -> bool {
+	descriptor_of_type_name(type_name).len() == 1
+}
+
+/// The value of the field `field_name` of `object`.
+fn get_field(object: &JvmValue, field_name: &str) -> Option<JvmValue> {
+	match object {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+			object.lock().ok()?.get_field_by_name(field_name)
+		}
+		_ => None,
+	}
+}
+
+/// Set the fields of `object` (e.g., a java.lang.reflect.Field that the
+/// VM created) named in `fields`.
+fn set_fields(object: &JvmValue, fields: Vec<(&str, JvmValue)>) {
+	if let JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) = object {
+		if let Ok(mut object) = object.lock() {
+			for (field_name, value) in fields {
+				object.set_field_by_name(field_name, value);
+			}
+		}
+	}
+}
+
+/// A Class[] of the Class objects of the types named `type_names`.
+fn class_array(thread: &mut JvmThread, type_names: Vec<String>) -> Option<JvmValue> {
+	let mut mirrors = Vec::with_capacity(type_names.len());
+	for type_name in type_names {
+		mirrors.push(thread.class_mirror(&type_name)?);
+	}
+	thread.create_array(
+		JvmType::Reference(JvmReferenceType::Class("java/lang/Class".to_string())),
+		mirrors,
+	)
+}
+
+/// The modifiers (java.lang.reflect.Modifier) of the type whose Class
+/// object is `mirror`.
+fn type_modifiers(thread: &mut JvmThread, mirror: &JvmValue) -> i32 {
+	let type_name = mirrored_type(thread, mirror);
+	if let Some(component) = type_name.strip_prefix('[') {
+		/*
+		 * An array type has the accessibility of its element type and
+		 * is final and abstract.
+		 */
+		let component = type_name_of_descriptor(component);
+		let component_modifiers = match thread.class_mirror(&component) {
+			Some(component) => type_modifiers(thread, &component),
+			None => 0,
+		};
+		return (component_modifiers & 0x7) | 0x410;
+	}
+	if is_primitive(&type_name) {
+		return PRIMITIVE_MODIFIERS;
+	}
+	match mirrored_class(thread, mirror) {
+		Some(class) => (class.access_flags & !(ClassAccessFlags::Super as u16)) as i32,
+		None => 0,
+	}
+}
+
+fn class_get_primitive_class(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let type_name = get_string_value(&arguments[0]).unwrap_or_default();
+	thread.class_mirror(&type_name)
+}
+
+/// The binary name of the class (e.g., java.lang.String or
+/// [Ljava.lang.String;).
+fn class_get_name0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let type_name = mirrored_type(thread, &arguments[0]);
+	thread.intern_string(&type_name.replace('/', "."))
+}
+
+/// The class loader that defined the class (null for the bootstrap
+/// class loader, and for primitive types and array types).
+fn class_get_class_loader0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(thread.class_loader_object(thread.mirrored_loader(&arguments[0])))
+}
+
+/// Load (and, if asked to, initialize) the class with the binary name
+/// `name` with the class loader arguments[2] (null for the bootstrap
+/// class loader).
+fn class_for_name0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let name = match get_string_value(&arguments[0]) {
+		Some(name) => name,
+		None => {
+			thread.throw_new("java/lang/NullPointerException", None);
+			return None;
+		}
+	};
+	let type_name = name.replace('.', "/");
+	let element = type_name.trim_start_matches('[');
+	let class_name = if type_name.starts_with('[') {
+		if element.starts_with('L') && element.ends_with(';') {
+			Some(&element[1..element.len() - 1])
+		} else if element.len() == 1 && is_primitive(&type_name_of_descriptor(element)) {
+			None
+		} else {
+			thread.throw_new("java/lang/ClassNotFoundException", Some(&name));
+			return None;
+		}
+	} else {
+		Some(element)
+	};
+	let loader = thread.register_class_loader(&arguments[2]);
+	if let Some(class_name) = class_name {
+		let class = if loader == BOOTSTRAP_LOADER {
+			if !thread.can_load_class(&class_name.to_string()) {
+				thread.throw_new("java/lang/ClassNotFoundException", Some(&name));
+				return None;
+			}
+			thread.resolve_class(class_name)?
+		} else {
+			match thread.load_class(class_name, loader) {
+				Some(class) => class,
+				None => {
+					if !thread.exception_pending() {
+						thread.throw_new("java/lang/ClassNotFoundException", Some(&name));
+					}
+					return None;
+				}
+			}
+		};
+		if !type_name.starts_with('[') {
+			if int_argument(arguments, 1) != 0 {
+				thread.maybe_initialize_class(&class);
+				if thread.exception_pending() {
+					return None;
+				}
+			}
+			return thread.class_object(&class);
+		}
+	}
+	thread.class_mirror(&type_name)
+}
+
+fn class_is_interface(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let is_interface = match mirrored_class(thread, &arguments[0]) {
+		Some(class) => class.is_interface(),
+		None => false,
+	};
+	boolean_value(is_interface)
+}
+
+fn class_is_array(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	boolean_value(mirrored_type(thread, &arguments[0]).starts_with('['))
+}
+
+fn class_is_primitive(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let type_name = mirrored_type(thread, &arguments[0]);
+	boolean_value(!type_name.starts_with('[') && is_primitive(&type_name))
+}
+
+/// The superclass of the class; that of an array type is Object. Object,
+/// interfaces and primitive types have none.
+fn class_get_superclass(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	if mirrored_type(thread, &arguments[0]).starts_with('[') {
+		return thread.class_mirror("java/lang/Object");
+	}
+	let class = match mirrored_class(thread, &arguments[0]) {
+		Some(class) if !class.is_interface() => class,
+		_ => return null(),
+	};
+	match class.superclass_name() {
+		Some(superclass_name) => {
+			let superclass = thread.resolve_class_from(&class, &superclass_name)?;
+			thread.class_object(&superclass)
+		}
+		None => null(),
+	}
+}
+
+/// The direct superinterfaces of the class. Those of an array type
+/// are Cloneable and Serializable.
+fn class_get_interfaces0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let interface_names = if mirrored_type(thread, &arguments[0]).starts_with('[') {
+		vec![
+			"java/lang/Cloneable".to_string(),
+			"java/io/Serializable".to_string(),
+		]
+	} else {
+		match mirrored_class(thread, &arguments[0]) {
+			Some(class) => class.get_interface_names(),
+			None => Vec::new(),
+		}
+	};
+	class_array(thread, interface_names)
+}
+
+fn class_get_modifiers(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	Some(JvmValue::int(type_modifiers(thread, &arguments[0])))
+}
+
+fn class_get_component_type(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match mirrored_type(thread, &arguments[0]).strip_prefix('[') {
+		Some(component) => thread.class_mirror(&type_name_of_descriptor(component)),
+		None => null(),
+	}
+}
+
+fn class_is_assignable_from(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	if arguments[1].is_null() {
+		thread.throw_new("java/lang/NullPointerException", None);
+		return None;
+	}
+	let target = mirrored_type(thread, &arguments[0]);
+	let source = mirrored_type(thread, &arguments[1]);
+	boolean_value(thread.is_assignable(&source, &target))
+}
+
+fn class_is_instance(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	if arguments[1].is_null() {
+		return boolean_value(false);
+	}
+	let target = mirrored_type(thread, &arguments[0]);
+	let target_class = mirrored_class(thread, &arguments[0]);
+	boolean_value(thread.is_value_assignable(&arguments[1], &target, target_class.as_ref()))
+}
+
+/// The fields that the class declares (only the public ones, if the
+/// argument is true), as java.lang.reflect.Fields. The slot of each is
+/// its index in the class file.
+fn class_get_declared_fields0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let public_only = int_argument(arguments, 1) != 0;
+	let mut fields = Vec::new();
+	if let Some(class) = mirrored_class(thread, &arguments[0]) {
+		let cp = class.get_constant_pool_ref();
+		for (slot, field) in class.get_fields_ref().iter().enumerate() {
+			if public_only && field.access_flags & (FieldAccessFlags::Public as u16) == 0 {
+				continue;
+			}
+			let name = thread.intern_string(&field.get_name(cp).unwrap_or_default())?;
+			let field_type = type_name_of_descriptor(&field.get_descriptor(cp).unwrap_or_default());
+			let field_type = thread.class_mirror(&field_type)?;
+			let reflected = thread.create_object_by_name("java/lang/reflect/Field")?;
+			set_fields(
+				&reflected,
+				vec![
+					("clazz", arguments[0].clone()),
+					("name", name),
+					("type", field_type),
+					("modifiers", JvmValue::int(field.access_flags as i32)),
+					("slot", JvmValue::int(slot as i32)),
+				],
+			);
+			fields.push(reflected);
+		}
+	}
+	thread.create_array(
+		JvmType::Reference(JvmReferenceType::Class(
+			"java/lang/reflect/Field".to_string(),
+		)),
+		fields,
+	)
+}
+
+/// The constructors (if `constructors`) or the methods that the class
+/// declares (only the public ones, if the argument is true), as
+/// java.lang.reflect.Constructors or Methods. The slot of each is its
+/// index in the class file.
+fn declared_executables(
+	thread: &mut JvmThread,
+	arguments: &[JvmValue],
+	constructors: bool,
+) -> Option<JvmValue> {
+	let public_only = int_argument(arguments, 1) != 0;
+	let reflected_class_name = if constructors {
+		"java/lang/reflect/Constructor"
+	} else {
+		"java/lang/reflect/Method"
+	};
+	let mut executables = Vec::new();
+	if let Some(class) = mirrored_class(thread, &arguments[0]) {
+		let cp = class.get_constant_pool_ref();
+		let methods = class.get_methods_ref();
+		for slot in 0..methods.methods_count() as usize {
+			let method = methods.get(slot);
+			let name = method.get_name(cp).unwrap_or_default();
+			if (name == "<init>") != constructors || name == "<clinit>" {
+				continue;
+			}
+			if public_only && method.access_flags & (MethodAccessFlags::Public as u16) == 0 {
+				continue;
+			}
+			let descriptor = method.get_descriptor(cp).unwrap_or_default();
+			let parameter_types = parameter_descriptors(&descriptor)
+				.iter()
+				.map(|parameter| type_name_of_descriptor(parameter))
+				.collect();
+			let parameter_types = class_array(thread, parameter_types)?;
+			let exception_types = class_array(thread, Vec::new())?;
+			let reflected = thread.create_object_by_name(reflected_class_name)?;
+			set_fields(
+				&reflected,
+				vec![
+					("clazz", arguments[0].clone()),
+					("parameterTypes", parameter_types),
+					("exceptionTypes", exception_types),
+					("modifiers", JvmValue::int(method.access_flags as i32)),
+					("slot", JvmValue::int(slot as i32)),
+				],
+			);
+			if !constructors {
+				let return_type = match descriptor.rfind(')') {
+					Some(index) => type_name_of_descriptor(&descriptor[index + 1..]),
+					None => "void".to_string(),
+				};
+				let return_type = thread.class_mirror(&return_type)?;
+				let name = thread.intern_string(&name)?;
+				set_fields(
+					&reflected,
+					vec![("name", name), ("returnType", return_type)],
+				);
+			}
+			executables.push(reflected);
+		}
+	}
+	thread.create_array(
+		JvmType::Reference(JvmReferenceType::Class(reflected_class_name.to_string())),
+		executables,
+	)
+}
+
+/// Whether `frame` executes the implementation of reflection
+/// (Method.invoke and its accessors), which getCallerClass skips.
+fn is_reflection_frame(frame: &Frame) -> bool {
+	let class_name = frame
+		.class()
+		.and_then(|class| class.get_class_name())
+		.unwrap_or_default();
+	if class_name.starts_with("sun/reflect/") && class_name.ends_with("MethodAccessorImpl") {
+		return true;
+	}
+	match (frame.class(), frame.method()) {
+		(Some(class), Some(method)) => {
+			class_name == "java/lang/reflect/Method"
+				&& method.get_name(class.get_constant_pool_ref()) == Some("invoke".to_string())
+		}
+		_ => false,
+	}
+}
+
+/// The class whose method (transitively) called the one that called
+/// getCallerClass. The frame on top of the stack is that of the method
+/// that called getCallerClass -- natives have no frames.
+fn reflection_get_caller_class(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	_: &[JvmValue],
+) -> Option<JvmValue> {
+	let caller = thread
+		.frames()
+		.iter()
+		.rev()
+		.skip(1)
+		.find(|frame| !is_reflection_frame(frame))
+		.and_then(|frame| frame.class());
+	caller_class(thread, caller)
+}
+
+/// The class `depth` frames down the stack: 0 is Reflection itself and
+/// 1 is the caller of getCallerClass. Reflection's frames do not count.
+fn reflection_get_caller_class_at(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let depth = int_argument(arguments, 0);
+	if depth <= 0 {
+		return thread.class_mirror("sun/reflect/Reflection");
+	}
+	let caller = thread
+		.frames()
+		.iter()
+		.rev()
+		.enumerate()
+		.filter(|(index, frame)| *index == 0 || !is_reflection_frame(frame))
+		.nth(depth as usize - 1)
+		.and_then(|(_, frame)| frame.class());
+	caller_class(thread, caller)
+}
+
+fn caller_class(thread: &mut JvmThread, caller: Option<Rc<Class>>) -> Option<JvmValue> {
+	match caller.and_then(|caller| caller.get_class_name()) {
+		Some(class_name) => thread.class_mirror(&class_name),
+		None => null(),
+	}
+}
+
+fn reflection_get_class_access_flags(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match mirrored_class(thread, &arguments[1]) {
+		Some(class) => Some(JvmValue::int(class.access_flags as i32)),
+		None => Some(JvmValue::int(type_modifiers(thread, &arguments[1]))),
+	}
+}
+
+/// The class and the method that a java.lang.reflect.Method (or
+/// Constructor) reflects.
+fn reflected_method(
+	thread: &mut JvmThread,
+	executable: &JvmValue,
+) -> Option<(Rc<Class>, Rc<Method>)> {
+	let class = mirrored_class(thread, &get_field(executable, "clazz")?)?;
+	let slot = int_of(&get_field(executable, "slot")?) as usize;
+	if slot >= class.get_methods_ref().methods_count() as usize {
+		return None;
+	}
+	let method = class.get_methods_ref().get(slot);
+	Some((class, method))
+}
+
+/// The arguments (an Object[]) of a reflective call of a method with the
+/// type `descriptor`. A primitive argument is unboxed; an argument that
+/// does not fit throws IllegalArgumentException.
+fn unbox_arguments(
+	thread: &mut JvmThread,
+	descriptor: &str,
+	arguments: &JvmValue,
+) -> Option<Vec<JvmValue>> {
+	let parameters = parameter_descriptors(descriptor);
+	let values: Vec<JvmValue> = match arguments {
+		JvmValue::Reference(_, JvmReferenceTargetType::Array(array), _) => {
+			let array = array.lock().ok()?;
+			array
+				.get_range(0, array.dimension())
+				.into_iter()
+				.map(|value| value.unwrap_or_else(create_null_value))
+				.collect()
+		}
+		_ => Vec::new(),
+	};
+	if values.len() != parameters.len() {
+		thread.throw_new(
+			"java/lang/IllegalArgumentException",
+			Some("wrong number of arguments"),
+		);
+		return None;
+	}
+	let mut unboxed = Vec::with_capacity(values.len());
+	for (parameter, value) in parameters.iter().zip(values) {
+		if parameter.len() != 1 {
+			unboxed.push(value);
+			continue;
+		}
+		match get_field(&value, "value") {
+			Some(primitive) if !value.is_null() => unboxed.push(primitive),
+			_ => {
+				thread.throw_new(
+					"java/lang/IllegalArgumentException",
+					Some("argument type mismatch"),
+				);
+				return None;
+			}
+		}
+	}
+	Some(unboxed)
+}
+
+/// Box `value`, the result of a method whose return type has the
+/// descriptor `return_type`.
+fn box_result(thread: &mut JvmThread, return_type: &str, value: JvmValue) -> Option<JvmValue> {
+	let wrapper = match return_type {
+		"Z" => "java/lang/Boolean",
+		"B" => "java/lang/Byte",
+		"C" => "java/lang/Character",
+		"S" => "java/lang/Short",
+		"I" => "java/lang/Integer",
+		"J" => "java/lang/Long",
+		"F" => "java/lang/Float",
+		"D" => "java/lang/Double",
+		"V" => return null(),
+		_ => return Some(value),
+	};
+	thread.new_object(wrapper, &format!("({})V", return_type), vec![value])
+}
+
+/// Wrap the exception that a reflectively invoked method threw in an
+/// InvocationTargetException.
+fn throw_invocation_target_exception(thread: &mut JvmThread) {
+	if let Some(exception) = thread.catch() {
+		if let Some(wrapped) = thread.new_object(
+			"java/lang/reflect/InvocationTargetException",
+			"(Ljava/lang/Throwable;)V",
+			vec![exception],
+		) {
+			thread.throw(wrapped);
+		}
+	}
+}
+
+fn native_constructor_accessor_new_instance0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let (class, constructor) = reflected_method(thread, &arguments[0])?;
+	if class.access_flags & (ClassAccessFlags::Abstract as u16) != 0 {
+		thread.throw_new(
+			"java/lang/InstantiationException",
+			Some(&class.get_class_name().unwrap_or_default().replace('/', ".")),
+		);
+		return None;
+	}
+	let descriptor = constructor
+		.get_descriptor(class.get_constant_pool_ref())
+		.unwrap_or_default();
+	let constructor_arguments = unbox_arguments(thread, &descriptor, &arguments[1])?;
+	thread.maybe_initialize_class(&class);
+	if thread.exception_pending() {
+		return None;
+	}
+	let object = thread.create_object(Rc::clone(&class))?;
+	let mut all_arguments = vec![object.clone()];
+	all_arguments.extend(constructor_arguments);
+	if thread.invoke(&class, &constructor, all_arguments).is_none() {
+		throw_invocation_target_exception(thread);
+		return None;
+	}
+	Some(object)
+}
+
+fn native_method_accessor_invoke0(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let (class, method) = reflected_method(thread, &arguments[0])?;
+	let cp = class.get_constant_pool_ref();
+	let method_name = method.get_name(cp).unwrap_or_default();
+	let descriptor = method.get_descriptor(cp).unwrap_or_default();
+	let method_arguments = unbox_arguments(thread, &descriptor, &arguments[2])?;
+	let result = if method.access_flags & (MethodAccessFlags::Static as u16) != 0 {
+		thread.maybe_initialize_class(&class);
+		if thread.exception_pending() {
+			return None;
+		}
+		thread.invoke(&class, &method, method_arguments)
+	} else {
+		let receiver = &arguments[1];
+		if receiver.is_null() {
+			thread.throw_new("java/lang/NullPointerException", None);
+			return None;
+		}
+		let receiver_type = receiver.type_name().unwrap_or_default();
+		let class_name = class.get_class_name().unwrap_or_default();
+		if !thread.is_assignable(&receiver_type, &class_name) {
+			thread.throw_new(
+				"java/lang/IllegalArgumentException",
+				Some("object is not an instance of declaring class"),
+			);
+			return None;
+		}
+		thread.call_virtual(receiver, &method_name, &descriptor, method_arguments)
+	};
+	match result {
+		Some(result) => {
+			let return_type = match descriptor.rfind(')') {
+				Some(index) => descriptor[index + 1..].to_string(),
+				None => "V".to_string(),
+			};
+			box_result(thread, &return_type, result)
+		}
+		None => {
+			throw_invocation_target_exception(thread);
+			None
+		}
+	}
+}
+
+fn array_new_array(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	if arguments[0].is_null() {
+		thread.throw_new("java/lang/NullPointerException", None);
+		return None;
+	}
+	let component = mirrored_type(thread, &arguments[0]);
+	if component == "void" {
+		thread.throw_new("java/lang/IllegalArgumentException", None);
+		return None;
+	}
+	let length = int_argument(arguments, 1);
+	if length < 0 {
+		thread.throw_new(
+			"java/lang/NegativeArraySizeException",
+			Some(&length.to_string()),
+		);
+		return None;
+	}
+	let component = JvmType::from_field_descriptor(&descriptor_of_type_name(&component))?;
+	thread.create_default_array(component, length as usize)
+}
+
+fn array_get_length(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	match &arguments[0] {
+		JvmValue::Reference(_, JvmReferenceTargetType::Array(array), _) => {
+			Some(JvmValue::int(array.lock().ok()?.dimension() as i32))
+		}
+		JvmValue::Reference(_, JvmReferenceTargetType::Null, _) => {
+			thread.throw_new("java/lang/NullPointerException", None);
+			None
+		}
+		_ => {
+			thread.throw_new(
+				"java/lang/IllegalArgumentException",
+				Some("Argument is not an array"),
+			);
+			None
+		}
+	}
+}
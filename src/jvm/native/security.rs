@@ -0,0 +1,112 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::jvmthread::JvmThread;
+use jvm::methodarea::MethodArea;
+use jvm::native::{null, NativeRegistry};
+use jvm::typevalues::JvmValue;
+use std::sync::{Arc, Mutex};
+
+/// Register the natives of the java.security package.
+pub fn register(registry: &mut NativeRegistry) {
+	let access_controller = "java/security/AccessController";
+	registry.register(
+		access_controller,
+		"doPrivileged",
+		"(Ljava/security/PrivilegedAction;)Ljava/lang/Object;",
+		do_privileged,
+	);
+	registry.register(
+		access_controller,
+		"doPrivileged",
+		"(Ljava/security/PrivilegedAction;Ljava/security/AccessControlContext;)Ljava/lang/Object;",
+		do_privileged,
+	);
+	registry.register(
+		access_controller,
+		"doPrivileged",
+		"(Ljava/security/PrivilegedExceptionAction;)Ljava/lang/Object;",
+		do_privileged_exception_action,
+	);
+	registry.register(
+		access_controller,
+		"doPrivileged",
+		"(Ljava/security/PrivilegedExceptionAction;Ljava/security/AccessControlContext;)Ljava/lang/Object;",
+		do_privileged_exception_action,
+	);
+	/*
+	 * With no protection domains on the stack, there is no context to
+	 * restrict anything: AccessController.getContext makes a context
+	 * that grants everything.
+	 */
+	registry.register(
+		access_controller,
+		"getStackAccessControlContext",
+		"()Ljava/security/AccessControlContext;",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| null(),
+	);
+	registry.register(
+		access_controller,
+		"getInheritedAccessControlContext",
+		"()Ljava/security/AccessControlContext;",
+		|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| null(),
+	);
+}
+
+/// Run the action (a PrivilegedAction). Every class is loaded by the
+/// bootstrap class loader, so every frame already has all privileges:
+/// the action simply runs.
+fn do_privileged(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	thread.call_virtual(&arguments[0], "run", "()Ljava/lang/Object;", Vec::new())
+}
+
+/// Run the action (a PrivilegedExceptionAction). A checked exception
+/// that it throws is wrapped in a PrivilegedActionException.
+fn do_privileged_exception_action(
+	thread: &mut JvmThread,
+	_: &Arc<Mutex<MethodArea>>,
+	arguments: &[JvmValue],
+) -> Option<JvmValue> {
+	let result = thread.call_virtual(&arguments[0], "run", "()Ljava/lang/Object;", Vec::new());
+	if result.is_some() {
+		return result;
+	}
+	let exception = thread.catch()?;
+	let exception_type = exception.type_name().unwrap_or_default();
+	if thread.is_assignable(&exception_type, "java/lang/Exception")
+		&& !thread.is_assignable(&exception_type, "java/lang/RuntimeException")
+	{
+		if let Some(wrapped) = thread.new_object(
+			"java/security/PrivilegedActionException",
+			"(Ljava/lang/Exception;)V",
+			vec![exception],
+		) {
+			thread.throw(wrapped);
+		}
+	} else {
+		thread.throw(exception);
+	}
+	None
+}
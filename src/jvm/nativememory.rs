@@ -0,0 +1,114 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use std::collections::BTreeMap;
+
+/// The address of the first block of native memory. Zero is never
+/// an address: it is the null pointer.
+const FIRST_ADDRESS: u64 = 0x1000;
+
+/// Blocks are aligned to (at least) this many bytes.
+const ALIGNMENT: u64 = 16;
+
+/// NativeMemory is the memory outside of the heap that a program
+/// allocates (with sun.misc.Unsafe.allocateMemory) and accesses by
+/// address. Addresses are not the host's: they are offsets into
+/// blocks that the VM keeps, so a bad one is an error, not a crash.
+pub struct NativeMemory {
+	/// The allocated blocks, by their addresses.
+	blocks: BTreeMap<u64, Vec<u8>>,
+	next_address: u64,
+}
+
+impl NativeMemory {
+	pub fn new() -> Self {
+		NativeMemory {
+			blocks: BTreeMap::new(),
+			next_address: FIRST_ADDRESS,
+		}
+	}
+
+	/// Allocate a block of `size` (uninitialized, here zeroed) bytes.
+	///
+	/// # Return value:
+	/// The address of the block. 0 for a block of no bytes.
+	pub fn allocate(&mut self, size: usize) -> u64 {
+		if size == 0 {
+			return 0;
+		}
+		let address = self.next_address;
+		self.next_address += (size as u64 + ALIGNMENT - 1) / ALIGNMENT * ALIGNMENT + ALIGNMENT;
+		self.blocks.insert(address, vec![0; size]);
+		address
+	}
+
+	/// Resize the block at `address` to `size` bytes, keeping its
+	/// contents. Like C's realloc, the block may move.
+	///
+	/// # Return value:
+	/// The block's (new) address. None if `address` is not that of a block.
+	pub fn reallocate(&mut self, address: u64, size: usize) -> Option<u64> {
+		if address == 0 {
+			return Some(self.allocate(size));
+		}
+		let mut contents = self.blocks.remove(&address)?;
+		let new_address = self.allocate(size);
+		contents.resize(size, 0);
+		if let Some(block) = self.blocks.get_mut(&new_address) {
+			*block = contents;
+		}
+		Some(new_address)
+	}
+
+	/// Free the block at `address`. Freeing 0 does nothing.
+	pub fn free(&mut self, address: u64) -> bool {
+		address == 0 || self.blocks.remove(&address).is_some()
+	}
+
+	/// The block that holds the `length` bytes at `address` and the
+	/// offset of `address` in it.
+	fn block(&mut self, address: u64, length: usize) -> Option<(&mut Vec<u8>, usize)> {
+		let (start, block) = self.blocks.range_mut(..=address).next_back()?;
+		let offset = (address - start) as usize;
+		if offset + length > block.len() {
+			return None;
+		}
+		Some((block, offset))
+	}
+
+	/// The `length` bytes at `address`. None if they are not all in
+	/// one allocated block.
+	pub fn read(&mut self, address: u64, length: usize) -> Option<Vec<u8>> {
+		let (block, offset) = self.block(address, length)?;
+		Some(block[offset..offset + length].to_vec())
+	}
+
+	/// Write `bytes` at `address`. See read.
+	pub fn write(&mut self, address: u64, bytes: &[u8]) -> bool {
+		match self.block(address, bytes.len()) {
+			Some((block, offset)) => {
+				block[offset..offset + bytes.len()].copy_from_slice(bytes);
+				true
+			}
+			None => false,
+		}
+	}
+}
@@ -21,64 +21,126 @@
  */
 use super::array::JvmArray;
 use jvm::class::Class;
-use jvm::constant::Constant;
 use jvm::constantpool::ConstantPool;
 use jvm::debug::{Debug, DebugLevel};
 use jvm::error::FatalError;
 use jvm::error::FatalErrorType;
 use jvm::jvmthread::JvmThread;
 use jvm::methodarea::MethodArea;
-use jvm::typevalues::create_null_value;
 use jvm::typevalues::JvmPrimitiveType;
 use jvm::typevalues::JvmReferenceTargetType;
 use jvm::typevalues::JvmReferenceType;
 use jvm::typevalues::JvmType;
 use jvm::typevalues::JvmValue;
-use std::collections::HashMap;
 use std::fmt;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
 pub struct JvmObject {
-	spr: Option<Rc<JvmObject>>,
 	class: Rc<Class>,
-	fields: HashMap<String, Rc<JvmValue>>,
+	/// The values of the object's fields, laid out
+	/// according to its class' FieldLayout.
+	slots: Vec<JvmValue>,
 	debug_level: DebugLevel,
 }
 
 /*
  * This assumes that the String class has already been loaded and initialized.
+ * The characters of `value` are stored (as UTF-16 code units) in the String's
+ * `value` field.
  */
 pub fn create_static_string_object(
 	value: String,
 	thread: &mut JvmThread,
 	methodarea_mutex: Arc<Mutex<MethodArea>>,
 ) -> Option<JvmObject> {
-	if let Ok(methodarea) = methodarea_mutex.lock() {
-		let string_class_name = format!("java/lang/String");
-		if let Some(string_class) = methodarea.get_class_rc(&string_class_name) {
-			let mut string_object = JvmObject::new(Rc::clone(&string_class), thread.debug_level());
-			string_object.instantiate(thread, Arc::clone(&methodarea_mutex));
-			return Some(string_object);
-		} else {
-			FatalError::new(FatalErrorType::ClassNotFound(string_class_name)).call();
-		}
+	let string_class_name = format!("java/lang/String");
+	let string_class = if let Ok(methodarea) = methodarea_mutex.lock() {
+		methodarea.get_class_rc(&string_class_name)
 	} else {
 		FatalError::new(FatalErrorType::CouldNotLock(
 			format!("Method Area"),
 			format!("create_static_string_object"),
 		))
 		.call();
+		return None;
+	};
+
+	if let Some(string_class) = string_class {
+		let mut string_object = JvmObject::new(Rc::clone(&string_class), thread.debug_level());
+		string_object.instantiate();
+
+		let characters: Vec<u16> = value.encode_utf16().collect();
+		let mut array = JvmArray::new(characters.len());
+		for (index, character) in characters.iter().enumerate() {
+			array.set_at(
+				index,
+				JvmValue::Primitive(JvmPrimitiveType::Char, *character as u64, 0, 0),
+			);
+		}
+		if !string_object.set_field_by_name(
+			"value",
+			JvmValue::Reference(
+				JvmReferenceType::Array(
+					Rc::new(JvmType::Primitive(JvmPrimitiveType::Char)),
+					characters.len() as u32,
+				),
+				JvmReferenceTargetType::Array(thread.allocate_array(array)?),
+				0,
+			),
+		) {
+			FatalError::new(FatalErrorType::FieldNotFound(
+				format!("value"),
+				string_class_name,
+			))
+			.call();
+			return None;
+		}
+		return Some(string_object);
+	} else {
+		FatalError::new(FatalErrorType::ClassNotFound(string_class_name)).call();
 	}
 	None
 }
 
+/*
+ * The characters of `string`, a reference to a String object. None if
+ * `string` is null or not a String.
+ */
+pub fn get_string_value(string: &JvmValue) -> Option<String> {
+	Some(String::from_utf16_lossy(&get_string_characters(string)?))
+}
+
+/*
+ * The characters of `string` as UTF-16 code units. See get_string_value.
+ */
+pub fn get_string_characters(string: &JvmValue) -> Option<Vec<u16>> {
+	let characters = match string {
+		JvmValue::Reference(_, JvmReferenceTargetType::Object(string), _) => {
+			string.lock().ok()?.get_field_by_name("value")?
+		}
+		_ => return None,
+	};
+	let characters: Vec<u16> = match characters {
+		JvmValue::Reference(_, JvmReferenceTargetType::Array(characters), _) => characters
+			.lock()
+			.ok()?
+			.values()
+			.map(|character| match character {
+				JvmValue::Primitive(_, character, _, _) => *character as u16,
+				_ => 0,
+			})
+			.collect(),
+		_ => return None,
+	};
+	Some(characters)
+}
+
 impl JvmObject {
 	pub fn new(class: Rc<Class>, debug_level: DebugLevel) -> Self {
 		JvmObject {
-			spr: None,
 			class: class,
-			fields: HashMap::<String, Rc<JvmValue>>::new(),
+			slots: Vec::new(),
 			debug_level,
 		}
 	}
@@ -87,167 +149,59 @@ impl JvmObject {
 		Rc::clone(&self.class)
 	}
 
-	pub fn set_field(&mut self, field_name: &String, value: Rc<JvmValue>) {
-		self.fields.insert(field_name.clone(), value);
-	}
-
-	pub fn get_field(&mut self, field_name: &String) -> Option<Rc<JvmValue>> {
-		if let Some(field_value) = self.fields.get(field_name) {
-			Some(Rc::clone(field_value))
-		} else {
-			None
-		}
-	}
-
-	pub fn hierarchy(&self) -> String {
-		let mut result = self.class.get_class_name().unwrap();
-		if let Some(spr) = &self.spr {
-			result = format!("{}, {}", result, spr.hierarchy());
-		}
-		result
-	}
-
-	pub fn is_type_of(&self, r#type: &String) -> bool {
-		if self.class.get_class_name().unwrap() == *r#type {
+	/// Set the field at `offset` (in the object's layout) to `value`.
+	/// Returns false if the object has no such field.
+	pub fn set_field(&mut self, offset: usize, value: JvmValue) -> bool {
+		if let Some(slot) = self.slots.get_mut(offset) {
+			*slot = value;
 			true
-		} else if let Some(spr) = &self.spr {
-			spr.is_type_of(r#type)
 		} else {
 			false
 		}
 	}
 
-	pub fn instantiate(
-		&mut self,
-		initializing_thread: &mut JvmThread,
-		methodarea: Arc<Mutex<MethodArea>>,
-	) -> bool {
-		let fields = self.class.get_fields_ref();
-		let constantpool = self.class.get_constant_pool_ref();
-
-		Debug(format!("instantiate."), &self.debug_level, DebugLevel::Info);
-
-		for i in 0..fields.fields_count() {
-			let field = fields.get(i as usize);
-			/*
-			 * Get the field type.
-			 */
-			let r#type: JvmType =
-				match constantpool.get_constant_ref(field.descriptor_index as usize) {
-					Constant::Utf8(_, _, _, d) => {
-						let descriptor = d.as_bytes();
-						JvmType::from(descriptor)
-					}
-					_ => {
-						FatalError::new(FatalErrorType::InvalidConstantReference(
-							self.class.get_class_name().unwrap(),
-							"Utf8".to_string(),
-							field.descriptor_index,
-						))
-						.call();
-						JvmType::Primitive(JvmPrimitiveType::Void)
-					}
-				};
-
-			/*
-			 * Get the field access modifiers.
-			 */
-			let access_flags = field.access_flags;
-
-			/*
-			 * Get the default field value.
-			 */
-			let value = match r#type {
-				JvmType::Primitive(primitive) => JvmValue::Primitive(primitive, 0, 0, access_flags),
-				JvmType::Reference(reference) => match reference {
-					JvmReferenceType::Array(r#type, access) => JvmValue::Reference(
-						JvmReferenceType::Array(Rc::clone(&r#type), access),
-						JvmReferenceTargetType::Array(Arc::new(Mutex::new(JvmArray::new(0)))),
-						0,
-					),
-					_ => {
-						FatalError::new(FatalErrorType::NotImplemented(format!(
-							"Getting a reference type field other than an array."
-						)))
-						.call();
-						create_null_value()
-					}
-				},
-			};
-
-			/*
-			 * Get the field name.
-			 */
-			let name = match constantpool.get_constant_ref(field.name_index as usize) {
-				Constant::Utf8(_, _, _, name) => name.clone(),
-				_ => {
-					FatalError::new(FatalErrorType::InvalidConstantReference(
-						self.class.get_class_name().unwrap(),
-						"Utf8".to_string(),
-						field.name_index,
-					))
-					.call();
-					"".to_string()
-				}
-			};
+	/// Get the value of the field at `offset` (in the object's layout).
+	pub fn get_field(&self, offset: usize) -> Option<JvmValue> {
+		self.slots.get(offset).cloned()
+	}
 
-			/*
-			 * Now, put it in our field table.
-			 */
-			self.fields.insert(name, Rc::new(value));
+	/// Set the most-derived field named `field_name`. Only for the
+	/// VM's own use on well-known classes; bytecode accesses fields
+	/// by their resolved offset.
+	pub fn set_field_by_name(&mut self, field_name: &str, value: JvmValue) -> bool {
+		match self.class.get_layout().get_offset_by_name(field_name) {
+			Some(offset) => self.set_field(offset, value),
+			None => false,
 		}
+	}
 
-		/*
-		 * TODO: Handle superclass instantiation!
-		 */
-		if let Some(superclass_name) = self.class.resolve_superclass() {
-			// We have a superclass and we know it's name.
-			Debug(
-				format!("Make a new superclass of {}.", superclass_name),
-				&self.debug_level,
-				DebugLevel::Info,
-			);
-
-			/*
-			 * TODO: Let this go all the way to object!
-			 */
+	/// Get the most-derived field named `field_name`. See set_field_by_name.
+	pub fn get_field_by_name(&self, field_name: &str) -> Option<JvmValue> {
+		self.get_field(self.class.get_layout().get_offset_by_name(field_name)?)
+	}
 
-			if superclass_name == format!("java/lang/Object") {
-				Debug(
-					format!("Not making the base class java/lang/Object"),
-					&self.debug_level,
-					DebugLevel::Info,
-				);
-				return true;
-			}
+	/// The values of all of the object's fields.
+	pub fn values(&self) -> impl Iterator<Item = &JvmValue> {
+		self.slots.iter()
+	}
 
-			let mut instantiated_class: Option<Rc<Class>> = None;
-			if let Ok(mut methodarea) = methodarea.lock() {
-				(*methodarea).maybe_load_class(&superclass_name);
-				instantiated_class = (*methodarea).get_class_rc(&superclass_name);
-			} else {
-				FatalError::new(FatalErrorType::CouldNotLock(
-					"Method Area.".to_string(),
-					"instantiate".to_string(),
-				))
-				.call();
-			}
-			if let Some(instantiated_class) = instantiated_class {
-				initializing_thread.maybe_initialize_class(&instantiated_class);
+	/// Remove (and return) the values of all of the object's fields.
+	/// The heap does this to garbage, to break reference cycles.
+	pub fn clear(&mut self) -> Vec<JvmValue> {
+		std::mem::replace(&mut self.slots, Vec::new())
+	}
 
-				let mut object = JvmObject::new(instantiated_class, self.debug_level.clone());
+	/// The (approximate) number of bytes that the object occupies.
+	pub fn size(&self) -> usize {
+		std::mem::size_of::<JvmObject>() + self.slots.len() * std::mem::size_of::<JvmValue>()
+	}
 
-				object.instantiate(initializing_thread, Arc::clone(&methodarea));
-				self.spr = Some(Rc::new(object));
-				Debug(
-					format!("Made a new superclass of {}.", superclass_name),
-					&self.debug_level,
-					DebugLevel::Info,
-				);
-			} else {
-				FatalError::new(FatalErrorType::ClassNotLoaded(superclass_name.to_string())).call();
-			}
-		}
+	/// Give each of the object's fields (including those it inherits)
+	/// its default value: typed zero for primitives and null for
+	/// references (to objects and to arrays).
+	pub fn instantiate(&mut self) -> bool {
+		Debug(format!("instantiate."), &self.debug_level, DebugLevel::Info);
+		self.slots = self.class.get_layout().default_values();
 		true
 	}
 }
@@ -0,0 +1,426 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+/// The VM's own (minimal) Java class library: the names and class
+/// files of the classes of runtime/src, compiled to runtime/classes
+/// (see `make runtime`) and embedded in the VM. Programs that only use
+/// the basics of java.lang, java.io and java.util run with them and no
+/// JDK. A class path with a class library of its own replaces them
+/// (see ClassPath::new). Add a class here when runtime/src gets one.
+pub static CLASSES: &[(&str, &[u8])] = &[
+	(
+		"java/io/Closeable",
+		include_bytes!("../../runtime/classes/java/io/Closeable.class"),
+	),
+	(
+		"java/io/EOFException",
+		include_bytes!("../../runtime/classes/java/io/EOFException.class"),
+	),
+	(
+		"java/io/FileDescriptor",
+		include_bytes!("../../runtime/classes/java/io/FileDescriptor.class"),
+	),
+	(
+		"java/io/FileInputStream",
+		include_bytes!("../../runtime/classes/java/io/FileInputStream.class"),
+	),
+	(
+		"java/io/FileNotFoundException",
+		include_bytes!("../../runtime/classes/java/io/FileNotFoundException.class"),
+	),
+	(
+		"java/io/FileOutputStream",
+		include_bytes!("../../runtime/classes/java/io/FileOutputStream.class"),
+	),
+	(
+		"java/io/Flushable",
+		include_bytes!("../../runtime/classes/java/io/Flushable.class"),
+	),
+	(
+		"java/io/IOException",
+		include_bytes!("../../runtime/classes/java/io/IOException.class"),
+	),
+	(
+		"java/io/InputStream",
+		include_bytes!("../../runtime/classes/java/io/InputStream.class"),
+	),
+	(
+		"java/io/OutputStream",
+		include_bytes!("../../runtime/classes/java/io/OutputStream.class"),
+	),
+	(
+		"java/io/PrintStream",
+		include_bytes!("../../runtime/classes/java/io/PrintStream.class"),
+	),
+	(
+		"java/io/RandomAccessFile",
+		include_bytes!("../../runtime/classes/java/io/RandomAccessFile.class"),
+	),
+	(
+		"java/io/Serializable",
+		include_bytes!("../../runtime/classes/java/io/Serializable.class"),
+	),
+	(
+		"java/io/SyncFailedException",
+		include_bytes!("../../runtime/classes/java/io/SyncFailedException.class"),
+	),
+	(
+		"java/lang/AbstractMethodError",
+		include_bytes!("../../runtime/classes/java/lang/AbstractMethodError.class"),
+	),
+	(
+		"java/lang/ArithmeticException",
+		include_bytes!("../../runtime/classes/java/lang/ArithmeticException.class"),
+	),
+	(
+		"java/lang/ArrayIndexOutOfBoundsException",
+		include_bytes!("../../runtime/classes/java/lang/ArrayIndexOutOfBoundsException.class"),
+	),
+	(
+		"java/lang/ArrayStoreException",
+		include_bytes!("../../runtime/classes/java/lang/ArrayStoreException.class"),
+	),
+	(
+		"java/lang/AssertionError",
+		include_bytes!("../../runtime/classes/java/lang/AssertionError.class"),
+	),
+	(
+		"java/lang/AutoCloseable",
+		include_bytes!("../../runtime/classes/java/lang/AutoCloseable.class"),
+	),
+	(
+		"java/lang/Boolean",
+		include_bytes!("../../runtime/classes/java/lang/Boolean.class"),
+	),
+	(
+		"java/lang/Byte",
+		include_bytes!("../../runtime/classes/java/lang/Byte.class"),
+	),
+	(
+		"java/lang/CharSequence",
+		include_bytes!("../../runtime/classes/java/lang/CharSequence.class"),
+	),
+	(
+		"java/lang/Character",
+		include_bytes!("../../runtime/classes/java/lang/Character.class"),
+	),
+	(
+		"java/lang/Class",
+		include_bytes!("../../runtime/classes/java/lang/Class.class"),
+	),
+	(
+		"java/lang/ClassCastException",
+		include_bytes!("../../runtime/classes/java/lang/ClassCastException.class"),
+	),
+	(
+		"java/lang/ClassFormatError",
+		include_bytes!("../../runtime/classes/java/lang/ClassFormatError.class"),
+	),
+	(
+		"java/lang/ClassLoader",
+		include_bytes!("../../runtime/classes/java/lang/ClassLoader.class"),
+	),
+	(
+		"java/lang/ClassNotFoundException",
+		include_bytes!("../../runtime/classes/java/lang/ClassNotFoundException.class"),
+	),
+	(
+		"java/lang/CloneNotSupportedException",
+		include_bytes!("../../runtime/classes/java/lang/CloneNotSupportedException.class"),
+	),
+	(
+		"java/lang/Cloneable",
+		include_bytes!("../../runtime/classes/java/lang/Cloneable.class"),
+	),
+	(
+		"java/lang/Comparable",
+		include_bytes!("../../runtime/classes/java/lang/Comparable.class"),
+	),
+	(
+		"java/lang/Double",
+		include_bytes!("../../runtime/classes/java/lang/Double.class"),
+	),
+	(
+		"java/lang/Error",
+		include_bytes!("../../runtime/classes/java/lang/Error.class"),
+	),
+	(
+		"java/lang/Exception",
+		include_bytes!("../../runtime/classes/java/lang/Exception.class"),
+	),
+	(
+		"java/lang/ExceptionInInitializerError",
+		include_bytes!("../../runtime/classes/java/lang/ExceptionInInitializerError.class"),
+	),
+	(
+		"java/lang/Float",
+		include_bytes!("../../runtime/classes/java/lang/Float.class"),
+	),
+	(
+		"java/lang/IllegalArgumentException",
+		include_bytes!("../../runtime/classes/java/lang/IllegalArgumentException.class"),
+	),
+	(
+		"java/lang/IllegalMonitorStateException",
+		include_bytes!("../../runtime/classes/java/lang/IllegalMonitorStateException.class"),
+	),
+	(
+		"java/lang/IllegalStateException",
+		include_bytes!("../../runtime/classes/java/lang/IllegalStateException.class"),
+	),
+	(
+		"java/lang/IncompatibleClassChangeError",
+		include_bytes!("../../runtime/classes/java/lang/IncompatibleClassChangeError.class"),
+	),
+	(
+		"java/lang/IndexOutOfBoundsException",
+		include_bytes!("../../runtime/classes/java/lang/IndexOutOfBoundsException.class"),
+	),
+	(
+		"java/lang/InstantiationException",
+		include_bytes!("../../runtime/classes/java/lang/InstantiationException.class"),
+	),
+	(
+		"java/lang/Integer",
+		include_bytes!("../../runtime/classes/java/lang/Integer.class"),
+	),
+	(
+		"java/lang/InternalError",
+		include_bytes!("../../runtime/classes/java/lang/InternalError.class"),
+	),
+	(
+		"java/lang/InterruptedException",
+		include_bytes!("../../runtime/classes/java/lang/InterruptedException.class"),
+	),
+	(
+		"java/lang/Iterable",
+		include_bytes!("../../runtime/classes/java/lang/Iterable.class"),
+	),
+	(
+		"java/lang/LinkageError",
+		include_bytes!("../../runtime/classes/java/lang/LinkageError.class"),
+	),
+	(
+		"java/lang/Long",
+		include_bytes!("../../runtime/classes/java/lang/Long.class"),
+	),
+	(
+		"java/lang/Math",
+		include_bytes!("../../runtime/classes/java/lang/Math.class"),
+	),
+	(
+		"java/lang/NegativeArraySizeException",
+		include_bytes!("../../runtime/classes/java/lang/NegativeArraySizeException.class"),
+	),
+	(
+		"java/lang/NoClassDefFoundError",
+		include_bytes!("../../runtime/classes/java/lang/NoClassDefFoundError.class"),
+	),
+	(
+		"java/lang/NoSuchFieldError",
+		include_bytes!("../../runtime/classes/java/lang/NoSuchFieldError.class"),
+	),
+	(
+		"java/lang/NoSuchMethodError",
+		include_bytes!("../../runtime/classes/java/lang/NoSuchMethodError.class"),
+	),
+	(
+		"java/lang/NullPointerException",
+		include_bytes!("../../runtime/classes/java/lang/NullPointerException.class"),
+	),
+	(
+		"java/lang/Number",
+		include_bytes!("../../runtime/classes/java/lang/Number.class"),
+	),
+	(
+		"java/lang/NumberFormatException",
+		include_bytes!("../../runtime/classes/java/lang/NumberFormatException.class"),
+	),
+	(
+		"java/lang/Object",
+		include_bytes!("../../runtime/classes/java/lang/Object.class"),
+	),
+	(
+		"java/lang/OutOfMemoryError",
+		include_bytes!("../../runtime/classes/java/lang/OutOfMemoryError.class"),
+	),
+	(
+		"java/lang/ReflectiveOperationException",
+		include_bytes!("../../runtime/classes/java/lang/ReflectiveOperationException.class"),
+	),
+	(
+		"java/lang/Runnable",
+		include_bytes!("../../runtime/classes/java/lang/Runnable.class"),
+	),
+	(
+		"java/lang/Runtime",
+		include_bytes!("../../runtime/classes/java/lang/Runtime.class"),
+	),
+	(
+		"java/lang/RuntimeException",
+		include_bytes!("../../runtime/classes/java/lang/RuntimeException.class"),
+	),
+	(
+		"java/lang/Short",
+		include_bytes!("../../runtime/classes/java/lang/Short.class"),
+	),
+	(
+		"java/lang/Shutdown",
+		include_bytes!("../../runtime/classes/java/lang/Shutdown.class"),
+	),
+	(
+		"java/lang/StackOverflowError",
+		include_bytes!("../../runtime/classes/java/lang/StackOverflowError.class"),
+	),
+	(
+		"java/lang/StackTraceElement",
+		include_bytes!("../../runtime/classes/java/lang/StackTraceElement.class"),
+	),
+	(
+		"java/lang/String",
+		include_bytes!("../../runtime/classes/java/lang/String.class"),
+	),
+	(
+		"java/lang/StringBuilder",
+		include_bytes!("../../runtime/classes/java/lang/StringBuilder.class"),
+	),
+	(
+		"java/lang/StringIndexOutOfBoundsException",
+		include_bytes!("../../runtime/classes/java/lang/StringIndexOutOfBoundsException.class"),
+	),
+	(
+		"java/lang/System",
+		include_bytes!("../../runtime/classes/java/lang/System.class"),
+	),
+	(
+		"java/lang/Thread",
+		include_bytes!("../../runtime/classes/java/lang/Thread.class"),
+	),
+	(
+		"java/lang/ThreadGroup",
+		include_bytes!("../../runtime/classes/java/lang/ThreadGroup.class"),
+	),
+	(
+		"java/lang/Throwable",
+		include_bytes!("../../runtime/classes/java/lang/Throwable.class"),
+	),
+	(
+		"java/lang/UnsatisfiedLinkError",
+		include_bytes!("../../runtime/classes/java/lang/UnsatisfiedLinkError.class"),
+	),
+	(
+		"java/lang/UnsupportedOperationException",
+		include_bytes!("../../runtime/classes/java/lang/UnsupportedOperationException.class"),
+	),
+	(
+		"java/lang/VirtualMachineError",
+		include_bytes!("../../runtime/classes/java/lang/VirtualMachineError.class"),
+	),
+	(
+		"java/security/ProtectionDomain",
+		include_bytes!("../../runtime/classes/java/security/ProtectionDomain.class"),
+	),
+	(
+		"java/util/AbstractCollection",
+		include_bytes!("../../runtime/classes/java/util/AbstractCollection.class"),
+	),
+	(
+		"java/util/ArrayList$1",
+		include_bytes!("../../runtime/classes/java/util/ArrayList$1.class"),
+	),
+	(
+		"java/util/ArrayList",
+		include_bytes!("../../runtime/classes/java/util/ArrayList.class"),
+	),
+	(
+		"java/util/Arrays",
+		include_bytes!("../../runtime/classes/java/util/Arrays.class"),
+	),
+	(
+		"java/util/Collection",
+		include_bytes!("../../runtime/classes/java/util/Collection.class"),
+	),
+	(
+		"java/util/HashMap$1",
+		include_bytes!("../../runtime/classes/java/util/HashMap$1.class"),
+	),
+	(
+		"java/util/HashMap$EntrySet$1",
+		include_bytes!("../../runtime/classes/java/util/HashMap$EntrySet$1.class"),
+	),
+	(
+		"java/util/HashMap$EntrySet",
+		include_bytes!("../../runtime/classes/java/util/HashMap$EntrySet.class"),
+	),
+	(
+		"java/util/HashMap$HashIterator",
+		include_bytes!("../../runtime/classes/java/util/HashMap$HashIterator.class"),
+	),
+	(
+		"java/util/HashMap$KeySet$1",
+		include_bytes!("../../runtime/classes/java/util/HashMap$KeySet$1.class"),
+	),
+	(
+		"java/util/HashMap$KeySet",
+		include_bytes!("../../runtime/classes/java/util/HashMap$KeySet.class"),
+	),
+	(
+		"java/util/HashMap$Node",
+		include_bytes!("../../runtime/classes/java/util/HashMap$Node.class"),
+	),
+	(
+		"java/util/HashMap",
+		include_bytes!("../../runtime/classes/java/util/HashMap.class"),
+	),
+	(
+		"java/util/Iterator",
+		include_bytes!("../../runtime/classes/java/util/Iterator.class"),
+	),
+	(
+		"java/util/List",
+		include_bytes!("../../runtime/classes/java/util/List.class"),
+	),
+	(
+		"java/util/Map$Entry",
+		include_bytes!("../../runtime/classes/java/util/Map$Entry.class"),
+	),
+	(
+		"java/util/Map",
+		include_bytes!("../../runtime/classes/java/util/Map.class"),
+	),
+	(
+		"java/util/NoSuchElementException",
+		include_bytes!("../../runtime/classes/java/util/NoSuchElementException.class"),
+	),
+	(
+		"java/util/Objects",
+		include_bytes!("../../runtime/classes/java/util/Objects.class"),
+	),
+	(
+		"java/util/Properties",
+		include_bytes!("../../runtime/classes/java/util/Properties.class"),
+	),
+	(
+		"java/util/Set",
+		include_bytes!("../../runtime/classes/java/util/Set.class"),
+	),
+];
@@ -0,0 +1,70 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::frame::Frame;
+use std::fmt;
+
+/// The line number of a native method's stack trace element, as
+/// java.lang.StackTraceElement has it.
+pub const NATIVE_METHOD_LINE_NUMBER: i32 = -2;
+
+/// One frame of a Java stack trace: the contents of a
+/// java.lang.StackTraceElement.
+pub struct StackTraceElement {
+	/// The binary name of the class (e.g., java.lang.Object).
+	pub class_name: String,
+	pub method_name: String,
+	pub file_name: Option<String>,
+	/// Negative if the line is unknown.
+	pub line_number: i32,
+}
+
+impl StackTraceElement {
+	/// Describe the instruction that `frame` is executing.
+	pub fn from_frame(frame: &Frame) -> Option<StackTraceElement> {
+		let class = frame.class()?;
+		let method = frame.method()?;
+		let cp = class.get_constant_pool_ref();
+		Some(StackTraceElement {
+			class_name: class.get_class_name()?.replace('/', "."),
+			method_name: method.get_name(cp)?,
+			file_name: class.get_source_file(),
+			line_number: method
+				.get_line_number(cp, frame.pc)
+				.map_or(-1, |line_number| line_number as i32),
+		})
+	}
+}
+
+/// The same format as java.lang.StackTraceElement.toString().
+impl fmt::Display for StackTraceElement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}.{}", self.class_name, self.method_name)?;
+		match &self.file_name {
+			_ if self.line_number == NATIVE_METHOD_LINE_NUMBER => write!(f, "(Native Method)"),
+			Some(file_name) if self.line_number >= 0 => {
+				write!(f, "({}:{})", file_name, self.line_number)
+			}
+			Some(file_name) => write!(f, "({})", file_name),
+			None => write!(f, "(Unknown Source)"),
+		}
+	}
+}
@@ -0,0 +1,65 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+/// A TraceHook is told about the methods and instructions that the VM
+/// executes (e.g., to profile a program or to record its coverage). The
+/// VM calls it on the thread that executes them, so it should be quick.
+///
+/// Methods are named by the binary name of their class (e.g.,
+/// java/lang/String), their name and their descriptor. Native methods
+/// are not traced.
+pub trait TraceHook: Send + Sync {
+	/// A method starts executing in a new frame; `depth` is the number
+	/// of frames on the Java stack, its own included.
+	fn method_entry(
+		&self,
+		_class_name: &str,
+		_method_name: &str,
+		_descriptor: &str,
+		_depth: usize,
+	) {
+	}
+
+	/// A method completes: it returned or (if `threw`) it threw an
+	/// exception that it did not catch.
+	fn method_exit(
+		&self,
+		_class_name: &str,
+		_method_name: &str,
+		_descriptor: &str,
+		_depth: usize,
+		_threw: bool,
+	) {
+	}
+
+	/// The instruction with opcode `opcode` at `pc` in the code of a
+	/// method is about to execute.
+	fn instruction(
+		&self,
+		_class_name: &str,
+		_method_name: &str,
+		_descriptor: &str,
+		_pc: usize,
+		_opcode: u8,
+	) {
+	}
+}
@@ -85,12 +85,314 @@ pub fn create_null_value() -> JvmValue {
 	JvmValue::Reference(JvmReferenceType::Null, JvmReferenceTargetType::Null, 0)
 }
 
+/*
+ * The default value of a variable of type `r#type` -- zero for
+ * primitives and null for references. See JVMS 2.3 and 2.4.
+ */
+pub fn create_default_value(r#type: &JvmType, access: u16) -> JvmValue {
+	match r#type {
+		JvmType::Primitive(primitive) => JvmValue::Primitive(primitive.clone(), 0, 0, access),
+		JvmType::Reference(_) => {
+			JvmValue::Reference(JvmReferenceType::Null, JvmReferenceTargetType::Null, access)
+		}
+	}
+}
+
+impl JvmValue {
+	/*
+	 * Values of type boolean, byte, char and short are operated upon
+	 * as ints (JVMS 2.11.1). Convert a value read from a field to the
+	 * type that it has on the operand stack.
+	 */
+	pub fn to_computational_type(self) -> JvmValue {
+		match self {
+			JvmValue::Primitive(JvmPrimitiveType::Boolean, _, value, access)
+			| JvmValue::Primitive(JvmPrimitiveType::Byte, _, value, access)
+			| JvmValue::Primitive(JvmPrimitiveType::Char, _, value, access)
+			| JvmValue::Primitive(JvmPrimitiveType::Short, _, value, access) => {
+				JvmValue::Primitive(JvmPrimitiveType::Integer, 0, value, access)
+			}
+			value => value,
+		}
+	}
+
+	/*
+	 * Values of type long and double are of category 2: they take up
+	 * two local variables (JVMS 2.6.1) and the instructions that
+	 * manipulate the operand stack without regard to type treat them
+	 * as a unit (JVMS 2.11.1).
+	 */
+	pub fn is_category_2(&self) -> bool {
+		match self {
+			JvmValue::Primitive(JvmPrimitiveType::LongInteger, _, _, _)
+			| JvmValue::Primitive(JvmPrimitiveType::Double, _, _, _) => true,
+			_ => false,
+		}
+	}
+
+	pub fn int(value: i32) -> JvmValue {
+		JvmValue::Primitive(JvmPrimitiveType::Integer, 0, value as u32, 0)
+	}
+
+	pub fn long(value: i64) -> JvmValue {
+		JvmValue::Primitive(JvmPrimitiveType::LongInteger, value as u64, 0, 0)
+	}
+
+	pub fn float(value: f32) -> JvmValue {
+		JvmValue::Primitive(JvmPrimitiveType::Float, 0, value.to_bits(), 0)
+	}
+
+	pub fn double(value: f64) -> JvmValue {
+		JvmValue::Primitive(JvmPrimitiveType::Double, value.to_bits(), 0, 0)
+	}
+
+	/*
+	 * The int that this value (of computational type int: a boolean,
+	 * byte, char, short or int) holds. Chars stored in arrays hold
+	 * their value in the 64-bit slot.
+	 */
+	pub fn as_int(&self) -> Option<i32> {
+		match self {
+			JvmValue::Primitive(JvmPrimitiveType::Char, value64, value32, _) => {
+				Some(((*value64 as u32) | *value32) as i32)
+			}
+			JvmValue::Primitive(JvmPrimitiveType::Integer, _, value, _)
+			| JvmValue::Primitive(JvmPrimitiveType::Short, _, value, _)
+			| JvmValue::Primitive(JvmPrimitiveType::Byte, _, value, _)
+			| JvmValue::Primitive(JvmPrimitiveType::Boolean, _, value, _) => Some(*value as i32),
+			_ => None,
+		}
+	}
+
+	pub fn as_long(&self) -> Option<i64> {
+		match self {
+			JvmValue::Primitive(JvmPrimitiveType::LongInteger, value, _, _) => Some(*value as i64),
+			_ => None,
+		}
+	}
+
+	pub fn as_float(&self) -> Option<f32> {
+		match self {
+			JvmValue::Primitive(JvmPrimitiveType::Float, _, value, _) => {
+				Some(f32::from_bits(*value))
+			}
+			_ => None,
+		}
+	}
+
+	pub fn as_double(&self) -> Option<f64> {
+		match self {
+			JvmValue::Primitive(JvmPrimitiveType::Double, value, _, _) => {
+				Some(f64::from_bits(*value))
+			}
+			_ => None,
+		}
+	}
+
+	/*
+	 * The name of the type of the object or array that this value
+	 * references (see type_name_of_descriptor). None for null and
+	 * for primitives.
+	 */
+	pub fn type_name(&self) -> Option<String> {
+		match self {
+			JvmValue::Reference(_, JvmReferenceTargetType::Object(object), _) => {
+				object.lock().ok()?.get_class().get_class_name()
+			}
+			JvmValue::Reference(
+				JvmReferenceType::Array(component, _),
+				JvmReferenceTargetType::Array(_),
+				_,
+			) => Some(format!("[{}", component.descriptor())),
+			_ => None,
+		}
+	}
+
+	/*
+	 * Whether this is the null reference.
+	 */
+	pub fn is_null(&self) -> bool {
+		match self {
+			JvmValue::Reference(_, JvmReferenceTargetType::Null, _) => true,
+			_ => false,
+		}
+	}
+
+	/*
+	 * Whether this and `other` reference the same object or array
+	 * (or are both null).
+	 */
+	pub fn is_same_reference(&self, other: &JvmValue) -> bool {
+		match (self, other) {
+			(
+				JvmValue::Reference(_, JvmReferenceTargetType::Null, _),
+				JvmValue::Reference(_, JvmReferenceTargetType::Null, _),
+			) => true,
+			(JvmValue::Reference(_, target, _), JvmValue::Reference(_, other_target, _)) => {
+				target == other_target
+			}
+			_ => false,
+		}
+	}
+}
+
+/// The name of the primitive type whose descriptor is `descriptor`
+/// (e.g., int for I), as Class.getName gives it.
+pub fn primitive_type_name(descriptor: char) -> Option<&'static str> {
+	match descriptor {
+		'B' => Some("byte"),
+		'C' => Some("char"),
+		'D' => Some("double"),
+		'F' => Some("float"),
+		'I' => Some("int"),
+		'J' => Some("long"),
+		'S' => Some("short"),
+		'Z' => Some("boolean"),
+		'V' => Some("void"),
+		_ => None,
+	}
+}
+
+/// The name of the type that the field descriptor `descriptor`
+/// describes: a primitive type's name (e.g., int), a class' binary
+/// name (e.g., java/lang/String) or, for an array, its descriptor.
+/// The VM names each type that has a Class object this way.
+pub fn type_name_of_descriptor(descriptor: &str) -> String {
+	if descriptor.len() == 1 {
+		if let Some(name) = primitive_type_name(descriptor.chars().next().unwrap()) {
+			return name.to_string();
+		}
+	}
+	if descriptor.starts_with('L') && descriptor.ends_with(';') {
+		return descriptor[1..descriptor.len() - 1].to_string();
+	}
+	descriptor.to_string()
+}
+
+/// The field descriptor of the type named `type_name`. See
+/// type_name_of_descriptor.
+pub fn descriptor_of_type_name(type_name: &str) -> String {
+	for descriptor in "BCDFIJSZV".chars() {
+		if primitive_type_name(descriptor) == Some(type_name) {
+			return descriptor.to_string();
+		}
+	}
+	if type_name.starts_with('[') {
+		type_name.to_string()
+	} else {
+		format!("L{};", type_name)
+	}
+}
+
+/// The binary names of the classes in the method or field descriptor
+/// `descriptor` (and of the element classes of its array types), e.g.,
+/// java/lang/String and java/lang/Object for
+/// (Ljava/lang/String;[Ljava/lang/Object;)V.
+pub fn class_names_of_descriptor(descriptor: &str) -> Vec<String> {
+	let mut class_names = Vec::<String>::new();
+	let mut rest = descriptor;
+	while let Some(start) = rest.find('L') {
+		match rest[start..].find(';') {
+			Some(end) => {
+				class_names.push(rest[start + 1..start + end].to_string());
+				rest = &rest[start + end + 1..];
+			}
+			None => break,
+		}
+	}
+	class_names
+}
+
 #[derive(Clone)]
 pub enum JvmType {
 	Primitive(JvmPrimitiveType),
 	Reference(JvmReferenceType),
 }
 
+impl JvmType {
+	/// Parse `descriptor` as a field descriptor (JVMS 4.3.2).
+	///
+	/// # Return value:
+	/// The type that `descriptor` describes. None if `descriptor`
+	/// is not exactly one well-formed field type.
+	pub fn from_field_descriptor(descriptor: &str) -> Option<JvmType> {
+		match JvmType::parse_field_type(descriptor.as_bytes(), 0) {
+			Some((r#type, length)) if length == descriptor.len() => Some(r#type),
+			_ => None,
+		}
+	}
+
+	fn parse_field_type(descriptor: &[u8], dimensions: usize) -> Option<(JvmType, usize)> {
+		let primitive = |primitive| Some((JvmType::Primitive(primitive), 1));
+		match descriptor.first().map(|c| *c as char) {
+			Some('B') => primitive(JvmPrimitiveType::Byte),
+			Some('C') => primitive(JvmPrimitiveType::Char),
+			Some('D') => primitive(JvmPrimitiveType::Double),
+			Some('F') => primitive(JvmPrimitiveType::Float),
+			Some('I') => primitive(JvmPrimitiveType::Integer),
+			Some('J') => primitive(JvmPrimitiveType::LongInteger),
+			Some('S') => primitive(JvmPrimitiveType::Short),
+			Some('Z') => primitive(JvmPrimitiveType::Boolean),
+			Some('L') => {
+				/*
+				 * Lsome/class/name; -- the class name must be non-empty
+				 * and must not contain any of . ; [
+				 */
+				let end = descriptor.iter().position(|c| *c == ';' as u8)?;
+				let class_name = std::str::from_utf8(&descriptor[1..end]).ok()?;
+				if class_name.is_empty() || class_name.contains(|c| c == '.' || c == '[') {
+					return None;
+				}
+				Some((
+					JvmType::Reference(JvmReferenceType::Class(class_name.to_string())),
+					end + 1,
+				))
+			}
+			Some('[') => {
+				/*
+				 * An array type descriptor is valid only if it represents
+				 * 255 or fewer dimensions (JVMS 4.3.2).
+				 */
+				if dimensions >= 255 {
+					return None;
+				}
+				let (component, length) =
+					JvmType::parse_field_type(&descriptor[1..], dimensions + 1)?;
+				Some((
+					JvmType::Reference(JvmReferenceType::Array(Rc::new(component), 0)),
+					length + 1,
+				))
+			}
+			_ => None,
+		}
+	}
+}
+
+impl JvmType {
+	/// The field descriptor of this type (JVMS 4.3.2).
+	pub fn descriptor(&self) -> String {
+		match self {
+			JvmType::Primitive(JvmPrimitiveType::Byte) => "B".to_string(),
+			JvmType::Primitive(JvmPrimitiveType::Char) => "C".to_string(),
+			JvmType::Primitive(JvmPrimitiveType::Double) => "D".to_string(),
+			JvmType::Primitive(JvmPrimitiveType::Float) => "F".to_string(),
+			JvmType::Primitive(JvmPrimitiveType::Integer) => "I".to_string(),
+			JvmType::Primitive(JvmPrimitiveType::LongInteger) => "J".to_string(),
+			JvmType::Primitive(JvmPrimitiveType::Short) => "S".to_string(),
+			JvmType::Primitive(JvmPrimitiveType::Boolean) => "Z".to_string(),
+			JvmType::Primitive(_) => "V".to_string(),
+			JvmType::Reference(JvmReferenceType::Array(component, _)) => {
+				format!("[{}", component.descriptor())
+			}
+			JvmType::Reference(JvmReferenceType::Class(class_name))
+			| JvmType::Reference(JvmReferenceType::Interface(class_name)) => {
+				format!("L{};", class_name)
+			}
+			JvmType::Reference(JvmReferenceType::Null) => "Ljava/lang/Object;".to_string(),
+		}
+	}
+}
+
 impl Default for JvmType {
 	fn default() -> Self {
 		JvmType::Primitive(JvmPrimitiveType::Invalid)
@@ -156,24 +458,12 @@ impl PartialEq for JvmType {
 	fn eq(&self, other: &Self) -> bool {
 		match self {
 			JvmType::Primitive(s) => match other {
-				JvmType::Primitive(o) => {
-					println!("Comparing two primitives.");
-					s == o
-				}
-				_ => {
-					println!("Comparing a primitive with a non-primitive.");
-					false
-				}
+				JvmType::Primitive(o) => s == o,
+				_ => false,
 			},
 			JvmType::Reference(s) => match other {
-				JvmType::Reference(o) => {
-					println!("Comparing two references.");
-					o == s
-				}
-				_ => {
-					println!("Comparing a reference with a non-reference.");
-					false
-				}
+				JvmType::Reference(o) => o == s,
+				_ => false,
 			},
 		}
 	}
@@ -0,0 +1,594 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use jvm::array::array_element;
+use jvm::jvmthread::JvmThread;
+use jvm::method::parameter_descriptors;
+use jvm::object::get_string_value;
+use jvm::typevalues::create_null_value;
+use jvm::typevalues::type_name_of_descriptor;
+use jvm::typevalues::{JvmReferenceTargetType, JvmReferenceType, JvmType, JvmValue};
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// A value that Rust code passes to Java code or gets back from it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JavaValue {
+	/// What a void method returns.
+	Void,
+	Boolean(bool),
+	Byte(i8),
+	Char(u16),
+	Short(i16),
+	Int(i32),
+	Long(i64),
+	Float(f32),
+	Double(f64),
+	/// A java.lang.String, by its contents, to pass to Java code: it
+	/// passes a new String. Java code's Strings come back as Objects
+	/// (see Vm::to_rust_string).
+	String(String),
+	/// An object (including a String) or array.
+	Object(JavaObject),
+	Null,
+}
+
+impl From<bool> for JavaValue {
+	fn from(value: bool) -> JavaValue {
+		JavaValue::Boolean(value)
+	}
+}
+
+impl From<i8> for JavaValue {
+	fn from(value: i8) -> JavaValue {
+		JavaValue::Byte(value)
+	}
+}
+
+impl From<u16> for JavaValue {
+	fn from(value: u16) -> JavaValue {
+		JavaValue::Char(value)
+	}
+}
+
+impl From<i16> for JavaValue {
+	fn from(value: i16) -> JavaValue {
+		JavaValue::Short(value)
+	}
+}
+
+impl From<i32> for JavaValue {
+	fn from(value: i32) -> JavaValue {
+		JavaValue::Int(value)
+	}
+}
+
+impl From<i64> for JavaValue {
+	fn from(value: i64) -> JavaValue {
+		JavaValue::Long(value)
+	}
+}
+
+impl From<f32> for JavaValue {
+	fn from(value: f32) -> JavaValue {
+		JavaValue::Float(value)
+	}
+}
+
+impl From<f64> for JavaValue {
+	fn from(value: f64) -> JavaValue {
+		JavaValue::Double(value)
+	}
+}
+
+impl<'a> From<&'a str> for JavaValue {
+	fn from(value: &'a str) -> JavaValue {
+		JavaValue::String(value.to_string())
+	}
+}
+
+impl From<String> for JavaValue {
+	fn from(value: String) -> JavaValue {
+		JavaValue::String(value)
+	}
+}
+
+impl From<JavaObject> for JavaValue {
+	fn from(value: JavaObject) -> JavaValue {
+		JavaValue::Object(value)
+	}
+}
+
+/// A reference that Rust code holds to a Java object or array. The heap
+/// treats it as a root: what it references is not collected while it
+/// (or a clone of it) exists.
+#[derive(Clone)]
+pub struct JavaObject {
+	reference: JvmValue,
+}
+
+impl JavaObject {
+	/// The name of the object's class (e.g., java.lang.StringBuilder) or,
+	/// for an array, its descriptor (e.g., [I).
+	pub fn type_name(&self) -> String {
+		self.reference
+			.type_name()
+			.unwrap_or_default()
+			.replace('/', ".")
+	}
+
+	/// Whether this is an array.
+	pub fn is_array(&self) -> bool {
+		match self.reference {
+			JvmValue::Reference(_, JvmReferenceTargetType::Array(_), _) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Two JavaObjects are equal when they reference the same object.
+impl PartialEq for JavaObject {
+	fn eq(&self, other: &JavaObject) -> bool {
+		self.reference.is_same_reference(&other.reference)
+	}
+}
+
+impl fmt::Debug for JavaObject {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "JavaObject({})", self.type_name())
+	}
+}
+
+/// An exception that Java code threw and did not catch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JavaException {
+	/// The binary name of its class (e.g., java.lang.ArithmeticException).
+	pub class_name: String,
+	pub message: Option<String>,
+	/// Its stack trace, newest frame first, as printStackTrace prints it.
+	pub stack_trace: Vec<String>,
+}
+
+impl fmt::Display for JavaException {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match &self.message {
+			Some(message) => write!(f, "{}: {}", self.class_name, message),
+			None => write!(f, "{}", self.class_name),
+		}
+	}
+}
+
+/// Why calling into Java failed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CallError {
+	/// The method descriptor is malformed.
+	InvalidDescriptor(String),
+	/// The arguments do not match the method descriptor (or a value
+	/// does not match the type of a field or array).
+	WrongArguments(String),
+	/// The Java code threw an exception.
+	Exception(JavaException),
+	/// The program halted (e.g., called System.exit) with the status.
+	Exited(i32),
+	/// The VM failed (e.g., with a FatalError), for the reason given: it
+	/// runs no more Java code.
+	VmError(String),
+}
+
+impl fmt::Display for CallError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			CallError::InvalidDescriptor(descriptor) => {
+				write!(f, "invalid method descriptor {}", descriptor)
+			}
+			CallError::WrongArguments(message) => write!(f, "wrong arguments: {}", message),
+			CallError::Exception(exception) => write!(f, "{}", exception),
+			CallError::Exited(exit_status) => {
+				write!(f, "the program exited with status {}", exit_status)
+			}
+			CallError::VmError(message) => write!(f, "the VM failed: {}", message),
+		}
+	}
+}
+
+impl Error for CallError {}
+
+/// A booted VM that Rust code calls into, like a JNIEnv: it invokes
+/// methods, creates objects, strings and arrays and accesses their
+/// fields and elements. Java code runs on the calling thread, whose
+/// native stack grows when the VM calls into Java while executing an
+/// instruction (e.g., to run <clinit>): see Jvm::with_vm.
+///
+/// If the VM fails (e.g., with a FatalError), the call fails with a
+/// VmError, as does every call after it.
+pub struct Vm {
+	thread: JvmThread,
+	/// Why the VM failed, if it has.
+	failure: Option<String>,
+}
+
+/// The message of the panic whose payload is `payload`.
+fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+	if let Some(message) = payload.downcast_ref::<String>() {
+		message.to_string()
+	} else if let Some(message) = payload.downcast_ref::<&str>() {
+		message.to_string()
+	} else {
+		"the VM panicked".to_string()
+	}
+}
+
+impl Vm {
+	/// Boot the class library on `thread` (see JvmThread::boot).
+	pub fn new(thread: JvmThread) -> Result<Vm, CallError> {
+		let mut vm = Vm {
+			thread,
+			failure: None,
+		};
+		if !vm.guard(|vm| Ok(vm.thread.boot()))? {
+			return Err(CallError::Exited(vm.thread.exit_status().unwrap_or(1)));
+		}
+		Ok(vm)
+	}
+
+	/// Invoke the static method `method_name` with type `descriptor`
+	/// (e.g., (II)I) of the class named `class_name` (e.g., com/acme/Calc
+	/// or com.acme.Calc) with `arguments`, whose types must be those of
+	/// the descriptor's parameters. The class is initialized first.
+	pub fn call_static(
+		&mut self,
+		class_name: &str,
+		method_name: &str,
+		descriptor: &str,
+		arguments: &[JavaValue],
+	) -> Result<JavaValue, CallError> {
+		self.guard(|vm| {
+			let (arguments, return_descriptor) = vm.to_jvm_arguments(descriptor, arguments)?;
+			let result = vm.thread.call_static(
+				&class_name.replace('.', "/"),
+				method_name,
+				descriptor,
+				arguments,
+			);
+			vm.to_result(return_descriptor, result)
+		})
+	}
+
+	/// Invoke the instance method `method_name` with type `descriptor` on
+	/// `object` with `arguments`, selecting the method the way that
+	/// invokevirtual does. See call_static.
+	pub fn call_method(
+		&mut self,
+		object: &JavaObject,
+		method_name: &str,
+		descriptor: &str,
+		arguments: &[JavaValue],
+	) -> Result<JavaValue, CallError> {
+		self.guard(|vm| {
+			let (arguments, return_descriptor) = vm.to_jvm_arguments(descriptor, arguments)?;
+			let result =
+				vm.thread
+					.call_virtual(&object.reference, method_name, descriptor, arguments);
+			vm.to_result(return_descriptor, result)
+		})
+	}
+
+	/// Create an instance of the class named `class_name` with its
+	/// constructor of type `descriptor` (e.g., (I)V). See call_static.
+	pub fn new_object(
+		&mut self,
+		class_name: &str,
+		descriptor: &str,
+		arguments: &[JavaValue],
+	) -> Result<JavaObject, CallError> {
+		self.guard(|vm| {
+			let (arguments, _) = vm.to_jvm_arguments(descriptor, arguments)?;
+			let object = vm
+				.thread
+				.new_object(&class_name.replace('.', "/"), descriptor, arguments);
+			match object {
+				Some(reference) => Ok(JavaObject { reference }),
+				None => Err(vm.take_error()),
+			}
+		})
+	}
+
+	/// Create a java.lang.String whose contents are `value`.
+	pub fn new_string(&mut self, value: &str) -> Result<JavaObject, CallError> {
+		self.guard(|vm| match vm.thread.create_string(value) {
+			Some(reference) => Ok(JavaObject { reference }),
+			None => Err(vm.take_error()),
+		})
+	}
+
+	/// The contents of `string`. None if it is not a java.lang.String.
+	pub fn to_rust_string(&self, string: &JavaObject) -> Option<String> {
+		match string.reference.type_name() {
+			Some(ref type_name) if type_name == "java/lang/String" => {
+				get_string_value(&string.reference)
+			}
+			_ => None,
+		}
+	}
+
+	/// Create an array whose component type is `component` (a field
+	/// descriptor, e.g., I or Ljava/lang/String;) and whose elements
+	/// are `elements`.
+	pub fn new_array(
+		&mut self,
+		component: &str,
+		elements: &[JavaValue],
+	) -> Result<JavaObject, CallError> {
+		self.guard(|vm| {
+			let component_type = match JvmType::from_field_descriptor(component) {
+				Some(component_type) => component_type,
+				None => return Err(CallError::InvalidDescriptor(component.to_string())),
+			};
+			let mut values = Vec::with_capacity(elements.len());
+			for element in elements {
+				let value = vm.to_jvm_value(component, element)?;
+				values.push(array_element(&component_type, value));
+			}
+			match vm.thread.create_array(component_type, values) {
+				Some(reference) => Ok(JavaObject { reference }),
+				None => Err(vm.take_error()),
+			}
+		})
+	}
+
+	/// The elements of the array `array`.
+	pub fn array_elements(&self, array: &JavaObject) -> Result<Vec<JavaValue>, CallError> {
+		let (component, elements) = match &array.reference {
+			JvmValue::Reference(
+				JvmReferenceType::Array(component, _),
+				JvmReferenceTargetType::Array(elements),
+				_,
+			) => match elements.lock() {
+				Ok(elements) => (
+					component.descriptor(),
+					elements.get_range(0, elements.dimension()),
+				),
+				_ => return Err(CallError::WrongArguments(format!("{:?}", array))),
+			},
+			_ => {
+				return Err(CallError::WrongArguments(format!(
+					"{:?} is not an array",
+					array
+				)))
+			}
+		};
+		Ok(elements
+			.into_iter()
+			.map(|element| {
+				self.to_java_value(&component, &element.unwrap_or_else(create_null_value))
+			})
+			.collect())
+	}
+
+	/// The value of the field named `field_name` with type `descriptor`
+	/// (e.g., I) of `object`, resolved the way that getfield does.
+	pub fn get_field(
+		&mut self,
+		object: &JavaObject,
+		field_name: &str,
+		descriptor: &str,
+	) -> Result<JavaValue, CallError> {
+		self.guard(|vm| {
+			let value = vm
+				.thread
+				.get_field(&object.reference, field_name, descriptor);
+			vm.to_result(descriptor, value)
+		})
+	}
+
+	/// Set the field named `field_name` with type `descriptor` of
+	/// `object` to `value`. See get_field.
+	pub fn set_field(
+		&mut self,
+		object: &JavaObject,
+		field_name: &str,
+		descriptor: &str,
+		value: &JavaValue,
+	) -> Result<(), CallError> {
+		self.guard(|vm| {
+			let value = vm.to_jvm_value(descriptor, value)?;
+			if vm
+				.thread
+				.set_field(&object.reference, field_name, descriptor, value)
+			{
+				Ok(())
+			} else {
+				Err(vm.take_error())
+			}
+		})
+	}
+
+	/// Fail if the program has halted or the VM has failed: it runs no
+	/// more Java code.
+	fn check_running(&self) -> Result<(), CallError> {
+		if let Some(failure) = &self.failure {
+			return Err(CallError::VmError(failure.to_string()));
+		}
+		match self.thread.exit_status() {
+			Some(exit_status) => Err(CallError::Exited(exit_status)),
+			None => Ok(()),
+		}
+	}
+
+	/// Make `call` on the VM, if it is running. If the VM fails during
+	/// the call (i.e., it panics), the call fails with a VmError.
+	fn guard<T, F>(&mut self, call: F) -> Result<T, CallError>
+	where
+		F: FnOnce(&mut Vm) -> Result<T, CallError>,
+	{
+		self.check_running()?;
+		match panic::catch_unwind(AssertUnwindSafe(|| call(self))) {
+			Ok(result) => result,
+			Err(payload) => {
+				let failure = panic_message(&payload);
+				self.failure = Some(failure.to_string());
+				Err(CallError::VmError(failure))
+			}
+		}
+	}
+
+	/// `arguments` as the arguments of a method with type `descriptor`,
+	/// and the descriptor of its return type.
+	fn to_jvm_arguments<'d>(
+		&mut self,
+		descriptor: &'d str,
+		arguments: &[JavaValue],
+	) -> Result<(Vec<JvmValue>, &'d str), CallError> {
+		let return_descriptor = match descriptor.rfind(')') {
+			Some(end) if descriptor.starts_with('(') && end + 1 < descriptor.len() => {
+				&descriptor[end + 1..]
+			}
+			_ => return Err(CallError::InvalidDescriptor(descriptor.to_string())),
+		};
+		let parameters = parameter_descriptors(descriptor);
+		if parameters.len() != arguments.len() {
+			return Err(CallError::WrongArguments(format!(
+				"{} takes {} arguments, not {}",
+				descriptor,
+				parameters.len(),
+				arguments.len()
+			)));
+		}
+		let mut values = Vec::with_capacity(arguments.len());
+		for (parameter, argument) in parameters.iter().zip(arguments) {
+			values.push(self.to_jvm_value(parameter, argument)?);
+		}
+		Ok((values, return_descriptor))
+	}
+
+	/// `result` (of type `descriptor`) as a JavaValue or, if there is
+	/// none, the error for why.
+	fn to_result(
+		&mut self,
+		descriptor: &str,
+		result: Option<JvmValue>,
+	) -> Result<JavaValue, CallError> {
+		match result {
+			Some(result) => Ok(self.to_java_value(descriptor, &result)),
+			None => Err(self.take_error()),
+		}
+	}
+
+	/// The error for the exception that the Java code threw (or for its
+	/// halting).
+	fn take_error(&mut self) -> CallError {
+		if let Some(exit_status) = self.thread.exit_status() {
+			return CallError::Exited(exit_status);
+		}
+		let exception = self.thread.take_exception();
+		let description = exception
+			.as_ref()
+			.and_then(|exception| self.thread.describe_exception(exception));
+		match description {
+			Some((class_name, message, stack_trace)) => CallError::Exception(JavaException {
+				class_name: class_name.replace('/', "."),
+				message,
+				stack_trace: stack_trace
+					.iter()
+					.map(|element| element.to_string())
+					.collect(),
+			}),
+			None => CallError::Exception(JavaException {
+				class_name: "java.lang.InternalError".to_string(),
+				message: None,
+				stack_trace: Vec::new(),
+			}),
+		}
+	}
+
+	/// `argument` as the value of a parameter of type `parameter` (a
+	/// field descriptor).
+	fn to_jvm_value(
+		&mut self,
+		parameter: &str,
+		argument: &JavaValue,
+	) -> Result<JvmValue, CallError> {
+		let value = match (parameter, argument) {
+			("Z", JavaValue::Boolean(value)) => JvmValue::int(*value as i32),
+			("B", JavaValue::Byte(value)) => JvmValue::int(*value as i32),
+			("C", JavaValue::Char(value)) => JvmValue::int(*value as i32),
+			("S", JavaValue::Short(value)) => JvmValue::int(*value as i32),
+			("I", JavaValue::Int(value)) => JvmValue::int(*value),
+			("J", JavaValue::Long(value)) => JvmValue::long(*value),
+			("F", JavaValue::Float(value)) => JvmValue::float(*value),
+			("D", JavaValue::Double(value)) => JvmValue::double(*value),
+			(_, JavaValue::Null) if parameter.starts_with('L') || parameter.starts_with('[') => {
+				create_null_value()
+			}
+			(_, JavaValue::Object(object))
+				if (parameter.starts_with('L') || parameter.starts_with('['))
+					&& self.thread.is_value_assignable(
+						&object.reference,
+						&type_name_of_descriptor(parameter),
+						None,
+					) =>
+			{
+				object.reference.clone()
+			}
+			(_, JavaValue::String(value))
+				if parameter.starts_with('L')
+					&& self
+						.thread
+						.is_assignable("java/lang/String", &type_name_of_descriptor(parameter)) =>
+			{
+				match self.thread.create_string(value) {
+					Some(string) => string,
+					None => return Err(self.take_error()),
+				}
+			}
+			_ => {
+				return Err(CallError::WrongArguments(format!(
+					"{:?} is not a {}",
+					argument, parameter
+				)))
+			}
+		};
+		Ok(value)
+	}
+
+	/// `value` (of type `descriptor`) as a JavaValue. A String is an
+	/// Object, so that Rust code can pass it back (as the same object).
+	fn to_java_value(&self, descriptor: &str, value: &JvmValue) -> JavaValue {
+		let int = || value.as_int().unwrap_or(0);
+		match descriptor {
+			"V" => JavaValue::Void,
+			"Z" => JavaValue::Boolean(int() != 0),
+			"B" => JavaValue::Byte(int() as i8),
+			"C" => JavaValue::Char(int() as u16),
+			"S" => JavaValue::Short(int() as i16),
+			"I" => JavaValue::Int(int()),
+			"J" => JavaValue::Long(value.as_long().unwrap_or(0)),
+			"F" => JavaValue::Float(value.as_float().unwrap_or(0.0)),
+			"D" => JavaValue::Double(value.as_double().unwrap_or(0.0)),
+			_ if value.is_null() => JavaValue::Null,
+			_ => JavaValue::Object(JavaObject {
+				reference: value.clone(),
+			}),
+		}
+	}
+}
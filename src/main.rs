@@ -28,6 +28,18 @@ use jvm::jvm::debug::DebugLevel;
 
 fn main() {
 	let mut debug = DebugLevel::Error;
+	/*
+	 * java spells it -jar, which clap would take for -j -a -r: accept
+	 * it as --jar (but not among the application's arguments).
+	 */
+	let mut command_line: Vec<String> = std::env::args().collect();
+	if let Some(jar) = command_line
+		.iter()
+		.take_while(|arg| *arg != "-a" && *arg != "--")
+		.position(|arg| arg == "-jar")
+	{
+		command_line[jar] = "--jar".to_string();
+	}
 	/*
 	 * TODO: Update this so that we use a yaml file to
 	 * generate these options!
@@ -37,9 +49,17 @@ fn main() {
 		.arg(
 			Arg::with_name("class")
 				.help("Main class.")
-				.required(true)
+				.required_unless("jar")
+				.conflicts_with("jar")
 				.index(1),
 		)
+		.arg(
+			Arg::with_name("jar")
+				.help("Executable JAR file whose Main-Class to run (-jar works, too).")
+				.long("jar")
+				.takes_value(true)
+				.conflicts_with("classpath"),
+		)
 		.arg(Arg::with_name("method").help("Main method to execute."))
 		.arg(
 			Arg::with_name("debug")
@@ -48,10 +68,34 @@ fn main() {
 		)
 		.arg(
 			Arg::with_name("classpath")
-				.help("Class path.")
+				.help(
+					"Class path: directories, JAR files and dir/* (the JAR files in dir). \
+					 By default, $CLASSPATH or the current directory.",
+				)
 				.short("c")
+				.long("class-path")
+				.alias("classpath")
 				.takes_value(true),
 		)
+		.arg(
+			Arg::with_name("X")
+				.help(
+					"Non-standard options: -Xmx<size> sets the maximum heap size (e.g., -Xmx64m); \
+					 -Xss<size> sets the size of the Java stack (e.g., -Xss1m).",
+				)
+				.short("X")
+				.takes_value(true)
+				.multiple(true)
+				.number_of_values(1),
+		)
+		.arg(
+			Arg::with_name("D")
+				.help("Set a system property: -D<name>=<value> (e.g., -Dapp.mode=batch).")
+				.short("D")
+				.takes_value(true)
+				.multiple(true)
+				.number_of_values(1),
+		)
 		.arg(
 			Arg::with_name("args")
 				.help("Java application arguments.")
@@ -59,24 +103,90 @@ fn main() {
 				.takes_value(true)
 				.multiple(true),
 		)
-		.get_matches();
+		.get_matches_from(command_line);
 
 	if cli_matches.is_present("debug") {
 		debug = DebugLevel::Info;
 	}
 
-	let class = format!("{}", cli_matches.value_of("class").unwrap());
 	let method = cli_matches.value_of("method").unwrap_or("main").to_string();
 
-	let classpath_arg = cli_matches.value_of("classpath").unwrap_or("");
-	let classpath: Vec<&str> = classpath_arg.split(":").collect();
+	/*
+	 * As in java, the class path is -c, or the CLASSPATH environment
+	 * variable, or the current directory.
+	 */
+	let classpath_arg = match cli_matches.value_of("classpath") {
+		Some(classpath) => classpath.to_string(),
+		None => std::env::var("CLASSPATH").unwrap_or(".".to_string()),
+	};
+	let separator = if cfg!(windows) { ';' } else { ':' };
+	let classpath: Vec<&str> = classpath_arg.split(separator).collect();
 
 	let args: Vec<&str> = cli_matches
 		.values_of("args")
 		.unwrap_or(clap::Values::default())
 		.collect();
 
-	if let Some(jvm) = jvm::jvm::Jvm::new(debug) {
-		jvm.run(&class, &method, classpath.as_slice(), args.as_slice());
+	let mut max_heap_size = jvm::jvm::DEFAULT_MAX_HEAP_SIZE;
+	let mut max_stack_depth = jvm::jvm::DEFAULT_MAX_STACK_DEPTH;
+	for option in cli_matches
+		.values_of("X")
+		.unwrap_or(clap::Values::default())
+	{
+		if option.starts_with("mx") {
+			match jvm::jvm::parse_memory_size(&option[2..]) {
+				Some(size) => max_heap_size = size,
+				None => {
+					eprintln!("Invalid maximum heap size: -X{}", option);
+					std::process::exit(1);
+				}
+			}
+		} else if option.starts_with("ss") {
+			/*
+			 * Frames do not live on the native stack, so the size
+			 * limits the number of frames instead.
+			 */
+			match jvm::jvm::parse_memory_size(&option[2..]) {
+				Some(size) if size >= jvm::jvm::STACK_FRAME_SIZE => {
+					max_stack_depth = size / jvm::jvm::STACK_FRAME_SIZE
+				}
+				_ => {
+					eprintln!("Invalid thread stack size: -X{}", option);
+					std::process::exit(1);
+				}
+			}
+		} else {
+			eprintln!("Unrecognized option: -X{}", option);
+			std::process::exit(1);
+		}
+	}
+
+	let mut builder = jvm::jvm::JvmBuilder::new()
+		.debug_level(debug)
+		.classpath(&classpath)
+		.max_heap_size(max_heap_size)
+		.max_stack_depth(max_stack_depth);
+
+	/*
+	 * As in java, -Dname sets the property name to the empty string.
+	 */
+	for property in cli_matches
+		.values_of("D")
+		.unwrap_or(clap::Values::default())
+	{
+		let mut parts = property.splitn(2, '=');
+		let name = parts.next().unwrap_or("");
+		if name.is_empty() {
+			eprintln!("Invalid system property: -D{}", property);
+			std::process::exit(1);
+		}
+		builder = builder.system_property(name, parts.next().unwrap_or(""));
+	}
+
+	let jvm = builder.build();
+	if let Some(jar) = cli_matches.value_of("jar") {
+		std::process::exit(jvm.run_jar(jar, args.as_slice()));
 	}
+	let class = cli_matches.value_of("class").unwrap();
+	std::process::exit(jvm.run(class, &method, args.as_slice()));
 }
@@ -0,0 +1,161 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+extern crate jvm;
+
+mod common;
+
+use common::builder;
+use jvm::jvm::{JvmThread, JvmValue, MethodArea, TraceHook, INSTRUCTION_BUDGET_EXIT_STATUS};
+use std::io::{Cursor, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// What a program writes to a standard stream, kept for the test.
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Captured {
+	fn text(&self) -> String {
+		String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+	}
+}
+
+impl Write for Captured {
+	fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+		self.0.lock().unwrap().extend_from_slice(bytes);
+		Ok(bytes.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
+#[test]
+fn standard_streams_are_redirected() {
+	let stdout = Captured::default();
+	let stderr = Captured::default();
+	let jvm = builder("programs")
+		.stdin(Cursor::new(b"hello, world\n".to_vec()))
+		.stdout(stdout.clone())
+		.stderr(stderr.clone())
+		.build();
+	assert_eq!(jvm.run("Echo", "main", &[]), 0);
+	assert_eq!(stdout.text(), "HELLO, WORLD\n");
+	assert_eq!(stderr.text(), "copied 13\n");
+}
+
+#[test]
+fn the_instruction_budget_halts_a_program() {
+	let stderr = Captured::default();
+	let jvm = builder("programs")
+		.instruction_budget(100_000)
+		.stderr(stderr.clone())
+		.build();
+	assert_eq!(jvm.run("Spin", "main", &[]), INSTRUCTION_BUDGET_EXIT_STATUS);
+	assert!(stderr.text().contains("instruction budget"));
+}
+
+#[test]
+fn registered_natives_are_called() {
+	let called = Arc::new(AtomicBool::new(false));
+	let native_called = Arc::clone(&called);
+	let jvm = builder("programs")
+		.native(
+			"Twice",
+			"twice",
+			"(I)I",
+			move |_: &mut JvmThread,
+			      _: &Arc<Mutex<MethodArea>>,
+			      arguments: &[JvmValue]|
+			      -> Option<JvmValue> {
+				native_called.store(true, Ordering::SeqCst);
+				Some(JvmValue::int(arguments[0].as_int().unwrap_or(0) * 2))
+			},
+		)
+		.build();
+	assert_eq!(jvm.run("Twice", "main", &[]), 0);
+	assert!(called.load(Ordering::SeqCst));
+}
+
+/// Records what the program that it traces executes (in what its
+/// clones share, so that the test can look at it).
+#[derive(Clone, Default)]
+struct Recorder {
+	events: Arc<Mutex<Vec<String>>>,
+	instructions: Arc<AtomicUsize>,
+}
+
+impl TraceHook for Recorder {
+	fn method_entry(&self, class_name: &str, method_name: &str, descriptor: &str, _: usize) {
+		self.events.lock().unwrap().push(format!(
+			"enter {}.{}{}",
+			class_name, method_name, descriptor
+		));
+	}
+
+	fn method_exit(
+		&self,
+		class_name: &str,
+		method_name: &str,
+		descriptor: &str,
+		_: usize,
+		threw: bool,
+	) {
+		self.events.lock().unwrap().push(format!(
+			"exit {}.{}{}{}",
+			class_name,
+			method_name,
+			descriptor,
+			if threw { " (threw)" } else { "" }
+		));
+	}
+
+	fn instruction(&self, class_name: &str, _: &str, _: &str, _: usize, _: u8) {
+		if class_name == "Squares" {
+			self.instructions.fetch_add(1, Ordering::SeqCst);
+		}
+	}
+}
+
+#[test]
+fn trace_hooks_see_methods_and_instructions() {
+	let recorder = Recorder::default();
+	let jvm = builder("programs").trace_hook(recorder.clone()).build();
+	assert_eq!(jvm.run("Squares", "main", &[]), 0);
+
+	let events = recorder.events.lock().unwrap();
+	let squares: Vec<&String> = events
+		.iter()
+		.filter(|event| event.contains("Squares."))
+		.collect();
+	assert_eq!(
+		squares,
+		vec![
+			"enter Squares.main([Ljava/lang/String;)V",
+			"enter Squares.square(I)I",
+			"exit Squares.square(I)I",
+			"exit Squares.main([Ljava/lang/String;)V",
+		]
+	);
+	assert!(recorder.instructions.load(Ordering::SeqCst) > 0);
+}
@@ -0,0 +1,117 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+extern crate jvm;
+
+mod common;
+
+use common::test_path;
+use jvm::jvm::{CallError, JavaValue, JvmBuilder, Vm};
+use std::process::{Command, Stdio};
+
+/// A VM whose class path is `entries` (under tests).
+fn vm(entries: &[&str]) -> Vm {
+	let entries: Vec<String> = entries
+		.iter()
+		.map(|entry| test_path(entry).to_str().unwrap().to_string())
+		.collect();
+	let entries: Vec<&str> = entries.iter().map(|entry| entry.as_str()).collect();
+	JvmBuilder::new()
+		.classpath(&entries)
+		.build()
+		.start()
+		.unwrap()
+}
+
+#[test]
+fn classes_in_packages_are_found() {
+	/*
+	 * classes/packages also has Broken.class, which is not a class
+	 * file: only the classes that are asked for are read.
+	 */
+	for entry in &["classes/packages", "jars/packages.jar"] {
+		let mut vm = vm(&[entry]);
+		assert_eq!(
+			vm.call_static("com.acme.Tool", "answer", "()I", &[]),
+			Ok(JavaValue::Int(42))
+		);
+	}
+	let mut vm = vm(&["classes/packages"]);
+	match vm.call_static("Tool", "answer", "()I", &[]) {
+		Err(CallError::Exception(exception)) => {
+			assert_eq!(exception.class_name, "java.lang.NoClassDefFoundError")
+		}
+		result => panic!("{:?}", result),
+	}
+}
+
+#[test]
+fn wildcards_are_the_jar_files_in_a_directory() {
+	let mut jars = vm(&["jars/*"]);
+	assert_eq!(
+		jars.call_static("com.acme.Tool", "answer", "()I", &[]),
+		Ok(JavaValue::Int(42))
+	);
+	/*
+	 * Not the class files.
+	 */
+	let mut classes = vm(&["classes/packages/*"]);
+	match classes.call_static("com.acme.Tool", "answer", "()I", &[]) {
+		Err(CallError::Exception(exception)) => {
+			assert_eq!(exception.class_name, "java.lang.NoClassDefFoundError")
+		}
+		result => panic!("{:?}", result),
+	}
+}
+
+#[test]
+fn the_jvm_command_falls_back_to_the_classpath_variable() {
+	/*
+	 * App exits with 42 when it finds Tool.
+	 */
+	let packages = test_path("classes/packages");
+	let jvm = || {
+		let mut command = Command::new(env!("CARGO_BIN_EXE_jvm"));
+		command.env_remove("CLASSPATH").args(&["App", "main"]);
+		command
+	};
+	let status = jvm().env("CLASSPATH", &packages).status().unwrap();
+	assert_eq!(status.code(), Some(42));
+
+	/*
+	 * -c takes precedence, and the current directory is the default.
+	 */
+	let status = jvm()
+		.env("CLASSPATH", test_path("classes/missing"))
+		.arg("-c")
+		.arg(&packages)
+		.status()
+		.unwrap();
+	assert_eq!(status.code(), Some(42));
+	let status = jvm().current_dir(&packages).status().unwrap();
+	assert_eq!(status.code(), Some(42));
+	let status = jvm()
+		.env("CLASSPATH", test_path("classes/missing"))
+		.stderr(Stdio::null())
+		.status()
+		.unwrap();
+	assert_ne!(status.code(), Some(42));
+}
@@ -0,0 +1,127 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+extern crate jvm;
+
+mod common;
+
+use common::test_path;
+use jvm::jvm::{ClassSource, DirectorySource, JarSource, JavaValue, JvmBuilder, MapSource};
+use std::collections::HashMap;
+use std::fs;
+
+fn class_file(directory: &str, class_name: &str) -> Vec<u8> {
+	fs::read(test_path(&format!(
+		"classes/{}/{}.class",
+		directory, class_name
+	)))
+	.unwrap()
+}
+
+#[test]
+fn map_source_has_the_classes_inserted() {
+	let mut source = MapSource::new();
+	source.insert("com.acme.Tool", vec![0xCA, 0xFE]);
+	assert!(source.has_class("com/acme/Tool"));
+	assert_eq!(source.class_bytes("com/acme/Tool"), Some(vec![0xCA, 0xFE]));
+	assert!(!source.has_class("com/acme/Missing"));
+	assert_eq!(source.class_bytes("com/acme/Missing"), None);
+
+	let mut classes = HashMap::new();
+	classes.insert("com.acme.Tool".to_string(), vec![0xBE, 0xEF]);
+	let source = MapSource::from(classes);
+	assert_eq!(source.class_bytes("com/acme/Tool"), Some(vec![0xBE, 0xEF]));
+}
+
+#[test]
+fn directory_source_reads_class_files() {
+	let source = DirectorySource::new(test_path("classes/source"));
+	assert!(source.has_class("Sourced"));
+	assert_eq!(
+		source.class_bytes("Sourced"),
+		Some(class_file("source", "Sourced"))
+	);
+	assert!(!source.has_class("Missing"));
+	assert_eq!(source.class_bytes("Missing"), None);
+}
+
+#[test]
+fn jar_source_reads_class_files() {
+	let source = JarSource::open(test_path("jars/source.jar").to_str().unwrap()).unwrap();
+	assert!(source.has_class("Sourced"));
+	/*
+	 * Twice: the JAR file stays open between lookups.
+	 */
+	for _ in 0..2 {
+		assert_eq!(
+			source.class_bytes("Sourced"),
+			Some(class_file("source", "Sourced"))
+		);
+	}
+	assert!(!source.has_class("Missing"));
+	assert_eq!(source.class_bytes("Missing"), None);
+	assert!(JarSource::open(test_path("jars/missing.jar").to_str().unwrap()).is_none());
+}
+
+#[test]
+fn vm_loads_classes_from_a_map_source() {
+	let mut source = MapSource::new();
+	source.insert("Sourced", class_file("source", "Sourced"));
+	let mut vm = JvmBuilder::new()
+		.class_source(source)
+		.build()
+		.start()
+		.unwrap();
+	assert_eq!(
+		vm.call_static("Sourced", "answer", "()I", &[]),
+		Ok(JavaValue::Int(42))
+	);
+}
+
+#[test]
+fn class_path_takes_precedence_over_class_sources() {
+	let source = || {
+		let mut source = MapSource::new();
+		source.insert("Origin", class_file("source", "Origin"));
+		source
+	};
+
+	let mut vm = JvmBuilder::new()
+		.class_source(source())
+		.build()
+		.start()
+		.unwrap();
+	assert_eq!(
+		vm.call_static("Origin", "origin", "()I", &[]),
+		Ok(JavaValue::Int(2))
+	);
+
+	let mut vm = JvmBuilder::new()
+		.classpath_entry(test_path("classes/classpath").to_str().unwrap())
+		.class_source(source())
+		.build()
+		.start()
+		.unwrap();
+	assert_eq!(
+		vm.call_static("Origin", "origin", "()I", &[]),
+		Ok(JavaValue::Int(1))
+	);
+}
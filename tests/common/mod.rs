@@ -0,0 +1,46 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Helpers shared by the tests: each test file uses some of them.
+#![allow(dead_code)]
+
+use jvm::jvm::{JvmBuilder, Vm};
+use std::path::PathBuf;
+
+/// The path of `path` under tests (see test-classes in the Makefile).
+pub fn test_path(path: &str) -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.join("tests")
+		.join(path)
+}
+
+/// A builder of a Jvm whose class path has the classes of
+/// tests/java/`directory` (see test-classes in the Makefile).
+pub fn builder(directory: &str) -> JvmBuilder {
+	let classes = test_path(&format!("classes/{}", directory));
+	JvmBuilder::new().classpath_entry(classes.to_str().unwrap())
+}
+
+/// A started VM whose class path has the classes of
+/// tests/java/`directory`.
+pub fn vm(directory: &str) -> Vm {
+	builder(directory).build().start().unwrap()
+}
@@ -0,0 +1,69 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+extern crate jvm;
+
+mod common;
+
+use common::test_path;
+use jvm::jvm::{JvmBuilder, LAUNCH_ERROR_EXIT_STATUS};
+use std::process::Command;
+
+fn jar_file(name: &str) -> String {
+	test_path(&format!("jars/{}", name))
+		.to_str()
+		.unwrap()
+		.to_string()
+}
+
+#[test]
+fn executable_jar_files_run_their_main_class() {
+	/*
+	 * App exits with 42 (from Tool, which only packages.jar, on the
+	 * Class-Path of app.jar's manifest, has) plus its argument count.
+	 * jar wrapped the Class-Path in the middle of packages.jar.
+	 */
+	let jvm = JvmBuilder::new().build();
+	assert_eq!(jvm.run_jar(&jar_file("app.jar"), &[]), 42);
+	assert_eq!(jvm.run_jar(&jar_file("app.jar"), &["one"]), 43);
+}
+
+#[test]
+fn jar_files_without_a_main_class_do_not_run() {
+	let jvm = JvmBuilder::new().build();
+	assert_eq!(
+		jvm.run_jar(&jar_file("source.jar"), &[]),
+		LAUNCH_ERROR_EXIT_STATUS
+	);
+	assert_eq!(
+		jvm.run_jar(&jar_file("missing.jar"), &[]),
+		LAUNCH_ERROR_EXIT_STATUS
+	);
+}
+
+#[test]
+fn the_jvm_command_runs_executable_jar_files() {
+	let status = Command::new(env!("CARGO_BIN_EXE_jvm"))
+		.args(&["-jar", &jar_file("app.jar"), "-a", "one", "two"])
+		.status()
+		.unwrap();
+	assert_eq!(status.code(), Some(44));
+}
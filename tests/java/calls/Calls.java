@@ -0,0 +1,38 @@
+/*
+ * Static methods that the Rust tests call into (see tests/vm.rs).
+ */
+public class Calls {
+    public static int add(int a, int b) {
+        return a + b;
+    }
+
+    public static long multiply(long a, long b) {
+        return a * b;
+    }
+
+    public static int divide(int a, int b) {
+        return a / b;
+    }
+
+    public static int depth(int n) {
+        return n == 0 ? 0 : 1 + depth(n - 1);
+    }
+
+    /*
+     * Allocates, and drops, count arrays of 1024 bytes.
+     */
+    public static void garbage(int count) {
+        for (int i = 0; i < count; i++) {
+            byte[] bytes = new byte[1024];
+        }
+    }
+
+    public static void exit(int status) {
+        System.exit(status);
+    }
+
+    /*
+     * Registered by the tests.
+     */
+    public static native void fail();
+}
@@ -0,0 +1,40 @@
+/*
+ * An object that the Rust tests create and call into (see
+ * tests/objects.rs).
+ */
+public class Counter {
+    private int count;
+    public String label;
+
+    public Counter(int count, String label) {
+        this.count = count;
+        this.label = label;
+    }
+
+    public int increment() {
+        return ++count;
+    }
+
+    public int check(int limit) {
+        if (count > limit) {
+            throw new IllegalStateException("over " + limit);
+        }
+        return count;
+    }
+
+    public String getLabel() {
+        return label;
+    }
+
+    public boolean isLabel(String other) {
+        return label == other;
+    }
+
+    public static int sum(int[] values) {
+        int sum = 0;
+        for (int value : values) {
+            sum += value;
+        }
+        return sum;
+    }
+}
@@ -0,0 +1,8 @@
+/*
+ * The class path's Origin, which hides the one of the class source.
+ */
+public class Origin {
+    public static int origin() {
+        return 1;
+    }
+}
@@ -0,0 +1,11 @@
+import com.acme.Tool;
+
+/*
+ * The Main-Class of tests/jars/app.jar, which has only App: Tool is on
+ * the Class-Path of its manifest (see tests/jar_files.rs).
+ */
+public class App {
+    public static void main(String[] args) {
+        System.exit(Tool.answer() + args.length);
+    }
+}
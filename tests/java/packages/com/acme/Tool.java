@@ -0,0 +1,11 @@
+package com.acme;
+
+/*
+ * A class in a package: the class path has it at com/acme/Tool.class
+ * (see tests/class_path.rs).
+ */
+public class Tool {
+    public static int answer() {
+        return 42;
+    }
+}
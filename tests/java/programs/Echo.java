@@ -0,0 +1,17 @@
+import java.io.IOException;
+
+/*
+ * Copy the standard input to the standard output, upper-cased, and say
+ * so on the standard error.
+ */
+public class Echo {
+    public static void main(String[] args) throws IOException {
+        int copied = 0;
+        for (int b = System.in.read(); b != -1; b = System.in.read()) {
+            System.out.write(Character.toUpperCase((char) b));
+            copied++;
+        }
+        System.out.flush();
+        System.err.println("copied " + copied);
+    }
+}
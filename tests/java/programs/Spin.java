@@ -0,0 +1,11 @@
+/*
+ * Never returns (unless the VM limits the instructions it executes).
+ */
+public class Spin {
+    public static void main(String[] args) {
+        long spins = 0;
+        while (true) {
+            spins++;
+        }
+    }
+}
@@ -0,0 +1,12 @@
+/*
+ * Calls square, so that a trace hook sees it.
+ */
+public class Squares {
+    static int square(int value) {
+        return value * value;
+    }
+
+    public static void main(String[] args) {
+        if (square(3) != 9) System.exit(1);
+    }
+}
@@ -0,0 +1,10 @@
+/*
+ * Exits with 0 if the embedder's twice doubles its argument.
+ */
+public class Twice {
+    static native int twice(int value);
+
+    public static void main(String[] args) {
+        System.exit(twice(21) == 42 ? 0 : 1);
+    }
+}
@@ -0,0 +1,8 @@
+/*
+ * The class source's Origin (see tests/java/classpath/Origin.java).
+ */
+public class Origin {
+    public static int origin() {
+        return 2;
+    }
+}
@@ -0,0 +1,8 @@
+/*
+ * A class that only a class source has.
+ */
+public class Sourced {
+    public static int answer() {
+        return 42;
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+extern crate jvm;
+
+mod common;
+
+use common::test_path;
+use jvm::jvm::{CallError, JavaValue, JvmBuilder, Vm};
+
+/// A VM whose class path is the jmod file or jimage `path` (see
+/// test-modules in the Makefile).
+fn vm(path: &str) -> Vm {
+	JvmBuilder::new()
+		.classpath_entry(test_path(path).to_str().unwrap())
+		.build()
+		.start()
+		.unwrap()
+}
+
+#[test]
+fn classes_are_loaded_from_a_jimage() {
+	let mut vm = vm("images/modules");
+	/*
+	 * Modular uses Helper, of another package of the module.
+	 */
+	assert_eq!(
+		vm.call_static("com.acme.Modular", "answer", "()I", &[]),
+		Ok(JavaValue::Int(42))
+	);
+	/*
+	 * The jimage's java.base has java.lang, but not the VM's runtime
+	 * library's classes.
+	 */
+	assert_eq!(
+		vm.call_static(
+			"java.lang.Integer",
+			"parseInt",
+			"(Ljava/lang/String;)I",
+			&["7".into()]
+		),
+		Ok(JavaValue::Int(7))
+	);
+	match vm.call_static("com.acme.Missing", "answer", "()I", &[]) {
+		Err(CallError::Exception(exception)) => {
+			assert_eq!(exception.class_name, "java.lang.NoClassDefFoundError")
+		}
+		result => panic!("{:?}", result),
+	}
+}
+
+#[test]
+fn classes_are_loaded_from_a_jmod_file() {
+	let mut vm = vm("jars/fixture.jmod");
+	assert_eq!(
+		vm.call_static("com.acme.Modular", "answer", "()I", &[]),
+		Ok(JavaValue::Int(42))
+	);
+	match vm.call_static("com.acme.Missing", "answer", "()I", &[]) {
+		Err(CallError::Exception(exception)) => {
+			assert_eq!(exception.class_name, "java.lang.NoClassDefFoundError")
+		}
+		result => panic!("{:?}", result),
+	}
+}
+
+#[test]
+fn compressed_jimages_are_rejected() {
+	let mut vm = vm("images/compressed");
+	match vm.call_static("com.acme.Modular", "answer", "()I", &[]) {
+		Err(CallError::VmError(message)) => assert!(message.contains("compressed"), "{}", message),
+		result => panic!("{:?}", result),
+	}
+}
@@ -0,0 +1,12 @@
+package com.acme;
+
+import com.acme.tools.Helper;
+
+/*
+ * A class of the module fixture that uses a class of its other package.
+ */
+public class Modular {
+    public static int answer() {
+        return Helper.half(84);
+    }
+}
@@ -0,0 +1,7 @@
+package com.acme.tools;
+
+public class Helper {
+    public static int half(int n) {
+        return n / 2;
+    }
+}
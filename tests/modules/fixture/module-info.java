@@ -0,0 +1,7 @@
+/*
+ * The module in the jmod file and the jimages that the Rust tests load
+ * classes from (see tests/modules.rs).
+ */
+module fixture {
+    exports com.acme;
+}
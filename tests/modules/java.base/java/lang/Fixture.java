@@ -0,0 +1,7 @@
+package java.lang;
+
+/*
+ * java.base has to have java.lang.
+ */
+class Fixture {
+}
@@ -0,0 +1,7 @@
+package java.lang;
+
+/*
+ * Only there to compile java.base.
+ */
+public class Object {
+}
@@ -0,0 +1,7 @@
+/*
+ * jlink links java.base into every jimage. This one has no
+ * java.lang.Object (see test-classes in the Makefile), so the VM that
+ * loads the jimage uses its own runtime library.
+ */
+module java.base {
+}
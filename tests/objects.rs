@@ -0,0 +1,205 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+extern crate jvm;
+
+mod common;
+
+use common::{builder, vm};
+use jvm::jvm::{CallError, JavaObject, JavaValue, Vm};
+
+/// A new Counter that counts from `count`, labelled `label`.
+fn counter(vm: &mut Vm, count: i32, label: &str) -> JavaObject {
+	vm.new_object(
+		"Counter",
+		"(ILjava/lang/String;)V",
+		&[count.into(), label.into()],
+	)
+	.unwrap()
+}
+
+fn object(value: Result<JavaValue, CallError>) -> JavaObject {
+	match value {
+		Ok(JavaValue::Object(object)) => object,
+		value => panic!("{:?} is not an object", value),
+	}
+}
+
+#[test]
+fn new_object_runs_the_constructor() {
+	let mut vm = vm("calls");
+	let counter = counter(&mut vm, 1, "first");
+	assert_eq!(counter.type_name(), "Counter");
+	assert!(!counter.is_array());
+	assert_eq!(
+		vm.call_method(&counter, "increment", "()I", &[]),
+		Ok(JavaValue::Int(2))
+	);
+	assert_eq!(
+		vm.call_method(&counter, "increment", "()I", &[]),
+		Ok(JavaValue::Int(3))
+	);
+}
+
+#[test]
+fn fields_are_read_and_written() {
+	let mut vm = vm("calls");
+	let counter = counter(&mut vm, 1, "first");
+	assert_eq!(vm.get_field(&counter, "count", "I"), Ok(JavaValue::Int(1)));
+	assert_eq!(vm.set_field(&counter, "count", "I", &10.into()), Ok(()));
+	assert_eq!(
+		vm.call_method(&counter, "increment", "()I", &[]),
+		Ok(JavaValue::Int(11))
+	);
+
+	let label = object(vm.get_field(&counter, "label", "Ljava/lang/String;"));
+	assert_eq!(vm.to_rust_string(&label), Some("first".to_string()));
+	assert_eq!(
+		vm.set_field(&counter, "label", "Ljava/lang/String;", &JavaValue::Null),
+		Ok(())
+	);
+	assert_eq!(
+		vm.get_field(&counter, "label", "Ljava/lang/String;"),
+		Ok(JavaValue::Null)
+	);
+}
+
+#[test]
+fn strings_come_back_as_the_same_object() {
+	let mut vm = vm("calls");
+	let counter = counter(&mut vm, 1, "first");
+	let label = object(vm.call_method(&counter, "getLabel", "()Ljava/lang/String;", &[]));
+	assert_eq!(label.type_name(), "java.lang.String");
+	assert_eq!(vm.to_rust_string(&label), Some("first".to_string()));
+	assert_eq!(
+		vm.call_method(
+			&counter,
+			"isLabel",
+			"(Ljava/lang/String;)Z",
+			&[label.clone().into()]
+		),
+		Ok(JavaValue::Boolean(true))
+	);
+	/*
+	 * A String passed by its contents is a new object.
+	 */
+	assert_eq!(
+		vm.call_method(
+			&counter,
+			"isLabel",
+			"(Ljava/lang/String;)Z",
+			&["first".into()]
+		),
+		Ok(JavaValue::Boolean(false))
+	);
+	assert_eq!(vm.to_rust_string(&counter), None);
+}
+
+#[test]
+fn strings_and_arrays_are_created() {
+	let mut vm = vm("calls");
+	let string = vm.new_string("héllo").unwrap();
+	assert_eq!(vm.to_rust_string(&string), Some("héllo".to_string()));
+
+	let values = vm.new_array("I", &[1.into(), 2.into(), 3.into()]).unwrap();
+	assert!(values.is_array());
+	assert_eq!(
+		vm.array_elements(&values),
+		Ok(vec![
+			JavaValue::Int(1),
+			JavaValue::Int(2),
+			JavaValue::Int(3)
+		])
+	);
+	assert_eq!(
+		vm.call_static("Counter", "sum", "([I)I", &[values.into()]),
+		Ok(JavaValue::Int(6))
+	);
+	assert_eq!(
+		vm.new_array("Q", &[]),
+		Err(CallError::InvalidDescriptor("Q".to_string()))
+	);
+}
+
+#[test]
+fn objects_survive_garbage_collection() {
+	let mut vm = builder("calls")
+		.max_heap_size(4 * 1024 * 1024)
+		.build()
+		.start()
+		.unwrap();
+	let counter = counter(&mut vm, 1, "kept");
+	/*
+	 * More garbage than the heap holds, so the heap is collected.
+	 * The counter (with its label) is only referenced from here.
+	 */
+	assert_eq!(
+		vm.call_static("Calls", "garbage", "(I)V", &[64.into()]),
+		Ok(JavaValue::Void)
+	);
+	assert_eq!(
+		vm.call_method(&counter, "increment", "()I", &[]),
+		Ok(JavaValue::Int(2))
+	);
+	let label = vm.call_method(&counter, "getLabel", "()Ljava/lang/String;", &[]);
+	assert_eq!(vm.to_rust_string(&object(label)), Some("kept".to_string()));
+}
+
+#[test]
+fn errors_are_reported() {
+	let mut vm = vm("calls");
+	let counter = counter(&mut vm, 5, "first");
+	match vm.call_method(&counter, "check", "(I)I", &[2.into()]) {
+		Err(CallError::Exception(exception)) => {
+			assert_eq!(exception.class_name, "java.lang.IllegalStateException");
+			assert_eq!(exception.message, Some("over 2".to_string()));
+			assert!(!exception.stack_trace.is_empty());
+		}
+		result => panic!("{:?}", result),
+	}
+	match vm.call_method(&counter, "check", "(I)I", &["2".into()]) {
+		Err(CallError::WrongArguments(_)) => (),
+		result => panic!("{:?}", result),
+	}
+	match vm.set_field(&counter, "count", "I", &JavaValue::Long(1)) {
+		Err(CallError::WrongArguments(_)) => (),
+		result => panic!("{:?}", result),
+	}
+	match vm.get_field(&counter, "missing", "I") {
+		Err(CallError::Exception(exception)) => {
+			assert_eq!(exception.class_name, "java.lang.NoSuchFieldError")
+		}
+		result => panic!("{:?}", result),
+	}
+	match vm.new_object("Missing", "()V", &[]) {
+		Err(CallError::Exception(exception)) => {
+			assert_eq!(exception.class_name, "java.lang.NoClassDefFoundError")
+		}
+		result => panic!("{:?}", result),
+	}
+	/*
+	 * None of which stops the VM.
+	 */
+	assert_eq!(
+		vm.call_method(&counter, "check", "(I)I", &[10.into()]),
+		Ok(JavaValue::Int(5))
+	);
+}
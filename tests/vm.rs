@@ -0,0 +1,135 @@
+/*
+ * FILE: XXXXX
+ * DESCRIPTION:
+ *
+ * Copyright (c) 2019, Will Hawkins
+ *
+ * This file is part of Rust-JVM.
+ *
+ * Rust-JVM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Rust-JVM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with Rust-JVM.  If not, see <https://www.gnu.org/licenses/>.
+ */
+extern crate jvm;
+
+mod common;
+
+use common::{builder, vm};
+use jvm::jvm::{CallError, JavaValue, JvmThread, JvmValue, MethodArea};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn call_static_passes_arguments_and_returns_the_result() {
+	let mut vm = vm("calls");
+	assert_eq!(
+		vm.call_static("Calls", "add", "(II)I", &[2.into(), 3.into()]),
+		Ok(JavaValue::Int(5))
+	);
+	assert_eq!(
+		vm.call_static(
+			"Calls",
+			"multiply",
+			"(JJ)J",
+			&[JavaValue::Long(1 << 40), JavaValue::Long(3)]
+		),
+		Ok(JavaValue::Long(3 << 40))
+	);
+}
+
+#[test]
+fn call_static_reports_the_exception_thrown() {
+	let mut vm = vm("calls");
+	match vm.call_static("Calls", "divide", "(II)I", &[1.into(), 0.into()]) {
+		Err(CallError::Exception(exception)) => {
+			assert_eq!(exception.class_name, "java.lang.ArithmeticException");
+			assert_eq!(exception.message, Some("/ by zero".to_string()));
+		}
+		result => panic!("{:?}", result),
+	}
+	/*
+	 * The VM goes on.
+	 */
+	assert_eq!(
+		vm.call_static("Calls", "divide", "(II)I", &[6.into(), 2.into()]),
+		Ok(JavaValue::Int(3))
+	);
+}
+
+#[test]
+fn call_static_checks_the_descriptor_and_arguments() {
+	let mut vm = vm("calls");
+	assert_eq!(
+		vm.call_static("Calls", "add", "(II", &[]),
+		Err(CallError::InvalidDescriptor("(II".to_string()))
+	);
+	match vm.call_static("Calls", "add", "(II)I", &[2.into()]) {
+		Err(CallError::WrongArguments(_)) => (),
+		result => panic!("{:?}", result),
+	}
+	match vm.call_static("Calls", "add", "(II)I", &[2.into(), JavaValue::Long(3)]) {
+		Err(CallError::WrongArguments(_)) => (),
+		result => panic!("{:?}", result),
+	}
+}
+
+#[test]
+fn nothing_runs_once_the_program_exits() {
+	let mut vm = vm("calls");
+	assert_eq!(
+		vm.call_static("Calls", "exit", "(I)V", &[3.into()]),
+		Err(CallError::Exited(3))
+	);
+	assert_eq!(
+		vm.call_static("Calls", "add", "(II)I", &[2.into(), 3.into()]),
+		Err(CallError::Exited(3))
+	);
+	assert_eq!(vm.new_string("after"), Err(CallError::Exited(3)));
+}
+
+#[test]
+fn a_failed_vm_reports_a_vm_error() {
+	let mut vm = builder("calls")
+		.native(
+			"Calls",
+			"fail",
+			"()V",
+			|_: &mut JvmThread, _: &Arc<Mutex<MethodArea>>, _: &[JvmValue]| -> Option<JvmValue> {
+				panic!("the native failed")
+			},
+		)
+		.build()
+		.start()
+		.unwrap();
+	assert_eq!(
+		vm.call_static("Calls", "fail", "()V", &[]),
+		Err(CallError::VmError("the native failed".to_string()))
+	);
+	assert_eq!(
+		vm.call_static("Calls", "add", "(II)I", &[2.into(), 3.into()]),
+		Err(CallError::VmError("the native failed".to_string()))
+	);
+}
+
+#[test]
+fn with_vm_calls_into_a_vm_on_a_thread_of_its_own() {
+	/*
+	 * What the VM returns cannot leave its thread, so the closure
+	 * returns the depth that it got.
+	 */
+	let depth = builder("calls").build().with_vm(|vm| {
+		match vm.call_static("Calls", "depth", "(I)I", &[5000.into()]) {
+			Ok(JavaValue::Int(depth)) => Some(depth),
+			_ => None,
+		}
+	});
+	assert_eq!(depth, Ok(Some(5000)));
+}